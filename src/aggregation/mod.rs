@@ -0,0 +1,660 @@
+//! Contains a builder for aggregation pipelines.
+//!
+//! A [`Pipeline`] can be passed anywhere a pipeline of stage documents is accepted, such as
+//! [`Collection::aggregate`](../struct.Collection.html#method.aggregate) or
+//! [`Database::aggregate`](../struct.Database.html#method.aggregate):
+//!
+//! ```rust
+//! # use mongodb::{bson::{doc, Document}, Client, error::Result};
+//! #
+//! # async fn do_stuff() -> Result<()> {
+//! # let client = Client::with_uri_str("mongodb://example.com").await?;
+//! # let coll = client.database("foo").collection::<Document>("orders");
+//! use mongodb::aggregation::{field, Accumulator, Group, Pipeline};
+//!
+//! let pipeline = Pipeline::new()
+//!     .match_(doc! { "status": "shipped" })
+//!     .group(
+//!         Group::by(field("customerId"))
+//!             .field("total", Accumulator::sum(field("amount")))
+//!             .field("orders", Accumulator::count()),
+//!     )
+//!     .sort(doc! { "total": -1 })
+//!     .limit(10);
+//!
+//! let mut cursor = coll.aggregate(pipeline, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! See the documentation [here](https://docs.mongodb.com/manual/reference/operator/aggregation-pipeline/)
+//! for more information on each of the stages.
+
+#[cfg(test)]
+mod test;
+
+use typed_builder::TypedBuilder;
+
+use crate::{
+    bson::{doc, Bson, Document},
+    Namespace,
+};
+
+/// Returns the expression referring to the field at the given path in the current document, e.g.
+/// `field("a.b")` returns `"$a.b"`.
+pub fn field(path: impl AsRef<str>) -> String {
+    format!("${}", path.as_ref())
+}
+
+/// Returns the expression referring to the given variable, e.g. `variable("ROOT")` returns
+/// `"$$ROOT"`.
+pub fn variable(name: impl AsRef<str>) -> String {
+    format!("$${}", name.as_ref())
+}
+
+/// An aggregation pipeline, built up one stage at a time.
+///
+/// A `Pipeline` serializes each stage to the same `Document` that would otherwise be written by
+/// hand, and can be converted into a `Vec<Document>` or iterated over to obtain them. Because it
+/// implements `IntoIterator<Item = Document>`, it can be passed to any method that takes a
+/// pipeline as a list of stage documents, including the `aggregate` methods of `Collection` and
+/// `Database` and their `_with_session` and sync counterparts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pipeline {
+    stages: Vec<Document>,
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The stages in this pipeline.
+    pub fn stages(&self) -> &[Document] {
+        &self.stages
+    }
+
+    /// Appends the given stage document to the pipeline. This can be used for stages that don't
+    /// have a dedicated method.
+    pub fn stage(mut self, stage: Document) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Appends a `$match` stage that filters documents using the given query.
+    pub fn match_(self, filter: Document) -> Self {
+        self.stage(doc! { "$match": filter })
+    }
+
+    /// Appends a `$group` stage.
+    pub fn group(self, group: Group) -> Self {
+        self.stage(doc! { "$group": Document::from(group) })
+    }
+
+    /// Appends a `$project` stage with the given specification.
+    pub fn project(self, projection: Document) -> Self {
+        self.stage(doc! { "$project": projection })
+    }
+
+    /// Appends a `$lookup` stage.
+    pub fn lookup(self, lookup: Lookup) -> Self {
+        self.stage(doc! { "$lookup": Document::from(lookup) })
+    }
+
+    /// Appends an `$unwind` stage. The argument may be either the path of the array field to
+    /// unwind (e.g. `"$items"`) or an [`Unwind`] with additional options.
+    pub fn unwind(self, unwind: impl Into<Unwind>) -> Self {
+        let unwind = unwind.into();
+        let spec = if unwind.include_array_index.is_none()
+            && unwind.preserve_null_and_empty_arrays.is_none()
+        {
+            Bson::String(unwind.path)
+        } else {
+            Bson::Document(unwind.into())
+        };
+        self.stage(doc! { "$unwind": spec })
+    }
+
+    /// Appends a `$sort` stage with the given sort specification.
+    pub fn sort(self, sort: Document) -> Self {
+        self.stage(doc! { "$sort": sort })
+    }
+
+    /// Appends a `$limit` stage.
+    pub fn limit(self, limit: i64) -> Self {
+        self.stage(doc! { "$limit": limit })
+    }
+
+    /// Appends a `$skip` stage.
+    pub fn skip(self, skip: i64) -> Self {
+        self.stage(doc! { "$skip": skip })
+    }
+
+    /// Appends a `$facet` stage that runs each of the given sub-pipelines on the same input
+    /// documents, outputting their results in the field with the corresponding name.
+    pub fn facet<S: Into<String>>(self, facets: impl IntoIterator<Item = (S, Pipeline)>) -> Self {
+        let facets: Document = facets
+            .into_iter()
+            .map(|(name, pipeline)| (name.into(), Bson::from(pipeline)))
+            .collect();
+        self.stage(doc! { "$facet": facets })
+    }
+
+    /// Appends a `$merge` stage. This must be the last stage in the pipeline.
+    pub fn merge(self, merge: Merge) -> Self {
+        self.stage(doc! { "$merge": Document::from(merge) })
+    }
+
+    /// Appends an `$out` stage that writes the results to the given collection. This must be the
+    /// last stage in the pipeline.
+    pub fn out(self, target: impl Into<OutputCollection>) -> Self {
+        self.stage(doc! { "$out": target.into() })
+    }
+
+    /// Appends a `$setWindowFields` stage.
+    pub fn set_window_fields(self, set_window_fields: SetWindowFields) -> Self {
+        self.stage(doc! { "$setWindowFields": Document::from(set_window_fields) })
+    }
+}
+
+impl IntoIterator for Pipeline {
+    type Item = Document;
+    type IntoIter = std::vec::IntoIter<Document>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.stages.into_iter()
+    }
+}
+
+impl From<Pipeline> for Vec<Document> {
+    fn from(pipeline: Pipeline) -> Self {
+        pipeline.stages
+    }
+}
+
+impl From<Pipeline> for Bson {
+    fn from(pipeline: Pipeline) -> Self {
+        Bson::Array(pipeline.stages.into_iter().map(Bson::Document).collect())
+    }
+}
+
+/// An accumulator expression, used to compute a value from a group of documents in a `$group`
+/// stage or from a window of documents in a `$setWindowFields` stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accumulator {
+    operator: &'static str,
+    argument: Bson,
+}
+
+impl Accumulator {
+    fn new(operator: &'static str, argument: impl Into<Bson>) -> Self {
+        Self {
+            operator,
+            argument: argument.into(),
+        }
+    }
+
+    /// `$sum`: the sum of the values of the given expression.
+    pub fn sum(expression: impl Into<Bson>) -> Self {
+        Self::new("$sum", expression)
+    }
+
+    /// `$avg`: the average of the values of the given expression.
+    pub fn avg(expression: impl Into<Bson>) -> Self {
+        Self::new("$avg", expression)
+    }
+
+    /// `$min`: the minimum of the values of the given expression.
+    pub fn min(expression: impl Into<Bson>) -> Self {
+        Self::new("$min", expression)
+    }
+
+    /// `$max`: the maximum of the values of the given expression.
+    pub fn max(expression: impl Into<Bson>) -> Self {
+        Self::new("$max", expression)
+    }
+
+    /// `$first`: the value of the given expression for the first document.
+    pub fn first(expression: impl Into<Bson>) -> Self {
+        Self::new("$first", expression)
+    }
+
+    /// `$last`: the value of the given expression for the last document.
+    pub fn last(expression: impl Into<Bson>) -> Self {
+        Self::new("$last", expression)
+    }
+
+    /// `$push`: an array of the values of the given expression.
+    pub fn push(expression: impl Into<Bson>) -> Self {
+        Self::new("$push", expression)
+    }
+
+    /// `$addToSet`: an array of the unique values of the given expression.
+    pub fn add_to_set(expression: impl Into<Bson>) -> Self {
+        Self::new("$addToSet", expression)
+    }
+
+    /// `$stdDevPop`: the population standard deviation of the values of the given expression.
+    pub fn std_dev_pop(expression: impl Into<Bson>) -> Self {
+        Self::new("$stdDevPop", expression)
+    }
+
+    /// `$stdDevSamp`: the sample standard deviation of the values of the given expression.
+    pub fn std_dev_samp(expression: impl Into<Bson>) -> Self {
+        Self::new("$stdDevSamp", expression)
+    }
+
+    /// `$count`: the number of documents.
+    pub fn count() -> Self {
+        Self::new("$count", Document::new())
+    }
+
+    /// `$rank`: the position of the document relative to the others in the window's partition,
+    /// with ties receiving the same rank. Only valid in `$setWindowFields`.
+    pub fn rank() -> Self {
+        Self::new("$rank", Document::new())
+    }
+
+    /// `$denseRank`: like [`Accumulator::rank`], but without gaps in the ranking after ties. Only
+    /// valid in `$setWindowFields`.
+    pub fn dense_rank() -> Self {
+        Self::new("$denseRank", Document::new())
+    }
+
+    /// `$documentNumber`: the position of the document in the window's partition. Only valid in
+    /// `$setWindowFields`.
+    pub fn document_number() -> Self {
+        Self::new("$documentNumber", Document::new())
+    }
+}
+
+impl From<Accumulator> for Bson {
+    fn from(accumulator: Accumulator) -> Self {
+        Bson::Document(doc! { accumulator.operator: accumulator.argument })
+    }
+}
+
+/// A `$group` stage, grouping documents by the value of an expression and computing the given
+/// fields for each group.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    id: Bson,
+    fields: Vec<(String, Accumulator)>,
+}
+
+impl Group {
+    /// Groups documents by the value of the given expression. Use `Bson::Null` to compute the
+    /// fields over all of the input documents.
+    pub fn by(id: impl Into<Bson>) -> Self {
+        Self {
+            id: id.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field to the output documents computed using the given accumulator.
+    pub fn field(mut self, name: impl Into<String>, accumulator: Accumulator) -> Self {
+        self.fields.push((name.into(), accumulator));
+        self
+    }
+}
+
+impl From<Group> for Document {
+    fn from(group: Group) -> Self {
+        let mut doc = doc! { "_id": group.id };
+        for (name, accumulator) in group.fields {
+            doc.insert(name, accumulator);
+        }
+        doc
+    }
+}
+
+/// A `$lookup` stage, joining documents from another collection in the same database.
+///
+/// Either `local_field` and `foreign_field` or `pipeline` (or all three) must be specified.
+#[derive(Clone, Debug, PartialEq, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct Lookup {
+    /// The collection to join documents from.
+    #[builder(!default)]
+    pub from: String,
+
+    /// The field from the input documents to match against `foreign_field`.
+    pub local_field: Option<String>,
+
+    /// The field from the documents in the `from` collection to match against `local_field`.
+    pub foreign_field: Option<String>,
+
+    /// Variables to make available to `pipeline`.
+    pub let_vars: Option<Document>,
+
+    /// A pipeline to run on the documents in the `from` collection.
+    pub pipeline: Option<Pipeline>,
+
+    /// The name of the array field to add to the input documents containing the joined documents.
+    #[builder(!default)]
+    pub as_: String,
+}
+
+impl From<Lookup> for Document {
+    fn from(lookup: Lookup) -> Self {
+        let mut doc = doc! { "from": lookup.from };
+        if let Some(local_field) = lookup.local_field {
+            doc.insert("localField", local_field);
+        }
+        if let Some(foreign_field) = lookup.foreign_field {
+            doc.insert("foreignField", foreign_field);
+        }
+        if let Some(let_vars) = lookup.let_vars {
+            doc.insert("let", let_vars);
+        }
+        if let Some(pipeline) = lookup.pipeline {
+            doc.insert("pipeline", pipeline);
+        }
+        doc.insert("as", lookup.as_);
+        doc
+    }
+}
+
+/// An `$unwind` stage, outputting a document for each element of an array field.
+#[derive(Clone, Debug, PartialEq, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct Unwind {
+    /// The path of the array field to unwind, prefixed with `$`.
+    #[builder(!default)]
+    pub path: String,
+
+    /// The name of a field to add to the output documents containing the element's index.
+    pub include_array_index: Option<String>,
+
+    /// Whether to output a document for input documents where the field is missing, null or an
+    /// empty array.
+    pub preserve_null_and_empty_arrays: Option<bool>,
+}
+
+impl From<&str> for Unwind {
+    fn from(path: &str) -> Self {
+        path.to_string().into()
+    }
+}
+
+impl From<String> for Unwind {
+    fn from(path: String) -> Self {
+        Self::builder().path(path).build()
+    }
+}
+
+impl From<Unwind> for Document {
+    fn from(unwind: Unwind) -> Self {
+        let mut doc = doc! { "path": unwind.path };
+        if let Some(include_array_index) = unwind.include_array_index {
+            doc.insert("includeArrayIndex", include_array_index);
+        }
+        if let Some(preserve) = unwind.preserve_null_and_empty_arrays {
+            doc.insert("preserveNullAndEmptyArrays", preserve);
+        }
+        doc
+    }
+}
+
+/// The collection that an `$out` or `$merge` stage writes to.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum OutputCollection {
+    /// A collection in the database the aggregation is run on.
+    Collection(String),
+
+    /// A collection in the specified database.
+    Namespace(Namespace),
+}
+
+impl From<&str> for OutputCollection {
+    fn from(coll: &str) -> Self {
+        Self::Collection(coll.to_string())
+    }
+}
+
+impl From<String> for OutputCollection {
+    fn from(coll: String) -> Self {
+        Self::Collection(coll)
+    }
+}
+
+impl From<Namespace> for OutputCollection {
+    fn from(ns: Namespace) -> Self {
+        Self::Namespace(ns)
+    }
+}
+
+impl From<OutputCollection> for Bson {
+    fn from(target: OutputCollection) -> Self {
+        match target {
+            OutputCollection::Collection(coll) => Bson::String(coll),
+            OutputCollection::Namespace(ns) => {
+                Bson::Document(doc! { "db": ns.db, "coll": ns.coll })
+            }
+        }
+    }
+}
+
+/// A `$merge` stage, writing the results of the pipeline to a collection.
+#[derive(Clone, Debug, PartialEq, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct Merge {
+    /// The collection to write to.
+    #[builder(!default)]
+    pub into: OutputCollection,
+
+    /// The fields used to identify matching documents in the output collection. Defaults to
+    /// `_id`.
+    pub on: Option<Vec<String>>,
+
+    /// Variables to make available to a `when_matched` pipeline.
+    pub let_vars: Option<Document>,
+
+    /// What to do when a result matches an existing document. Defaults to merging them.
+    pub when_matched: Option<WhenMatched>,
+
+    /// What to do when a result doesn't match an existing document. Defaults to inserting it.
+    pub when_not_matched: Option<WhenNotMatched>,
+}
+
+impl From<Merge> for Document {
+    fn from(merge: Merge) -> Self {
+        let mut doc = doc! { "into": merge.into };
+        if let Some(on) = merge.on {
+            doc.insert("on", on);
+        }
+        if let Some(let_vars) = merge.let_vars {
+            doc.insert("let", let_vars);
+        }
+        if let Some(when_matched) = merge.when_matched {
+            let when_matched = match when_matched {
+                WhenMatched::Replace => Bson::from("replace"),
+                WhenMatched::KeepExisting => Bson::from("keepExisting"),
+                WhenMatched::Merge => Bson::from("merge"),
+                WhenMatched::Fail => Bson::from("fail"),
+                WhenMatched::Pipeline(pipeline) => Bson::from(pipeline),
+            };
+            doc.insert("whenMatched", when_matched);
+        }
+        if let Some(when_not_matched) = merge.when_not_matched {
+            let when_not_matched = match when_not_matched {
+                WhenNotMatched::Insert => "insert",
+                WhenNotMatched::Discard => "discard",
+                WhenNotMatched::Fail => "fail",
+            };
+            doc.insert("whenNotMatched", when_not_matched);
+        }
+        doc
+    }
+}
+
+/// The behavior of a `$merge` stage when a result matches an existing document.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum WhenMatched {
+    /// Replace the existing document with the result.
+    Replace,
+
+    /// Keep the existing document.
+    KeepExisting,
+
+    /// Merge the result into the existing document.
+    Merge,
+
+    /// Stop the aggregation with an error.
+    Fail,
+
+    /// Update the existing document using the given pipeline.
+    Pipeline(Pipeline),
+}
+
+/// The behavior of a `$merge` stage when a result doesn't match an existing document.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum WhenNotMatched {
+    /// Insert the result.
+    Insert,
+
+    /// Discard the result.
+    Discard,
+
+    /// Stop the aggregation with an error.
+    Fail,
+}
+
+/// A `$setWindowFields` stage, computing fields from windows of documents.
+#[derive(Clone, Debug, PartialEq, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct SetWindowFields {
+    /// The expression used to partition the input documents. By default, all of the documents
+    /// form a single partition.
+    pub partition_by: Option<Bson>,
+
+    /// The order of the documents within each partition.
+    pub sort_by: Option<Document>,
+
+    /// The fields to compute.
+    #[builder(!default)]
+    pub output: Vec<WindowOutput>,
+}
+
+impl From<SetWindowFields> for Document {
+    fn from(set_window_fields: SetWindowFields) -> Self {
+        let mut doc = Document::new();
+        if let Some(partition_by) = set_window_fields.partition_by {
+            doc.insert("partitionBy", partition_by);
+        }
+        if let Some(sort_by) = set_window_fields.sort_by {
+            doc.insert("sortBy", sort_by);
+        }
+        let output: Document = set_window_fields
+            .output
+            .into_iter()
+            .map(|output| {
+                let mut spec = match Bson::from(output.operator) {
+                    Bson::Document(spec) => spec,
+                    _ => unreachable!("accumulators always serialize to documents"),
+                };
+                if let Some(window) = output.window {
+                    spec.insert("window", Document::from(window));
+                }
+                (output.field, Bson::Document(spec))
+            })
+            .collect();
+        doc.insert("output", output);
+        doc
+    }
+}
+
+/// A field computed by a `$setWindowFields` stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowOutput {
+    field: String,
+    operator: Accumulator,
+    window: Option<Window>,
+}
+
+impl WindowOutput {
+    /// Computes the given field using the given operator. By default, the window includes all of
+    /// the documents in the partition.
+    pub fn new(field: impl Into<String>, operator: Accumulator) -> Self {
+        Self {
+            field: field.into(),
+            operator,
+            window: None,
+        }
+    }
+
+    /// Sets the window of documents the operator is applied to.
+    pub fn window(mut self, window: Window) -> Self {
+        self.window = Some(window);
+        self
+    }
+}
+
+/// The window of documents used to compute a field in a `$setWindowFields` stage.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Window {
+    /// A window bounded by positions relative to the current document.
+    Documents(WindowBound<i64>, WindowBound<i64>),
+
+    /// A window bounded by values of the `sort_by` field relative to the current document's.
+    Range {
+        /// The lower bound of the window.
+        lower: WindowBound<Bson>,
+
+        /// The upper bound of the window.
+        upper: WindowBound<Bson>,
+
+        /// The unit of the bounds if the `sort_by` field is a date, e.g. `"day"`.
+        unit: Option<String>,
+    },
+}
+
+impl From<Window> for Document {
+    fn from(window: Window) -> Self {
+        match window {
+            Window::Documents(lower, upper) => doc! { "documents": [lower, upper] },
+            Window::Range { lower, upper, unit } => {
+                let mut doc = doc! { "range": [lower, upper] };
+                if let Some(unit) = unit {
+                    doc.insert("unit", unit);
+                }
+                doc
+            }
+        }
+    }
+}
+
+/// A bound of a [`Window`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum WindowBound<T> {
+    /// The first or last document in the partition.
+    Unbounded,
+
+    /// The current document.
+    Current,
+
+    /// An offset from the current document.
+    Offset(T),
+}
+
+impl<T: Into<Bson>> From<WindowBound<T>> for Bson {
+    fn from(bound: WindowBound<T>) -> Self {
+        match bound {
+            WindowBound::Unbounded => Bson::from("unbounded"),
+            WindowBound::Current => Bson::from("current"),
+            WindowBound::Offset(offset) => offset.into(),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use super::*;
+use crate::bson::Bson;
+
+#[test]
+fn simple_stages() {
+    let pipeline = Pipeline::new()
+        .match_(doc! { "x": { "$gt": 1 } })
+        .project(doc! { "x": 1, "_id": 0 })
+        .sort(doc! { "x": -1 })
+        .skip(5)
+        .limit(10)
+        .unwind("$items")
+        .out("results");
+
+    let expected = vec![
+        doc! { "$match": { "x": { "$gt": 1 } } },
+        doc! { "$project": { "x": 1, "_id": 0 } },
+        doc! { "$sort": { "x": -1 } },
+        doc! { "$skip": 5_i64 },
+        doc! { "$limit": 10_i64 },
+        doc! { "$unwind": "$items" },
+        doc! { "$out": "results" },
+    ];
+    assert_eq!(pipeline.stages(), expected.as_slice());
+    assert_eq!(Vec::<Document>::from(pipeline), expected);
+}
+
+#[test]
+fn group() {
+    let pipeline = Pipeline::new().group(
+        Group::by(field("customer.id"))
+            .field("total", Accumulator::sum(field("amount")))
+            .field("count", Accumulator::count())
+            .field("tags", Accumulator::add_to_set(field("tag"))),
+    );
+
+    assert_eq!(
+        pipeline.stages(),
+        &[doc! {
+            "$group": {
+                "_id": "$customer.id",
+                "total": { "$sum": "$amount" },
+                "count": { "$count": {} },
+                "tags": { "$addToSet": "$tag" },
+            }
+        }]
+    );
+    assert_eq!(variable("ROOT"), "$$ROOT");
+}
+
+#[test]
+fn lookup_and_unwind() {
+    let pipeline = Pipeline::new()
+        .lookup(
+            Lookup::builder()
+                .from("inventory")
+                .local_field("item".to_string())
+                .foreign_field("sku".to_string())
+                .as_("stock")
+                .build(),
+        )
+        .lookup(
+            Lookup::builder()
+                .from("warehouses")
+                .let_vars(doc! { "qty": "$ordered" })
+                .pipeline(Pipeline::new().match_(doc! { "$expr": { "$gte": ["$stock", "$$qty"] } }))
+                .as_("warehouses")
+                .build(),
+        )
+        .unwind(
+            Unwind::builder()
+                .path("$stock")
+                .include_array_index("index".to_string())
+                .preserve_null_and_empty_arrays(true)
+                .build(),
+        );
+
+    assert_eq!(
+        pipeline.stages(),
+        &[
+            doc! {
+                "$lookup": {
+                    "from": "inventory",
+                    "localField": "item",
+                    "foreignField": "sku",
+                    "as": "stock",
+                }
+            },
+            doc! {
+                "$lookup": {
+                    "from": "warehouses",
+                    "let": { "qty": "$ordered" },
+                    "pipeline": [{ "$match": { "$expr": { "$gte": ["$stock", "$$qty"] } } }],
+                    "as": "warehouses",
+                }
+            },
+            doc! {
+                "$unwind": {
+                    "path": "$stock",
+                    "includeArrayIndex": "index",
+                    "preserveNullAndEmptyArrays": true,
+                }
+            },
+        ]
+    );
+}
+
+#[test]
+fn facet() {
+    let pipeline = Pipeline::new().facet([
+        ("count", Pipeline::new().stage(doc! { "$count": "n" })),
+        ("top", Pipeline::new().sort(doc! { "x": -1 }).limit(1)),
+    ]);
+
+    assert_eq!(
+        pipeline.stages(),
+        &[doc! {
+            "$facet": {
+                "count": [{ "$count": "n" }],
+                "top": [{ "$sort": { "x": -1 } }, { "$limit": 1_i64 }],
+            }
+        }]
+    );
+}
+
+#[test]
+fn merge_and_out() {
+    let namespace = Namespace {
+        db: "reporting".to_string(),
+        coll: "totals".to_string(),
+    };
+
+    let pipeline = Pipeline::new().merge(
+        Merge::builder()
+            .into(namespace.clone())
+            .on(vec!["day".to_string()])
+            .when_matched(WhenMatched::Pipeline(
+                Pipeline::new().stage(doc! { "$set": { "total": "$$new.total" } }),
+            ))
+            .when_not_matched(WhenNotMatched::Discard)
+            .build(),
+    );
+    assert_eq!(
+        pipeline.stages(),
+        &[doc! {
+            "$merge": {
+                "into": { "db": "reporting", "coll": "totals" },
+                "on": ["day"],
+                "whenMatched": [{ "$set": { "total": "$$new.total" } }],
+                "whenNotMatched": "discard",
+            }
+        }]
+    );
+
+    let pipeline = Pipeline::new().out(namespace);
+    assert_eq!(
+        pipeline.stages(),
+        &[doc! { "$out": { "db": "reporting", "coll": "totals" } }]
+    );
+}
+
+#[test]
+fn set_window_fields() {
+    let pipeline = Pipeline::new().set_window_fields(
+        SetWindowFields::builder()
+            .partition_by(Bson::from(field("state")))
+            .sort_by(doc! { "date": 1 })
+            .output(vec![
+                WindowOutput::new("runningTotal", Accumulator::sum(field("qty"))).window(
+                    Window::Documents(WindowBound::Unbounded, WindowBound::Current),
+                ),
+                WindowOutput::new("recentAvg", Accumulator::avg(field("qty"))).window(
+                    Window::Range {
+                        lower: WindowBound::Offset(Bson::from(-10)),
+                        upper: WindowBound::Current,
+                        unit: Some("day".to_string()),
+                    },
+                ),
+                WindowOutput::new("rank", Accumulator::rank()),
+            ])
+            .build(),
+    );
+
+    assert_eq!(
+        pipeline.stages(),
+        &[doc! {
+            "$setWindowFields": {
+                "partitionBy": "$state",
+                "sortBy": { "date": 1 },
+                "output": {
+                    "runningTotal": {
+                        "$sum": "$qty",
+                        "window": { "documents": ["unbounded", "current"] },
+                    },
+                    "recentAvg": {
+                        "$avg": "$qty",
+                        "window": { "range": [-10, "current"], "unit": "day" },
+                    },
+                    "rank": { "$rank": {} },
+                },
+            }
+        }]
+    );
+}
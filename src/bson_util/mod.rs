@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     io::{Read, Write},
     time::Duration,
@@ -10,6 +11,7 @@ use serde::{de::Error as SerdeDeError, ser, Deserialize, Deserializer, Serialize
 use crate::{
     bson::{doc, Bson, Document},
     error::{Error, ErrorKind, Result},
+    options::AuthMechanism,
     runtime::{SyncLittleEndianRead, SyncLittleEndianWrite},
 };
 
@@ -35,6 +37,29 @@ pub(crate) fn get_int_raw(val: RawBsonRef<'_>) -> Option<i64> {
     }
 }
 
+/// Compares two values, treating numbers of different types as equal if they have the same value
+/// (e.g. a key of `1` and `1.0`).
+pub(crate) fn value_matches(a: &Bson, b: &Bson) -> bool {
+    match (a, b) {
+        (Bson::Document(a), Bson::Document(b)) => documents_match(a, b),
+        (Bson::Array(a), Bson::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| value_matches(a, b))
+        }
+        _ => match (get_int(a), get_int(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b,
+        },
+    }
+}
+
+/// Compares two documents field by field and in order, using [`value_matches`] for the values.
+pub(crate) fn documents_match(a: &Document, b: &Document) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|((k1, v1), (k2, v2))| k1 == k2 && value_matches(v1, v2))
+}
+
 /// Coerce numeric types into an `u64` if it would be lossless to do so. If this Bson is not numeric
 /// or the conversion would be lossy (e.g. 1.5 -> 1), this returns `None`.
 pub(crate) fn get_u64(val: &Bson) -> Option<u64> {
@@ -189,6 +214,51 @@ where
         .ok_or_else(|| D::Error::custom(format!("could not deserialize u64 from {:?}", bson)))
 }
 
+/// Deserialize a map of u64s from a document whose values may be any BSON number type, if it could
+/// be done losslessly.
+pub(crate) fn deserialize_u64_map_from_bson_numbers<'de, D>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Document::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| match get_u64(&value) {
+            Some(n) => Ok((key, n)),
+            None => Err(D::Error::custom(format!(
+                "could not deserialize u64 from {:?}",
+                value
+            ))),
+        })
+        .collect()
+}
+
+/// Serialize a list of authentication mechanisms as the strings the server uses for them, e.g.
+/// `"SCRAM-SHA-256"`.
+pub(crate) fn serialize_auth_mechanisms<S: Serializer>(
+    val: &Option<Vec<AuthMechanism>>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match val {
+        Some(mechanisms) => serializer.collect_seq(mechanisms.iter().map(AuthMechanism::as_str)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize a list of authentication mechanisms from the strings the server uses for them.
+pub(crate) fn deserialize_auth_mechanisms<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<AuthMechanism>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|mechanism| mechanism.parse().map_err(D::Error::custom))
+        .collect()
+}
+
 /// The size in bytes of the provided document's entry in a BSON array at the given index.
 pub(crate) fn array_entry_size_bytes(index: usize, doc_len: usize) -> u64 {
     // 
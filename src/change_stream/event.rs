@@ -1,14 +1,7 @@
 //! Contains the types related to a `ChangeStream` event.
-use std::convert::TryInto;
+use crate::{coll::Namespace, cursor::CursorSpecification, options::ChangeStreamOptions};
 
-use crate::{
-    coll::Namespace,
-    cursor::CursorSpecification,
-    error::Result,
-    options::ChangeStreamOptions,
-};
-
-use bson::{Bson, Document, RawBson, RawDocument, RawDocumentBuf};
+use bson::{Document, RawBson, RawDocumentBuf};
 use serde::{Deserialize, Serialize};
 
 /// An opaque token used for resuming an interrupted
@@ -1,38 +1,32 @@
 //! Contains the functionality for change streams.
 pub mod event;
-pub(crate) mod options;
+pub mod options;
 pub mod session;
 
 use std::{
     future::Future,
-    marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
 };
 
-use bson::Document;
-use futures_core::Stream;
-use serde::{de::DeserializeOwned, Deserialize};
+use bson::{Document, Timestamp};
+use derivative::Derivative;
+use futures_core::{future::BoxFuture, Stream};
+use serde::de::DeserializeOwned;
 
 use crate::{
-    change_stream::{
-        event::{ChangeStreamEvent, ResumeToken},
-        options::ChangeStreamOptions,
-    },
+    change_stream::{event::ResumeToken, options::ChangeStreamOptions},
+    cmap::StreamDescription,
     cursor::{stream_poll_next, BatchValue, CursorStream, NextInBatchFuture},
-    error::Result,
+    error::{Error, Result},
     operation::AggregateTarget,
-    options::AggregateOptions,
-    selection_criteria::{ReadPreference, SelectionCriteria},
     Client,
-    Collection,
     Cursor,
-    Database,
 };
 
 /// A `ChangeStream` streams the ongoing changes of its associated collection, database or
-/// deployment. `ChangeStream` instances should be created with method `watch` or
-/// `watch_with_pipeline` against the relevant target.
+/// deployment. `ChangeStream` instances should be created with method `watch` against the relevant
+/// target.
 ///
 /// `ChangeStream`s are "resumable", meaning that they can be restarted at a given place in the
 /// stream of events. This is done automatically when the `ChangeStream` encounters certain
@@ -46,19 +40,20 @@ use crate::{
 ///
 /// A `ChangeStream` can be iterated like any other [`Stream`]:
 ///
-/// ```ignore
+/// ```no_run
 /// # #[cfg(not(feature = "sync"))]
 /// # use futures::stream::StreamExt;
-/// # use mongodb::{Client, error::Result, bson::doc,
+/// # use mongodb::{Client, error::Result, bson::{doc, Document},
 /// # change_stream::event::ChangeStreamEvent};
 /// # #[cfg(feature = "async-std-runtime")]
 /// # use async_std::task;
 /// # #[cfg(feature = "tokio-runtime")]
 /// # use tokio::task;
 /// #
+/// # #[cfg(not(feature = "sync"))]
 /// # async fn func() -> Result<()> {
 /// # let client = Client::with_uri_str("mongodb://example.com").await?;
-/// # let coll = client.database("foo").collection("bar");
+/// # let coll = client.database("foo").collection::<Document>("bar");
 /// let mut change_stream = coll.watch(None, None).await?;
 /// let coll_ref = coll.clone();
 /// task::spawn(async move {
@@ -75,7 +70,8 @@ use crate::{
 ///
 /// See the documentation [here](https://docs.mongodb.com/manual/changeStreams) for more
 /// details. Also see the documentation on [usage recommendations](https://docs.mongodb.com/manual/administration/change-streams-production-recommendations/).
-#[derive(Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
 pub struct ChangeStream<T>
 where
     T: DeserializeOwned + Unpin + Send + Sync,
@@ -88,6 +84,10 @@ where
 
     /// The cached resume token.
     resume_token: Option<ResumeToken>,
+
+    /// The in-progress aggregation re-issued to resume this change stream, if any.
+    #[derivative(Debug = "ignore")]
+    pending_resume: Option<BoxFuture<'static, Result<ChangeStream<Document>>>>,
 }
 
 impl<T> ChangeStream<T>
@@ -103,6 +103,7 @@ where
             cursor,
             data,
             resume_token,
+            pending_resume: None,
         }
     }
 
@@ -122,6 +123,7 @@ where
             cursor: self.cursor.with_type(),
             data: self.data,
             resume_token: self.resume_token,
+            pending_resume: self.pending_resume,
         }
     }
 
@@ -137,15 +139,15 @@ where
     /// empty.  This method should be used when storing the resume token in order to ensure the
     /// most up to date token is received, e.g.
     ///
-    /// ```ignore
-    /// # use mongodb::{Client, error::Result};
+    /// ```no_run
+    /// # use mongodb::{bson::Document, Client, error::Result};
     /// # async fn func() -> Result<()> {
     /// # let client = Client::with_uri_str("mongodb://example.com").await?;
-    /// # let coll = client.database("foo").collection("bar");
+    /// # let coll = client.database("foo").collection::<Document>("bar");
     /// let mut change_stream = coll.watch(None, None).await?;
     /// let mut resume_token = None;
     /// while change_stream.is_alive() {
-    ///     if let Some(event) = change_stream.next_if_any() {
+    ///     if let Some(event) = change_stream.next_if_any().await? {
     ///         // process event
     ///     }
     ///     resume_token = change_stream.resume_token().cloned();
@@ -160,6 +162,22 @@ where
             BatchValue::Empty | BatchValue::Exhausted => None,
         })
     }
+
+    /// Re-issues the initial aggregation in the background so that iteration can continue after a
+    /// resumable error.
+    fn start_resume(&mut self) {
+        self.data.resume_attempted = true;
+        let client = self.data.client.clone();
+        let pipeline = self.data.pipeline.clone();
+        let target = self.data.target.clone();
+        let options = self.data.resume_options(self.resume_token.clone());
+        self.pending_resume = Some(Box::pin(async move {
+            client
+                .execute_watch::<Document>(pipeline, options, target)
+                .await
+                .map(ChangeStream::with_type)
+        }));
+    }
 }
 
 #[derive(Debug)]
@@ -185,6 +203,14 @@ pub(crate) struct ChangeStreamData {
     /// Whether or not the change stream has returned a document, used to update resume token
     /// during an automatic resume.
     document_returned: bool,
+
+    /// The `operationTime` returned by the initial aggregation, used to resume a change stream
+    /// that has not yet received a resume token.
+    initial_operation_time: Option<Timestamp>,
+
+    /// The max wire version of the server the change stream is iterating on, used to determine
+    /// whether an error is resumable.
+    max_wire_version: Option<i32>,
 }
 
 impl ChangeStreamData {
@@ -201,6 +227,62 @@ impl ChangeStreamData {
             options,
             resume_attempted: false,
             document_returned: false,
+            initial_operation_time: None,
+            max_wire_version: None,
+        }
+    }
+
+    /// Records the details of the aggregation that created the change stream's cursor.
+    pub(crate) fn record_initial_response(
+        &mut self,
+        operation_time: Option<Timestamp>,
+        description: &StreamDescription,
+    ) {
+        self.initial_operation_time = operation_time;
+        self.max_wire_version = description.max_wire_version;
+    }
+
+    /// Whether the change stream should attempt to resume after the given error.
+    fn should_resume(&self, error: &Error) -> bool {
+        !self.resume_attempted && error.is_resumable(self.max_wire_version)
+    }
+
+    /// Updates this data after the change stream has been resumed using the given new stream
+    /// data. The initial operation time is kept so that repeated resumes before a resume token
+    /// has been received all start at the same point.
+    fn record_resume(&mut self, new_data: &ChangeStreamData) {
+        self.max_wire_version = new_data.max_wire_version;
+    }
+
+    /// Returns the options to use when re-issuing the initial aggregation to resume a change
+    /// stream, starting after the provided cached resume token if there is one. Without a cached
+    /// resume token, the change stream is resumed at the original `startAtOperationTime` if one was
+    /// provided, or otherwise at the operation time of the initial aggregation on 4.0+ servers.
+    fn resume_options(&self, resume_token: Option<ResumeToken>) -> Option<ChangeStreamOptions> {
+        let mut options = self.options.clone().unwrap_or_default();
+        if let Some(token) = resume_token {
+            if options.start_after.is_some() && !self.document_returned {
+                options.start_after = Some(token);
+                options.resume_after = None;
+            } else {
+                options.resume_after = Some(token);
+                options.start_after = None;
+            }
+            options.start_at_operation_time = None;
+        } else if options.start_at_operation_time.is_none()
+            && self.max_wire_version.unwrap_or(0) >= 7
+        {
+            options.start_at_operation_time = self.initial_operation_time;
+        }
+        Some(options)
+    }
+
+    /// Records the outcome of successfully retrieving a batch value so that a subsequent error can
+    /// trigger another resume.
+    fn record_batch_value(&mut self, batch_value: &BatchValue) {
+        self.resume_attempted = false;
+        if matches!(batch_value, BatchValue::Some { .. }) {
+            self.document_returned = true;
         }
     }
 }
@@ -227,13 +309,42 @@ where
     T: DeserializeOwned + Unpin + Send + Sync,
 {
     fn poll_next_in_batch(&mut self, cx: &mut Context<'_>) -> Poll<Result<BatchValue>> {
-        let out = self.cursor.poll_next_in_batch(cx);
-        if let Poll::Ready(Ok(bv)) = &out {
-            if let Some(token) = get_resume_token(bv, self.cursor.post_batch_resume_token())? {
-                self.resume_token = Some(token);
+        loop {
+            if let Some(mut pending) = self.pending_resume.take() {
+                match Pin::new(&mut pending).poll(cx) {
+                    Poll::Pending => {
+                        self.pending_resume = Some(pending);
+                        return Poll::Pending;
+                    }
+                    Poll::Ready(Ok(new_stream)) => {
+                        self.data.record_resume(&new_stream.data);
+                        self.cursor = new_stream.cursor.with_type();
+                        if new_stream.resume_token.is_some() {
+                            self.resume_token = new_stream.resume_token;
+                        }
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                }
+            }
+
+            let out = self.cursor.poll_next_in_batch(cx);
+            match &out {
+                Poll::Ready(Ok(bv)) => {
+                    if let Some(token) =
+                        get_resume_token(bv, self.cursor.post_batch_resume_token())?
+                    {
+                        self.resume_token = Some(token);
+                    }
+                    self.data.record_batch_value(bv);
+                }
+                Poll::Ready(Err(e)) if self.data.should_resume(e) => {
+                    self.start_resume();
+                    continue;
+                }
+                _ => {}
             }
+            return out;
         }
-        out
     }
 }
 
@@ -243,7 +354,7 @@ where
 {
     type Item = Result<T>;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         stream_poll_next(Pin::into_inner(self), cx)
     }
 }
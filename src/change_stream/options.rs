@@ -6,6 +6,7 @@ use typed_builder::TypedBuilder;
 
 use crate::{
     bson::Timestamp,
+    bson_util,
     change_stream::event::ResumeToken,
     collation::Collation,
     concern::ReadConcern,
@@ -89,6 +90,19 @@ pub struct ChangeStreamOptions {
     #[builder(default)]
     #[serde(skip_serializing)]
     pub selection_criteria: Option<SelectionCriteria>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[builder(default)]
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 impl ChangeStreamOptions {
@@ -99,6 +113,7 @@ impl ChangeStreamOptions {
             .max_await_time(self.max_await_time)
             .read_concern(self.read_concern.clone())
             .selection_criteria(self.selection_criteria.clone())
+            .timeout(self.timeout)
             .build()
     }
 }
@@ -6,10 +6,10 @@ use std::{
 
 use bson::Document;
 use futures_core::Stream;
-use futures_util::StreamExt;
 use serde::de::DeserializeOwned;
 
 use crate::{
+    change_stream::event::ChangeStreamEvent,
     cursor::{BatchValue, CursorStream, NextInBatchFuture},
     error::Result,
     ClientSession,
@@ -21,11 +21,12 @@ use super::{event::ResumeToken, get_resume_token, stream_poll_next, ChangeStream
 
 /// A [`SessionChangeStream`] is a change stream that was created with a [`ClientSession`] that must
 /// be iterated using one. To iterate, use [`SessionChangeStream::next`] or retrieve a
-/// [`SessionCursorStream`] using [`SessionChangeStream::stream`]:
+/// [`SessionChangeStreamValues`] using [`SessionChangeStream::values`]:
 ///
-/// ```ignore
+/// ```no_run
 /// # use mongodb::{bson::Document, Client, error::Result, ClientSession, SessionCursor};
 /// #
+/// # #[cfg(not(feature = "sync"))]
 /// # async fn do_stuff() -> Result<()> {
 /// # let client = Client::with_uri_str("mongodb://example.com").await?;
 /// # let mut session = client.start_session(None).await?;
@@ -46,6 +47,7 @@ use super::{event::ResumeToken, get_resume_token, stream_poll_next, ChangeStream
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Debug)]
 pub struct SessionChangeStream<T>
 where
     T: DeserializeOwned + Unpin,
@@ -97,9 +99,13 @@ where
     /// of this stream. In order to do that, either use [`SessionChangeStream::next`] instead or
     /// drop the stream before using the session.
     ///
-    /// ```ignore
+    /// Unlike [`SessionChangeStream::next`] and [`SessionChangeStream::next_if_any`], the returned
+    /// stream does not automatically resume after a resumable error.
+    ///
+    /// ```no_run
     /// # use bson::{doc, Document};
     /// # use mongodb::{Client, change_stream::event::ChangeStreamEvent, error::Result};
+    /// # #[cfg(not(feature = "sync"))]
     /// # fn main() {
     /// # async {
     /// # let client = Client::with_uri_str("foo").await?;
@@ -132,6 +138,8 @@ where
     /// # Ok::<(), mongodb::error::Error>(())
     /// # };
     /// # }
+    /// # #[cfg(feature = "sync")]
+    /// # fn main() {}
     /// ```
     pub fn values<'session>(
         &mut self,
@@ -139,6 +147,7 @@ where
     ) -> SessionChangeStreamValues<'_, 'session, T> {
         SessionChangeStreamValues {
             stream: self.cursor.stream(session),
+            data: &mut self.data,
             resume_token: &mut self.resume_token,
         }
     }
@@ -149,9 +158,10 @@ where
     /// Use this method when the session needs to be used again between iterations or when the added
     /// functionality of `Stream` is not needed.
     ///
-    /// ```ignore
+    /// ```no_run
     /// # use bson::{doc, Document};
     /// # use mongodb::Client;
+    /// # #[cfg(not(feature = "sync"))]
     /// # fn main() {
     /// # async {
     /// # let client = Client::with_uri_str("foo").await?;
@@ -166,9 +176,18 @@ where
     /// # Ok::<(), mongodb::error::Error>(())
     /// # };
     /// # }
+    /// # #[cfg(feature = "sync")]
+    /// # fn main() {}
     /// ```
     pub async fn next(&mut self, session: &mut ClientSession) -> Option<Result<T>> {
-        self.values(session).next().await
+        loop {
+            match self.next_if_any(session).await {
+                Ok(Some(value)) => return Some(Ok(value)),
+                Ok(None) if self.is_alive() => continue,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
     }
 
     /// Returns whether the change stream will continue to receive events.
@@ -183,16 +202,17 @@ where
     /// empty.  This method should be used when storing the resume token in order to ensure the
     /// most up to date token is received, e.g.
     ///
-    /// ```ignore
-    /// # use mongodb::{Client, error::Result};
+    /// ```no_run
+    /// # use mongodb::{bson::Document, Client, error::Result};
+    /// # #[cfg(not(feature = "sync"))]
     /// # async fn func() -> Result<()> {
     /// # let client = Client::with_uri_str("mongodb://example.com").await?;
-    /// # let coll = client.database("foo").collection("bar");
+    /// # let coll = client.database("foo").collection::<Document>("bar");
     /// # let mut session = client.start_session(None).await?;
     /// let mut change_stream = coll.watch_with_session(None, None, &mut session).await?;
     /// let mut resume_token = None;
     /// while change_stream.is_alive() {
-    ///     if let Some(event) = change_stream.next_if_any(&mut session) {
+    ///     if let Some(event) = change_stream.next_if_any(&mut session).await? {
     ///         // process event
     ///     }
     ///     resume_token = change_stream.resume_token().cloned();
@@ -202,7 +222,45 @@ where
     /// # }
     /// ```
     pub async fn next_if_any(&mut self, session: &mut ClientSession) -> Result<Option<T>> {
-        self.values(session).next_if_any().await
+        loop {
+            let result = {
+                let mut values = self.values(session);
+                match values.next_if_any().await {
+                    // When its buffer is empty, a new stream only starts a getMore on the first
+                    // poll, so poll it again to execute the getMore before the stream is dropped.
+                    Ok(None) if values.is_alive() => values.next_if_any().await,
+                    other => other,
+                }
+            };
+            match result {
+                Err(e) if self.data.should_resume(&e) => {
+                    self.resume(session).await?;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Re-issues the initial aggregation using the provided session so that iteration can continue
+    /// after a resumable error.
+    async fn resume(&mut self, session: &mut ClientSession) -> Result<()> {
+        self.data.resume_attempted = true;
+        let client = self.data.client.clone();
+        let options = self.data.resume_options(self.resume_token.clone());
+        let new_stream: SessionChangeStream<ChangeStreamEvent<Document>> = client
+            .execute_watch_with_session(
+                self.data.pipeline.clone(),
+                options,
+                self.data.target.clone(),
+                session,
+            )
+            .await?;
+        self.data.record_resume(&new_stream.data);
+        self.cursor = new_stream.cursor.with_type();
+        if new_stream.resume_token.is_some() {
+            self.resume_token = new_stream.resume_token;
+        }
+        Ok(())
     }
 }
 
@@ -217,6 +275,7 @@ where
     T: DeserializeOwned + Unpin + Send + Sync,
 {
     stream: SessionCursorStream<'cursor, 'session, T>,
+    data: &'cursor mut ChangeStreamData,
     resume_token: &'cursor mut Option<ResumeToken>,
 }
 
@@ -224,14 +283,19 @@ impl<'cursor, 'session, T> SessionChangeStreamValues<'cursor, 'session, T>
 where
     T: DeserializeOwned + Unpin + Send + Sync,
 {
+    /// Returns the cached resume token that can be used to resume after the most recently returned
+    /// change.
     pub fn resume_token(&self) -> Option<&ResumeToken> {
         self.resume_token.as_ref()
     }
 
+    /// Returns whether the change stream will continue to receive events.
     pub fn is_alive(&self) -> bool {
         !self.stream.is_exhausted()
     }
 
+    /// Retrieve the next result from the change stream, if any. See
+    /// [`SessionChangeStream::next_if_any`] for more details.
     pub async fn next_if_any<'a>(&'a mut self) -> Result<Option<T>> {
         Ok(match NextInBatchFuture::new(self).await? {
             BatchValue::Some { doc, .. } => Some(bson::from_slice(doc.as_bytes())?),
@@ -250,6 +314,7 @@ where
             if let Some(token) = get_resume_token(bv, self.stream.post_batch_resume_token())? {
                 *self.resume_token = Some(token);
             }
+            self.data.record_batch_value(bv);
         }
         out
     }
@@ -261,7 +326,7 @@ where
 {
     type Item = Result<T>;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         stream_poll_next(Pin::into_inner(self), cx)
     }
 }
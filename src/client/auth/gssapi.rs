@@ -0,0 +1,403 @@
+use std::{net::IpAddr, sync::Arc};
+
+use crate::{
+    bson::Bson,
+    client::{
+        auth::{
+            sasl::{SaslContinue, SaslResponse, SaslStart},
+            AuthMechanism,
+            Credential,
+            GSSAPI_STR,
+        },
+        options::{ServerAddress, ServerApi},
+    },
+    cmap::Connection,
+    error::{Error, ErrorKind, Result},
+    runtime::AsyncResolver,
+};
+
+const SERVICE_NAME: &str = "SERVICE_NAME";
+const CANONICALIZE_HOST_NAME: &str = "CANONICALIZE_HOST_NAME";
+const SERVICE_REALM: &str = "SERVICE_REALM";
+const DEFAULT_SERVICE_NAME: &str = "mongodb";
+
+/// Provides the Kerberos security contexts used to perform GSSAPI authentication.
+///
+/// When the `gssapi-auth` feature flag is enabled, a provider backed by the system's Kerberos
+/// library is used by default. A custom provider can be supplied via
+/// [`Credential::gssapi_token_provider`], e.g. to obtain tokens from a different source.
+pub trait GssapiTokenProvider: Send + Sync {
+    /// Starts a new security context for authenticating as `user_principal` to the service
+    /// identified by `service_principal` (e.g. `mongodb/db.example.com@EXAMPLE.COM`). If no user
+    /// principal is provided, the default principal from the credential cache should be used.
+    fn start_context(
+        &self,
+        user_principal: Option<&str>,
+        service_principal: &str,
+    ) -> Result<Box<dyn GssapiContext>>;
+}
+
+/// A Kerberos security context established with a server over the course of a single GSSAPI
+/// authentication conversation.
+pub trait GssapiContext: Send {
+    /// Advances the context using the token most recently received from the server, or `None` when
+    /// starting the conversation, and returns the token to send to the server in response. An
+    /// empty token should be returned if there is nothing to send.
+    fn step(&mut self, server_token: Option<&[u8]>) -> Result<Vec<u8>>;
+
+    /// Whether the context has been fully established.
+    fn is_complete(&self) -> bool;
+
+    /// Unwraps a message that was wrapped by the server using this context.
+    fn unwrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>>;
+
+    /// Wraps a message to be sent to the server using this context.
+    fn wrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// How the hostname of a server is canonicalized before being used in the service principal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CanonicalizeHostName {
+    /// The hostname is used as provided.
+    None,
+
+    /// The hostname is replaced with its canonical name as determined by a forward DNS lookup.
+    Forward,
+
+    /// The hostname is resolved to an IP address, which is then resolved to a hostname via a
+    /// reverse DNS lookup.
+    ForwardAndReverse,
+}
+
+/// The GSSAPI-specific properties of a [`Credential`], parsed from its `mechanism_properties`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct GssapiProperties {
+    pub(crate) service_name: String,
+    pub(crate) canonicalize_host_name: CanonicalizeHostName,
+    pub(crate) service_realm: Option<String>,
+}
+
+impl GssapiProperties {
+    pub(crate) fn from_credential(credential: &Credential) -> Result<Self> {
+        let mut properties = Self {
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            canonicalize_host_name: CanonicalizeHostName::None,
+            service_realm: None,
+        };
+
+        let document = match credential.mechanism_properties {
+            Some(ref document) => document,
+            None => return Ok(properties),
+        };
+
+        if let Some(value) = document.get(SERVICE_NAME) {
+            properties.service_name = property_str(SERVICE_NAME, value)?.to_string();
+        }
+
+        if let Some(value) = document.get(CANONICALIZE_HOST_NAME) {
+            properties.canonicalize_host_name = match value {
+                Bson::Boolean(false) => CanonicalizeHostName::None,
+                Bson::Boolean(true) => CanonicalizeHostName::ForwardAndReverse,
+                Bson::String(s) => match s.as_str() {
+                    "none" => CanonicalizeHostName::None,
+                    "forward" => CanonicalizeHostName::Forward,
+                    "forwardAndReverse" => CanonicalizeHostName::ForwardAndReverse,
+                    _ => return Err(invalid_property(CANONICALIZE_HOST_NAME)),
+                },
+                _ => return Err(invalid_property(CANONICALIZE_HOST_NAME)),
+            };
+        }
+
+        if let Some(value) = document.get(SERVICE_REALM) {
+            properties.service_realm = Some(property_str(SERVICE_REALM, value)?.to_string());
+        }
+
+        Ok(properties)
+    }
+
+    /// Builds the principal of the service running on the given host.
+    pub(crate) fn service_principal(&self, host: &str) -> String {
+        let mut principal = format!("{}/{}", self.service_name, host);
+        if let Some(ref realm) = self.service_realm {
+            principal.push('@');
+            principal.push_str(realm);
+        }
+        principal
+    }
+}
+
+fn property_str<'a>(name: &str, value: &'a Bson) -> Result<&'a str> {
+    match value {
+        Bson::String(s) => Ok(s.as_str()),
+        _ => Err(invalid_property(name)),
+    }
+}
+
+fn invalid_property(name: &str) -> Error {
+    ErrorKind::InvalidArgument {
+        message: format!("invalid value for GSSAPI mechanism property {}", name),
+    }
+    .into()
+}
+
+/// Performs GSSAPI authentication for a given stream.
+pub(super) async fn authenticate_stream(
+    conn: &mut Connection,
+    credential: &Credential,
+    server_api: Option<&ServerApi>,
+) -> Result<()> {
+    let properties = GssapiProperties::from_credential(credential)?;
+    let user_principal = credential
+        .username
+        .as_deref()
+        .ok_or_else(|| Error::authentication_error(GSSAPI_STR, "no username supplied"))?;
+
+    let host = canonicalize_host_name(&conn.address, properties.canonicalize_host_name).await?;
+    let service_principal = properties.service_principal(&host);
+
+    let provider = match credential.gssapi_token_provider {
+        Some(ref provider) => provider.clone(),
+        None => default_token_provider()?,
+    };
+    let context = provider.start_context(Some(user_principal), &service_principal)?;
+    let mut conversation = Conversation::new(context, user_principal);
+
+    let source = credential.source.as_deref().unwrap_or("$external");
+    let sasl_start = SaslStart::new(
+        source.into(),
+        AuthMechanism::Gssapi,
+        conversation.initial_payload()?,
+        server_api.cloned(),
+    )
+    .into_command();
+
+    let response = conn.send_command(sasl_start, None).await?;
+    let mut sasl_response =
+        SaslResponse::parse(GSSAPI_STR, response.auth_response_body(GSSAPI_STR)?)?;
+
+    while !conversation.is_finished() {
+        if sasl_response.done {
+            return Err(Error::invalid_authentication_response(GSSAPI_STR));
+        }
+
+        let sasl_continue = SaslContinue::new(
+            source.into(),
+            sasl_response.conversation_id.clone(),
+            conversation.respond(&sasl_response.payload)?,
+            server_api.cloned(),
+        )
+        .into_command();
+
+        let response = conn.send_command(sasl_continue, None).await?;
+        sasl_response = SaslResponse::parse(GSSAPI_STR, response.auth_response_body(GSSAPI_STR)?)?;
+    }
+
+    if !sasl_response.done {
+        return Err(Error::invalid_authentication_response(GSSAPI_STR));
+    }
+
+    Ok(())
+}
+
+/// Drives the client side of a GSSAPI SASL conversation as described in
+/// [RFC 4752](https://tools.ietf.org/html/rfc4752).
+pub(super) struct Conversation<'a> {
+    context: Box<dyn GssapiContext>,
+    user_principal: &'a str,
+    security_layer_negotiated: bool,
+}
+
+impl<'a> Conversation<'a> {
+    pub(super) fn new(context: Box<dyn GssapiContext>, user_principal: &'a str) -> Self {
+        Self {
+            context,
+            user_principal,
+            security_layer_negotiated: false,
+        }
+    }
+
+    /// The payload of the `saslStart` command.
+    pub(super) fn initial_payload(&mut self) -> Result<Vec<u8>> {
+        self.context.step(None)
+    }
+
+    /// Computes the payload of the next `saslContinue` command from the payload of the server's
+    /// most recent response.
+    pub(super) fn respond(&mut self, server_payload: &[u8]) -> Result<Vec<u8>> {
+        if !self.context.is_complete() {
+            return self.context.step(Some(server_payload));
+        }
+
+        if self.security_layer_negotiated {
+            return Err(Error::invalid_authentication_response(GSSAPI_STR));
+        }
+
+        // Once the context is established, the server sends the security layers it supports and
+        // its maximum message size. No security layer is used on top of TLS, so the client
+        // responds with the "no security layer" bit, a maximum message size of zero, and the
+        // principal to authorize as.
+        let server_security_layer = self.context.unwrap_message(server_payload)?;
+        if server_security_layer.len() != 4 {
+            return Err(Error::invalid_authentication_response(GSSAPI_STR));
+        }
+
+        let mut client_security_layer = vec![1, 0, 0, 0];
+        client_security_layer.extend(self.user_principal.as_bytes());
+        self.security_layer_negotiated = true;
+
+        self.context.wrap_message(&client_security_layer)
+    }
+
+    /// Whether the client has sent all of its messages in the conversation.
+    pub(super) fn is_finished(&self) -> bool {
+        self.security_layer_negotiated
+    }
+}
+
+async fn canonicalize_host_name(
+    address: &ServerAddress,
+    canonicalize: CanonicalizeHostName,
+) -> Result<String> {
+    let host = address.host();
+    if canonicalize == CanonicalizeHostName::None || host.parse::<IpAddr>().is_ok() {
+        return Ok(host.to_string());
+    }
+
+    let resolver = AsyncResolver::new(None).await?;
+    let canonical_name = match canonicalize {
+        CanonicalizeHostName::Forward => resolver.canonical_name(host.as_ref()).await?,
+        _ => {
+            let ip = resolver.lookup_ip(host.as_ref()).await?;
+            resolver.reverse_lookup(ip).await?
+        }
+    };
+
+    Ok(canonical_name
+        .map(|name| name.trim_end_matches('.').to_lowercase())
+        .unwrap_or_else(|| host.to_string()))
+}
+
+#[cfg(feature = "gssapi-auth")]
+fn default_token_provider() -> Result<Arc<dyn GssapiTokenProvider>> {
+    Ok(Arc::new(kerberos::KerberosTokenProvider))
+}
+
+#[cfg(not(feature = "gssapi-auth"))]
+fn default_token_provider() -> Result<Arc<dyn GssapiTokenProvider>> {
+    Err(ErrorKind::Authentication {
+        message: "GSSAPI authentication requires either the gssapi-auth feature flag or a \
+                  Credential::gssapi_token_provider"
+            .into(),
+    }
+    .into())
+}
+
+#[cfg(feature = "gssapi-auth")]
+mod kerberos {
+    use cross_krb5::{ClientCtx, InitiateFlags, K5Ctx, PendingClientCtx, Step};
+
+    use super::{GssapiContext, GssapiTokenProvider};
+    use crate::{
+        client::auth::GSSAPI_STR,
+        error::{Error, Result},
+    };
+
+    /// A [`GssapiTokenProvider`] backed by the system's Kerberos library.
+    pub(super) struct KerberosTokenProvider;
+
+    impl GssapiTokenProvider for KerberosTokenProvider {
+        fn start_context(
+            &self,
+            user_principal: Option<&str>,
+            service_principal: &str,
+        ) -> Result<Box<dyn GssapiContext>> {
+            Ok(Box::new(KerberosContext {
+                state: State::Initial {
+                    user_principal: user_principal.map(String::from),
+                    service_principal: service_principal.to_string(),
+                },
+            }))
+        }
+    }
+
+    enum State {
+        Initial {
+            user_principal: Option<String>,
+            service_principal: String,
+        },
+        Pending(PendingClientCtx),
+        Complete(ClientCtx),
+        Failed,
+    }
+
+    struct KerberosContext {
+        state: State,
+    }
+
+    fn kerberos_error(error: impl std::fmt::Display) -> Error {
+        Error::authentication_error(GSSAPI_STR, &error.to_string())
+    }
+
+    impl GssapiContext for KerberosContext {
+        fn step(&mut self, server_token: Option<&[u8]>) -> Result<Vec<u8>> {
+            match (
+                std::mem::replace(&mut self.state, State::Failed),
+                server_token,
+            ) {
+                (
+                    State::Initial {
+                        user_principal,
+                        service_principal,
+                    },
+                    None,
+                ) => {
+                    let (pending, token) = ClientCtx::new(
+                        InitiateFlags::empty(),
+                        user_principal.as_deref(),
+                        &service_principal,
+                        None,
+                    )
+                    .map_err(kerberos_error)?;
+                    self.state = State::Pending(pending);
+                    Ok(token.to_vec())
+                }
+                (State::Pending(pending), Some(server_token)) => {
+                    match pending.step(server_token).map_err(kerberos_error)? {
+                        Step::Continue((pending, token)) => {
+                            self.state = State::Pending(pending);
+                            Ok(token.to_vec())
+                        }
+                        Step::Finished((context, token)) => {
+                            self.state = State::Complete(context);
+                            Ok(token.map(|t| t.to_vec()).unwrap_or_default())
+                        }
+                    }
+                }
+                _ => Err(Error::unknown_authentication_error(GSSAPI_STR)),
+            }
+        }
+
+        fn is_complete(&self) -> bool {
+            matches!(self.state, State::Complete(..))
+        }
+
+        fn unwrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>> {
+            match self.state {
+                State::Complete(ref mut context) => {
+                    Ok(context.unwrap(message).map_err(kerberos_error)?.to_vec())
+                }
+                _ => Err(Error::unknown_authentication_error(GSSAPI_STR)),
+            }
+        }
+
+        fn wrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>> {
+            match self.state {
+                State::Complete(ref mut context) => Ok(context
+                    .wrap(false, message)
+                    .map_err(kerberos_error)?
+                    .to_vec()),
+                _ => Err(Error::unknown_authentication_error(GSSAPI_STR)),
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 #[cfg(feature = "aws-auth")]
 mod aws;
+mod gssapi;
 mod plain;
 mod sasl;
 mod scram;
@@ -10,13 +11,15 @@ mod scram;
 mod test;
 mod x509;
 
-use std::{borrow::Cow, fmt::Debug, str::FromStr};
+use std::{borrow::Cow, fmt::Debug, str::FromStr, sync::Arc};
 
+use derivative::Derivative;
 use hmac::{Mac, NewMac};
 use rand::Rng;
 use serde::Deserialize;
 use typed_builder::TypedBuilder;
 
+pub use self::gssapi::{GssapiContext, GssapiTokenProvider};
 use self::scram::ScramVersion;
 use crate::{
     bson::Document,
@@ -64,7 +67,9 @@ pub enum AuthMechanism {
     ///
     /// See the [MongoDB documentation](https://docs.mongodb.com/manual/core/kerberos/) for more information.
     ///
-    /// Note: This mechanism is not currently supported by this driver but will be in the future.
+    /// Note: GSSAPI authentication requires either the `gssapi-auth` feature flag, which uses the
+    /// system's Kerberos library, or a custom
+    /// [`gssapi_token_provider`](Credential::gssapi_token_provider) to be set on the credential.
     Gssapi,
 
     /// The SASL PLAIN mechanism, as defined in [RFC 4616](), is used in MongoDB to perform LDAP
@@ -141,6 +146,26 @@ impl AuthMechanism {
 
                 Ok(())
             }
+            AuthMechanism::Gssapi => {
+                if credential.username.is_none() {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: "No username provided for GSSAPI authentication".to_string(),
+                    }
+                    .into());
+                }
+
+                if credential.source.as_deref().unwrap_or("$external") != "$external" {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: "only $external may be specified as an auth source for GSSAPI"
+                            .to_string(),
+                    }
+                    .into());
+                }
+
+                gssapi::GssapiProperties::from_credential(credential)?;
+
+                Ok(())
+            }
             AuthMechanism::Plain => {
                 if credential.username.is_none() {
                     return Err(ErrorKind::InvalidArgument {
@@ -199,16 +224,14 @@ impl AuthMechanism {
     /// Get the default authSource for a given mechanism depending on the database provided in the
     /// connection string.
     pub(crate) fn default_source<'a>(&'a self, uri_db: Option<&'a str>) -> &'a str {
-        // TODO: fill in others as they're implemented
         match self {
             AuthMechanism::ScramSha1 | AuthMechanism::ScramSha256 | AuthMechanism::MongoDbCr => {
                 uri_db.unwrap_or("admin")
             }
-            AuthMechanism::MongoDbX509 => "$external",
+            AuthMechanism::MongoDbX509 | AuthMechanism::Gssapi => "$external",
             AuthMechanism::Plain => uri_db.unwrap_or("$external"),
             #[cfg(feature = "aws-auth")]
             AuthMechanism::MongoDbAws => "$external",
-            _ => "",
         }
     }
 
@@ -233,7 +256,7 @@ impl AuthMechanism {
             Self::MongoDbX509 => Ok(Some(ClientFirst::X509(Box::new(
                 x509::build_speculative_client_first(credential),
             )))),
-            Self::Plain => Ok(None),
+            Self::Plain | Self::Gssapi => Ok(None),
             #[cfg(feature = "aws-auth")]
             AuthMechanism::MongoDbAws => Ok(None),
             AuthMechanism::MongoDbCr => Err(ErrorKind::Authentication {
@@ -242,10 +265,6 @@ impl AuthMechanism {
                     .into(),
             }
             .into()),
-            _ => Err(ErrorKind::Authentication {
-                message: format!("Authentication mechanism {:?} not yet implemented.", self),
-            }
-            .into()),
         }
     }
 
@@ -275,6 +294,9 @@ impl AuthMechanism {
             AuthMechanism::Plain => {
                 plain::authenticate_stream(stream, credential, server_api).await
             }
+            AuthMechanism::Gssapi => {
+                gssapi::authenticate_stream(stream, credential, server_api).await
+            }
             #[cfg(feature = "aws-auth")]
             AuthMechanism::MongoDbAws => {
                 aws::authenticate_stream(stream, credential, server_api, http_client).await
@@ -285,10 +307,6 @@ impl AuthMechanism {
                     .into(),
             }
             .into()),
-            _ => Err(ErrorKind::Authentication {
-                message: format!("Authentication mechanism {:?} not yet implemented.", self),
-            }
-            .into()),
         }
     }
 }
@@ -327,7 +345,8 @@ impl FromStr for AuthMechanism {
 ///
 /// Some fields (mechanism and source) may be omitted and will either be negotiated or assigned a
 /// default value, depending on the values of other fields in the credential.
-#[derive(Clone, Default, Deserialize, TypedBuilder, PartialEq)]
+#[derive(Clone, Default, Deserialize, TypedBuilder, Derivative)]
+#[derivative(PartialEq)]
 #[builder(field_defaults(default, setter(into)))]
 #[non_exhaustive]
 pub struct Credential {
@@ -348,7 +367,22 @@ pub struct Credential {
     pub mechanism: Option<AuthMechanism>,
 
     /// Additional properties for the given mechanism.
+    ///
+    /// The following properties are supported for GSSAPI:
+    ///   * `SERVICE_NAME`: the service name of the server's principal. Defaults to "mongodb".
+    ///   * `CANONICALIZE_HOST_NAME`: how to canonicalize the server's hostname before using it in
+    ///     the service principal; one of `"none"`, `"forward"`, `"forwardAndReverse"`, or a
+    ///     boolean, where `true` is equivalent to `"forwardAndReverse"`. Defaults to `"none"`.
+    ///   * `SERVICE_REALM`: the realm of the server's principal, if it differs from the realm of
+    ///     the user.
     pub mechanism_properties: Option<Document>,
+
+    /// The provider of Kerberos security contexts to use for GSSAPI authentication. If not
+    /// provided, the system's Kerberos library will be used when the `gssapi-auth` feature flag is
+    /// enabled.
+    #[derivative(PartialEq = "ignore")]
+    #[serde(skip)]
+    pub gssapi_token_provider: Option<Arc<dyn GssapiTokenProvider>>,
 }
 
 impl Credential {
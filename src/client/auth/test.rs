@@ -1,8 +1,17 @@
 use lazy_static::lazy_static;
 
-use crate::{cmap::StreamDescription, options::AuthMechanism};
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    error::Result,
+    options::{AuthMechanism, Credential},
+};
 
-use super::sasl::SaslStart;
+use super::{
+    gssapi::{CanonicalizeHostName, Conversation, GssapiProperties},
+    sasl::SaslStart,
+    GssapiContext,
+};
 
 lazy_static! {
     static ref MECHS: [String; 2] = [
@@ -106,3 +115,118 @@ async fn sasl_first_options_not_specified() {
         "SaslStart should not contain options document for X.509 authentication"
     );
 }
+
+/// A fake security context that completes after exchanging two tokens with the server and
+/// "wraps" messages by prepending a marker byte.
+struct FakeGssapiContext {
+    steps: u8,
+}
+
+impl GssapiContext for FakeGssapiContext {
+    fn step(&mut self, server_token: Option<&[u8]>) -> Result<Vec<u8>> {
+        assert_eq!(
+            server_token.map(|t| t[0]),
+            self.steps.checked_sub(1).map(|s| s + 10)
+        );
+        self.steps += 1;
+        Ok(vec![self.steps])
+    }
+
+    fn is_complete(&self) -> bool {
+        self.steps == 2
+    }
+
+    fn unwrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>> {
+        assert_eq!(message[0], 0xff);
+        Ok(message[1..].to_vec())
+    }
+
+    fn wrap_message(&mut self, message: &[u8]) -> Result<Vec<u8>> {
+        let mut wrapped = vec![0xff];
+        wrapped.extend(message);
+        Ok(wrapped)
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn gssapi_conversation() {
+    let mut conversation = Conversation::new(Box::new(FakeGssapiContext { steps: 0 }), "user");
+
+    assert_eq!(conversation.initial_payload().unwrap(), vec![1]);
+    assert!(!conversation.is_finished());
+
+    assert_eq!(conversation.respond(&[10]).unwrap(), vec![2]);
+    assert!(!conversation.is_finished());
+
+    let response = conversation.respond(&[0xff, 1, 0, 0, 0]).unwrap();
+    assert_eq!(response, b"\xff\x01\x00\x00\x00user".to_vec());
+    assert!(conversation.is_finished());
+
+    assert!(conversation.respond(&[0xff, 1, 0, 0, 0]).is_err());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn gssapi_conversation_invalid_security_layer() {
+    let mut conversation = Conversation::new(Box::new(FakeGssapiContext { steps: 0 }), "user");
+    conversation.initial_payload().unwrap();
+    conversation.respond(&[10]).unwrap();
+
+    assert!(conversation.respond(&[0xff, 1, 0]).is_err());
+    assert!(!conversation.is_finished());
+}
+
+fn gssapi_credential(properties: Option<crate::bson::Document>) -> Credential {
+    Credential::builder()
+        .username("user@EXAMPLE.COM".to_string())
+        .mechanism(AuthMechanism::Gssapi)
+        .mechanism_properties(properties)
+        .build()
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn gssapi_properties() {
+    let properties = GssapiProperties::from_credential(&gssapi_credential(None)).unwrap();
+    assert_eq!(
+        properties.canonicalize_host_name,
+        CanonicalizeHostName::None
+    );
+    assert_eq!(
+        properties.service_principal("db.example.com"),
+        "mongodb/db.example.com"
+    );
+
+    let properties = GssapiProperties::from_credential(&gssapi_credential(Some(doc! {
+        "SERVICE_NAME": "other",
+        "CANONICALIZE_HOST_NAME": true,
+        "SERVICE_REALM": "EXAMPLE.COM",
+    })))
+    .unwrap();
+    assert_eq!(
+        properties.canonicalize_host_name,
+        CanonicalizeHostName::ForwardAndReverse
+    );
+    assert_eq!(
+        properties.service_principal("db.example.com"),
+        "other/db.example.com@EXAMPLE.COM"
+    );
+
+    let properties = GssapiProperties::from_credential(&gssapi_credential(Some(doc! {
+        "CANONICALIZE_HOST_NAME": "forward",
+    })))
+    .unwrap();
+    assert_eq!(
+        properties.canonicalize_host_name,
+        CanonicalizeHostName::Forward
+    );
+
+    for invalid in [
+        doc! { "CANONICALIZE_HOST_NAME": "sideways" },
+        doc! { "CANONICALIZE_HOST_NAME": 1 },
+        doc! { "SERVICE_NAME": false },
+    ] {
+        assert!(GssapiProperties::from_credential(&gssapi_credential(Some(invalid))).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Automatic encryption of commands based on the JSON schemas provided in
+//! [`AutoEncryptionOptions::schema_map`](super::options::AutoEncryptionOptions::schema_map), and
+//! automatic decryption of replies.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use super::{
+    crypt::{self, EncryptedValue},
+    encryption_error,
+    options::Algorithm,
+};
+use crate::{
+    bson::{spec::BinarySubtype, Bson, Document},
+    error::Result,
+};
+
+/// How a single field is encrypted.
+#[derive(Clone, Debug)]
+struct EncryptedField {
+    key_id: Vec<u8>,
+    algorithm: Algorithm,
+}
+
+#[derive(Debug)]
+enum Node {
+    Encrypted(EncryptedField),
+    Object(Schema),
+}
+
+/// The encrypted fields of a collection's documents, as described by a JSON schema.
+#[derive(Debug, Default)]
+pub(crate) struct Schema {
+    properties: HashMap<String, Node>,
+}
+
+/// The `keyId` and `algorithm` of an `encrypt` or `encryptMetadata` object, either of which may be
+/// inherited from an enclosing `encryptMetadata`.
+#[derive(Clone, Default)]
+struct EncryptMetadata {
+    key_id: Option<Vec<u8>>,
+    algorithm: Option<Algorithm>,
+}
+
+impl EncryptMetadata {
+    fn parse(document: &Document, inherited: &EncryptMetadata) -> Result<Self> {
+        let key_id = match document.get("keyId") {
+            Some(Bson::Array(key_ids)) => match key_ids.as_slice() {
+                [Bson::Binary(key_id)] if key_id.subtype == BinarySubtype::Uuid => {
+                    Some(key_id.bytes.clone())
+                }
+                _ => {
+                    return Err(encryption_error(
+                        "keyId must be an array containing a single UUID",
+                    ))
+                }
+            },
+            Some(_) => {
+                return Err(encryption_error(
+                    "keyId must be an array containing a single UUID",
+                ))
+            }
+            None => inherited.key_id.clone(),
+        };
+        let algorithm = match document.get("algorithm") {
+            Some(Bson::String(name)) => Some(Algorithm::from_name(name).ok_or_else(|| {
+                encryption_error(format!("unsupported encryption algorithm {:?}", name))
+            })?),
+            Some(_) => return Err(encryption_error("algorithm must be a string")),
+            None => inherited.algorithm,
+        };
+        Ok(Self { key_id, algorithm })
+    }
+}
+
+impl Schema {
+    pub(crate) fn parse(schema: &Document) -> Result<Self> {
+        Self::parse_object(schema, &EncryptMetadata::default())
+    }
+
+    fn parse_object(schema: &Document, inherited: &EncryptMetadata) -> Result<Self> {
+        let metadata = match schema.get_document("encryptMetadata") {
+            Ok(metadata) => EncryptMetadata::parse(metadata, inherited)?,
+            Err(_) => inherited.clone(),
+        };
+
+        let mut properties = HashMap::new();
+        if let Ok(props) = schema.get_document("properties") {
+            for (name, property) in props {
+                let property = match property {
+                    Bson::Document(property) => property,
+                    _ => continue,
+                };
+                if let Ok(encrypt) = property.get_document("encrypt") {
+                    let field = EncryptMetadata::parse(encrypt, &metadata)?;
+                    let field = match (field.key_id, field.algorithm) {
+                        (Some(key_id), Some(algorithm)) => EncryptedField { key_id, algorithm },
+                        _ => {
+                            return Err(encryption_error(format!(
+                                "the encrypt schema for field {:?} must specify a keyId and an \
+                                 algorithm",
+                                name
+                            )))
+                        }
+                    };
+                    properties.insert(name.clone(), Node::Encrypted(field));
+                } else if property.contains_key("properties") {
+                    let object = Self::parse_object(property, &metadata)?;
+                    properties.insert(name.clone(), Node::Object(object));
+                }
+            }
+        }
+
+        Ok(Self { properties })
+    }
+
+    /// The ids of all of the data keys referenced by the schema.
+    pub(crate) fn key_ids(&self) -> Vec<Vec<u8>> {
+        fn collect(schema: &Schema, key_ids: &mut HashSet<Vec<u8>>) {
+            for node in schema.properties.values() {
+                match node {
+                    Node::Encrypted(field) => {
+                        key_ids.insert(field.key_id.clone());
+                    }
+                    Node::Object(object) => collect(object, key_ids),
+                }
+            }
+        }
+
+        let mut key_ids = HashSet::new();
+        collect(self, &mut key_ids);
+        key_ids.into_iter().collect()
+    }
+
+    /// Looks up the schema node for a possibly dotted field path.
+    fn lookup(&self, path: &str) -> Option<&Node> {
+        let mut parts = path.splitn(2, '.');
+        let node = self.properties.get(parts.next()?)?;
+        match (node, parts.next()) {
+            (_, None) => Some(node),
+            (Node::Object(object), Some(rest)) => object.lookup(rest),
+            (Node::Encrypted(_), Some(_)) => None,
+        }
+    }
+}
+
+struct Encryptor<'a> {
+    keys: &'a HashMap<Vec<u8>, Arc<Vec<u8>>>,
+}
+
+impl Encryptor<'_> {
+    fn encrypt_value(&self, value: &mut Bson, field: &EncryptedField) -> Result<()> {
+        if matches!(value, Bson::Binary(b) if b.subtype == BinarySubtype::Encrypted) {
+            return Ok(());
+        }
+        let key = self
+            .keys
+            .get(&field.key_id)
+            .ok_or_else(|| encryption_error("data key not found"))?;
+        *value = Bson::Binary(crypt::encrypt_value(
+            value,
+            &field.key_id,
+            key,
+            field.algorithm,
+        )?);
+        Ok(())
+    }
+
+    /// Encrypts the fields of a document to be stored in the collection.
+    fn encrypt_document(&self, document: &mut Document, schema: &Schema) -> Result<()> {
+        for (name, node) in &schema.properties {
+            match (node, document.get_mut(name)) {
+                (Node::Encrypted(field), Some(value)) => self.encrypt_value(value, field)?,
+                (Node::Object(object), Some(Bson::Document(subdocument))) => {
+                    self.encrypt_document(subdocument, object)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Encrypts the values of a document mapping possibly dotted field paths to values, such as the
+    /// argument to a `$set` update operator.
+    fn encrypt_paths(&self, document: &mut Document, schema: &Schema) -> Result<()> {
+        for_each_value_mut(document, |path, value| {
+            match (schema.lookup(path), value) {
+                (Some(Node::Encrypted(field)), value) => self.encrypt_value(value, field)?,
+                (Some(Node::Object(object)), Bson::Document(subdocument)) => {
+                    self.encrypt_document(subdocument, object)?
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    }
+
+    /// Encrypts the values compared against encrypted fields in a query filter. Only equality
+    /// comparisons on deterministically encrypted fields are supported.
+    fn encrypt_filter(&self, filter: &mut Document, schema: &Schema) -> Result<()> {
+        for_each_value_mut(filter, |key, value| {
+            if key == "$and" || key == "$or" || key == "$nor" {
+                if let Bson::Array(clauses) = value {
+                    for clause in clauses {
+                        if let Bson::Document(clause) = clause {
+                            self.encrypt_filter(clause, schema)?;
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
+            match schema.lookup(key) {
+                Some(Node::Encrypted(field)) => {
+                    if field.algorithm != Algorithm::Deterministic {
+                        return Err(encryption_error(format!(
+                            "cannot query on field {:?}, which is encrypted with the random \
+                             algorithm",
+                            key
+                        )));
+                    }
+                    self.encrypt_comparison(key, value, field)?;
+                }
+                Some(Node::Object(object)) => {
+                    if let Bson::Document(subdocument) = value {
+                        if !is_operator_document(subdocument) {
+                            self.encrypt_document(subdocument, object)?;
+                        }
+                    }
+                }
+                None => {}
+            }
+            Ok(())
+        })
+    }
+
+    fn encrypt_comparison(
+        &self,
+        key: &str,
+        value: &mut Bson,
+        field: &EncryptedField,
+    ) -> Result<()> {
+        let operators = match value {
+            Bson::Document(operators) if is_operator_document(operators) => operators,
+            value => return self.encrypt_value(value, field),
+        };
+
+        for_each_value_mut(operators, |operator, operand| {
+            match (operator, operand) {
+                ("$eq", operand) | ("$ne", operand) => self.encrypt_value(operand, field)?,
+                ("$in", Bson::Array(operands)) | ("$nin", Bson::Array(operands)) => {
+                    for operand in operands {
+                        self.encrypt_value(operand, field)?;
+                    }
+                }
+                ("$exists", _) => {}
+                (operator, _) => {
+                    return Err(encryption_error(format!(
+                        "unsupported operator {} on encrypted field {:?}",
+                        operator, key
+                    )))
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Encrypts the update document or replacement document of an update.
+    fn encrypt_update(&self, update: &mut Bson, schema: &Schema) -> Result<()> {
+        match update {
+            Bson::Document(update) if is_operator_document(update) => {
+                for_each_value_mut(update, |operator, arguments| {
+                    match (operator, arguments) {
+                        ("$set", Bson::Document(arguments))
+                        | ("$setOnInsert", Bson::Document(arguments)) => {
+                            self.encrypt_paths(arguments, schema)?
+                        }
+                        ("$unset", _) | ("$currentDate", _) | ("$rename", _) => {}
+                        (operator, Bson::Document(arguments))
+                            if arguments.keys().any(|path| schema.lookup(path).is_some()) =>
+                        {
+                            return Err(encryption_error(format!(
+                                "the {} update operator is not supported on encrypted fields",
+                                operator
+                            )));
+                        }
+                        _ => {}
+                    }
+                    Ok(())
+                })
+            }
+            Bson::Document(replacement) => self.encrypt_document(replacement, schema),
+            Bson::Array(_) => Err(encryption_error(
+                "pipeline updates are not supported on collections with encrypted fields",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn encrypt_pipeline(&self, pipeline: &mut [Bson], schema: &Schema) -> Result<()> {
+        for stage in pipeline {
+            if let Bson::Document(stage) = stage {
+                if let Ok(filter) = stage.get_document_mut("$match") {
+                    self.encrypt_filter(filter, schema)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Calls `f` with each key and mutable value of the document, in order.
+fn for_each_value_mut(
+    document: &mut Document,
+    mut f: impl FnMut(&str, &mut Bson) -> Result<()>,
+) -> Result<()> {
+    let mut result = Ok(());
+    for (key, mut value) in std::mem::take(document) {
+        if result.is_ok() {
+            result = f(&key, &mut value);
+        }
+        document.insert(key, value);
+    }
+    result
+}
+
+fn is_operator_document(document: &Document) -> bool {
+    document
+        .keys()
+        .next()
+        .map_or(false, |key| key.starts_with('$'))
+}
+
+fn documents_mut<'a>(
+    command: &'a mut Document,
+    key: &str,
+) -> impl Iterator<Item = &'a mut Document> {
+    let documents = match command.get_mut(key) {
+        Some(Bson::Array(documents)) => Some(documents),
+        _ => None,
+    };
+    documents
+        .into_iter()
+        .flatten()
+        .filter_map(|document| match document {
+            Bson::Document(document) => Some(document),
+            _ => None,
+        })
+}
+
+/// Encrypts the fields of the command described as encrypted by `schema`. Returns whether the
+/// command is one that may contain encrypted fields.
+pub(crate) fn encrypt_command(
+    command_name: &str,
+    command: &mut Document,
+    schema: &Schema,
+    keys: &HashMap<Vec<u8>, Arc<Vec<u8>>>,
+) -> Result<bool> {
+    let encryptor = Encryptor { keys };
+    match command_name {
+        "insert" => {
+            for document in documents_mut(command, "documents") {
+                encryptor.encrypt_document(document, schema)?;
+            }
+        }
+        "update" => {
+            for update in documents_mut(command, "updates") {
+                if let Ok(filter) = update.get_document_mut("q") {
+                    encryptor.encrypt_filter(filter, schema)?;
+                }
+                if let Some(update) = update.get_mut("u") {
+                    encryptor.encrypt_update(update, schema)?;
+                }
+            }
+        }
+        "delete" => {
+            for delete in documents_mut(command, "deletes") {
+                if let Ok(filter) = delete.get_document_mut("q") {
+                    encryptor.encrypt_filter(filter, schema)?;
+                }
+            }
+        }
+        "find" => {
+            if let Ok(filter) = command.get_document_mut("filter") {
+                encryptor.encrypt_filter(filter, schema)?;
+            }
+        }
+        "count" | "distinct" => {
+            if let Ok(filter) = command.get_document_mut("query") {
+                encryptor.encrypt_filter(filter, schema)?;
+            }
+        }
+        "findAndModify" => {
+            if let Ok(filter) = command.get_document_mut("query") {
+                encryptor.encrypt_filter(filter, schema)?;
+            }
+            if let Some(update) = command.get_mut("update") {
+                encryptor.encrypt_update(update, schema)?;
+            }
+        }
+        "aggregate" => {
+            if let Ok(pipeline) = command.get_array_mut("pipeline") {
+                encryptor.encrypt_pipeline(pipeline, schema)?;
+            }
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Returns the ids of the data keys used to encrypt any encrypted values in the document.
+pub(crate) fn encrypted_key_ids(document: &Document) -> Result<Vec<Vec<u8>>> {
+    fn collect<'a>(
+        values: impl Iterator<Item = &'a Bson>,
+        key_ids: &mut HashSet<Vec<u8>>,
+    ) -> Result<()> {
+        for value in values {
+            match value {
+                Bson::Binary(binary) if binary.subtype == BinarySubtype::Encrypted => {
+                    key_ids.insert(EncryptedValue::parse(binary)?.key_id.to_vec());
+                }
+                Bson::Document(document) => collect(document.values(), key_ids)?,
+                Bson::Array(array) => collect(array.iter(), key_ids)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    let mut key_ids = HashSet::new();
+    collect(document.values(), &mut key_ids)?;
+    Ok(key_ids.into_iter().collect())
+}
+
+/// Decrypts all of the encrypted values in the document using the given data keys.
+pub(crate) fn decrypt_document(
+    document: &mut Document,
+    keys: &HashMap<Vec<u8>, Arc<Vec<u8>>>,
+) -> Result<()> {
+    fn decrypt<'a>(
+        values: impl Iterator<Item = &'a mut Bson>,
+        keys: &HashMap<Vec<u8>, Arc<Vec<u8>>>,
+    ) -> Result<()> {
+        for value in values {
+            match value {
+                Bson::Binary(binary) if binary.subtype == BinarySubtype::Encrypted => {
+                    let encrypted = EncryptedValue::parse(binary)?;
+                    let key = keys
+                        .get(encrypted.key_id)
+                        .ok_or_else(|| encryption_error("data key not found"))?;
+                    *value = encrypted.decrypt(key)?;
+                }
+                Bson::Document(document) => decrypt_document(document, keys)?,
+                Bson::Array(array) => decrypt(array.iter_mut(), keys)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    for_each_value_mut(document, |_, value| decrypt(std::iter::once(value), keys))
+}
@@ -0,0 +1,104 @@
+//! Contains the [`ClientEncryption`] type used for explicit client-side field level encryption.
+
+use super::{
+    kms::KmsProviders,
+    options::{DataKeyOptions, EncryptOptions},
+    KeyVault,
+};
+use crate::{
+    bson::{Binary, Bson},
+    error::Result,
+    Client,
+    Namespace,
+};
+
+pub use super::kms::{KmsProvider, LocalKmsProvider};
+
+/// A `ClientEncryption` is used to create data keys and to explicitly encrypt and decrypt values
+/// with them, e.g. when automatic encryption is not enabled or to encrypt values that can't be
+/// described by a schema.
+///
+/// ```rust
+/// # #[cfg(not(feature = "sync"))]
+/// # use mongodb::{
+/// #     bson::doc,
+/// #     client_encryption::ClientEncryption,
+/// #     error::Result,
+/// #     options::{Algorithm, EncryptOptions, KmsProviders},
+/// #     Client,
+/// #     Namespace,
+/// # };
+/// #
+/// # #[cfg(not(feature = "sync"))]
+/// # async fn func(master_key: Vec<u8>) -> Result<()> {
+/// # let client = Client::with_uri_str("mongodb://example.com").await?;
+/// let key_vault_namespace = Namespace {
+///     db: "encryption".to_string(),
+///     coll: "__keyVault".to_string(),
+/// };
+/// let client_encryption = ClientEncryption::new(
+///     client.clone(),
+///     key_vault_namespace,
+///     KmsProviders::local(master_key)?,
+/// );
+///
+/// let key_id = client_encryption.create_data_key("local", None).await?;
+/// let options = EncryptOptions::builder()
+///     .algorithm(Algorithm::Deterministic)
+///     .key_id(key_id)
+///     .build();
+/// let encrypted = client_encryption.encrypt("123-45-6789", options).await?;
+///
+/// let coll = client.database("db").collection("people");
+/// coll.insert_one(doc! { "ssn": encrypted }, None).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientEncryption {
+    key_vault: KeyVault,
+}
+
+impl ClientEncryption {
+    /// Creates a new `ClientEncryption` that stores its data keys in the `key_vault_namespace`
+    /// collection accessed via `key_vault_client`, protected by the given KMS providers.
+    pub fn new(
+        key_vault_client: Client,
+        key_vault_namespace: Namespace,
+        kms_providers: KmsProviders,
+    ) -> Self {
+        Self {
+            key_vault: KeyVault::new(&key_vault_client, &key_vault_namespace, kms_providers),
+        }
+    }
+
+    /// Creates a new data key encrypted with the master key of the KMS provider named
+    /// `kms_provider`, stores it in the key vault collection, and returns its id.
+    pub async fn create_data_key(
+        &self,
+        kms_provider: impl AsRef<str>,
+        options: impl Into<Option<DataKeyOptions>>,
+    ) -> Result<Binary> {
+        self.key_vault
+            .create_data_key(kms_provider.as_ref(), options.into())
+            .await
+    }
+
+    /// Encrypts a value with the data key and algorithm specified in the options, returning the
+    /// encrypted value as a BSON binary of subtype 6.
+    pub async fn encrypt(&self, value: impl Into<Bson>, options: EncryptOptions) -> Result<Binary> {
+        self.key_vault
+            .encrypt(
+                &value.into(),
+                options.key_id.as_ref(),
+                options.key_alt_name.as_deref(),
+                options.algorithm,
+            )
+            .await
+    }
+
+    /// Decrypts a value that was encrypted with a data key stored in the key vault collection.
+    pub async fn decrypt(&self, value: &Binary) -> Result<Bson> {
+        self.key_vault.decrypt(value).await
+    }
+}
@@ -0,0 +1,202 @@
+//! An implementation of the AEAD_AES_256_CBC_HMAC_SHA_512 algorithm used to encrypt values with a
+//! data key, as well as the encoding of encrypted values as BSON binary subtype 6.
+
+use aes::Aes256;
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+use hmac::{Hmac, Mac, NewMac};
+use rand::RngCore;
+use sha2::Sha512;
+
+use super::{encryption_error, options::Algorithm};
+use crate::{
+    bson::{spec::BinarySubtype, Binary, Bson, Document},
+    error::Result,
+};
+
+type Aes256Cbc = Cbc<Aes256, Pkcs7>;
+
+/// The length of a data key or local master key, in bytes.
+pub(crate) const KEY_LEN: usize = 96;
+
+/// The length of a data key's identifier, which is a UUID.
+const KEY_ID_LEN: usize = 16;
+
+const SUBKEY_LEN: usize = 32;
+const IV_LEN: usize = 16;
+const TAG_LEN: usize = 32;
+const BLOCK_LEN: usize = 16;
+
+/// Encrypts `plaintext` with `key` using AEAD_AES_256_CBC_HMAC_SHA_512. If `deterministic` is
+/// true, the IV is derived from the key, associated data and plaintext so that the same inputs
+/// always produce the same ciphertext.
+pub(crate) fn encrypt(
+    key: &[u8],
+    plaintext: &[u8],
+    associated_data: &[u8],
+    deterministic: bool,
+) -> Result<Vec<u8>> {
+    let (mac_key, enc_key, iv_key) = split_key(key)?;
+    let associated_data_len = ((associated_data.len() as u64) * 8).to_be_bytes();
+
+    let mut iv = [0u8; IV_LEN];
+    if deterministic {
+        let mut mac = new_mac(iv_key)?;
+        mac.update(associated_data);
+        mac.update(&associated_data_len);
+        mac.update(plaintext);
+        iv.copy_from_slice(&mac.finalize().into_bytes()[..IV_LEN]);
+    } else {
+        rand::thread_rng().fill_bytes(&mut iv);
+    }
+
+    let cipher = Aes256Cbc::new_from_slices(enc_key, &iv)
+        .map_err(|e| encryption_error(format!("invalid data key: {}", e)))?;
+    let mut ciphertext = iv.to_vec();
+    ciphertext.extend(cipher.encrypt_vec(plaintext));
+
+    let mut mac = new_mac(mac_key)?;
+    mac.update(associated_data);
+    mac.update(&ciphertext);
+    mac.update(&associated_data_len);
+    ciphertext.extend_from_slice(&mac.finalize().into_bytes()[..TAG_LEN]);
+
+    Ok(ciphertext)
+}
+
+/// Decrypts `ciphertext` that was produced by [`encrypt`] with the same `key` and
+/// `associated_data`, verifying its authentication tag.
+pub(crate) fn decrypt(key: &[u8], ciphertext: &[u8], associated_data: &[u8]) -> Result<Vec<u8>> {
+    let (mac_key, enc_key, _) = split_key(key)?;
+    if ciphertext.len() < IV_LEN + BLOCK_LEN + TAG_LEN {
+        return Err(encryption_error("ciphertext is too short"));
+    }
+    let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+
+    let mut mac = new_mac(mac_key)?;
+    mac.update(associated_data);
+    mac.update(ciphertext);
+    mac.update(&((associated_data.len() as u64) * 8).to_be_bytes());
+    let expected_tag = mac.finalize().into_bytes();
+    // Compare in constant time to avoid leaking how much of the tag matched.
+    let tag_mismatch = expected_tag[..TAG_LEN]
+        .iter()
+        .zip(tag)
+        .fold(0u8, |acc, (expected, actual)| acc | (expected ^ actual));
+    if tag_mismatch != 0 {
+        return Err(encryption_error("HMAC validation failure"));
+    }
+
+    let (iv, ciphertext) = ciphertext.split_at(IV_LEN);
+    let cipher = Aes256Cbc::new_from_slices(enc_key, iv)
+        .map_err(|e| encryption_error(format!("invalid data key: {}", e)))?;
+    cipher
+        .decrypt_vec(ciphertext)
+        .map_err(|_| encryption_error("invalid padding in decrypted value"))
+}
+
+fn split_key(key: &[u8]) -> Result<(&[u8], &[u8], &[u8])> {
+    if key.len() != KEY_LEN {
+        return Err(encryption_error(format!(
+            "expected a {}-byte key, got {} bytes",
+            KEY_LEN,
+            key.len()
+        )));
+    }
+    let (mac_key, rest) = key.split_at(SUBKEY_LEN);
+    let (enc_key, iv_key) = rest.split_at(SUBKEY_LEN);
+    Ok((mac_key, enc_key, iv_key))
+}
+
+fn new_mac(key: &[u8]) -> Result<Hmac<Sha512>> {
+    Hmac::<Sha512>::new_from_slice(key).map_err(|e| encryption_error(e.to_string()))
+}
+
+/// Encrypts a BSON value with the data key identified by `key_id`, returning the encrypted value
+/// as a BSON binary of subtype 6. The binary consists of the algorithm, the key's UUID and the
+/// original BSON type, followed by the ciphertext; the first three are authenticated as
+/// associated data.
+pub(crate) fn encrypt_value(
+    value: &Bson,
+    key_id: &[u8],
+    key: &[u8],
+    algorithm: Algorithm,
+) -> Result<Binary> {
+    if key_id.len() != KEY_ID_LEN {
+        return Err(encryption_error("data key id must be a UUID"));
+    }
+    let (element_type, plaintext) = serialize_value(value)?;
+
+    let mut bytes = Vec::with_capacity(2 + KEY_ID_LEN);
+    bytes.push(algorithm.as_byte());
+    bytes.extend_from_slice(key_id);
+    bytes.push(element_type);
+    let ciphertext = encrypt(
+        key,
+        &plaintext,
+        &bytes,
+        algorithm == Algorithm::Deterministic,
+    )?;
+    bytes.extend(ciphertext);
+
+    Ok(Binary {
+        subtype: BinarySubtype::Encrypted,
+        bytes,
+    })
+}
+
+/// The components of an encrypted BSON binary value.
+pub(crate) struct EncryptedValue<'a> {
+    pub(crate) key_id: &'a [u8],
+    associated_data: &'a [u8],
+    ciphertext: &'a [u8],
+}
+
+impl<'a> EncryptedValue<'a> {
+    pub(crate) fn parse(binary: &'a Binary) -> Result<Self> {
+        if binary.subtype != BinarySubtype::Encrypted {
+            return Err(encryption_error("expected a binary value of subtype 6"));
+        }
+        let bytes = binary.bytes.as_slice();
+        if bytes.len() < 2 + KEY_ID_LEN || Algorithm::from_byte(bytes[0]).is_none() {
+            return Err(encryption_error("malformed encrypted value"));
+        }
+        let (associated_data, ciphertext) = bytes.split_at(2 + KEY_ID_LEN);
+        Ok(Self {
+            key_id: &associated_data[1..1 + KEY_ID_LEN],
+            associated_data,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts the value with the data key it was encrypted with.
+    pub(crate) fn decrypt(&self, key: &[u8]) -> Result<Bson> {
+        let plaintext = decrypt(key, self.ciphertext, self.associated_data)?;
+        deserialize_value(self.associated_data[1 + KEY_ID_LEN], &plaintext)
+    }
+}
+
+/// Serializes a BSON value to its element type and the bytes of its encoding without a key.
+fn serialize_value(value: &Bson) -> Result<(u8, Vec<u8>)> {
+    let mut doc = Document::new();
+    doc.insert("", value.clone());
+    let mut bytes = Vec::new();
+    doc.to_writer(&mut bytes)?;
+    // The document is laid out as a 4-byte length, the element type, an empty key terminated by a
+    // null byte, the value, and a trailing null byte.
+    let element_type = bytes[4];
+    Ok((element_type, bytes[6..bytes.len() - 1].to_vec()))
+}
+
+fn deserialize_value(element_type: u8, bytes: &[u8]) -> Result<Bson> {
+    let len = (4 + 2 + bytes.len() + 1) as i32;
+    let mut doc_bytes = Vec::with_capacity(len as usize);
+    doc_bytes.extend_from_slice(&len.to_le_bytes());
+    doc_bytes.push(element_type);
+    doc_bytes.push(0);
+    doc_bytes.extend_from_slice(bytes);
+    doc_bytes.push(0);
+    let mut doc = Document::from_reader(doc_bytes.as_slice())
+        .map_err(|e| encryption_error(format!("invalid decrypted value: {}", e)))?;
+    doc.remove("")
+        .ok_or_else(|| encryption_error("invalid decrypted value"))
+}
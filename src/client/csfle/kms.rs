@@ -0,0 +1,119 @@
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::{crypt, encryption_error};
+use crate::{bson::Document, error::Result};
+
+/// A key management service (KMS) that protects the data keys used for client-side field level
+/// encryption. Data keys are stored in the key vault collection encrypted with a master key held
+/// by the KMS provider, and are decrypted by the provider when they are needed.
+///
+/// A [`LocalKmsProvider`] that uses a locally held master key is provided by the driver; other
+/// services (e.g. cloud key management services) can be supported by implementing this trait.
+#[async_trait]
+pub trait KmsProvider: Send + Sync + Debug {
+    /// The name of the provider, e.g. "local". This is stored in the `masterKey.provider` field of
+    /// the data keys created with the provider and is used to select the provider when decrypting
+    /// them.
+    fn name(&self) -> &str;
+
+    /// Encrypts the material of a newly created data key. `master_key` is the `masterKey` document
+    /// stored alongside the data key, which identifies the master key to use.
+    async fn encrypt_data_key(&self, master_key: &Document, key_material: &[u8])
+        -> Result<Vec<u8>>;
+
+    /// Decrypts the material of a data key that was encrypted by
+    /// [`encrypt_data_key`](KmsProvider::encrypt_data_key).
+    async fn decrypt_data_key(
+        &self,
+        master_key: &Document,
+        encrypted_key_material: &[u8],
+    ) -> Result<Vec<u8>>;
+}
+
+/// A [`KmsProvider`] that encrypts data keys with a 96-byte master key held by the application.
+///
+/// This is intended for development and testing, or for deployments that manage the master key
+/// themselves; the master key must be kept secret, as anyone holding it can decrypt all of the
+/// data keys created with it.
+#[derive(Clone)]
+pub struct LocalKmsProvider {
+    master_key: Vec<u8>,
+}
+
+impl LocalKmsProvider {
+    /// The name of the local KMS provider.
+    pub const NAME: &'static str = "local";
+
+    /// Creates a new `LocalKmsProvider` using the given master key, which must be 96 bytes long.
+    pub fn new(master_key: impl Into<Vec<u8>>) -> Result<Self> {
+        let master_key = master_key.into();
+        if master_key.len() != crypt::KEY_LEN {
+            return Err(encryption_error(format!(
+                "local master key must be {} bytes, got {} bytes",
+                crypt::KEY_LEN,
+                master_key.len()
+            )));
+        }
+        Ok(Self { master_key })
+    }
+}
+
+impl Debug for LocalKmsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKmsProvider").finish()
+    }
+}
+
+#[async_trait]
+impl KmsProvider for LocalKmsProvider {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    async fn encrypt_data_key(&self, _: &Document, key_material: &[u8]) -> Result<Vec<u8>> {
+        crypt::encrypt(&self.master_key, key_material, &[], false)
+    }
+
+    async fn decrypt_data_key(
+        &self,
+        _: &Document,
+        encrypted_key_material: &[u8],
+    ) -> Result<Vec<u8>> {
+        crypt::decrypt(&self.master_key, encrypted_key_material, &[])
+    }
+}
+
+/// The set of [`KmsProvider`]s available for creating and decrypting data keys, keyed by their
+/// names.
+#[derive(Clone, Debug, Default)]
+pub struct KmsProviders {
+    providers: HashMap<String, Arc<dyn KmsProvider>>,
+}
+
+impl KmsProviders {
+    /// Creates an empty set of KMS providers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a provider to the set, replacing any existing provider with the same name.
+    pub fn with_provider(mut self, provider: impl KmsProvider + 'static) -> Self {
+        self.providers
+            .insert(provider.name().to_string(), Arc::new(provider));
+        self
+    }
+
+    /// Creates a set of KMS providers containing only a [`LocalKmsProvider`] using the given
+    /// master key, which must be 96 bytes long.
+    pub fn local(master_key: impl Into<Vec<u8>>) -> Result<Self> {
+        Ok(Self::new().with_provider(LocalKmsProvider::new(master_key)?))
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Result<&Arc<dyn KmsProvider>> {
+        self.providers.get(name).ok_or_else(|| {
+            encryption_error(format!("no KMS provider named {:?} was configured", name))
+        })
+    }
+}
@@ -0,0 +1,377 @@
+//! Support for client-side field level encryption (CSFLE), which encrypts fields of documents
+//! before they are sent to the server so that the server never sees their plaintext values.
+//!
+//! Values are encrypted with data keys stored in a key vault collection. The data keys are
+//! themselves encrypted with a master key held by a [`KmsProvider`](kms::KmsProvider).
+
+mod auto;
+pub mod client_encryption;
+mod crypt;
+mod kms;
+pub mod options;
+#[cfg(test)]
+mod test;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{future::BoxFuture, FutureExt};
+use serde::{Deserialize, Serialize};
+
+use self::{
+    auto::Schema,
+    crypt::EncryptedValue,
+    kms::KmsProviders,
+    options::{Algorithm, AutoEncryptionOptions, DataKeyOptions},
+};
+use crate::{
+    bson::{doc, spec::BinarySubtype, Binary, Bson, DateTime, Document},
+    cmap::RawCommandResponse,
+    concern::{Acknowledgment, ReadConcern, WriteConcern},
+    error::{Error, ErrorKind, Result},
+    options::{ClientOptions, CollectionOptions},
+    Client,
+    Collection,
+    Namespace,
+};
+
+pub(crate) fn encryption_error(message: impl Into<String>) -> Error {
+    ErrorKind::Encryption {
+        message: message.into(),
+    }
+    .into()
+}
+
+/// The decrypted material of a data key.
+type DataKeyMaterial = Arc<Vec<u8>>;
+
+/// A data key as stored in the key vault collection.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataKey {
+    #[serde(rename = "_id")]
+    id: Binary,
+    key_material: Binary,
+    creation_date: DateTime,
+    update_date: DateTime,
+    status: i32,
+    master_key: Document,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_alt_names: Option<Vec<String>>,
+}
+
+/// Provides access to the data keys stored in a key vault collection, decrypting them with the
+/// configured KMS providers. Decrypted keys are cached for the lifetime of the `KeyVault`.
+#[derive(Debug)]
+pub(crate) struct KeyVault {
+    collection: Collection<DataKey>,
+    kms_providers: KmsProviders,
+    cache: Mutex<HashMap<Vec<u8>, Arc<Vec<u8>>>>,
+}
+
+impl KeyVault {
+    fn new(client: &Client, namespace: &Namespace, kms_providers: KmsProviders) -> Self {
+        let options = CollectionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+        let collection = client
+            .database(&namespace.db)
+            .collection_with_options(&namespace.coll, options);
+        Self {
+            collection,
+            kms_providers,
+            cache: Default::default(),
+        }
+    }
+
+    /// Gets the decrypted material of the data key with the given id.
+    ///
+    /// This is boxed because fetching a data key executes an operation, and executing an operation
+    /// may require fetching a data key.
+    fn key_material<'a>(&'a self, key_id: &'a [u8]) -> BoxFuture<'a, Result<Arc<Vec<u8>>>> {
+        async move {
+            if let Some(key) = self.cache.lock().unwrap().get(key_id) {
+                return Ok(key.clone());
+            }
+
+            let id = Binary {
+                subtype: BinarySubtype::Uuid,
+                bytes: key_id.to_vec(),
+            };
+            let data_key = self
+                .collection
+                .find_one(doc! { "_id": id }, None)
+                .await?
+                .ok_or_else(|| {
+                    encryption_error(format!("data key {} not found", hex::encode(key_id)))
+                })?;
+            self.decrypt_data_key(data_key).await
+        }
+        .boxed()
+    }
+
+    /// Gets the id and decrypted material of the data key with the given alternate name.
+    fn key_by_alt_name<'a>(
+        &'a self,
+        key_alt_name: &'a str,
+    ) -> BoxFuture<'a, Result<(Vec<u8>, DataKeyMaterial)>> {
+        async move {
+            let data_key = self
+                .collection
+                .find_one(doc! { "keyAltNames": key_alt_name }, None)
+                .await?
+                .ok_or_else(|| {
+                    encryption_error(format!("data key {:?} not found", key_alt_name))
+                })?;
+            let id = data_key.id.bytes.clone();
+            let key = self.decrypt_data_key(data_key).await?;
+            Ok((id, key))
+        }
+        .boxed()
+    }
+
+    async fn decrypt_data_key(&self, data_key: DataKey) -> Result<Arc<Vec<u8>>> {
+        let provider_name = data_key
+            .master_key
+            .get_str("provider")
+            .map_err(|_| encryption_error("data key is missing its masterKey provider"))?;
+        let key = self
+            .kms_providers
+            .get(provider_name)?
+            .decrypt_data_key(&data_key.master_key, &data_key.key_material.bytes)
+            .await?;
+        let key = Arc::new(key);
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(data_key.id.bytes, key.clone());
+        Ok(key)
+    }
+
+    /// Creates a new data key, encrypting it with the given KMS provider and inserting it into
+    /// the key vault collection. Returns the new key's id.
+    async fn create_data_key(
+        &self,
+        kms_provider: &str,
+        options: Option<DataKeyOptions>,
+    ) -> Result<Binary> {
+        let options = options.unwrap_or_default();
+        let provider = self.kms_providers.get(kms_provider)?;
+
+        let mut master_key = options.master_key.unwrap_or_default();
+        master_key.insert("provider", provider.name());
+
+        let mut key = vec![0u8; crypt::KEY_LEN];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut key);
+        let key_material = provider.encrypt_data_key(&master_key, &key).await?;
+
+        let id = Binary {
+            subtype: BinarySubtype::Uuid,
+            bytes: uuid::Uuid::new_v4().as_bytes().to_vec(),
+        };
+        let now = DateTime::now();
+        let data_key = DataKey {
+            id: id.clone(),
+            key_material: Binary {
+                subtype: BinarySubtype::Generic,
+                bytes: key_material,
+            },
+            creation_date: now,
+            update_date: now,
+            status: 0,
+            master_key,
+            key_alt_names: options.key_alt_names,
+        };
+        self.collection.insert_one(data_key, None).await?;
+
+        Ok(id)
+    }
+
+    async fn encrypt(
+        &self,
+        value: &Bson,
+        key_id: Option<&Binary>,
+        key_alt_name: Option<&str>,
+        algorithm: Algorithm,
+    ) -> Result<Binary> {
+        let (key_id, key) = match (key_id, key_alt_name) {
+            (Some(key_id), None) => (
+                key_id.bytes.clone(),
+                self.key_material(&key_id.bytes).await?,
+            ),
+            (None, Some(key_alt_name)) => self.key_by_alt_name(key_alt_name).await?,
+            _ => {
+                return Err(Error::invalid_argument(
+                    "exactly one of key_id and key_alt_name must be specified",
+                ))
+            }
+        };
+        crypt::encrypt_value(value, &key_id, &key, algorithm)
+    }
+
+    async fn decrypt(&self, value: &Binary) -> Result<Bson> {
+        let encrypted = EncryptedValue::parse(value)?;
+        let key = self.key_material(encrypted.key_id).await?;
+        encrypted.decrypt(&key)
+    }
+
+    /// Fetches the data keys with the given ids.
+    async fn keys(&self, key_ids: Vec<Vec<u8>>) -> Result<HashMap<Vec<u8>, Arc<Vec<u8>>>> {
+        let mut keys = HashMap::new();
+        for key_id in key_ids {
+            let key = self.key_material(&key_id).await?;
+            keys.insert(key_id, key);
+        }
+        Ok(keys)
+    }
+}
+
+/// The state of a [`Client`] with automatic encryption enabled.
+#[derive(Debug)]
+pub(crate) struct ClientState {
+    key_vault: KeyVault,
+
+    /// The client created for operations on the key vault collection if no key vault client was
+    /// provided, which is shut down along with the client that created it.
+    internal_client: Option<Client>,
+    schemas: HashMap<String, Schema>,
+    bypass_auto_encryption: bool,
+}
+
+impl ClientState {
+    pub(crate) fn new(
+        client_options: &ClientOptions,
+        options: &AutoEncryptionOptions,
+    ) -> Result<Self> {
+        // Operations on the key vault collection must not themselves be encrypted, so an internal
+        // client without automatic encryption is used if none was provided.
+        let (key_vault_client, internal_client) = match options.key_vault_client {
+            #[cfg(not(feature = "sync"))]
+            Some(ref client) => (client.clone(), None),
+            #[cfg(feature = "sync")]
+            Some(ref client) => (client.async_client.clone(), None),
+            None => {
+                let mut internal_options = client_options.clone();
+                internal_options.auto_encryption_options = None;
+                let client = Client::with_options(internal_options)?;
+                (client.clone(), Some(client))
+            }
+        };
+
+        let schemas = options
+            .schema_map
+            .iter()
+            .flatten()
+            .map(|(namespace, schema)| Ok((namespace.clone(), Schema::parse(schema)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            key_vault: KeyVault::new(
+                &key_vault_client,
+                &options.key_vault_namespace,
+                options.kms_providers.clone(),
+            ),
+            internal_client,
+            schemas,
+            bypass_auto_encryption: options.bypass_auto_encryption.unwrap_or(false),
+        })
+    }
+
+    /// Encrypts the fields of the serialized command that are described as encrypted by the
+    /// schema for the command's target collection.
+    async fn encrypt_command(
+        &self,
+        target_db: &str,
+        command_name: &str,
+        command: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        if self.bypass_auto_encryption || self.schemas.is_empty() {
+            return Ok(command);
+        }
+
+        let mut document = Document::from_reader(command.as_slice())?;
+        let schema = match document
+            .get_str(command_name)
+            .ok()
+            .and_then(|coll| self.schemas.get(&format!("{}.{}", target_db, coll)))
+        {
+            Some(schema) => schema,
+            None => return Ok(command),
+        };
+
+        let keys = self.key_vault.keys(schema.key_ids()).await?;
+        if !auto::encrypt_command(command_name, &mut document, schema, &keys)? {
+            return Ok(command);
+        }
+
+        let mut encrypted = Vec::new();
+        document.to_writer(&mut encrypted)?;
+        Ok(encrypted)
+    }
+
+    /// Decrypts any encrypted values in the response.
+    async fn decrypt_response(&self, response: RawCommandResponse) -> Result<RawCommandResponse> {
+        let mut document: Document = response.body()?;
+        let key_ids = auto::encrypted_key_ids(&document)?;
+        if key_ids.is_empty() {
+            return Ok(response);
+        }
+
+        let keys = self.key_vault.keys(key_ids).await?;
+        auto::decrypt_document(&mut document, &keys)?;
+        RawCommandResponse::with_document_and_address(response.source, document)
+    }
+}
+
+impl Client {
+    /// Encrypts the serialized command if automatic encryption is enabled for this client.
+    pub(crate) async fn auto_encrypt(
+        &self,
+        target_db: &str,
+        command_name: &str,
+        command: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        match self.inner.csfle {
+            Some(ref csfle) => {
+                csfle
+                    .encrypt_command(target_db, command_name, command)
+                    .await
+            }
+            None => Ok(command),
+        }
+    }
+
+    /// Shuts down the internal key vault client if automatic encryption is enabled for this client
+    /// and one was created. A key vault client provided in the options is left running, since it
+    /// may be used elsewhere.
+    ///
+    /// The future is boxed since shutting down the internal client recurses into
+    /// `Client::shutdown`.
+    pub(crate) fn shutdown_auto_encryption(&self) -> BoxFuture<'static, ()> {
+        let internal_client = self
+            .inner
+            .csfle
+            .as_ref()
+            .and_then(|csfle| csfle.internal_client.clone());
+        async move {
+            if let Some(client) = internal_client {
+                client.shutdown().await;
+            }
+        }
+        .boxed()
+    }
+
+    /// Decrypts the response if automatic encryption is enabled for this client.
+    pub(crate) async fn auto_decrypt(
+        &self,
+        response: RawCommandResponse,
+    ) -> Result<RawCommandResponse> {
+        match self.inner.csfle {
+            Some(ref csfle) => csfle.decrypt_response(response).await,
+            None => Ok(response),
+        }
+    }
+}
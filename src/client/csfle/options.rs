@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use typed_builder::TypedBuilder;
+
+pub use super::kms::KmsProviders;
+use crate::{
+    bson::{Binary, Document},
+    Namespace,
+};
+
+#[cfg(not(feature = "sync"))]
+type KeyVaultClient = crate::Client;
+#[cfg(feature = "sync")]
+type KeyVaultClient = crate::sync::Client;
+
+/// Contains the options for automatic client-side field level encryption, which can be enabled
+/// for a client by setting
+/// [`ClientOptions::auto_encryption_options`](crate::options::ClientOptions::auto_encryption_options).
+///
+/// When automatic encryption is enabled, fields described as encrypted by the `schema_map` are
+/// encrypted in outgoing commands, and any encrypted values in replies are decrypted before they
+/// are returned to the application.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+#[non_exhaustive]
+pub struct AutoEncryptionOptions {
+    /// The namespace of the key vault collection, which contains the data keys used to encrypt and
+    /// decrypt values.
+    pub key_vault_namespace: Namespace,
+
+    /// The KMS providers used to decrypt the data keys stored in the key vault collection.
+    pub kms_providers: KmsProviders,
+
+    /// The client used to access the key vault collection. By default, an internal client
+    /// connected to the same deployment is used.
+    #[builder(default)]
+    pub key_vault_client: Option<KeyVaultClient>,
+
+    /// A map from namespaces (in the form `"db.coll"`) to JSON schemas describing which fields of
+    /// the documents in the collection are encrypted, e.g.
+    ///
+    /// ```json
+    /// {
+    ///     "bsonType": "object",
+    ///     "properties": {
+    ///         "ssn": {
+    ///             "encrypt": {
+    ///                 "keyId": [<data key UUID>],
+    ///                 "bsonType": "string",
+    ///                 "algorithm": "AEAD_AES_256_CBC_HMAC_SHA_512-Deterministic"
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Only fields of collections that have a schema are encrypted.
+    #[builder(default)]
+    pub schema_map: Option<HashMap<String, Document>>,
+
+    /// If true, outgoing commands are not encrypted, but encrypted values in replies are still
+    /// decrypted. Defaults to false.
+    #[builder(default)]
+    pub bypass_auto_encryption: Option<bool>,
+}
+
+/// The algorithm used to encrypt a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// Encrypting the same value with the same data key always produces the same ciphertext,
+    /// which allows encrypted fields to be queried by equality.
+    Deterministic,
+
+    /// Encrypting a value produces a different ciphertext each time. Fields encrypted with this
+    /// algorithm cannot be queried.
+    Random,
+}
+
+impl Algorithm {
+    const DETERMINISTIC_NAME: &'static str = "AEAD_AES_256_CBC_HMAC_SHA_512-Deterministic";
+    const RANDOM_NAME: &'static str = "AEAD_AES_256_CBC_HMAC_SHA_512-Random";
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            Self::DETERMINISTIC_NAME => Some(Self::Deterministic),
+            Self::RANDOM_NAME => Some(Self::Random),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_byte(self) -> u8 {
+        match self {
+            Self::Deterministic => 1,
+            Self::Random => 2,
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(Self::Deterministic),
+            2 => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
+
+/// Contains the options for creating a data key with
+/// [`ClientEncryption::create_data_key`](crate::client_encryption::ClientEncryption::create_data_key).
+#[derive(Clone, Debug, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DataKeyOptions {
+    /// Provider-specific fields identifying the master key used to encrypt the data key. These are
+    /// stored in the `masterKey` field of the data key along with the provider name. Not needed
+    /// for the local KMS provider.
+    pub master_key: Option<Document>,
+
+    /// Alternate names that can be used to refer to the data key instead of its id.
+    pub key_alt_names: Option<Vec<String>>,
+}
+
+/// Contains the options for explicitly encrypting a value with
+/// [`ClientEncryption::encrypt`](crate::client_encryption::ClientEncryption::encrypt). Exactly one
+/// of `key_id` and `key_alt_name` must be specified.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+#[non_exhaustive]
+pub struct EncryptOptions {
+    /// The algorithm to encrypt the value with.
+    pub algorithm: Algorithm,
+
+    /// The id of the data key to encrypt the value with.
+    #[builder(default)]
+    pub key_id: Option<Binary>,
+
+    /// An alternate name of the data key to encrypt the value with.
+    #[builder(default)]
+    pub key_alt_name: Option<String>,
+}
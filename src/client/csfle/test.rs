@@ -0,0 +1,296 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{
+    auto::{self, Schema},
+    crypt::{self, EncryptedValue},
+    kms::KmsProviders,
+    options::{Algorithm, AutoEncryptionOptions},
+};
+use crate::{
+    bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime, Document},
+    error::ErrorKind,
+    options::ClientOptions,
+    test_util::MockServer,
+    Client,
+    Namespace,
+};
+
+const KEY_ID: [u8; 16] = [7; 16];
+
+fn key() -> Vec<u8> {
+    (0..crypt::KEY_LEN as u8).collect()
+}
+
+fn keys() -> HashMap<Vec<u8>, Arc<Vec<u8>>> {
+    let mut keys = HashMap::new();
+    keys.insert(KEY_ID.to_vec(), Arc::new(key()));
+    keys
+}
+
+fn key_id() -> Bson {
+    Bson::Binary(Binary {
+        subtype: BinarySubtype::Uuid,
+        bytes: KEY_ID.to_vec(),
+    })
+}
+
+fn schema() -> Schema {
+    Schema::parse(&doc! {
+        "bsonType": "object",
+        "encryptMetadata": { "keyId": [key_id()] },
+        "properties": {
+            "ssn": {
+                "encrypt": {
+                    "bsonType": "string",
+                    "algorithm": "AEAD_AES_256_CBC_HMAC_SHA_512-Deterministic",
+                },
+            },
+            "medical": {
+                "bsonType": "object",
+                "properties": {
+                    "notes": {
+                        "encrypt": { "algorithm": "AEAD_AES_256_CBC_HMAC_SHA_512-Random" },
+                    },
+                },
+            },
+        },
+    })
+    .unwrap()
+}
+
+fn is_encrypted(value: &Bson) -> bool {
+    matches!(value, Bson::Binary(b) if b.subtype == BinarySubtype::Encrypted)
+}
+
+#[test]
+fn aead_round_trip() {
+    let key = key();
+    let ciphertext = crypt::encrypt(&key, b"plaintext", b"aad", false).unwrap();
+    assert_eq!(
+        crypt::decrypt(&key, &ciphertext, b"aad").unwrap(),
+        b"plaintext"
+    );
+
+    // The associated data is authenticated.
+    assert!(crypt::decrypt(&key, &ciphertext, b"other").is_err());
+
+    // The ciphertext is authenticated.
+    let mut tampered = ciphertext;
+    tampered[20] ^= 1;
+    let error = crypt::decrypt(&key, &tampered, b"aad").unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Encryption { .. }));
+}
+
+#[test]
+fn aead_deterministic() {
+    let key = key();
+    let first = crypt::encrypt(&key, b"plaintext", b"aad", true).unwrap();
+    let second = crypt::encrypt(&key, b"plaintext", b"aad", true).unwrap();
+    assert_eq!(first, second);
+
+    let first = crypt::encrypt(&key, b"plaintext", b"aad", false).unwrap();
+    let second = crypt::encrypt(&key, b"plaintext", b"aad", false).unwrap();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn aead_invalid_key_length() {
+    assert!(crypt::encrypt(&[0; 32], b"plaintext", b"", false).is_err());
+}
+
+#[test]
+fn encrypted_value_round_trip() {
+    let key = key();
+    let values = vec![
+        Bson::String("123-45-6789".to_string()),
+        Bson::Int32(5),
+        Bson::Int64(-1),
+        Bson::Double(1.5),
+        Bson::Boolean(true),
+        Bson::ObjectId(ObjectId::new()),
+        Bson::DateTime(DateTime::now()),
+        Bson::Document(doc! { "a": [1, "b"] }),
+        Bson::Array(vec![Bson::Null, Bson::Int32(1)]),
+    ];
+
+    for value in values {
+        for algorithm in [Algorithm::Deterministic, Algorithm::Random].iter() {
+            let encrypted = crypt::encrypt_value(&value, &KEY_ID, &key, *algorithm).unwrap();
+            assert_eq!(encrypted.subtype, BinarySubtype::Encrypted);
+            assert_eq!(encrypted.bytes[0], algorithm.as_byte());
+
+            let parsed = EncryptedValue::parse(&encrypted).unwrap();
+            assert_eq!(parsed.key_id, KEY_ID);
+            assert_eq!(parsed.decrypt(&key).unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn schema_inherits_encrypt_metadata() {
+    let schema = schema();
+    assert_eq!(schema.key_ids(), vec![KEY_ID.to_vec()]);
+
+    let error = Schema::parse(&doc! {
+        "properties": { "ssn": { "encrypt": { "bsonType": "string" } } },
+    })
+    .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Encryption { .. }));
+}
+
+#[test]
+fn encrypt_insert_and_decrypt() {
+    let schema = schema();
+    let keys = keys();
+    let original = doc! {
+        "name": "Alice",
+        "ssn": "123-45-6789",
+        "medical": { "notes": "none", "height": 170 },
+    };
+    let mut command = doc! { "insert": "people", "documents": [original.clone()] };
+
+    assert!(auto::encrypt_command("insert", &mut command, &schema, &keys).unwrap());
+    let encrypted = command.get_array("documents").unwrap()[0]
+        .as_document()
+        .unwrap()
+        .clone();
+    assert_eq!(encrypted.get_str("name"), Ok("Alice"));
+    assert!(is_encrypted(encrypted.get("ssn").unwrap()));
+    let medical = encrypted.get_document("medical").unwrap();
+    assert!(is_encrypted(medical.get("notes").unwrap()));
+    assert_eq!(medical.get_i32("height"), Ok(170));
+
+    let mut reply = doc! { "cursor": { "firstBatch": [encrypted] }, "ok": 1 };
+    assert_eq!(
+        auto::encrypted_key_ids(&reply).unwrap(),
+        vec![KEY_ID.to_vec()]
+    );
+    auto::decrypt_document(&mut reply, &keys).unwrap();
+    assert_eq!(
+        reply,
+        doc! { "cursor": { "firstBatch": [original] }, "ok": 1 }
+    );
+}
+
+#[test]
+fn encrypt_find_filter() {
+    let schema = schema();
+    let keys = keys();
+
+    let mut command = doc! {
+        "find": "people",
+        "filter": { "$or": [{ "ssn": "1" }, { "ssn": { "$in": ["2", "3"] } }], "name": "Bob" },
+    };
+    assert!(auto::encrypt_command("find", &mut command, &schema, &keys).unwrap());
+    let filter = command.get_document("filter").unwrap();
+    let clauses = filter.get_array("$or").unwrap();
+    let equality = clauses[0].as_document().unwrap().get("ssn").unwrap();
+    assert!(is_encrypted(equality));
+    let in_values = clauses[1]
+        .as_document()
+        .unwrap()
+        .get_document("ssn")
+        .unwrap()
+        .get_array("$in")
+        .unwrap();
+    assert!(in_values.iter().all(is_encrypted));
+    assert_eq!(filter.get_str("name"), Ok("Bob"));
+
+    // Deterministic encryption produces the same ciphertext as when the value was inserted.
+    let mut insert = doc! { "insert": "people", "documents": [{ "ssn": "1" }] };
+    auto::encrypt_command("insert", &mut insert, &schema, &keys).unwrap();
+    let inserted = insert.get_array("documents").unwrap()[0]
+        .as_document()
+        .unwrap()
+        .get("ssn")
+        .unwrap()
+        .clone();
+    assert_eq!(equality, &inserted);
+}
+
+#[test]
+fn encrypt_filter_errors() {
+    let schema = schema();
+    let keys = keys();
+
+    let mut command = doc! { "find": "people", "filter": { "medical.notes": "x" } };
+    let error = auto::encrypt_command("find", &mut command, &schema, &keys).unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Encryption { .. }));
+
+    let mut command = doc! { "find": "people", "filter": { "ssn": { "$gt": "1" } } };
+    let error = auto::encrypt_command("find", &mut command, &schema, &keys).unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Encryption { .. }));
+}
+
+#[test]
+fn encrypt_update() {
+    let schema = schema();
+    let keys = keys();
+
+    let mut command = doc! {
+        "update": "people",
+        "updates": [
+            { "q": { "ssn": "1" }, "u": { "$set": { "medical.notes": "x", "name": "Bob" } } },
+            { "q": {}, "u": { "ssn": "2" } },
+        ],
+    };
+    assert!(auto::encrypt_command("update", &mut command, &schema, &keys).unwrap());
+    let updates: Vec<&Document> = command
+        .get_array("updates")
+        .unwrap()
+        .iter()
+        .map(|u| u.as_document().unwrap())
+        .collect();
+    assert!(is_encrypted(
+        updates[0].get_document("q").unwrap().get("ssn").unwrap()
+    ));
+    let set = updates[0]
+        .get_document("u")
+        .unwrap()
+        .get_document("$set")
+        .unwrap();
+    assert!(is_encrypted(set.get("medical.notes").unwrap()));
+    assert_eq!(set.get_str("name"), Ok("Bob"));
+    assert!(is_encrypted(
+        updates[1].get_document("u").unwrap().get("ssn").unwrap()
+    ));
+}
+
+#[test]
+fn unrelated_commands_unchanged() {
+    let mut command = doc! { "ping": 1 };
+    assert!(!auto::encrypt_command("ping", &mut command, &schema(), &keys()).unwrap());
+    assert_eq!(command, doc! { "ping": 1 });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn shutdown_shuts_down_internal_key_vault_client() {
+    let server = MockServer::start(None).unwrap();
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.auto_encryption_options = Some(
+        AutoEncryptionOptions::builder()
+            .key_vault_namespace(Namespace {
+                db: "keyvault".to_string(),
+                coll: "datakeys".to_string(),
+            })
+            .kms_providers(KmsProviders::new())
+            .build(),
+    );
+    let client = Client::with_options(options).unwrap();
+    let internal_client = client
+        .inner
+        .csfle
+        .as_ref()
+        .and_then(|csfle| csfle.internal_client.clone())
+        .expect("an internal key vault client should be created");
+
+    client.shutdown().await;
+
+    let error = internal_client
+        .database("admin")
+        .run_command(doc! { "ping": 1 }, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Shutdown), "{:?}", error);
+}
@@ -1,10 +1,17 @@
-use bson::{doc, RawBsonRef, RawDocument, Timestamp};
+use bson::{doc, RawBsonRef, RawDocument, RawDocumentBuf, Timestamp};
 use lazy_static::lazy_static;
 use serde::de::DeserializeOwned;
 
-use std::{collections::HashSet, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use super::{session::TransactionState, Client, ClientSession};
+#[cfg(feature = "tracing")]
+use crate::trace;
 use crate::{
     bson::Document,
     change_stream::{
@@ -50,6 +57,7 @@ use crate::{
     },
     selection_criteria::ReadPreference,
     ClusterTime,
+    RUNTIME,
 };
 
 lazy_static! {
@@ -74,6 +82,9 @@ lazy_static! {
     };
 }
 
+/// Commands that a `maxTimeMS` derived from an operation's timeout should not be appended to.
+const MAX_TIME_UNSUPPORTED_COMMANDS: &[&str] = &["getMore", "killCursors"];
+
 impl Client {
     /// Execute the given operation.
     ///
@@ -95,14 +106,11 @@ impl Client {
         op: T,
         session: impl Into<Option<&mut ClientSession>>,
     ) -> Result<ExecutionDetails<T>> {
-        Box::pin(async {
-            // TODO RUST-9: allow unacknowledged write concerns
-            if !op.is_acknowledged() {
-                return Err(ErrorKind::InvalidArgument {
-                    message: "Unacknowledged write concerns are not supported".to_string(),
-                }
-                .into());
-            }
+        #[cfg(feature = "tracing")]
+        let name = op.name().to_string();
+
+        let execution = Box::pin(async {
+            let deadline = self.operation_deadline(&op);
             let mut implicit_session = None;
             let session = match session.into() {
                 Some(session) => {
@@ -115,6 +123,14 @@ impl Client {
                         .into());
                     }
 
+                    if !op.is_acknowledged() {
+                        return Err(ErrorKind::InvalidArgument {
+                            message: "Cannot use ClientSessions with unacknowledged write concern"
+                                .to_string(),
+                        }
+                        .into());
+                    }
+
                     if let Some(SelectionCriteria::ReadPreference(read_preference)) =
                         op.selection_criteria()
                     {
@@ -132,13 +148,19 @@ impl Client {
                     implicit_session.as_mut()
                 }
             };
-            let output = self.execute_operation_with_retry(op, session).await?;
+            let output = self
+                .execute_operation_with_retry(op, session, deadline)
+                .await?;
             Ok(ExecutionDetails {
                 output,
                 implicit_session,
             })
-        })
-        .await
+        });
+
+        #[cfg(feature = "tracing")]
+        let execution = trace::span::instrument_operation(&name, execution);
+
+        execution.await
     }
 
     /// Execute the given operation, returning the cursor created by the operation.
@@ -183,6 +205,15 @@ impl Client {
         ))
     }
 
+    /// Computes the time by which the given operation must complete, if either the operation or
+    /// the client specifies a non-zero timeout.
+    fn operation_deadline<T: Operation>(&self, op: &T) -> Option<Instant> {
+        op.timeout()
+            .or(self.inner.options.timeout)
+            .filter(|timeout| *timeout != Duration::from_secs(0))
+            .map(|timeout| Instant::now() + timeout)
+    }
+
     fn is_load_balanced(&self) -> bool {
         self.inner.options.load_balanced.unwrap_or(false)
     }
@@ -233,6 +264,11 @@ impl Client {
             let mut details = self.execute_operation_with_details(op, None).await?;
             let pinned = self.pin_connection_for_cursor(&mut details.output)?;
             let resume_token = ResumeToken::initial(&options, &details.output.operation_output);
+            let mut data = ChangeStreamData::new(pipeline, self.clone(), target, options);
+            data.record_initial_response(
+                details.output.operation_output.operation_time,
+                details.output.connection.stream_description()?,
+            );
             let cursor = Cursor::new(
                 self.clone(),
                 details.output.operation_output,
@@ -240,11 +276,7 @@ impl Client {
                 pinned,
             );
 
-            Ok(ChangeStream::new(
-                cursor,
-                ChangeStreamData::new(pipeline, self.clone(), target, options),
-                resume_token,
-            ))
+            Ok(ChangeStream::new(cursor, data, resume_token))
         })
         .await
     }
@@ -268,23 +300,26 @@ impl Client {
                 .await?;
             let pinned = self.pin_connection_for_session(&mut details.output, session)?;
             let resume_token = ResumeToken::initial(&options, &details.output.operation_output);
+            let mut data = ChangeStreamData::new(pipeline, self.clone(), target, options);
+            data.record_initial_response(
+                details.output.operation_output.operation_time,
+                details.output.connection.stream_description()?,
+            );
             let cursor = SessionCursor::new(self.clone(), details.output.operation_output, pinned);
 
-            Ok(SessionChangeStream::new(
-                cursor,
-                ChangeStreamData::new(pipeline, self.clone(), target, options),
-                resume_token,
-            ))
+            Ok(SessionChangeStream::new(cursor, data, resume_token))
         })
         .await
     }
 
     /// Selects a server and executes the given operation on it, optionally using a provided
-    /// session. Retries the operation upon failure if retryability is supported.
+    /// session. Retries the operation upon failure if retryability is supported and the deadline,
+    /// if any, has not yet passed.
     async fn execute_operation_with_retry<T: Operation>(
         &self,
         mut op: T,
         mut session: Option<&mut ClientSession>,
+        deadline: Option<Instant>,
     ) -> Result<ExecutionOutput<T>> {
         // If the current transaction has been committed/aborted and it is not being
         // re-committed/re-aborted, reset the transaction's state to TransactionState::None.
@@ -305,7 +340,7 @@ impl Client {
             .and_then(|s| s.transaction.pinned_mongos())
             .or_else(|| op.selection_criteria());
 
-        let server = match self.select_server(selection_criteria).await {
+        let server = match self.select_server(selection_criteria, deadline).await {
             Ok(server) => server,
             Err(mut err) => {
                 err.add_labels_and_update_pin(None, &mut session, None)?;
@@ -313,13 +348,15 @@ impl Client {
             }
         };
 
-        let mut conn = match get_connection(&session, &op, &server.pool).await {
+        let mut conn = match get_connection(&session, &op, &server.pool, deadline).await {
             Ok(c) => c,
             Err(mut err) => {
                 err.add_labels_and_update_pin(None, &mut session, None)?;
 
                 if err.is_pool_cleared() {
-                    return self.execute_retry(&mut op, &mut session, None, err).await;
+                    return self
+                        .execute_retry(&mut op, &mut session, None, err, deadline)
+                        .await;
                 } else {
                     return Err(err);
                 }
@@ -342,16 +379,22 @@ impl Client {
             None => None,
         };
 
-        match self
-            .execute_operation_on_connection(
-                &mut op,
-                &mut conn,
-                &mut session,
-                txn_number,
-                &retryability,
-            )
-            .await
-        {
+        #[cfg(feature = "tracing")]
+        let (name, address) = (op.name().to_string(), conn.address().clone());
+
+        let attempt = self.execute_operation_on_connection(
+            &mut op,
+            &mut conn,
+            &mut session,
+            txn_number,
+            &retryability,
+            deadline,
+        );
+
+        #[cfg(feature = "tracing")]
+        let attempt = trace::span::instrument_command_attempt(&name, &address, 0, attempt);
+
+        match attempt.await {
             Ok(operation_output) => Ok(ExecutionOutput {
                 operation_output,
                 connection: conn,
@@ -386,7 +429,7 @@ impl Client {
                 if retryability == Retryability::Read && err.is_read_retryable()
                     || retryability == Retryability::Write && err.is_write_retryable()
                 {
-                    self.execute_retry(&mut op, &mut session, txn_number, err)
+                    self.execute_retry(&mut op, &mut session, txn_number, err, deadline)
                         .await
                 } else {
                     Err(err)
@@ -395,61 +438,107 @@ impl Client {
         }
     }
 
+    /// Retries an operation that failed with `first_error`. Without a deadline, the operation is
+    /// retried once; with one, it is retried for as long as it keeps failing with retryable errors
+    /// and the deadline has not passed.
     async fn execute_retry<T: Operation>(
         &self,
         op: &mut T,
         session: &mut Option<&mut ClientSession>,
         txn_number: Option<i64>,
         first_error: Error,
+        deadline: Option<Instant>,
     ) -> Result<ExecutionOutput<T>> {
-        op.update_for_retry();
+        let mut prior_error = first_error;
+        #[cfg(feature = "tracing")]
+        let mut retry_attempt = 0;
+        loop {
+            if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                return Err(prior_error);
+            }
 
-        let server = match self.select_server(op.selection_criteria()).await {
-            Ok(server) => server,
-            Err(_) => {
-                return Err(first_error);
+            op.update_for_retry();
+
+            let server = match self.select_server(op.selection_criteria(), deadline).await {
+                Ok(server) => server,
+                Err(_) => {
+                    return Err(prior_error);
+                }
+            };
+
+            let mut conn = match get_connection(session, op, &server.pool, deadline).await {
+                Ok(c) => c,
+                Err(err) if deadline.is_some() && err.is_pool_cleared() => {
+                    prior_error = err;
+                    continue;
+                }
+                Err(_) => return Err(prior_error),
+            };
+
+            let retryability = self.get_retryability(&conn, op, session).await?;
+            if retryability == Retryability::None {
+                return Err(prior_error);
             }
-        };
 
-        let mut conn = match get_connection(session, op, &server.pool).await {
-            Ok(c) => c,
-            Err(_) => return Err(first_error),
-        };
+            #[cfg(feature = "tracing")]
+            let (name, address) = (op.name().to_string(), conn.address().clone());
 
-        let retryability = self.get_retryability(&conn, op, session).await?;
-        if retryability == Retryability::None {
-            return Err(first_error);
-        }
+            let attempt = self.execute_operation_on_connection(
+                op,
+                &mut conn,
+                session,
+                txn_number,
+                &retryability,
+                deadline,
+            );
 
-        match self
-            .execute_operation_on_connection(op, &mut conn, session, txn_number, &retryability)
-            .await
-        {
-            Ok(operation_output) => Ok(ExecutionOutput {
-                operation_output,
-                connection: conn,
-            }),
-            Err(err) => {
-                self.inner
-                    .topology
-                    .handle_application_error(
-                        err.clone(),
-                        HandshakePhase::after_completion(&conn),
-                        &server,
-                    )
-                    .await;
-                drop(server);
+            #[cfg(feature = "tracing")]
+            let attempt = {
+                retry_attempt += 1;
+                trace::span::instrument_command_attempt(&name, &address, retry_attempt, attempt)
+            };
 
-                if err.is_server_error() || err.is_read_retryable() || err.is_write_retryable() {
-                    Err(err)
-                } else {
-                    Err(first_error)
+            match attempt.await {
+                Ok(operation_output) => {
+                    return Ok(ExecutionOutput {
+                        operation_output,
+                        connection: conn,
+                    })
+                }
+                Err(err) => {
+                    self.inner
+                        .topology
+                        .handle_application_error(
+                            err.clone(),
+                            HandshakePhase::after_completion(&conn),
+                            &server,
+                        )
+                        .await;
+                    drop(conn);
+                    drop(server);
+
+                    let is_retryable = retryability == Retryability::Read
+                        && err.is_read_retryable()
+                        || retryability == Retryability::Write && err.is_write_retryable();
+                    if deadline.is_some() && is_retryable {
+                        prior_error = err;
+                    } else if err.is_server_error()
+                        || err.is_read_retryable()
+                        || err.is_write_retryable()
+                    {
+                        return Err(err);
+                    } else {
+                        return Err(prior_error);
+                    }
                 }
             }
         }
     }
 
     /// Executes an operation on a given connection, optionally using a provided session.
+    ///
+    /// If a deadline is provided, the command is sent with a `maxTimeMS` derived from the time
+    /// remaining, and waiting for the reply is abandoned once the deadline passes.
     async fn execute_operation_on_connection<T: Operation>(
         &self,
         op: &mut T,
@@ -457,11 +546,19 @@ impl Client {
         session: &mut Option<&mut ClientSession>,
         txn_number: Option<i64>,
         retryability: &Retryability,
+        deadline: Option<Instant>,
     ) -> Result<T::O> {
         if let Some(wc) = op.write_concern() {
             wc.validate()?;
         }
 
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            return Err(Error::timeout(format!(
+                "Timed out before the {} command could be sent",
+                op.name()
+            )));
+        }
+
         let stream_description = connection.stream_description()?;
         let is_sharded = stream_description.initial_server_type == ServerType::Mongos;
         let mut cmd = op.build(stream_description)?;
@@ -470,6 +567,11 @@ impl Client {
             .update_command_with_read_pref(connection.address(), &mut cmd, op.selection_criteria())
             .await;
 
+        // Only the first command in a transaction may be sent with a maxTimeMS.
+        let continues_transaction = session.as_ref().map_or(false, |session| {
+            session.transaction.state == TransactionState::InProgress
+        });
+
         match session {
             Some(ref mut session) if op.supports_sessions() && op.is_acknowledged() => {
                 cmd.set_session(session);
@@ -551,13 +653,6 @@ impl Client {
                 }
                 .into());
             }
-            Some(ref session) if !op.is_acknowledged() && !session.is_implicit() => {
-                return Err(ErrorKind::InvalidArgument {
-                    message: "Cannot use ClientSessions with unacknowledged write concern"
-                        .to_string(),
-                }
-                .into());
-            }
             _ => {}
         }
 
@@ -582,10 +677,27 @@ impl Client {
         let target_db = cmd.target_db.clone();
 
         let serialized = op.serialize_command(cmd)?;
+
+        #[cfg(feature = "tracing")]
+        trace::span::record_command(&target_db, &cmd_name, &serialized);
+
+        #[cfg(feature = "csfle")]
+        let serialized = self.auto_encrypt(&target_db, &cmd_name, serialized).await?;
+        let serialized = match deadline {
+            Some(deadline)
+                if op.is_acknowledged()
+                    && !continues_transaction
+                    && !MAX_TIME_UNSUPPORTED_COMMANDS.contains(&cmd_name.as_str()) =>
+            {
+                append_max_time_ms(serialized, deadline)?
+            }
+            _ => serialized,
+        };
         let raw_cmd = RawCommand {
             name: cmd_name.clone(),
             target_db,
             bytes: serialized,
+            more_to_come: !op.is_acknowledged(),
         };
 
         self.emit_command_event(|handler| {
@@ -608,7 +720,38 @@ impl Client {
         });
 
         let start_time = Instant::now();
-        let command_result = match connection.send_raw_command(raw_cmd, request_id).await {
+        let socket_timeout = self
+            .inner
+            .options
+            .socket_timeout
+            .filter(|timeout| *timeout != Duration::from_secs(0));
+        let send_result = match (
+            deadline.map(|deadline| deadline.saturating_duration_since(start_time)),
+            socket_timeout,
+        ) {
+            (Some(remaining), socket_timeout)
+                if socket_timeout.map_or(true, |socket_timeout| remaining <= socket_timeout) =>
+            {
+                RUNTIME
+                    .timeout(remaining, connection.send_raw_command(raw_cmd, request_id))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(Error::timeout(format!(
+                            "Timed out while waiting for the reply to the {} command",
+                            cmd_name
+                        )))
+                    })
+            }
+            (_, Some(socket_timeout)) => RUNTIME
+                .timeout(
+                    socket_timeout,
+                    connection.send_raw_command(raw_cmd, request_id),
+                )
+                .await
+                .and_then(|result| result),
+            (_, None) => connection.send_raw_command(raw_cmd, request_id).await,
+        };
+        let command_result = match send_result {
             Ok(response) => {
                 async fn handle_response<T: Operation>(
                     client: &Client,
@@ -617,6 +760,8 @@ impl Client {
                     is_sharded: bool,
                     response: RawCommandResponse,
                 ) -> Result<RawCommandResponse> {
+                    #[cfg(feature = "csfle")]
+                    let response = client.auto_decrypt(response).await?;
                     let raw_doc = RawDocument::from_bytes(response.as_bytes())?;
 
                     let ok = match raw_doc.get("ok")? {
@@ -705,7 +850,7 @@ impl Client {
                 });
 
                 if let Some(ref mut session) = session {
-                    if err.is_network_error() {
+                    if err.is_network_error() || err.is_timeout() {
                         session.mark_dirty();
                     }
                 }
@@ -769,7 +914,7 @@ impl Client {
             (matches!(topology_type, TopologyType::Single) && server_type.is_available())
                 || server_type.is_data_bearing()
         }));
-        let _: SelectedServer = self.select_server(Some(&criteria)).await?;
+        let _: SelectedServer = self.select_server(Some(&criteria), None).await?;
         Ok(())
     }
 
@@ -823,7 +968,10 @@ impl Client {
                 Retryability::Read if self.inner.options.retry_reads != Some(false) => {
                     return Ok(Retryability::Read);
                 }
-                Retryability::Write if conn.stream_description()?.supports_retryable_writes() => {
+                Retryability::Write
+                    if op.is_acknowledged()
+                        && conn.stream_description()?.supports_retryable_writes() =>
+                {
                     // commitTransaction and abortTransaction should be retried regardless of the
                     // value for retry_writes set on the Client
                     if op.name() == CommitTransaction::NAME
@@ -864,6 +1012,7 @@ async fn get_connection<T: Operation>(
     session: &Option<&mut ClientSession>,
     op: &T,
     pool: &ConnectionPool,
+    deadline: Option<Instant>,
 ) -> Result<Connection> {
     let session_pinned = session
         .as_ref()
@@ -875,8 +1024,29 @@ async fn get_connection<T: Operation>(
             debug_assert_eq!(session_handle.id(), op_handle.id());
             session_handle.take_connection().await
         }
-        (None, None) => pool.check_out().await,
+        (None, None) => {
+            let timeout =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            pool.check_out(timeout).await
+        }
+    }
+}
+
+/// Appends a `maxTimeMS` field derived from the time remaining before the given deadline to a
+/// serialized command, unless the command already specifies one.
+fn append_max_time_ms(command: Vec<u8>, deadline: Instant) -> Result<Vec<u8>> {
+    let mut command = RawDocumentBuf::from_bytes(command)?;
+    if command.get("maxTimeMS")?.is_none() {
+        // A maxTimeMS of 0 means no limit, so at least one millisecond is always sent.
+        let remaining = deadline
+            .saturating_duration_since(Instant::now())
+            .as_millis();
+        command.append(
+            "maxTimeMS",
+            i64::try_from(remaining.max(1)).unwrap_or(i64::MAX),
+        );
     }
+    Ok(command.into_bytes())
 }
 
 impl Error {
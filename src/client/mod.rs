@@ -1,4 +1,6 @@
 pub mod auth;
+#[cfg(feature = "csfle")]
+pub(crate) mod csfle;
 mod executor;
 pub mod options;
 pub mod session;
@@ -12,19 +14,22 @@ use derivative::Derivative;
 
 #[cfg(test)]
 use crate::options::ServerAddress;
+#[cfg(feature = "tracing")]
+use crate::trace;
 use crate::{
-    bson::Document,
+    bson::{doc, Document},
     change_stream::{
         event::ChangeStreamEvent,
         options::ChangeStreamOptions,
         session::SessionChangeStream,
         ChangeStream,
     },
+    command::RunnableCommand,
     concern::{ReadConcern, WriteConcern},
     db::Database,
-    error::{ErrorKind, Result},
-    event::command::CommandEventHandler,
-    operation::{AggregateTarget, ListDatabases},
+    error::{Error, ErrorKind, Result},
+    event::{self, command::CommandEventHandler, sdam::TopologyDescription},
+    operation::{AggregateTarget, ListDatabases, RunCommand},
     options::{
         ClientOptions,
         DatabaseOptions,
@@ -34,7 +39,7 @@ use crate::{
         SessionOptions,
     },
     results::DatabaseSpecification,
-    sdam::{SelectedServer, SessionSupportStatus, Topology},
+    sdam::{SelectedServer, SessionSupportStatus, Topology, TopologyWatcher},
     ClientSession,
 };
 pub(crate) use executor::{HELLO_COMMAND_NAMES, REDACTED_COMMANDS};
@@ -44,6 +49,9 @@ use session::{ServerSession, ServerSessionPool};
 
 const DEFAULT_SERVER_SELECTION_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// The maximum number of session ids that can be included in a single `endSessions` command.
+const MAX_END_SESSIONS_BATCH_SIZE: usize = 10_000;
+
 /// This is the main entry point for the API. A `Client` is used to connect to a MongoDB cluster.
 /// By default, it will monitor the topology of the cluster, keeping track of any changes, such
 /// as servers being added or removed.
@@ -101,6 +109,8 @@ struct ClientInner {
     topology: Topology,
     options: ClientOptions,
     session_pool: ServerSessionPool,
+    #[cfg(feature = "csfle")]
+    csfle: Option<csfle::ClientState>,
 }
 
 impl Drop for ClientInner {
@@ -125,19 +135,28 @@ impl Client {
     pub fn with_options(options: ClientOptions) -> Result<Self> {
         options.validate()?;
 
+        #[cfg(feature = "csfle")]
+        let csfle = options
+            .auto_encryption_options
+            .as_ref()
+            .map(|auto_encryption_options| {
+                csfle::ClientState::new(&options, auto_encryption_options)
+            })
+            .transpose()?;
+
         let inner = Arc::new(ClientInner {
             topology: Topology::new(options.clone())?,
             session_pool: ServerSessionPool::new(),
             options,
+            #[cfg(feature = "csfle")]
+            csfle,
         });
 
         Ok(Self { inner })
     }
 
-    pub(crate) fn emit_command_event(&self, emit: impl FnOnce(&Arc<dyn CommandEventHandler>)) {
-        if let Some(ref handler) = self.inner.options.command_event_handler {
-            emit(handler);
-        }
+    pub(crate) fn emit_command_event(&self, emit: impl FnOnce(&dyn CommandEventHandler)) {
+        event::emit_command_event(&self.inner.options, emit);
     }
 
     /// Gets the default selection criteria the `Client` uses for operations..
@@ -155,6 +174,11 @@ impl Client {
         self.inner.options.write_concern.as_ref()
     }
 
+    /// Gets the default timeout the `Client` uses for operations.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.inner.options.timeout
+    }
+
     /// Gets a handle to a database specified by `name` in the cluster the `Client` is connected to.
     /// The `Database` options (e.g. read preference and write concern) will default to those of the
     /// `Client`.
@@ -249,6 +273,25 @@ impl Client {
         }
     }
 
+    /// Runs a command defined by a [`RunnableCommand`] against the `admin` database, returning its
+    /// typed output. See [`Database::run_typed_command`] for more information.
+    pub async fn run_typed_command<C: RunnableCommand>(&self, command: C) -> Result<C::Output> {
+        self.database("admin").run_typed_command(command).await
+    }
+
+    /// Runs a command defined by a [`RunnableCommand`] against the `admin` database using the
+    /// provided `ClientSession`, returning its typed output. See
+    /// [`Database::run_typed_command`] for more information.
+    pub async fn run_typed_command_with_session<C: RunnableCommand>(
+        &self,
+        command: C,
+        session: &mut ClientSession,
+    ) -> Result<C::Output> {
+        self.database("admin")
+            .run_typed_command_with_session(command, session)
+            .await
+    }
+
     /// Starts a new `ClientSession`.
     pub async fn start_session(&self, options: Option<SessionOptions>) -> Result<ClientSession> {
         if let Some(ref options) = options {
@@ -282,8 +325,7 @@ impl Client {
     ///
     /// If the pipeline alters the structure of the returned events, the parsed type will need to be
     /// changed via [`ChangeStream::with_type`].
-    #[allow(unused)]
-    pub(crate) async fn watch(
+    pub async fn watch(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
@@ -299,8 +341,7 @@ impl Client {
 
     /// Starts a new [`SessionChangeStream`] that receives events for all changes in the cluster
     /// using the provided [`ClientSession`].  See [`Client::watch`] for more information.
-    #[allow(unused)]
-    pub(crate) async fn watch_with_session(
+    pub async fn watch_with_session(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
@@ -317,6 +358,70 @@ impl Client {
             .await
     }
 
+    /// Gets a snapshot of the most up-to-date description of the cluster's topology, including the
+    /// type, round trip time and tags of each server the `Client` knows about.
+    ///
+    /// This does not perform any I/O; to wait for the topology to reach a certain state, use
+    /// [`Client::watch_topology`].
+    pub fn topology_description(&self) -> TopologyDescription {
+        self.inner
+            .topology
+            .watch_description()
+            .borrow()
+            .clone()
+            .into()
+    }
+
+    /// Returns a [`TopologyWatcher`], which is a `Stream` of the descriptions of the cluster's
+    /// topology that yields a new description each time the topology changes.
+    /// [`TopologyWatcher::wait_for`] can be used to wait for the topology to satisfy a condition,
+    /// e.g. that a primary has been discovered.
+    pub fn watch_topology(&self) -> TopologyWatcher {
+        TopologyWatcher::new(self.inner.topology.watch_description())
+    }
+
+    /// Shuts down the `Client`, gracefully releasing the resources it holds. This ends the server
+    /// sessions in the `Client`'s session pool, stops monitoring the cluster and waits for the
+    /// monitoring tasks to finish, and closes each connection pool, waiting for the connections
+    /// checked out by in-progress operations to be returned and closed. The `PoolClosedEvent`s
+    /// and the `TopologyClosedEvent` are emitted before this method returns. If automatic
+    /// encryption is enabled, the internal client created for the key vault is shut down as
+    /// well; a key vault client provided in the options is not.
+    ///
+    /// Because `Client` is shared via an `Arc`, this affects every clone of the `Client` along with
+    /// any `Database`s and `Collection`s created from it. Operations started after this method is
+    /// called will fail with [`ErrorKind::Shutdown`](error/enum.ErrorKind.html#variant.Shutdown).
+    ///
+    /// Note that in load-balanced mode, connections pinned to cursors or transactions are only
+    /// returned once the cursors and sessions they are pinned to are dropped, so those must be
+    /// dropped for this method to complete.
+    pub async fn shutdown(self) {
+        self.end_all_sessions().await;
+        self.inner.topology.shutdown().await;
+        #[cfg(feature = "csfle")]
+        self.shutdown_auto_encryption().await;
+    }
+
+    /// Sends `endSessions` for all of the server sessions in the pool. Errors are ignored, since
+    /// the server will clean up any sessions that weren't ended once they time out.
+    async fn end_all_sessions(&self) {
+        let session_ids = self.inner.session_pool.drain().await;
+        for ids in session_ids.chunks(MAX_END_SESSIONS_BATCH_SIZE) {
+            let selection_criteria =
+                SelectionCriteria::ReadPreference(ReadPreference::PrimaryPreferred {
+                    options: Default::default(),
+                });
+            if let Ok(op) = RunCommand::new(
+                "admin".to_string(),
+                doc! { "endSessions": ids.to_vec() },
+                Some(selection_criteria),
+                None,
+            ) {
+                let _: Result<_> = self.execute_operation(op, None).await;
+            }
+        }
+    }
+
     /// Check in a server session to the server session pool.
     /// If the session is expired or dirty, or the topology no longer supports sessions, the session
     /// will be discarded.
@@ -372,22 +477,57 @@ impl Client {
         &self,
         criteria: Option<&SelectionCriteria>,
     ) -> Result<ServerAddress> {
-        let server = self.select_server(criteria).await?;
+        let server = self.select_server(criteria, None).await?;
         Ok(server.address.clone())
     }
 
     /// Select a server using the provided criteria. If none is provided, a primary read preference
     /// will be used instead.
-    async fn select_server(&self, criteria: Option<&SelectionCriteria>) -> Result<SelectedServer> {
+    ///
+    /// If a deadline is provided and it is reached before the server selection timeout, an
+    /// `ErrorKind::Timeout` error is returned instead of a server selection error.
+    async fn select_server(
+        &self,
+        criteria: Option<&SelectionCriteria>,
+        deadline: Option<Instant>,
+    ) -> Result<SelectedServer> {
+        if !self.inner.topology.is_alive() {
+            return Err(ErrorKind::Shutdown.into());
+        }
+
         let criteria =
             criteria.unwrap_or(&SelectionCriteria::ReadPreference(ReadPreference::Primary));
 
+        #[cfg(feature = "tracing")]
+        trace::server_selection_started(criteria);
+
+        let result = self.select_server_until_timeout(criteria, deadline).await;
+
+        #[cfg(feature = "tracing")]
+        trace::server_selection_finished(criteria, &result);
+
+        result
+    }
+
+    async fn select_server_until_timeout(
+        &self,
+        criteria: &SelectionCriteria,
+        deadline: Option<Instant>,
+    ) -> Result<SelectedServer> {
         let start_time = Instant::now();
-        let timeout = self
+        let mut timeout = self
             .inner
             .options
             .server_selection_timeout
             .unwrap_or(DEFAULT_SERVER_SELECTION_TIMEOUT);
+        let mut bounded_by_deadline = false;
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(start_time);
+            if remaining < timeout {
+                timeout = remaining;
+                bounded_by_deadline = true;
+            }
+        }
 
         loop {
             let mut topology_change_subscriber =
@@ -412,19 +552,26 @@ impl Client {
                 .checked_sub(time_passed)
                 .unwrap_or_else(|| Duration::from_millis(0));
 
+            #[cfg(feature = "tracing")]
+            trace::server_selection_waiting(criteria, time_remaining);
+
             let change_occurred = topology_change_subscriber
                 .wait_for_message(time_remaining)
                 .await;
 
             if !change_occurred {
-                return Err(ErrorKind::ServerSelection {
-                    message: self
-                        .inner
-                        .topology
-                        .server_selection_timeout_error_message(criteria)
-                        .await,
+                let message = self
+                    .inner
+                    .topology
+                    .server_selection_timeout_error_message(criteria)
+                    .await;
+                if bounded_by_deadline {
+                    return Err(Error::timeout(format!(
+                        "Timed out during server selection: {}",
+                        message
+                    )));
                 }
-                .into());
+                return Err(ErrorKind::ServerSelection { message }.into());
             }
         }
     }
@@ -442,9 +589,4 @@ impl Client {
     pub(crate) async fn sync_workers(&self) {
         self.inner.topology.sync_workers().await;
     }
-
-    #[cfg(test)]
-    pub(crate) async fn topology_description(&self) -> crate::sdam::TopologyDescription {
-        self.inner.topology.description().await
-    }
 }
@@ -4,12 +4,11 @@ mod test;
 mod resolver_config;
 
 use std::{
+    borrow::Cow,
     collections::HashSet,
     convert::TryFrom,
     fmt::{self, Display, Formatter},
-    fs::File,
     hash::{Hash, Hasher},
-    io::{BufReader, Seek, SeekFrom},
     path::PathBuf,
     str::FromStr,
     sync::Arc,
@@ -18,15 +17,6 @@ use std::{
 
 use derivative::Derivative;
 use lazy_static::lazy_static;
-use rustls::{
-    internal::pemfile,
-    Certificate,
-    RootCertStore,
-    ServerCertVerified,
-    ServerCertVerifier,
-    TLSError,
-};
-use rustls_pemfile::{read_one, Item};
 use serde::{
     de::{Error, Unexpected},
     Deserialize,
@@ -36,8 +26,9 @@ use serde::{
 use serde_with::skip_serializing_none;
 use strsim::jaro_winkler;
 use typed_builder::TypedBuilder;
-use webpki_roots::TLS_SERVER_ROOTS;
 
+#[cfg(feature = "csfle")]
+use crate::client::csfle::options::AutoEncryptionOptions;
 #[cfg(test)]
 use crate::srv::LookupHosts;
 use crate::{
@@ -71,6 +62,7 @@ const URI_OPTIONS: &[&str] = &[
     "localthresholdms",
     "maxidletimems",
     "maxstalenessseconds",
+    "maxconnecting",
     "maxpoolsize",
     "minpoolsize",
     "readconcernlevel",
@@ -81,12 +73,17 @@ const URI_OPTIONS: &[&str] = &[
     "retryreads",
     "serverselectiontimeoutms",
     "sockettimeoutms",
+    "timeoutms",
     "tls",
     "ssl",
     "tlsinsecure",
     "tlsallowinvalidcertificates",
+    "tlsallowinvalidhostnames",
     "tlscafile",
     "tlscertificatekeyfile",
+    "tlscertificatekeyfilepassword",
+    "tlsdisablecertificaterevocationcheck",
+    "tlsdisableocspendpointcheck",
     "w",
     "waitqueuetimeoutms",
     "wtimeoutms",
@@ -108,8 +105,7 @@ lazy_static! {
 
 /// An enum representing the address of a MongoDB server.
 ///
-/// Currently this just supports addresses that can be connected to over TCP, but alternative
-/// address types may be supported in the future (e.g. Unix Domain Socket paths).
+/// Addresses can either be connected to over TCP or, on Unix platforms, via a Unix domain socket.
 #[derive(Clone, Debug, Eq, Serialize)]
 #[non_exhaustive]
 pub enum ServerAddress {
@@ -123,6 +119,17 @@ pub enum ServerAddress {
         /// The default is 27017.
         port: Option<u16>,
     },
+
+    /// A Unix domain socket path.
+    ///
+    /// Paths ending in ".sock" are parsed as Unix domain socket paths. When specified in a
+    /// connection string, the path must be percent-encoded (e.g.
+    /// `mongodb://%2Ftmp%2Fmongodb-27017.sock`).
+    #[cfg(unix)]
+    Unix {
+        /// The path to the Unix domain socket that the MongoDB server is listening on.
+        path: PathBuf,
+    },
 }
 
 impl<'de> Deserialize<'de> for ServerAddress {
@@ -154,6 +161,10 @@ impl PartialEq for ServerAddress {
                     port: other_port,
                 },
             ) => host == other_host && port.unwrap_or(27017) == other_port.unwrap_or(27017),
+            #[cfg(unix)]
+            (Self::Unix { path }, Self::Unix { path: other_path }) => path == other_path,
+            #[cfg(unix)]
+            _ => false,
         }
     }
 }
@@ -168,6 +179,8 @@ impl Hash for ServerAddress {
                 host.hash(state);
                 port.unwrap_or(27017).hash(state);
             }
+            #[cfg(unix)]
+            Self::Unix { path } => path.hash(state),
         }
     }
 }
@@ -183,6 +196,11 @@ impl ServerAddress {
     /// Parses an address string into a `ServerAddress`.
     pub fn parse(address: impl AsRef<str>) -> Result<Self> {
         let address = address.as_ref();
+
+        if address.ends_with(".sock") {
+            return Self::parse_unix_socket_path(address);
+        }
+
         let mut parts = address.split(':');
         let hostname = match parts.next() {
             Some(part) => {
@@ -244,6 +262,23 @@ impl ServerAddress {
         })
     }
 
+    #[cfg(unix)]
+    fn parse_unix_socket_path(path: &str) -> Result<Self> {
+        Ok(ServerAddress::Unix { path: path.into() })
+    }
+
+    #[cfg(not(unix))]
+    fn parse_unix_socket_path(path: &str) -> Result<Self> {
+        Err(ErrorKind::InvalidArgument {
+            message: format!(
+                "invalid server address: \"{}\"; Unix domain sockets are not supported on this \
+                 platform",
+                path
+            ),
+        }
+        .into())
+    }
+
     #[cfg(all(test, not(feature = "sync")))]
     pub(crate) fn into_document(self) -> Document {
         match self {
@@ -253,18 +288,39 @@ impl ServerAddress {
                     "port": port.map(|i| Bson::Int32(i.into())).unwrap_or(Bson::Null)
                 }
             }
+            #[cfg(unix)]
+            Self::Unix { path } => {
+                doc! {
+                    "host": path.to_string_lossy().into_owned(),
+                    "port": Bson::Null,
+                }
+            }
         }
     }
 
-    pub(crate) fn host(&self) -> &str {
+    /// The hostname of a TCP address, or the path of a Unix domain socket.
+    pub(crate) fn host(&self) -> Cow<'_, str> {
         match self {
-            Self::Tcp { host, .. } => host.as_str(),
+            Self::Tcp { host, .. } => Cow::Borrowed(host.as_str()),
+            #[cfg(unix)]
+            Self::Unix { path } => path.to_string_lossy(),
         }
     }
 
     pub(crate) fn port(&self) -> Option<u16> {
         match self {
             Self::Tcp { port, .. } => *port,
+            #[cfg(unix)]
+            Self::Unix { .. } => None,
+        }
+    }
+
+    /// Whether this address refers to a Unix domain socket.
+    pub(crate) fn is_unix_socket(&self) -> bool {
+        match self {
+            Self::Tcp { .. } => false,
+            #[cfg(unix)]
+            Self::Unix { .. } => true,
         }
     }
 }
@@ -275,6 +331,8 @@ impl fmt::Display for ServerAddress {
             Self::Tcp { host, port } => {
                 write!(fmt, "{}:{}", host, port.unwrap_or(DEFAULT_PORT))
             }
+            #[cfg(unix)]
+            Self::Unix { path } => write!(fmt, "{}", path.display()),
         }
     }
 }
@@ -396,6 +454,16 @@ pub struct ClientOptions {
     #[serde(skip)]
     pub command_event_handler: Option<Arc<dyn CommandEventHandler>>,
 
+    /// The options for automatic client-side field level encryption. See
+    /// [`AutoEncryptionOptions`] for more details.
+    ///
+    /// By default, automatic encryption is disabled.
+    #[cfg(feature = "csfle")]
+    #[builder(default)]
+    #[serde(skip)]
+    #[derivative(PartialEq = "ignore")]
+    pub auto_encryption_options: Option<AutoEncryptionOptions>,
+
     /// The connect timeout passed to each underlying TcpStream when attemtping to connect to the
     /// server.
     ///
@@ -461,6 +529,23 @@ pub struct ClientOptions {
     #[builder(default)]
     pub max_pool_size: Option<u32>,
 
+    /// The maximum number of connections that the Client should establish concurrently in a
+    /// connection pool for a given server. If an operation needs a new connection while
+    /// `max_connecting` connections are already being established, it will wait until one of
+    /// them finishes or an existing connection is checked back into the pool.
+    ///
+    /// The default value is 2.
+    #[builder(default)]
+    pub max_connecting: Option<u32>,
+
+    /// The amount of time an operation should wait for a connection to become available in a
+    /// connection pool before returning an error. A value of zero indicates that operations
+    /// should wait indefinitely.
+    ///
+    /// By default, operations will wait indefinitely.
+    #[builder(default)]
+    pub wait_queue_timeout: Option<Duration>,
+
     /// The minimum number of connections that should be available in a server's connection pool at
     /// a given time. If fewer than `min_pool_size` connections are in the pool, connections will
     /// be added to the pool in the background until `min_pool_size` is reached.
@@ -533,12 +618,31 @@ pub struct ClientOptions {
     #[derivative(Debug = "ignore")]
     pub(crate) socket_timeout: Option<Duration>,
 
+    /// The default amount of time that each operation performed by the Client may take in total,
+    /// including server selection, connection checkout, retries, and network round trips. When
+    /// this is set, the driver derives each command's `maxTimeMS` from the time remaining. This
+    /// can be overridden on a per-database, per-collection, or per-operation basis.
+    ///
+    /// A value of zero indicates that operations should not time out. By default, no timeout is
+    /// applied.
+    #[builder(default)]
+    pub timeout: Option<Duration>,
+
     /// The TLS configuration for the Client to use in its connections with the server.
     ///
     /// By default, TLS is disabled.
     #[builder(default)]
     pub tls: Option<Tls>,
 
+    /// The maximum length, in bytes, of the command, reply and heartbeat documents included in
+    /// events emitted via `tracing`. Longer documents are truncated.
+    ///
+    /// The default value is 1000.
+    #[cfg(feature = "tracing")]
+    #[builder(default)]
+    #[serde(skip)]
+    pub tracing_max_document_length_bytes: Option<usize>,
+
     /// Specifies the default write concern for operations performed on the Client. See the
     /// WriteConcern type documentation for more details.
     #[builder(default)]
@@ -625,6 +729,8 @@ impl Serialize for ClientOptions {
             #[serde(serialize_with = "bson_util::serialize_duration_option_as_int_millis")]
             maxidletimems: &'a Option<Duration>,
 
+            maxconnecting: &'a Option<u32>,
+
             maxpoolsize: &'a Option<u32>,
 
             minpoolsize: &'a Option<u32>,
@@ -650,9 +756,15 @@ impl Serialize for ClientOptions {
             #[serde(serialize_with = "bson_util::serialize_duration_option_as_int_millis")]
             sockettimeoutms: &'a Option<Duration>,
 
+            #[serde(serialize_with = "bson_util::serialize_duration_option_as_int_millis")]
+            timeoutms: &'a Option<Duration>,
+
             #[serde(flatten, serialize_with = "Tls::serialize_for_client_options")]
             tls: &'a Option<Tls>,
 
+            #[serde(serialize_with = "bson_util::serialize_duration_option_as_int_millis")]
+            waitqueuetimeoutms: &'a Option<Duration>,
+
             #[serde(flatten, serialize_with = "WriteConcern::serialize_for_client_options")]
             writeconcern: &'a Option<WriteConcern>,
 
@@ -669,6 +781,7 @@ impl Serialize for ClientOptions {
             heartbeatfrequencyms: &self.heartbeat_freq,
             localthresholdms: &self.local_threshold,
             maxidletimems: &self.max_idle_time,
+            maxconnecting: &self.max_connecting,
             maxpoolsize: &self.max_pool_size,
             minpoolsize: &self.min_pool_size,
             readconcern: &self.read_concern,
@@ -678,7 +791,9 @@ impl Serialize for ClientOptions {
             selectioncriteria: &self.selection_criteria,
             serverselectiontimeoutms: &self.server_selection_timeout,
             sockettimeoutms: &self.socket_timeout,
+            timeoutms: &self.timeout,
             tls: &self.tls,
+            waitqueuetimeoutms: &self.wait_queue_timeout,
             writeconcern: &self.write_concern,
             loadbalanced: &self.load_balanced,
             zlibcompressionlevel: &None,
@@ -704,12 +819,14 @@ struct ClientOptionsParser {
     pub max_pool_size: Option<u32>,
     pub min_pool_size: Option<u32>,
     pub max_idle_time: Option<Duration>,
+    pub max_connecting: Option<u32>,
     pub wait_queue_timeout: Option<Duration>,
     pub compressors: Option<Vec<Compressor>>,
     pub connect_timeout: Option<Duration>,
     pub retry_reads: Option<bool>,
     pub retry_writes: Option<bool>,
     pub socket_timeout: Option<Duration>,
+    pub timeout: Option<Duration>,
     pub zlib_compression: Option<i32>,
     pub direct_connection: Option<bool>,
     pub credential: Option<Credential>,
@@ -767,7 +884,11 @@ impl Tls {
 }
 
 /// Specifies the TLS configuration that the [`Client`](../struct.Client.html) should use.
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, TypedBuilder)]
+///
+/// By default, TLS connections are made using `rustls`. If the `openssl-tls` feature flag is
+/// enabled, OpenSSL is used instead.
+#[derive(Clone, Default, Deserialize, Derivative, PartialEq, TypedBuilder)]
+#[derivative(Debug)]
 #[builder(field_defaults(default, setter(into)))]
 #[non_exhaustive]
 pub struct TlsOptions {
@@ -778,109 +899,64 @@ pub struct TlsOptions {
     /// The default value is to error when the server presents an invalid certificate.
     pub allow_invalid_certificates: Option<bool>,
 
+    /// Whether or not the [`Client`](../struct.Client.html) should return an error if the
+    /// hostname the server was connected to does not match the one in the certificate it
+    /// presents. This setting should _not_ be set to `true` in production; it should only be used
+    /// for testing.
+    ///
+    /// The default value is to error when the hostname does not match.
+    pub allow_invalid_hostnames: Option<bool>,
+
     /// The path to the CA file that the [`Client`](../struct.Client.html) should use for TLS. If
-    /// none is specified, then the driver will use the Mozilla root certificates from the
-    /// `webpki-roots` crate.
+    /// neither this nor `ca_pem` is specified, then the driver will use the Mozilla root
+    /// certificates from the `webpki-roots` crate (or the system's root certificates when using
+    /// OpenSSL).
     pub ca_file_path: Option<PathBuf>,
 
+    /// The PEM-encoded CA certificates that the [`Client`](../struct.Client.html) should use for
+    /// TLS. This can be used instead of or in addition to `ca_file_path` when the certificates
+    /// are not stored on disk.
+    #[derivative(Debug = "ignore")]
+    pub ca_pem: Option<Vec<u8>>,
+
     /// The path to the certificate file that the [`Client`](../struct.Client.html) should present
-    /// to the server to verify its identify. If none is specified, then the
-    /// [`Client`](../struct.Client.html) will not attempt to verify its identity to the
+    /// to the server to verify its identify. If neither this nor `cert_key_pem` is specified,
+    /// then the [`Client`](../struct.Client.html) will not attempt to verify its identity to the
     /// server.
     pub cert_key_file_path: Option<PathBuf>,
-}
-
-struct NoCertVerifier {}
-
-impl ServerCertVerifier for NoCertVerifier {
-    fn verify_server_cert(
-        &self,
-        _: &RootCertStore,
-        _: &[Certificate],
-        _: webpki::DNSNameRef,
-        _: &[u8],
-    ) -> std::result::Result<ServerCertVerified, TLSError> {
-        Ok(ServerCertVerified::assertion())
-    }
-}
-
-impl TlsOptions {
-    /// Converts `TlsOptions` into a rustls::ClientConfig.
-    pub(crate) fn into_rustls_config(self) -> Result<rustls::ClientConfig> {
-        let mut config = rustls::ClientConfig::new();
-
-        if let Some(true) = self.allow_invalid_certificates {
-            config
-                .dangerous()
-                .set_certificate_verifier(Arc::new(NoCertVerifier {}));
-        }
 
-        let mut store = RootCertStore::empty();
-        if let Some(path) = self.ca_file_path {
-            store
-                .add_pem_file(&mut BufReader::new(File::open(&path)?))
-                .map_err(|_| ErrorKind::InvalidTlsConfig {
-                    message: format!(
-                        "Unable to parse PEM-encoded root certificate from {}",
-                        path.display()
-                    ),
-                })?;
-        } else {
-            store.add_server_trust_anchors(&TLS_SERVER_ROOTS);
-        }
-
-        config.root_store = store;
-
-        if let Some(path) = self.cert_key_file_path {
-            let mut file = BufReader::new(File::open(&path)?);
-            let certs = match pemfile::certs(&mut file) {
-                Ok(certs) => certs,
-                Err(()) => {
-                    return Err(ErrorKind::InvalidTlsConfig {
-                        message: format!(
-                            "Unable to parse PEM-encoded client certificate from {}",
-                            path.display()
-                        ),
-                    }
-                    .into())
-                }
-            };
+    /// The PEM-encoded certificate and private key that the [`Client`](../struct.Client.html)
+    /// should present to the server to verify its identity. This can be used instead of
+    /// `cert_key_file_path` when the certificate is not stored on disk; specifying both is an
+    /// error.
+    #[derivative(Debug = "ignore")]
+    pub cert_key_pem: Option<Vec<u8>>,
 
-            file.seek(SeekFrom::Start(0))?;
-            let key = loop {
-                match read_one(&mut file) {
-                    Ok(Some(Item::PKCS8Key(bytes))) | Ok(Some(Item::RSAKey(bytes))) => {
-                        break rustls::PrivateKey(bytes)
-                    }
-                    Ok(Some(_)) => continue,
-                    Ok(None) => {
-                        return Err(ErrorKind::InvalidTlsConfig {
-                            message: format!("No PEM-encoded keys in {}", path.display()),
-                        }
-                        .into())
-                    }
-                    Err(_) => {
-                        return Err(ErrorKind::InvalidTlsConfig {
-                            message: format!(
-                                "Unable to parse PEM-encoded item from {}",
-                                path.display()
-                            ),
-                        }
-                        .into())
-                    }
-                }
-            };
+    /// The password used to decrypt the private key in `cert_key_file_path` or `cert_key_pem`.
+    /// Encrypted keys must be in the PKCS#8 format.
+    ///
+    /// When using rustls, this requires the `cert-key-password` feature flag to be enabled.
+    #[derivative(Debug = "ignore")]
+    pub cert_key_password: Option<Vec<u8>>,
 
-            config
-                .set_single_client_cert(certs, key)
-                .map_err(|e| ErrorKind::InvalidTlsConfig {
-                    message: e.to_string(),
-                })?;
-        }
+    /// Whether or not the [`Client`](../struct.Client.html) should contact the server's OCSP
+    /// responder when the server does not staple a valid OCSP response to its certificate. A
+    /// stapled response is still checked when this is `true`.
+    ///
+    /// Revocation checking requires the `ocsp` feature flag to be enabled. The default value is
+    /// to contact the responder.
+    pub disable_ocsp_endpoint_check: Option<bool>,
 
-        Ok(config)
-    }
+    /// Whether or not the [`Client`](../struct.Client.html) should skip checking whether the
+    /// server's certificate has been revoked, both via stapled OCSP responses and by contacting
+    /// the server's OCSP responder.
+    ///
+    /// Revocation checking requires the `ocsp` feature flag to be enabled. The default value is
+    /// to check for revocation.
+    pub disable_certificate_revocation_check: Option<bool>,
+}
 
+impl TlsOptions {
     #[cfg(test)]
     pub(crate) fn serialize_for_client_options<S>(
         tls_options: &TlsOptions,
@@ -894,7 +970,11 @@ impl TlsOptions {
             tls: bool,
             tlscafile: Option<&'a str>,
             tlscertificatekeyfile: Option<&'a str>,
+            tlscertificatekeyfilepassword: Option<&'a str>,
             tlsallowinvalidcertificates: Option<bool>,
+            tlsallowinvalidhostnames: Option<bool>,
+            tlsdisableocspendpointcheck: Option<bool>,
+            tlsdisablecertificaterevocationcheck: Option<bool>,
         }
 
         let state = TlsOptionsHelper {
@@ -907,7 +987,14 @@ impl TlsOptions {
                 .cert_key_file_path
                 .as_ref()
                 .map(|s| s.to_str().unwrap()),
+            tlscertificatekeyfilepassword: tls_options
+                .cert_key_password
+                .as_deref()
+                .map(|s| std::str::from_utf8(s).unwrap()),
             tlsallowinvalidcertificates: tls_options.allow_invalid_certificates,
+            tlsallowinvalidhostnames: tls_options.allow_invalid_hostnames,
+            tlsdisableocspendpointcheck: tls_options.disable_ocsp_endpoint_check,
+            tlsdisablecertificaterevocationcheck: tls_options.disable_certificate_revocation_check,
         };
         state.serialize(serializer)
     }
@@ -946,18 +1033,25 @@ impl From<ClientOptionsParser> for ClientOptions {
             max_pool_size: parser.max_pool_size,
             min_pool_size: parser.min_pool_size,
             max_idle_time: parser.max_idle_time,
+            max_connecting: parser.max_connecting,
+            wait_queue_timeout: parser.wait_queue_timeout,
             server_selection_timeout: parser.server_selection_timeout,
             compressors: parser.compressors,
             connect_timeout: parser.connect_timeout,
             retry_reads: parser.retry_reads,
             retry_writes: parser.retry_writes,
             socket_timeout: parser.socket_timeout,
+            timeout: parser.timeout,
+            #[cfg(feature = "tracing")]
+            tracing_max_document_length_bytes: None,
             direct_connection: parser.direct_connection,
             default_database: parser.default_database,
             driver_info: None,
             credential: parser.credential,
             cmap_event_handler: None,
             command_event_handler: None,
+            #[cfg(feature = "csfle")]
+            auto_encryption_options: None,
             original_srv_info: None,
             original_uri: Some(parser.original_uri),
             resolver_config: None,
@@ -1011,6 +1105,7 @@ impl ClientOptions {
     ///   * `maxIdleTimeMS`: maps to the `max_idle_time` field
     ///   * `maxStalenessSeconds`: maps to the `max_staleness` field of the `selection_criteria`
     ///     field
+    ///   * `maxConnecting`: maps to the `max_connecting` field
     ///   * `maxPoolSize`: maps to the `max_pool_size` field
     ///   * `minPoolSize`: maps to the `min_pool_size` field
     ///   * `readConcernLevel`: maps to the `read_concern` field
@@ -1023,18 +1118,28 @@ impl ClientOptions {
     ///   * `retryWrites`: not yet implemented
     ///   * `retryReads`: maps to the `retry_reads` field
     ///   * `serverSelectionTimeoutMS`: maps to the `server_selection_timeout` field
-    ///   * `socketTimeoutMS`: unsupported, does not map to any field
+    ///   * `socketTimeoutMS`: bounds each individual network read or write, does not map to any
+    ///     public field
     ///   * `ssl`: an alias of the `tls` option
+    ///   * `timeoutMS`: maps to the `timeout` field
     ///   * `tls`: maps to the TLS variant of the `tls` field`.
     ///   * `tlsInsecure`: relaxes the TLS constraints on connections being made; currently is just
     ///     an alias of `tlsAllowInvalidCertificates`, but more behavior may be added to this option
     ///     in the future
     ///   * `tlsAllowInvalidCertificates`: maps to the `allow_invalidCertificates` field of the
     ///     `tls` field
+    ///   * `tlsAllowInvalidHostnames`: maps to the `allow_invalid_hostnames` field of the `tls`
+    ///     field
     ///   * `tlsCAFile`: maps to the `ca_file_path` field of the `tls` field
     ///   * `tlsCertificateKeyFile`: maps to the `cert_key_file_path` field of the `tls` field
+    ///   * `tlsCertificateKeyFilePassword`: maps to the `cert_key_password` field of the `tls`
+    ///     field
+    ///   * `tlsDisableCertificateRevocationCheck`: maps to the
+    ///     `disable_certificate_revocation_check` field of the `tls` field
+    ///   * `tlsDisableOCSPEndpointCheck`: maps to the `disable_ocsp_endpoint_check` field of the
+    ///     `tls` field
     ///   * `w`: maps to the `w` field of the `write_concern` field
-    ///   * `waitQueueTimeoutMS`: unsupported, does not map to any field
+    ///   * `waitQueueTimeoutMS`: maps to the `wait_queue_timeout` field
     ///   * `wTimeoutMS`: maps to the `w_timeout` field of the `write_concern` field
     ///   * `zlibCompressionLevel`: maps to the `level` field of the `Compressor::Zlib` variant
     ///     (which requires the `zlib-compression` feature flag) of the [`Compressor`] enum
@@ -1099,7 +1204,7 @@ impl ClientOptions {
         if srv {
             let mut resolver = SrvResolver::new(resolver_config.map(|config| config.inner)).await?;
             let mut config = resolver
-                .resolve_client_options(options.hosts[0].host())
+                .resolve_client_options(&options.hosts[0].host())
                 .await?;
 
             // Save the original SRV info to allow mongos polling.
@@ -1210,6 +1315,12 @@ impl ClientOptions {
             ));
         }
 
+        if let Some(0) = self.max_connecting {
+            return Err(crate::error::Error::invalid_argument(
+                "cannot specify maxConnecting=0",
+            ));
+        }
+
         Ok(())
     }
 
@@ -1234,6 +1345,7 @@ impl ClientOptions {
                 heartbeat_freq,
                 load_balanced,
                 local_threshold,
+                max_connecting,
                 max_idle_time,
                 max_pool_size,
                 min_pool_size,
@@ -1246,7 +1358,9 @@ impl ClientOptions {
                 server_selection_timeout,
                 socket_timeout,
                 test_options,
+                timeout,
                 tls,
+                wait_queue_timeout,
                 write_concern,
                 original_srv_info,
                 original_uri
@@ -1404,11 +1518,30 @@ impl ClientOptionsParser {
             None => (None, None),
         };
 
-        let hosts: Result<Vec<_>> = hosts_section.split(',').map(ServerAddress::parse).collect();
+        let hosts: Result<Vec<_>> = hosts_section
+            .split(',')
+            .map(|host| {
+                // Unix domain socket paths must be percent-encoded, since they contain '/'.
+                if host.ends_with(".sock") {
+                    let path =
+                        percent_decode(host, "Unix domain socket paths must be URL encoded")?;
+                    ServerAddress::parse(path)
+                } else {
+                    ServerAddress::parse(host)
+                }
+            })
+            .collect();
 
         let hosts = hosts?;
 
         if srv {
+            if hosts[0].is_unix_socket() {
+                return Err(ErrorKind::InvalidArgument {
+                    message: "a Unix domain socket cannot be specified with 'mongodb+srv'".into(),
+                }
+                .into());
+            }
+
             if hosts.len() != 1 {
                 return Err(ErrorKind::InvalidArgument {
                     message: "exactly one host must be specified with 'mongodb+srv'".into(),
@@ -1487,6 +1620,15 @@ impl ClientOptionsParser {
                     credential.mechanism_properties = Some(doc);
                 }
 
+                if mechanism == &AuthMechanism::Gssapi {
+                    let properties = credential
+                        .mechanism_properties
+                        .get_or_insert_with(Document::new);
+                    if !properties.contains_key("SERVICE_NAME") {
+                        properties.insert("SERVICE_NAME", "mongodb");
+                    }
+                }
+
                 mechanism.validate_credential(credential)?;
                 credential.mechanism = options.auth_mechanism.take();
             }
@@ -1601,6 +1743,38 @@ impl ClientOptionsParser {
             }
         }
 
+        if let Some(Tls::Enabled(ref options)) = self.tls {
+            let ocsp_option = match (
+                options.disable_ocsp_endpoint_check,
+                options.disable_certificate_revocation_check,
+            ) {
+                (Some(_), Some(_)) => {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: "'tlsDisableOCSPEndpointCheck' and \
+                                  'tlsDisableCertificateRevocationCheck' cannot both be specified"
+                            .to_string(),
+                    }
+                    .into())
+                }
+                (Some(_), None) => Some("tlsDisableOCSPEndpointCheck"),
+                (None, Some(_)) => Some("tlsDisableCertificateRevocationCheck"),
+                (None, None) => None,
+            };
+
+            if let Some(ocsp_option) = ocsp_option {
+                if options.allow_invalid_certificates.is_some() {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: format!(
+                            "'{}' cannot be specified with 'tlsInsecure' or \
+                             'tlsAllowInvalidCertificates'",
+                            ocsp_option
+                        ),
+                    }
+                    .into());
+                }
+            }
+        }
+
         // If zlib and zlib_compression_level are specified then write zlib_compression_level into
         // zlib enum
         if let (Some(compressors), Some(zlib_compression_level)) =
@@ -1773,6 +1947,9 @@ impl ClientOptionsParser {
 
                 self.max_staleness = Some(max_staleness);
             }
+            k @ "maxconnecting" => {
+                self.max_connecting = Some(get_u32!(value, k));
+            }
             k @ "maxpoolsize" => {
                 self.max_pool_size = Some(get_u32!(value, k));
             }
@@ -1850,6 +2027,9 @@ impl ClientOptionsParser {
             k @ "sockettimeoutms" => {
                 self.socket_timeout = Some(Duration::from_millis(get_duration!(value, k)));
             }
+            k @ "timeoutms" => {
+                self.timeout = Some(Duration::from_millis(get_duration!(value, k)));
+            }
             k @ "tls" | k @ "ssl" => {
                 let tls = get_bool!(value, k);
 
@@ -1880,6 +2060,20 @@ impl ClientOptionsParser {
 
                 let allow_invalid_certificates = if k == "tlsinsecure" { !val } else { val };
 
+                if k == "tlsinsecure" {
+                    if let Some(Tls::Enabled(ref options)) = self.tls {
+                        if options.allow_invalid_hostnames.is_some() {
+                            return Err(ErrorKind::InvalidArgument {
+                                message: "'tlsInsecure' and 'tlsAllowInvalidHostnames' cannot \
+                                          both be specified"
+                                    .into(),
+                            }
+                            .into());
+                        }
+                    }
+                    self.tls_insecure = Some(val);
+                }
+
                 match self.tls {
                     Some(Tls::Disabled) => {
                         return Err(ErrorKind::InvalidArgument {
@@ -1913,6 +2107,37 @@ impl ClientOptionsParser {
                     }
                 }
             }
+            k @ "tlsallowinvalidhostnames" => {
+                let allow_invalid_hostnames = get_bool!(value, k);
+
+                if self.tls_insecure.is_some() {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: "'tlsInsecure' and 'tlsAllowInvalidHostnames' cannot both be \
+                                  specified"
+                            .into(),
+                    }
+                    .into());
+                }
+
+                match self.tls {
+                    Some(Tls::Disabled) => {
+                        return Err(ErrorKind::InvalidArgument {
+                            message: "'tlsAllowInvalidHostnames' can't be set if tls=false".into(),
+                        }
+                        .into());
+                    }
+                    Some(Tls::Enabled(ref mut options)) => {
+                        options.allow_invalid_hostnames = Some(allow_invalid_hostnames);
+                    }
+                    None => {
+                        self.tls = Some(Tls::Enabled(
+                            TlsOptions::builder()
+                                .allow_invalid_hostnames(allow_invalid_hostnames)
+                                .build(),
+                        ))
+                    }
+                }
+            }
             "tlscafile" => match self.tls {
                 Some(Tls::Disabled) => {
                     return Err(ErrorKind::InvalidArgument {
@@ -1949,6 +2174,72 @@ impl ClientOptionsParser {
                     ))
                 }
             },
+            "tlscertificatekeyfilepassword" => match self.tls {
+                Some(Tls::Disabled) => {
+                    return Err(ErrorKind::InvalidArgument {
+                        message: "'tlsCertificateKeyFilePassword' can't be set if tls=false".into(),
+                    }
+                    .into());
+                }
+                Some(Tls::Enabled(ref mut options)) => {
+                    options.cert_key_password = Some(value.as_bytes().to_vec());
+                }
+                None => {
+                    self.tls = Some(Tls::Enabled(
+                        TlsOptions::builder()
+                            .cert_key_password(value.as_bytes().to_vec())
+                            .build(),
+                    ))
+                }
+            },
+            k @ "tlsdisableocspendpointcheck" => {
+                let disable_ocsp_endpoint_check = get_bool!(value, k);
+
+                match self.tls {
+                    Some(Tls::Disabled) => {
+                        return Err(ErrorKind::InvalidArgument {
+                            message: "'tlsDisableOCSPEndpointCheck' can't be set if tls=false"
+                                .into(),
+                        }
+                        .into());
+                    }
+                    Some(Tls::Enabled(ref mut options)) => {
+                        options.disable_ocsp_endpoint_check = Some(disable_ocsp_endpoint_check);
+                    }
+                    None => {
+                        self.tls = Some(Tls::Enabled(
+                            TlsOptions::builder()
+                                .disable_ocsp_endpoint_check(disable_ocsp_endpoint_check)
+                                .build(),
+                        ))
+                    }
+                }
+            }
+            k @ "tlsdisablecertificaterevocationcheck" => {
+                let disable_revocation_check = get_bool!(value, k);
+
+                match self.tls {
+                    Some(Tls::Disabled) => {
+                        return Err(ErrorKind::InvalidArgument {
+                            message: "'tlsDisableCertificateRevocationCheck' can't be set if \
+                                      tls=false"
+                                .into(),
+                        }
+                        .into());
+                    }
+                    Some(Tls::Enabled(ref mut options)) => {
+                        options.disable_certificate_revocation_check =
+                            Some(disable_revocation_check);
+                    }
+                    None => {
+                        self.tls = Some(Tls::Enabled(
+                            TlsOptions::builder()
+                                .disable_certificate_revocation_check(disable_revocation_check)
+                                .build(),
+                        ))
+                    }
+                }
+            }
             "w" => {
                 let mut write_concern = self.write_concern.get_or_insert_with(Default::default);
 
@@ -2057,20 +2348,60 @@ mod tests {
     #[test]
     fn test_parse_address_with_from_str() {
         let x = "localhost:27017".parse::<ServerAddress>().unwrap();
-        let ServerAddress::Tcp { host, port } = x;
-        assert_eq!(host, "localhost");
-        assert_eq!(port, Some(27017));
+        assert_eq!(x.host(), "localhost");
+        assert_eq!(x.port(), Some(27017));
 
         // Port defaults to 27017 (so this doesn't fail)
         let x = "localhost".parse::<ServerAddress>().unwrap();
-        let ServerAddress::Tcp { host, port } = x;
-        assert_eq!(host, "localhost");
-        assert_eq!(port, None);
+        assert_eq!(x.host(), "localhost");
+        assert_eq!(x.port(), None);
 
         let x = "localhost:not a number".parse::<ServerAddress>();
         assert!(x.is_err());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_unix_socket_address() {
+        let x = "/tmp/mongodb-27017.sock".parse::<ServerAddress>().unwrap();
+        assert_eq!(
+            x,
+            ServerAddress::Unix {
+                path: "/tmp/mongodb-27017.sock".into()
+            }
+        );
+        assert_eq!(x.port(), None);
+        assert_eq!(x.to_string(), "/tmp/mongodb-27017.sock");
+    }
+
+    #[cfg(unix)]
+    #[cfg_attr(feature = "tokio-runtime", tokio::test)]
+    #[cfg_attr(feature = "async-std-runtime", async_std::test)]
+    async fn parse_unix_socket_uri() {
+        let options = ClientOptions::parse(
+            "mongodb://%2Ftmp%2Fmongodb-27017.sock,%2Ftmp%2Fmongodb-27018.sock/db",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            options.hosts,
+            vec![
+                ServerAddress::Unix {
+                    path: "/tmp/mongodb-27017.sock".into()
+                },
+                ServerAddress::Unix {
+                    path: "/tmp/mongodb-27018.sock".into()
+                },
+            ]
+        );
+
+        assert!(
+            ClientOptions::parse("mongodb+srv://%2Ftmp%2Fmongodb-27017.sock")
+                .await
+                .is_err()
+        );
+    }
+
     #[cfg_attr(feature = "tokio-runtime", tokio::test)]
     #[cfg_attr(feature = "async-std-runtime", async_std::test)]
     async fn fails_without_scheme() {
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use pretty_assertions::assert_eq;
 use serde::Deserialize;
 
@@ -31,12 +33,7 @@ async fn run_test(test_file: TestFile) {
         // TODO: RUST-229: Implement IPv6 Support
         test_case.description.contains("ipv6")
             || test_case.description.contains("IP literal")
-            // TODO: RUST-226: Investigate whether tlsCertificateKeyFilePassword is supported in rustls
-            || test_case
-                .description
-                .contains("tlsCertificateKeyFilePassword")
             // Not Implementing
-            || test_case.description.contains("tlsAllowInvalidHostnames")
             || test_case.description.contains("single-threaded")
             || test_case.description.contains("serverSelectionTryOnce")
             || test_case.description.contains("Unix")
@@ -282,3 +279,71 @@ async fn options_debug_omits_uri() {
     assert!(!debug_output.contains("password"));
     assert!(!debug_output.contains("uri"));
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn parse_timeout() {
+    let options = ClientOptions::parse("mongodb://localhost/?timeoutMS=1500")
+        .await
+        .unwrap();
+    assert_eq!(options.timeout, Some(Duration::from_millis(1500)));
+
+    let options = ClientOptions::parse("mongodb://localhost/?timeoutMS=0")
+        .await
+        .unwrap();
+    assert_eq!(options.timeout, Some(Duration::from_secs(0)));
+
+    ClientOptions::parse("mongodb://localhost/?timeoutMS=-1")
+        .await
+        .expect_err("negative timeoutMS should be rejected");
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn parse_wait_queue_options() {
+    let options =
+        ClientOptions::parse("mongodb://localhost/?waitQueueTimeoutMS=250&maxConnecting=5")
+            .await
+            .unwrap();
+    assert_eq!(options.wait_queue_timeout, Some(Duration::from_millis(250)));
+    assert_eq!(options.max_connecting, Some(5));
+
+    ClientOptions::parse("mongodb://localhost/?maxConnecting=0")
+        .await
+        .expect_err("maxConnecting=0 should be rejected");
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn parse_ocsp_options() {
+    let options = ClientOptions::parse("mongodb://localhost/?tlsDisableOCSPEndpointCheck=true")
+        .await
+        .unwrap();
+    let tls_options = options.tls_options().unwrap();
+    assert_eq!(tls_options.disable_ocsp_endpoint_check, Some(true));
+    assert_eq!(tls_options.disable_certificate_revocation_check, None);
+
+    let options =
+        ClientOptions::parse("mongodb://localhost/?tlsDisableCertificateRevocationCheck=false")
+            .await
+            .unwrap();
+    let tls_options = options.tls_options().unwrap();
+    assert_eq!(
+        tls_options.disable_certificate_revocation_check,
+        Some(false)
+    );
+
+    for uri in &[
+        "mongodb://localhost/?tlsDisableOCSPEndpointCheck=true&\
+         tlsDisableCertificateRevocationCheck=false",
+        "mongodb://localhost/?tlsInsecure=true&tlsDisableOCSPEndpointCheck=false",
+        "mongodb://localhost/?tlsDisableCertificateRevocationCheck=true&\
+         tlsAllowInvalidCertificates=false",
+        "mongodb://localhost/?tls=false&tlsDisableOCSPEndpointCheck=true",
+    ] {
+        match ClientOptions::parse(uri).await.map_err(|e| *e.kind) {
+            Err(ErrorKind::InvalidArgument { .. }) => {}
+            other => panic!("expected InvalidArgument for {}, got {:?}", uri, other),
+        }
+    }
+}
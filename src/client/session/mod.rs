@@ -9,13 +9,14 @@ use std::{
     time::{Duration, Instant},
 };
 
+use futures_util::future::BoxFuture;
 use lazy_static::lazy_static;
 use uuid::Uuid;
 
 use crate::{
     bson::{doc, spec::BinarySubtype, Binary, Bson, Document, Timestamp},
     cmap::conn::PinnedConnectionHandle,
-    error::{ErrorKind, Result},
+    error::{ErrorKind, Result, TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
     operation::{AbortTransaction, CommitTransaction, Operation},
     options::{SessionOptions, TransactionOptions},
     sdam::{ServerInfo, TransactionSupportStatus},
@@ -28,9 +29,13 @@ pub(super) use pool::ServerSessionPool;
 
 use super::options::ServerAddress;
 
+/// The amount of time after which [`ClientSession::with_transaction`] stops retrying.
+pub(crate) const WITH_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
 lazy_static! {
     pub(crate) static ref SESSIONS_UNSUPPORTED_COMMANDS: HashSet<&'static str> = {
         let mut hash_set = HashSet::new();
+        hash_set.insert("endsessions");
         hash_set.insert("killcursors");
         hash_set.insert("parallelcollectionscan");
         hash_set
@@ -144,6 +149,14 @@ impl Transaction {
         self.recovery_token = None;
     }
 
+    /// Whether a transaction has been started and not yet committed or aborted.
+    pub(crate) fn is_active(&self) -> bool {
+        matches!(
+            self.state,
+            TransactionState::Starting | TransactionState::InProgress
+        )
+    }
+
     pub(crate) fn pinned_mongos(&self) -> Option<&SelectionCriteria> {
         match &self.pinned {
             Some(TransactionPin::Mongos(s)) => Some(s),
@@ -561,6 +574,99 @@ impl ClientSession {
         }
     }
 
+    /// Starts a transaction, runs the given callback, and commits or aborts the transaction.
+    ///
+    /// If the callback or the commit returns an error with a
+    /// [`crate::error::TRANSIENT_TRANSACTION_ERROR`] label, the entire transaction is retried, and
+    /// if the commit returns an error with a [`crate::error::UNKNOWN_TRANSACTION_COMMIT_RESULT`]
+    /// label, the commit is retried. Retries stop once 120 seconds have elapsed since this method
+    /// was called, in which case the last error is returned. If the callback returns any other
+    /// error, the transaction is aborted and the error is returned.
+    ///
+    /// Because the callback can be run multiple times, it must not have side effects outside of
+    /// the operations it executes with the provided session. The callback may commit or abort the
+    /// transaction itself, in which case its result is returned without committing.
+    ///
+    /// ```rust
+    /// # use futures::future::FutureExt;
+    /// # use mongodb::{bson::{doc, Document}, error::Result, Client, ClientSession};
+    /// #
+    /// # async fn do_stuff() -> Result<()> {
+    /// # let client = Client::with_uri_str("mongodb://example.com").await?;
+    /// # let coll = client.database("foo").collection::<Document>("bar");
+    /// # let mut session = client.start_session(None).await?;
+    /// session
+    ///     .with_transaction(
+    ///         |session| {
+    ///             let coll = coll.clone();
+    ///             async move {
+    ///                 coll.insert_one_with_session(doc! { "x": 1 }, None, session).await?;
+    ///                 coll.delete_one_with_session(doc! { "y": 2 }, None, session).await?;
+    ///                 Ok(())
+    ///             }
+    ///             .boxed()
+    ///         },
+    ///         None,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_transaction<R, F>(
+        &mut self,
+        mut callback: F,
+        options: impl Into<Option<TransactionOptions>>,
+    ) -> Result<R>
+    where
+        F: for<'a> FnMut(&'a mut ClientSession) -> BoxFuture<'a, Result<R>>,
+    {
+        let options = options.into();
+        let start = Instant::now();
+
+        'transaction: loop {
+            self.start_transaction(options.clone()).await?;
+
+            let result = match callback(self).await {
+                Ok(result) => result,
+                Err(error) => {
+                    if self.transaction.is_active() {
+                        self.abort_transaction().await?;
+                    }
+                    if error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+                        && start.elapsed() < WITH_TRANSACTION_TIMEOUT
+                    {
+                        continue 'transaction;
+                    }
+                    return Err(error);
+                }
+            };
+
+            // the callback committed or aborted the transaction itself.
+            if !self.transaction.is_active() {
+                return Ok(result);
+            }
+
+            'commit: loop {
+                let error = match self.commit_transaction().await {
+                    Ok(()) => return Ok(result),
+                    Err(error) => error,
+                };
+                if error.is_max_time_ms_expired_error()
+                    || start.elapsed() >= WITH_TRANSACTION_TIMEOUT
+                {
+                    return Err(error);
+                }
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue 'commit;
+                }
+                if error.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    continue 'transaction;
+                }
+                return Err(error);
+            }
+        }
+    }
+
     fn default_transaction_options(&self) -> Option<&TransactionOptions> {
         self.options
             .as_ref()
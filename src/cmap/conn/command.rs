@@ -5,7 +5,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::wire::Message;
 use crate::{
-    bson::Document,
+    bson::{doc, Document},
     client::{options::ServerApi, ClusterTime, HELLO_COMMAND_NAMES, REDACTED_COMMANDS},
     error::{Error, ErrorKind, Result},
     is_master::{IsMasterCommandResponse, IsMasterReply},
@@ -21,6 +21,10 @@ pub(crate) struct RawCommand {
     pub(crate) name: String,
     pub(crate) target_db: String,
     pub(crate) bytes: Vec<u8>,
+
+    /// Whether the server should not send a reply to this command, which is the case for writes
+    /// performed with an unacknowledged write concern.
+    pub(crate) more_to_come: bool,
 }
 
 impl RawCommand {
@@ -64,6 +68,11 @@ pub(crate) struct Command<T = Document> {
     read_concern: Option<ReadConcernInternal>,
 
     recovery_token: Option<Document>,
+
+    /// Whether the server may respond to this command with a stream of replies, each sent with
+    /// the `moreToCome` flag, rather than a single reply.
+    #[serde(skip)]
+    pub(crate) exhaust_allowed: bool,
 }
 
 impl<T> Command<T> {
@@ -81,6 +90,7 @@ impl<T> Command<T> {
             autocommit: None,
             read_concern: None,
             recovery_token: None,
+            exhaust_allowed: false,
         }
     }
 
@@ -103,6 +113,7 @@ impl<T> Command<T> {
             autocommit: None,
             read_concern: read_concern.map(Into::into),
             recovery_token: None,
+            exhaust_allowed: false,
         }
     }
 
@@ -179,7 +190,7 @@ pub(crate) struct RawCommandResponse {
 }
 
 impl RawCommandResponse {
-    #[cfg(test)]
+    #[cfg(any(test, feature = "csfle"))]
     pub(crate) fn with_document_and_address(source: ServerAddress, doc: Document) -> Result<Self> {
         let mut raw = Vec::new();
         doc.to_writer(&mut raw)?;
@@ -201,6 +212,15 @@ impl RawCommandResponse {
         )
     }
 
+    /// Creates the response used in place of a server reply for a command sent with the
+    /// `moreToCome` flag, to which the server does not reply.
+    pub(crate) fn unacknowledged(source: ServerAddress) -> Result<Self> {
+        Ok(Self {
+            source,
+            raw: RawDocumentBuf::from_document(&doc! { "ok": 1 })?,
+        })
+    }
+
     pub(crate) fn new(source: ServerAddress, message: Message) -> Result<Self> {
         let raw = message.single_document_response()?;
         Ok(Self {
@@ -1,6 +1,6 @@
 mod command;
 mod stream_description;
-mod wire;
+pub(crate) mod wire;
 
 use std::{
     sync::Arc,
@@ -10,7 +10,7 @@ use std::{
 use derivative::Derivative;
 use tokio::sync::{mpsc, Mutex};
 
-use self::wire::Message;
+use self::wire::{Message, MessageFlags};
 use super::manager::PoolManager;
 use crate::{
     bson::oid::ObjectId,
@@ -20,14 +20,17 @@ use crate::{
     },
     compression::Compressor,
     error::{load_balanced_mode_mismatch, Error, ErrorKind, Result},
-    event::cmap::{
-        CmapEventHandler,
-        ConnectionCheckedInEvent,
-        ConnectionCheckedOutEvent,
-        ConnectionClosedEvent,
-        ConnectionClosedReason,
-        ConnectionCreatedEvent,
-        ConnectionReadyEvent,
+    event::{
+        self,
+        cmap::{
+            CmapEventHandler,
+            ConnectionCheckedInEvent,
+            ConnectionCheckedOutEvent,
+            ConnectionClosedEvent,
+            ConnectionClosedReason,
+            ConnectionCreatedEvent,
+            ConnectionReadyEvent,
+        },
     },
     options::{ServerAddress, TlsOptions},
     runtime::AsyncStream,
@@ -75,6 +78,10 @@ pub(crate) struct Connection {
     /// into a pool.
     error: bool,
 
+    /// Whether the most recently received reply had the `moreToCome` flag set, meaning that the
+    /// server will send another reply on this connection without a new request being sent.
+    more_to_come: bool,
+
     stream: AsyncStream,
 
     /// Compressor that the client will use before sending messages.
@@ -117,6 +124,7 @@ impl Connection {
             handler: options.and_then(|options| options.event_handler),
             stream_description: None,
             error: false,
+            more_to_come: false,
             pinned_sender: None,
             compressor: None,
         };
@@ -222,10 +230,11 @@ impl Connection {
     }
 
     /// Helper to create a `ConnectionCheckedOutEvent` for the connection.
-    pub(super) fn checked_out_event(&self) -> ConnectionCheckedOutEvent {
+    pub(super) fn checked_out_event(&self, duration: Duration) -> ConnectionCheckedOutEvent {
         ConnectionCheckedOutEvent {
             address: self.address.clone(),
             connection_id: self.id,
+            duration,
         }
     }
 
@@ -260,6 +269,7 @@ impl Connection {
         to_compress: bool,
     ) -> Result<RawCommandResponse> {
         self.command_executing = true;
+        let more_to_come = message.flags.contains(MessageFlags::MORE_TO_COME);
 
         // If the client has agreed on a compressor with the server, and the command
         // is the right type of command, then compress the message.
@@ -275,11 +285,45 @@ impl Connection {
         self.error = write_result.is_err();
         write_result?;
 
+        // The server does not reply to messages sent with the moreToCome flag.
+        if more_to_come {
+            self.command_executing = false;
+            return RawCommandResponse::unacknowledged(self.address.clone());
+        }
+
+        self.read_response().await
+    }
+
+    async fn read_response(&mut self) -> Result<RawCommandResponse> {
         let response_message_result = Message::read_from(&mut self.stream).await;
         self.command_executing = false;
         self.error = response_message_result.is_err();
 
-        RawCommandResponse::new(self.address.clone(), response_message_result?)
+        let response_message = response_message_result?;
+        self.more_to_come = response_message.flags.contains(MessageFlags::MORE_TO_COME);
+
+        RawCommandResponse::new(self.address.clone(), response_message)
+    }
+
+    /// Receives the next reply streamed by the server in response to a command sent with the
+    /// `exhaustAllowed` flag. This must only be called when the previous reply on this connection
+    /// had the `moreToCome` flag set.
+    pub(crate) async fn receive_message(&mut self) -> Result<RawCommandResponse> {
+        if !self.more_to_come {
+            return Err(Error::internal(format!(
+                "no streamed reply is expected on connection (id = {})",
+                self.id
+            )));
+        }
+
+        self.command_executing = true;
+        self.read_response().await
+    }
+
+    /// Whether the server will send another reply on this connection without a new request being
+    /// sent.
+    pub(crate) fn is_streaming(&self) -> bool {
+        self.more_to_come
     }
 
     /// Executes a `Command` and returns a `CommandResponse` containing the result from the server.
@@ -302,7 +346,8 @@ impl Connection {
     ///
     /// An `Ok(...)` result simply means the server received the command and that the driver
     /// received the response; it does not imply anything about the success of the command
-    /// itself.
+    /// itself. If the command was sent with the `moreToCome` flag, no response is read and a
+    /// response of `{ ok: 1 }` is returned once the command has been written.
     pub(crate) async fn send_raw_command(
         &mut self,
         command: RawCommand,
@@ -353,9 +398,9 @@ impl Connection {
     /// Close this connection, emitting a `ConnectionClosedEvent` with the supplied reason.
     fn close(&mut self, reason: ConnectionClosedReason) {
         self.pool_manager.take();
-        if let Some(ref handler) = self.handler {
+        event::emit_cmap_event(self.handler.as_ref(), |handler| {
             handler.handle_connection_closed_event(self.closed_event(reason));
-        }
+        });
     }
 
     /// Nullify the inner state and return it in a new `Connection` for checking back in to
@@ -370,6 +415,7 @@ impl Connection {
             stream_description: self.stream_description.take(),
             command_executing: self.command_executing,
             error: self.error,
+            more_to_come: self.more_to_come,
             pool_manager: None,
             ready_and_available_time: None,
             pinned_sender: self.pinned_sender.clone(),
@@ -2,6 +2,9 @@ use std::time::Duration;
 
 use crate::{client::options::ServerAddress, is_master::IsMasterReply, sdam::ServerType};
 
+/// The value of `maxMessageSizeBytes` assumed when the server doesn't report one.
+const DEFAULT_MAX_MESSAGE_SIZE_BYTES: i64 = 48_000_000;
+
 /// Contains information about a given server in a format digestible by a connection.
 #[derive(Debug, Default, Clone)]
 pub(crate) struct StreamDescription {
@@ -32,6 +35,9 @@ pub(crate) struct StreamDescription {
     /// can be included in a write batch.  If more than this number of writes are included, the
     /// server cannot guarantee space in the response document to reply to the batch.
     pub(crate) max_write_batch_size: i64,
+
+    /// The maximum size of a wire protocol message that should be sent to the server.
+    pub(crate) max_message_size_bytes: i64,
 }
 
 impl StreamDescription {
@@ -50,6 +56,10 @@ impl StreamDescription {
                 .map(|mins| Duration::from_secs(mins as u64 * 60)),
             max_bson_object_size: reply.command_response.max_bson_object_size,
             max_write_batch_size: reply.command_response.max_write_batch_size,
+            max_message_size_bytes: reply
+                .command_response
+                .max_message_size_bytes
+                .unwrap_or(DEFAULT_MAX_MESSAGE_SIZE_BYTES),
         }
     }
 
@@ -78,6 +88,7 @@ impl StreamDescription {
             logical_session_timeout: Some(Duration::from_secs(30 * 60)),
             max_bson_object_size: 16 * 1024 * 1024,
             max_write_batch_size: 100_000,
+            max_message_size_bytes: DEFAULT_MAX_MESSAGE_SIZE_BYTES,
         }
     }
 }
@@ -37,23 +37,34 @@ impl Message {
     /// Note that `response_to` will need to be set manually.
     pub(crate) fn with_command(command: Command, request_id: Option<i32>) -> Result<Self> {
         let bytes = bson::to_vec(&command)?;
-        Ok(Self::with_raw_command(
+        let mut message = Self::with_raw_command(
             RawCommand {
                 bytes,
                 target_db: command.target_db,
                 name: command.name,
+                more_to_come: false,
             },
             request_id,
-        ))
+        );
+        if command.exhaust_allowed {
+            message.flags |= MessageFlags::EXHAUST_ALLOWED;
+        }
+        Ok(message)
     }
 
-    /// Creates a `Message` from a given `Command`.
+    /// Creates a `Message` from a given `RawCommand`. The `moreToCome` flag will be set if the
+    /// command does not expect a reply.
     ///
     /// Note that `response_to` will need to be set manually.
     pub(crate) fn with_raw_command(command: RawCommand, request_id: Option<i32>) -> Self {
+        let mut flags = MessageFlags::empty();
+        if command.more_to_come {
+            flags |= MessageFlags::MORE_TO_COME;
+        }
+
         Self {
             response_to: 0,
-            flags: MessageFlags::empty(),
+            flags,
             sections: vec![MessageSection::Document(command.bytes)],
             checksum: None,
             request_id,
@@ -211,7 +222,7 @@ impl Message {
             flags,
             sections,
             checksum,
-            request_id: None,
+            request_id: Some(header.request_id),
         })
     }
 
@@ -4,4 +4,9 @@ mod message;
 mod test;
 mod util;
 
-pub(crate) use self::{message::Message, util::next_request_id};
+#[cfg(any(feature = "test-util", test))]
+pub(crate) use self::message::MessageSection;
+pub(crate) use self::{
+    message::{Message, MessageFlags},
+    util::next_request_id,
+};
@@ -34,13 +34,14 @@ impl ConnectionRequester {
     pub(super) async fn request(&self) -> ConnectionRequestResult {
         let (sender, receiver) = oneshot::channel();
 
-        // this only errors if the receiver end is dropped, which can't happen because
-        // we own a handle to the worker, keeping it alive.
-        self.sender.send(sender).unwrap();
-
-        // similarly, the receiver only returns an error if the sender is dropped, which
-        // can't happen due to the handle.
-        receiver.await.unwrap()
+        // we own a handle to the worker, keeping it alive, so these only error if the pool was
+        // explicitly closed and its worker has already stopped.
+        if self.sender.send(sender).is_err() {
+            return ConnectionRequestResult::PoolClosed;
+        }
+        receiver
+            .await
+            .unwrap_or(ConnectionRequestResult::PoolClosed)
     }
 }
 
@@ -74,6 +75,12 @@ impl ConnectionRequest {
     ) -> std::result::Result<(), ConnectionRequestResult> {
         self.sender.send(result)
     }
+
+    /// Whether the requester has stopped waiting on this request (e.g. due to hitting the wait
+    /// queue timeout).
+    pub(super) fn is_abandoned(&self) -> bool {
+        self.sender.is_closed()
+    }
 }
 
 #[derive(Debug)]
@@ -88,6 +95,9 @@ pub(super) enum ConnectionRequestResult {
     /// The request was rejected because the pool was cleared before it could
     /// be fulfilled. The error that caused the pool to be cleared is returned.
     PoolCleared(Error),
+
+    /// The request was rejected because the pool was closed.
+    PoolClosed,
 }
 
 impl ConnectionRequestResult {
@@ -1,8 +1,6 @@
 #[cfg(test)]
 mod test;
 
-use std::sync::Arc;
-
 use lazy_static::lazy_static;
 use os_info::{Type, Version};
 
@@ -12,7 +10,6 @@ use crate::{
     cmap::{options::ConnectionPoolOptions, Command, Connection, StreamDescription},
     compression::Compressor,
     error::{ErrorKind, Result},
-    event::sdam::SdamEventHandler,
     is_master::{is_master_command, run_is_master, IsMasterReply},
     options::{AuthMechanism, ClientOptions, Credential, DriverInfo, ServerApi},
     sdam::Topology,
@@ -159,8 +156,10 @@ impl Handshaker {
 
         let mut compressors = None;
 
-        let mut command =
-            is_master_command(options.as_ref().and_then(|opts| opts.server_api.as_ref()));
+        let mut command = is_master_command(
+            options.as_ref().and_then(|opts| opts.server_api.as_ref()),
+            None,
+        );
 
         #[cfg(test)]
         let mut mock_service_id = false;
@@ -230,13 +229,13 @@ impl Handshaker {
         &self,
         conn: &mut Connection,
         topology: Option<&Topology>,
-        handler: &Option<Arc<dyn SdamEventHandler>>,
+        options: Option<&ClientOptions>,
     ) -> Result<HandshakeResult> {
         let mut command = self.command.clone();
 
         let client_first = set_speculative_auth_info(&mut command.body, self.credential.as_ref())?;
 
-        let mut is_master_reply = run_is_master(conn, command, topology, handler).await?;
+        let mut is_master_reply = run_is_master(conn, command, topology, options).await?;
         // TODO PM-2369 Remove serviceId mocking when it's returned by the server.
         #[cfg(test)]
         {
@@ -248,10 +247,8 @@ impl Handshaker {
                     is_master_reply
                         .command_response
                         .topology_version
-                        .as_ref()
                         .unwrap()
-                        .get_object_id("processId")
-                        .unwrap(),
+                        .process_id,
                 );
             }
         }
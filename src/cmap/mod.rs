@@ -9,7 +9,10 @@ pub(crate) mod options;
 mod status;
 mod worker;
 
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use derivative::Derivative;
 #[cfg(test)]
@@ -25,17 +28,21 @@ pub(crate) use self::{
 use self::{connection_requester::ConnectionRequestResult, options::ConnectionPoolOptions};
 use crate::{
     bson::oid::ObjectId,
-    error::{Error, Result},
-    event::cmap::{
-        CmapEventHandler,
-        ConnectionCheckoutFailedEvent,
-        ConnectionCheckoutFailedReason,
-        ConnectionCheckoutStartedEvent,
-        PoolCreatedEvent,
+    error::{Error, ErrorKind, Result},
+    event::{
+        self,
+        cmap::{
+            CmapEventHandler,
+            ConnectionCheckoutFailedEvent,
+            ConnectionCheckoutFailedReason,
+            ConnectionCheckoutStartedEvent,
+            PoolCreatedEvent,
+        },
     },
     options::ServerAddress,
     runtime::HttpClient,
     sdam::ServerUpdateSender,
+    RUNTIME,
 };
 use connection_requester::ConnectionRequester;
 use manager::PoolManager;
@@ -45,6 +52,7 @@ use worker::ConnectionPoolWorker;
 use self::worker::PoolWorkerHandle;
 
 const DEFAULT_MAX_POOL_SIZE: u32 = 10;
+const DEFAULT_MAX_CONNECTING: u32 = 2;
 
 /// A pool of connections implementing the CMAP spec. All state is kept internally in an `Arc`, and
 /// internal state that is mutable is additionally wrapped by a lock.
@@ -55,6 +63,7 @@ pub(crate) struct ConnectionPool {
     manager: PoolManager,
     connection_requester: ConnectionRequester,
     generation_subscriber: PoolGenerationSubscriber,
+    wait_queue_timeout: Option<Duration>,
 
     #[derivative(Debug = "ignore")]
     event_handler: Option<Arc<dyn CmapEventHandler>>,
@@ -78,18 +87,25 @@ impl ConnectionPool {
             .as_ref()
             .and_then(|opts| opts.cmap_event_handler.clone());
 
-        if let Some(ref handler) = event_handler {
+        // A wait queue timeout of zero means that checkouts should wait indefinitely.
+        let wait_queue_timeout = options
+            .as_ref()
+            .and_then(|opts| opts.wait_queue_timeout)
+            .filter(|timeout| *timeout > Duration::from_secs(0));
+
+        event::emit_cmap_event(event_handler.as_ref(), |handler| {
             handler.handle_pool_created_event(PoolCreatedEvent {
                 address: address.clone(),
                 options: options.map(|o| o.to_event_options()),
             });
-        };
+        });
 
         Self {
             address,
             manager,
             connection_requester,
             generation_subscriber,
+            wait_queue_timeout,
             event_handler,
         }
     }
@@ -106,23 +122,27 @@ impl ConnectionPool {
             manager,
             connection_requester,
             generation_subscriber,
+            wait_queue_timeout: None,
             event_handler: None,
         }
     }
 
     fn emit_event<F>(&self, emit: F)
     where
-        F: FnOnce(&Arc<dyn CmapEventHandler>),
+        F: FnOnce(&dyn CmapEventHandler),
     {
-        if let Some(ref handler) = self.event_handler {
-            emit(handler);
-        }
+        event::emit_cmap_event(self.event_handler.as_ref(), emit);
     }
 
     /// Checks out a connection from the pool. This method will yield until this thread is at the
     /// front of the wait queue, and then will block again if no available connections are in the
     /// pool and the total number of connections is not less than the max pool size.
-    pub(crate) async fn check_out(&self) -> Result<Connection> {
+    ///
+    /// If no connection could be checked out before the provided timeout or the pool's
+    /// `wait_queue_timeout` elapsed (whichever is shorter), an `ErrorKind::Timeout` error is
+    /// returned.
+    pub(crate) async fn check_out(&self, timeout: Option<Duration>) -> Result<Connection> {
+        let start_time = Instant::now();
         self.emit_event(|handler| {
             let event = ConnectionCheckoutStartedEvent {
                 address: self.address.clone(),
@@ -131,27 +151,56 @@ impl ConnectionPool {
             handler.handle_connection_checkout_started_event(event);
         });
 
-        let response = self.connection_requester.request().await;
+        let check_out = async {
+            let response = self.connection_requester.request().await;
 
-        let conn = match response {
-            ConnectionRequestResult::Pooled(c) => Ok(*c),
-            ConnectionRequestResult::Establishing(task) => task.await,
-            ConnectionRequestResult::PoolCleared(e) => {
-                Err(Error::pool_cleared_error(&self.address, &e))
+            match response {
+                ConnectionRequestResult::Pooled(c) => Ok(*c),
+                ConnectionRequestResult::Establishing(task) => task.await,
+                ConnectionRequestResult::PoolCleared(e) => {
+                    Err(Error::pool_cleared_error(&self.address, &e))
+                }
+                ConnectionRequestResult::PoolClosed => Err(ErrorKind::Shutdown.into()),
             }
         };
 
+        let timeout = match (timeout, self.wait_queue_timeout) {
+            (Some(timeout), Some(wait_queue_timeout)) => Some(timeout.min(wait_queue_timeout)),
+            (timeout, wait_queue_timeout) => timeout.or(wait_queue_timeout),
+        };
+
+        let conn = match timeout {
+            Some(timeout) => match RUNTIME.timeout(timeout, check_out).await {
+                Ok(result) => result,
+                Err(_) => Err(Error::timeout(format!(
+                    "Timed out while checking out a connection from the pool for {}",
+                    self.address
+                ))),
+            },
+            None => check_out.await,
+        };
+
         match conn {
             Ok(ref conn) => {
                 self.emit_event(|handler| {
-                    handler.handle_connection_checked_out_event(conn.checked_out_event());
+                    handler.handle_connection_checked_out_event(
+                        conn.checked_out_event(start_time.elapsed()),
+                    );
                 });
             }
-            Err(_) => {
+            Err(ref e) => {
+                let reason = if e.is_timeout() {
+                    ConnectionCheckoutFailedReason::Timeout
+                } else if matches!(*e.kind, ErrorKind::Shutdown) {
+                    ConnectionCheckoutFailedReason::PoolClosed
+                } else {
+                    ConnectionCheckoutFailedReason::ConnectionError
+                };
                 self.emit_event(|handler| {
                     handler.handle_connection_checkout_failed_event(ConnectionCheckoutFailedEvent {
                         address: self.address.clone(),
-                        reason: ConnectionCheckoutFailedReason::ConnectionError,
+                        reason,
+                        duration: start_time.elapsed(),
                     })
                 });
             }
@@ -171,6 +220,13 @@ impl ConnectionPool {
         self.manager.mark_as_ready().await;
     }
 
+    /// Close the pool, waiting for all connections currently checked out of it to be checked back
+    /// in. Any connections in the pool are closed and a `PoolClosedEvent` is emitted before this
+    /// returns, and all subsequent check outs will fail.
+    pub(crate) async fn close(&self) {
+        self.manager.close().await;
+    }
+
     pub(crate) fn generation(&self) -> PoolGeneration {
         self.generation_subscriber.generation()
     }
@@ -77,6 +77,20 @@ pub(crate) struct ConnectionPoolOptions {
     /// The default is that no minimum is enforced
     pub(crate) min_pool_size: Option<u32>,
 
+    /// The maximum number of connections that the pool can be establishing concurrently.
+    ///
+    /// The default is 2.
+    pub(crate) max_connecting: Option<u32>,
+
+    /// The amount of time a thread should wait for a connection to become available before
+    /// returning an error. A value of zero means that the thread will wait indefinitely.
+    ///
+    /// The default is that threads will wait indefinitely.
+    #[serde(rename = "waitQueueTimeoutMS")]
+    #[serde(default)]
+    #[serde(deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis")]
+    pub(crate) wait_queue_timeout: Option<Duration>,
+
     /// Whether to start the pool as "ready" or not.
     /// For tests only.
     #[cfg(test)]
@@ -112,6 +126,8 @@ impl ConnectionPoolOptions {
             max_idle_time: options.max_idle_time,
             min_pool_size: options.min_pool_size,
             max_pool_size: options.max_pool_size,
+            max_connecting: options.max_connecting,
+            wait_queue_timeout: options.wait_queue_timeout,
             server_api: options.server_api.clone(),
             tls_options: options.tls_options(),
             credential: options.credential.clone(),
@@ -222,15 +222,7 @@ where
 
 #[derive(Debug, Deserialize)]
 struct ConnectionCheckoutFailedHelper {
-    pub reason: CheckoutFailedReasonHelper,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-enum CheckoutFailedReasonHelper {
-    Timeout,
-    ConnectionError,
-    PoolClosed,
+    pub reason: ConnectionCheckoutFailedReason,
 }
 
 fn deserialize_checkout_failed<'de, D>(
@@ -241,23 +233,12 @@ where
 {
     let helper = ConnectionCheckoutFailedHelper::deserialize(deserializer)?;
 
-    // The driver doesn't have a concept of a "closed pool", instead having the pool closed when the
-    // pool is dropped. Because of this, the driver doesn't implement the "poolClosed" reason for a
-    // connection checkout failure. While we skip over the corresponding tests in our spec test
-    // runner, we still need to be able to deserialize the "poolClosed" reason to avoid the test
-    // harness panicking, so we arbitrarily map the "poolClosed" to "connectionError".
-    let reason = match helper.reason {
-        CheckoutFailedReasonHelper::PoolClosed | CheckoutFailedReasonHelper::ConnectionError => {
-            ConnectionCheckoutFailedReason::ConnectionError
-        }
-        CheckoutFailedReasonHelper::Timeout => ConnectionCheckoutFailedReason::Timeout,
-    };
-
     Ok(ConnectionCheckoutFailedEvent {
         address: ServerAddress::Tcp {
             host: Default::default(),
             port: None,
         },
-        reason,
+        reason: helper.reason,
+        duration: Default::default(),
     })
 }
@@ -34,11 +34,6 @@ use crate::{
 use bson::doc;
 
 const TEST_DESCRIPTIONS_TO_SKIP: &[&str] = &[
-    "must destroy checked in connection if pool has been closed",
-    "must throw error if checkOut is called on a closed pool",
-    // WaitQueueTimeoutMS is not supported
-    "must aggressively timeout threads enqueued longer than waitQueueTimeoutMS",
-    "waiting on maxConnecting is limited by WaitQueueTimeoutMS",
     // TODO DRIVERS-1785 remove this skip when test event order is fixed
     "error during minPoolSize population clears pool",
 ];
@@ -71,10 +66,7 @@ struct State {
     unlabeled_connections: Mutex<Vec<Connection>>,
     threads: RwLock<HashMap<String, CmapThread>>,
 
-    // In order to drop the pool when performing a `close` operation, we use an `Option` so that we
-    // can replace it with `None`. Since none of the tests should use the pool after its closed
-    // (besides the ones we manually skip over), it's fine for us to `unwrap` the pool during these
-    // tests, as panicking is sufficient to exit any aberrant test with a failure.
+    // The pool is only `None` until the test starts executing.
     pool: RwLock<Option<ConnectionPool>>,
 }
 
@@ -256,7 +248,7 @@ impl Operation {
             }
             Operation::CheckOut { label } => {
                 if let Some(pool) = state.pool.read().await.deref() {
-                    let conn = pool.check_out().await?;
+                    let conn = pool.check_out(None).await?;
 
                     if let Some(label) = label {
                         state.connections.write().await.insert(label, conn);
@@ -306,10 +298,13 @@ impl Operation {
             Operation::Close => {
                 let mut subscriber = state.handler.subscribe();
 
-                // pools are closed via their drop implementation
-                state.pool.write().await.take();
+                // closing waits for the checked out connections to be returned, which some tests
+                // only do after closing the pool.
+                if let Some(pool) = state.pool.read().await.clone() {
+                    RUNTIME.execute(async move { pool.close().await });
+                }
 
-                // wait for event to be emitted to ensure drop has completed.
+                // wait for event to be emitted to ensure the pool has been closed.
                 subscriber
                     .wait_for_event(EVENT_TIMEOUT, |e| matches!(e, Event::PoolClosed(_)))
                     .await
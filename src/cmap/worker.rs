@@ -18,21 +18,25 @@ use super::{
     status,
     status::{PoolGenerationPublisher, PoolGenerationSubscriber},
     Connection,
+    DEFAULT_MAX_CONNECTING,
     DEFAULT_MAX_POOL_SIZE,
 };
 use crate::{
     bson::oid::ObjectId,
     error::{load_balanced_mode_mismatch, Error, ErrorKind, Result},
-    event::cmap::{
-        CmapEventHandler,
-        ConnectionClosedEvent,
-        ConnectionClosedReason,
-        PoolClearedEvent,
-        PoolClosedEvent,
-        PoolReadyEvent,
+    event::{
+        self,
+        cmap::{
+            CmapEventHandler,
+            ConnectionClosedEvent,
+            ConnectionClosedReason,
+            PoolClearedEvent,
+            PoolClosedEvent,
+            PoolReadyEvent,
+        },
     },
     options::ServerAddress,
-    runtime::HttpClient,
+    runtime::{AcknowledgedMessage, HttpClient},
     sdam::ServerUpdateSender,
     RUNTIME,
 };
@@ -44,7 +48,6 @@ use std::{
 };
 use tokio::sync::mpsc;
 
-const MAX_CONNECTING: u32 = 2;
 const MAINTENACE_FREQUENCY: Duration = Duration::from_millis(500);
 
 /// A worker task that manages the shared state of the pool.
@@ -65,6 +68,11 @@ pub(crate) struct ConnectionPoolWorker {
     /// The number of connections currently being established by this pool.
     pending_connection_count: u32,
 
+    /// The maximum number of connections that the pool can be establishing concurrently. Requests
+    /// that would require a new connection to be created while this limit is reached wait in the
+    /// queue until a pending connection finishes establishing or a connection is checked back in.
+    max_connecting: u32,
+
     /// The ID of the next connection created by the pool.
     next_connection_id: u32,
 
@@ -135,6 +143,9 @@ pub(crate) struct ConnectionPoolWorker {
     /// A handle used to notify SDAM that a connection establishment error happened. This will
     /// allow the server to transition to Unknown and clear the pool as necessary.
     server_updater: ServerUpdateSender,
+
+    /// Handlers to notify once the pool has been closed.
+    close_handlers: Vec<AcknowledgedMessage<()>>,
 }
 
 impl ConnectionPoolWorker {
@@ -166,6 +177,11 @@ impl ConnectionPoolWorker {
 
         let min_pool_size = options.as_ref().and_then(|opts| opts.min_pool_size);
 
+        let max_connecting = options
+            .as_ref()
+            .and_then(|opts| opts.max_connecting)
+            .unwrap_or(DEFAULT_MAX_CONNECTING);
+
         let connection_options: Option<ConnectionOptions> = options
             .as_ref()
             .map(|pool_options| ConnectionOptions::from(pool_options.clone()));
@@ -225,6 +241,7 @@ impl ConnectionPoolWorker {
             next_connection_id: 1,
             total_connection_count: 0,
             pending_connection_count: 0,
+            max_connecting,
             generation,
             service_connection_count: HashMap::new(),
             connection_options,
@@ -239,6 +256,7 @@ impl ConnectionPoolWorker {
             generation_publisher,
             maintenance_frequency,
             server_updater,
+            close_handlers: Vec::new(),
         };
 
         RUNTIME.execute(async move {
@@ -249,8 +267,9 @@ impl ConnectionPoolWorker {
     }
 
     /// Run the worker thread, listening on the various receivers until all handles have been
-    /// dropped. Once all handles are dropped, the pool will close any available connections and
-    /// emit a pool closed event.
+    /// dropped or the pool has been explicitly closed and all of its checked out connections have
+    /// been returned. Once that happens, the pool will close any available connections and, if it
+    /// wasn't closed explicitly, emit a pool closed event.
     async fn execute(mut self) {
         let mut maintenance_interval = RUNTIME.interval(self.maintenance_frequency);
 
@@ -282,6 +301,9 @@ impl ConnectionPoolWorker {
                         // if receiver doesn't listen to error that's ok.
                         let _ = request.fulfill(ConnectionRequestResult::PoolCleared(e.clone()));
                     }
+                    PoolState::Closed => {
+                        let _ = request.fulfill(ConnectionRequestResult::PoolClosed);
+                    }
                     PoolState::New => {
                         let _ = request.fulfill(ConnectionRequestResult::PoolCleared(
                             ErrorKind::Internal {
@@ -306,6 +328,11 @@ impl ConnectionPoolWorker {
                         self.mark_as_ready();
                         _completion_handler.acknowledge(());
                     }
+                    PoolManagementRequest::Close {
+                        _completion_handler,
+                    } => {
+                        self.close(_completion_handler);
+                    }
                     PoolManagementRequest::HandleConnectionSucceeded(conn) => {
                         self.handle_connection_succeeded(conn);
                     }
@@ -322,22 +349,35 @@ impl ConnectionPoolWorker {
                 }
             }
 
+            // drop any requests whose requesters have stopped waiting (e.g. due to hitting the
+            // wait queue timeout) so they don't hold up the queue or trigger new connections.
+            self.wait_queue.retain(|request| !request.is_abandoned());
+
             if self.can_service_connection_request() {
                 if let Some(request) = self.wait_queue.pop_front() {
                     self.check_out(request).await;
                 }
             }
+
+            if matches!(self.state, PoolState::Closed)
+                && self.total_connection_count as usize == self.available_connections.len()
+            {
+                break;
+            }
         }
 
         while let Some(connection) = self.available_connections.pop_front() {
             connection.close_and_drop(ConnectionClosedReason::PoolClosed);
         }
 
-        self.emit_event(|handler| {
-            handler.handle_pool_closed_event(PoolClosedEvent {
-                address: self.address.clone(),
-            });
-        });
+        // An explicitly closed pool emitted its closed event when it was closed.
+        if !matches!(self.state, PoolState::Closed) {
+            self.emit_pool_closed_event();
+        }
+
+        for handler in self.close_handlers {
+            handler.acknowledge(());
+        }
     }
 
     fn below_max_connections(&self) -> bool {
@@ -353,7 +393,7 @@ impl ConnectionPoolWorker {
             return true;
         }
 
-        self.below_max_connections() && self.pending_connection_count < MAX_CONNECTING
+        self.below_max_connections() && self.pending_connection_count < self.max_connecting
     }
 
     async fn check_out(&mut self, request: ConnectionRequest) {
@@ -384,7 +424,7 @@ impl ConnectionPoolWorker {
         }
 
         // otherwise, attempt to create a connection.
-        if self.below_max_connections() {
+        if self.below_max_connections() && self.pending_connection_count < self.max_connecting {
             let event_handler = self.event_handler.clone();
             let establisher = self.establisher.clone();
             let pending_connection = self.create_pending_connection();
@@ -480,7 +520,9 @@ impl ConnectionPoolWorker {
 
         conn.mark_as_available();
 
-        if conn.has_errored() {
+        if matches!(self.state, PoolState::Closed) {
+            self.close_connection(conn, ConnectionClosedReason::PoolClosed);
+        } else if conn.has_errored() {
             self.close_connection(conn, ConnectionClosedReason::Error);
         } else if conn.generation.is_stale(&self.generation) {
             self.close_connection(conn, ConnectionClosedReason::Stale);
@@ -492,6 +534,10 @@ impl ConnectionPoolWorker {
     }
 
     fn clear(&mut self, cause: Error, service_id: Option<ObjectId>) {
+        if matches!(self.state, PoolState::Closed) {
+            return;
+        }
+
         let was_ready = match (&mut self.generation, service_id) {
             (PoolGeneration::Normal(gen), None) => {
                 *gen += 1;
@@ -529,7 +575,7 @@ impl ConnectionPoolWorker {
     }
 
     fn mark_as_ready(&mut self) {
-        if matches!(self.state, PoolState::Ready) {
+        if matches!(self.state, PoolState::Ready | PoolState::Closed) {
             return;
         }
 
@@ -543,13 +589,41 @@ impl ConnectionPoolWorker {
         });
     }
 
+    /// Transition the pool to Closed, rejecting any requests in the wait queue, closing the
+    /// available connections and emitting a pool closed event. Connections that are checked out
+    /// will be closed when they are checked back in. The worker will stop and notify the given
+    /// handler once all of them have been returned.
+    fn close(&mut self, completion_handler: AcknowledgedMessage<()>) {
+        self.close_handlers.push(completion_handler);
+        if matches!(self.state, PoolState::Closed) {
+            return;
+        }
+        self.state = PoolState::Closed;
+
+        for request in self.wait_queue.drain(..) {
+            let _: std::result::Result<_, _> = request.fulfill(ConnectionRequestResult::PoolClosed);
+        }
+
+        while let Some(connection) = self.available_connections.pop_front() {
+            self.close_connection(connection, ConnectionClosedReason::PoolClosed);
+        }
+
+        self.emit_pool_closed_event();
+    }
+
+    fn emit_pool_closed_event(&self) {
+        self.emit_event(|handler| {
+            handler.handle_pool_closed_event(PoolClosedEvent {
+                address: self.address.clone(),
+            });
+        });
+    }
+
     fn emit_event<F>(&self, emit: F)
     where
-        F: FnOnce(&Arc<dyn CmapEventHandler>),
+        F: FnOnce(&dyn CmapEventHandler),
     {
-        if let Some(ref handler) = self.event_handler {
-            emit(handler);
-        }
+        event::emit_cmap_event(self.event_handler.as_ref(), emit);
     }
 
     /// Close a connection, emit the event for it being closed, and decrement the
@@ -606,7 +680,7 @@ impl ConnectionPoolWorker {
     fn ensure_min_connections(&mut self) {
         if let Some(min_pool_size) = self.min_pool_size {
             while self.total_connection_count < min_pool_size
-                && self.pending_connection_count < MAX_CONNECTING
+                && self.pending_connection_count < self.max_connecting
             {
                 let pending_connection = self.create_pending_connection();
                 let event_handler = self.event_handler.clone();
@@ -652,20 +726,20 @@ async fn establish_connection(
     match establish_result {
         Err(ref e) => {
             server_updater.handle_error(e.clone()).await;
-            if let Some(handler) = event_handler {
+            event::emit_cmap_event(event_handler, |handler| {
                 let event = ConnectionClosedEvent {
                     address,
                     reason: ConnectionClosedReason::Error,
                     connection_id,
                 };
                 handler.handle_connection_closed_event(event);
-            }
+            });
             manager.handle_connection_failed();
         }
         Ok(ref mut connection) => {
-            if let Some(handler) = event_handler {
+            event::emit_cmap_event(event_handler, |handler| {
                 handler.handle_connection_ready_event(connection.ready_event())
-            };
+            });
         }
     }
 
@@ -673,9 +747,6 @@ async fn establish_connection(
 }
 
 /// Enum modeling the possible pool states as described in the CMAP spec.
-///
-/// The "closed" state is omitted here because the pool considered closed only
-/// once it goes out of scope and cannot be manually closed before then.
 #[derive(Debug)]
 enum PoolState {
     /// Same as Paused, but only for a new pool, not one that has been cleared due to an error.
@@ -686,6 +757,10 @@ enum PoolState {
 
     /// Pool is operational.
     Ready,
+
+    /// Pool has been closed and is waiting for its checked out connections to be returned.
+    /// Connections may not be checked out nor created.
+    Closed,
 }
 
 /// Task to process by the worker.
@@ -1,6 +1,6 @@
 pub mod options;
 
-use std::{borrow::Borrow, collections::HashSet, fmt, fmt::Debug, sync::Arc};
+use std::{borrow::Borrow, collections::HashSet, fmt, fmt::Debug, sync::Arc, time::Duration};
 
 use futures_util::{
     future,
@@ -26,9 +26,11 @@ use crate::{
     cmap::conn::PinnedConnectionHandle,
     concern::{ReadConcern, WriteConcern},
     error::{convert_bulk_errors, BulkWriteError, BulkWriteFailure, Error, ErrorKind, Result},
-    index::IndexModel,
+    index::{reconcile, IndexModel},
     operation::{
         Aggregate,
+        BulkWrite,
+        CollStats,
         Count,
         CountDocuments,
         CreateIndexes,
@@ -36,18 +38,26 @@ use crate::{
         Distinct,
         DropCollection,
         DropIndexes,
+        Explain,
         Find,
         FindAndModify,
         Insert,
         ListIndexes,
+        ModifyCollection,
+        RenameCollection,
         Update,
+        WriteStatement,
     },
     results::{
+        BulkWriteResult,
+        CollectionStats,
         CreateIndexResult,
         CreateIndexesResult,
         DeleteResult,
+        ExplainResult,
         InsertManyResult,
         InsertOneResult,
+        SyncIndexesResult,
         UpdateResult,
     },
     selection_criteria::SelectionCriteria,
@@ -142,6 +152,7 @@ struct CollectionInner {
     selection_criteria: Option<SelectionCriteria>,
     read_concern: Option<ReadConcern>,
     write_concern: Option<WriteConcern>,
+    timeout: Option<Duration>,
 }
 
 impl<T> Collection<T> {
@@ -157,6 +168,8 @@ impl<T> Collection<T> {
             .write_concern
             .or_else(|| db.write_concern().cloned());
 
+        let timeout = options.timeout.or_else(|| db.timeout());
+
         Self {
             inner: Arc::new(CollectionInner {
                 client: db.client().clone(),
@@ -165,6 +178,7 @@ impl<T> Collection<T> {
                 selection_criteria,
                 read_concern,
                 write_concern,
+                timeout,
             }),
             _phantom: Default::default(),
         }
@@ -216,6 +230,11 @@ impl<T> Collection<T> {
         self.inner.write_concern.as_ref()
     }
 
+    /// Gets the timeout of the `Collection`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+
     async fn drop_common(
         &self,
         options: impl Into<Option<DropCollectionOptions>>,
@@ -224,7 +243,7 @@ impl<T> Collection<T> {
         let session = session.into();
 
         let mut options = options.into();
-        resolve_options!(self, options, [write_concern]);
+        resolve_options!(self, options, [write_concern, timeout]);
 
         let drop = DropCollection::new(self.namespace(), options);
         self.client().execute_operation(drop, session).await
@@ -245,6 +264,121 @@ impl<T> Collection<T> {
         self.drop_common(options, session).await
     }
 
+    async fn rename_common(
+        &self,
+        to: impl AsRef<str>,
+        options: impl Into<Option<RenameCollectionOptions>>,
+        session: impl Into<Option<&mut ClientSession>>,
+    ) -> Result<()> {
+        let session = session.into();
+
+        let mut options = options.into();
+        resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
+
+        let to = Namespace {
+            db: self.inner.db.name().to_string(),
+            coll: to.as_ref().to_string(),
+        };
+        let rename = RenameCollection::new(self.namespace(), to, options);
+        self.client().execute_operation(rename, session).await
+    }
+
+    /// Renames the collection to `to` within the same database.
+    ///
+    /// By default, this fails if a collection named `to` already exists; set
+    /// [`RenameCollectionOptions::drop_target`] to replace it instead. This `Collection` will
+    /// continue to refer to the old name.
+    pub async fn rename(
+        &self,
+        to: impl AsRef<str>,
+        options: impl Into<Option<RenameCollectionOptions>>,
+    ) -> Result<()> {
+        self.rename_common(to, options, None).await
+    }
+
+    /// Renames the collection to `to` within the same database using the provided
+    /// `ClientSession`.
+    ///
+    /// By default, this fails if a collection named `to` already exists; set
+    /// [`RenameCollectionOptions::drop_target`] to replace it instead. This `Collection` will
+    /// continue to refer to the old name.
+    pub async fn rename_with_session(
+        &self,
+        to: impl AsRef<str>,
+        options: impl Into<Option<RenameCollectionOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        self.rename_common(to, options, session).await
+    }
+
+    async fn modify_common(
+        &self,
+        options: ModifyCollectionOptions,
+        session: impl Into<Option<&mut ClientSession>>,
+    ) -> Result<()> {
+        let session = session.into();
+
+        let mut options = Some(options);
+        resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
+
+        let modify = ModifyCollection::new(self.namespace(), options);
+        self.client().execute_operation(modify, session).await
+    }
+
+    /// Changes the properties of the collection or one of its indexes, such as its validator or
+    /// whether an index is hidden.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/collMod/)
+    /// for more information.
+    pub async fn modify(&self, options: ModifyCollectionOptions) -> Result<()> {
+        self.modify_common(options, None).await
+    }
+
+    /// Changes the properties of the collection or one of its indexes, such as its validator or
+    /// whether an index is hidden, using the provided `ClientSession`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/collMod/)
+    /// for more information.
+    pub async fn modify_with_session(
+        &self,
+        options: ModifyCollectionOptions,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        self.modify_common(options, session).await
+    }
+
+    async fn stats_common(
+        &self,
+        options: impl Into<Option<CollectionStatsOptions>>,
+        session: impl Into<Option<&mut ClientSession>>,
+    ) -> Result<CollectionStats> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let stats = CollStats::new(self.namespace(), options);
+        self.client().execute_operation(stats, session).await
+    }
+
+    /// Gets storage statistics for the collection, such as its size and the sizes of its indexes.
+    pub async fn stats(
+        &self,
+        options: impl Into<Option<CollectionStatsOptions>>,
+    ) -> Result<CollectionStats> {
+        self.stats_common(options, None).await
+    }
+
+    /// Gets storage statistics for the collection, such as its size and the sizes of its indexes,
+    /// using the provided `ClientSession`.
+    pub async fn stats_with_session(
+        &self,
+        options: impl Into<Option<CollectionStatsOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<CollectionStats> {
+        self.stats_common(options, session).await
+    }
+
     /// Runs an aggregation operation.
     ///
     /// See the documentation [here](https://docs.mongodb.com/manual/aggregation/) for more
@@ -258,7 +392,7 @@ impl<T> Collection<T> {
         resolve_options!(
             self,
             options,
-            [read_concern, write_concern, selection_criteria]
+            [read_concern, write_concern, selection_criteria, timeout]
         );
 
         let aggregate = Aggregate::new(self.namespace(), pipeline, options);
@@ -280,6 +414,7 @@ impl<T> Collection<T> {
         resolve_read_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_write_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_selection_criteria_with_session!(self, options, Some(&mut *session))?;
+        resolve_options!(self, options, [timeout]);
 
         let aggregate = Aggregate::new(self.namespace(), pipeline, options);
         let client = self.client();
@@ -294,7 +429,7 @@ impl<T> Collection<T> {
         options: impl Into<Option<EstimatedDocumentCountOptions>>,
     ) -> Result<u64> {
         let mut options = options.into();
-        resolve_options!(self, options, [read_concern, selection_criteria]);
+        resolve_options!(self, options, [read_concern, selection_criteria, timeout]);
 
         let op = Count::new(self.namespace(), options);
 
@@ -312,6 +447,7 @@ impl<T> Collection<T> {
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, session.as_ref())?;
         resolve_selection_criteria_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let op = CountDocuments::new(self.namespace(), filter.into(), options)?;
         self.client().execute_operation(op, session).await
@@ -352,6 +488,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let delete = Delete::new(self.namespace(), query, None, options);
         self.client().execute_operation(delete, session).await
@@ -367,6 +504,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let indexes: Vec<IndexModel> = indexes.into_iter().collect();
 
@@ -456,6 +594,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let delete = Delete::new(self.namespace(), query, Some(1), options);
         self.client().execute_operation(delete, session).await
@@ -502,6 +641,7 @@ impl<T> Collection<T> {
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, session.as_ref())?;
         resolve_selection_criteria_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let op = Distinct::new(
             self.namespace(),
@@ -546,6 +686,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         // If there is no provided name, that means we should drop all indexes.
         let index_name = name.into().unwrap_or("*").to_string();
@@ -610,7 +751,10 @@ impl<T> Collection<T> {
         &self,
         options: impl Into<Option<ListIndexesOptions>>,
     ) -> Result<Cursor<IndexModel>> {
-        let list_indexes = ListIndexes::new(self.namespace(), options.into());
+        let mut options = options.into();
+        resolve_options!(self, options, [timeout]);
+
+        let list_indexes = ListIndexes::new(self.namespace(), options);
         let client = self.client();
         client.execute_cursor_operation(list_indexes).await
     }
@@ -621,7 +765,10 @@ impl<T> Collection<T> {
         options: impl Into<Option<ListIndexesOptions>>,
         session: &mut ClientSession,
     ) -> Result<SessionCursor<IndexModel>> {
-        let list_indexes = ListIndexes::new(self.namespace(), options.into());
+        let mut options = options.into();
+        resolve_options!(self, options, [timeout]);
+
+        let list_indexes = ListIndexes::new(self.namespace(), options);
         let client = self.client();
         client
             .execute_session_cursor_operation(list_indexes, session)
@@ -653,6 +800,69 @@ impl<T> Collection<T> {
         self.list_index_names_common(cursor.stream(session)).await
     }
 
+    /// Converges the indexes on this collection to `models`.
+    ///
+    /// The desired indexes are compared against the collection's existing indexes by name, or by
+    /// keys for existing indexes with a different name, taking into account options such as
+    /// `unique`, `partial_filter_expression`, `expire_after` and `collation`. Missing indexes are
+    /// created, and existing indexes that match none of `models` are dropped if
+    /// [`SyncIndexOptions::drop_extraneous`] is set. Indexes whose definitions conflict with a
+    /// desired index are reported in the result rather than being modified.
+    ///
+    /// If [`SyncIndexOptions::dry_run`] is set, the changes are computed and returned without
+    /// being made.
+    pub async fn sync_indexes(
+        &self,
+        models: impl IntoIterator<Item = IndexModel>,
+        options: impl Into<Option<SyncIndexOptions>>,
+    ) -> Result<SyncIndexesResult> {
+        let options = options.into().unwrap_or_default();
+
+        let list_options = ListIndexesOptions::builder()
+            .timeout(options.timeout)
+            .build();
+        let existing: Vec<IndexModel> = match self.list_indexes(list_options).await {
+            Ok(cursor) => cursor.try_collect().await?,
+            // The collection will be created along with the first index.
+            Err(error) if error.is_ns_not_found() => Vec::new(),
+            Err(error) => return Err(error),
+        };
+
+        let plan = reconcile::plan(models, existing, options.drop_extraneous.unwrap_or(false));
+        let dry_run = options.dry_run.unwrap_or(false);
+
+        if !dry_run {
+            for name in &plan.drop {
+                let drop_options = DropIndexOptions::builder()
+                    .write_concern(options.write_concern.clone())
+                    .timeout(options.timeout)
+                    .build();
+                self.drop_index(name, drop_options).await?;
+            }
+
+            if !plan.create.is_empty() {
+                let create_options = CreateIndexOptions::builder()
+                    .write_concern(options.write_concern.clone())
+                    .timeout(options.timeout)
+                    .build();
+                self.create_indexes(plan.create.clone(), create_options)
+                    .await?;
+            }
+        }
+
+        Ok(SyncIndexesResult {
+            created: plan
+                .create
+                .iter()
+                .filter_map(IndexModel::get_name)
+                .collect(),
+            dropped: plan.drop,
+            unchanged: plan.unchanged,
+            conflicts: plan.conflicts,
+            dry_run,
+        })
+    }
+
     async fn update_many_common(
         &self,
         query: Document,
@@ -670,6 +880,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let update = Update::new(self.namespace(), query, update, true, options);
         self.client().execute_operation(update, session).await
@@ -723,6 +934,7 @@ impl<T> Collection<T> {
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let update = Update::new(self.namespace(), query, update, false, options);
         self.client().execute_operation(update, session).await
@@ -812,8 +1024,7 @@ impl<T> Collection<T> {
     ///
     /// If the pipeline alters the structure of the returned events, the parsed type will need to be
     /// changed via [`ChangeStream::with_type`].
-    #[allow(unused)]
-    pub(crate) async fn watch(
+    pub async fn watch(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
@@ -822,15 +1033,14 @@ impl<T> Collection<T> {
         T: DeserializeOwned + Unpin + Send + Sync,
     {
         let mut options = options.into();
-        resolve_options!(self, options, [read_concern, selection_criteria]);
+        resolve_options!(self, options, [read_concern, selection_criteria, timeout]);
         let target = self.namespace().into();
         self.client().execute_watch(pipeline, options, target).await
     }
 
     /// Starts a new [`SessionChangeStream`] that receives events for all changes in this collection
     /// using the provided [`ClientSession`].  See [`Client::watch`] for more information.
-    #[allow(unused)]
-    pub(crate) async fn watch_with_session(
+    pub async fn watch_with_session(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
@@ -842,11 +1052,169 @@ impl<T> Collection<T> {
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_selection_criteria_with_session!(self, options, Some(&mut *session))?;
+        resolve_options!(self, options, [timeout]);
         let target = self.namespace().into();
         self.client()
             .execute_watch_with_session(pipeline, options, target, session)
             .await
     }
+
+    /// Explains how the server would execute a [`Collection::find`](#method.find) with the given
+    /// filter and options, without returning any documents. If `verbosity` is `None`,
+    /// [`ExplainVerbosity::QueryPlanner`] is used.
+    ///
+    /// See the MongoDB [manual](https://www.mongodb.com/docs/manual/reference/command/explain/)
+    /// for more information on explaining operations.
+    pub async fn find_explain(
+        &self,
+        filter: impl Into<Option<Document>>,
+        options: impl Into<Option<FindOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let find = Find::new(self.namespace(), filter.into(), options);
+        self.client()
+            .execute_operation(Explain::new(find, verbosity), None)
+            .await
+    }
+
+    /// Explains how the server would execute a [`Collection::aggregate`](#method.aggregate) with
+    /// the given pipeline and options, without running it. If `verbosity` is `None`,
+    /// [`ExplainVerbosity::QueryPlanner`] is used.
+    ///
+    /// Note that explaining a pipeline with an `$out` or `$merge` stage with a verbosity of
+    /// [`ExplainVerbosity::ExecutionStats`] or higher is not supported by the server.
+    pub async fn aggregate_explain(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<AggregateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let aggregate = Aggregate::new(self.namespace(), pipeline, options);
+        self.client()
+            .execute_operation(Explain::new(aggregate, verbosity), None)
+            .await
+    }
+
+    /// Explains how the server would execute a
+    /// [`Collection::count_documents`](#method.count_documents) with the given filter and options,
+    /// without running it. If `verbosity` is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub async fn count_documents_explain(
+        &self,
+        filter: impl Into<Option<Document>>,
+        options: impl Into<Option<CountOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let op = CountDocuments::new(self.namespace(), filter.into(), options)?;
+        self.client()
+            .execute_operation(Explain::new(op, verbosity), None)
+            .await
+    }
+
+    async fn update_explain_common(
+        &self,
+        query: Document,
+        update: UpdateModifications,
+        multi: bool,
+        options: Option<UpdateOptions>,
+        verbosity: Option<ExplainVerbosity>,
+    ) -> Result<ExplainResult> {
+        if let UpdateModifications::Document(ref d) = update {
+            bson_util::update_document_check(d)?;
+        }
+
+        let mut options = options;
+        resolve_options!(self, options, [timeout]);
+
+        let update = Update::new(self.namespace(), query, update, multi, options);
+        self.client()
+            .execute_operation(Explain::new(update, verbosity), None)
+            .await
+    }
+
+    /// Explains how the server would execute a [`Collection::update_one`](#method.update_one)
+    /// with the given query, update and options, without modifying any documents. If `verbosity`
+    /// is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub async fn update_one_explain(
+        &self,
+        query: Document,
+        update: impl Into<UpdateModifications>,
+        options: impl Into<Option<UpdateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        self.update_explain_common(
+            query,
+            update.into(),
+            false,
+            options.into(),
+            verbosity.into(),
+        )
+        .await
+    }
+
+    /// Explains how the server would execute a [`Collection::update_many`](#method.update_many)
+    /// with the given query, update and options, without modifying any documents. If `verbosity`
+    /// is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub async fn update_many_explain(
+        &self,
+        query: Document,
+        update: impl Into<UpdateModifications>,
+        options: impl Into<Option<UpdateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        self.update_explain_common(query, update.into(), true, options.into(), verbosity.into())
+            .await
+    }
+
+    async fn delete_explain_common(
+        &self,
+        query: Document,
+        limit: Option<u32>,
+        options: Option<DeleteOptions>,
+        verbosity: Option<ExplainVerbosity>,
+    ) -> Result<ExplainResult> {
+        let mut options = options;
+        resolve_options!(self, options, [timeout]);
+
+        let delete = Delete::new(self.namespace(), query, limit, options);
+        self.client()
+            .execute_operation(Explain::new(delete, verbosity), None)
+            .await
+    }
+
+    /// Explains how the server would execute a [`Collection::delete_one`](#method.delete_one)
+    /// with the given query and options, without deleting any documents. If `verbosity` is
+    /// `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub async fn delete_one_explain(
+        &self,
+        query: Document,
+        options: impl Into<Option<DeleteOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        self.delete_explain_common(query, Some(1), options.into(), verbosity.into())
+            .await
+    }
+
+    /// Explains how the server would execute a [`Collection::delete_many`](#method.delete_many)
+    /// with the given query and options, without deleting any documents. If `verbosity` is
+    /// `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub async fn delete_many_explain(
+        &self,
+        query: Document,
+        options: impl Into<Option<DeleteOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        self.delete_explain_common(query, None, options.into(), verbosity.into())
+            .await
+    }
 }
 
 impl<T> Collection<T>
@@ -860,7 +1228,7 @@ where
         options: impl Into<Option<FindOptions>>,
     ) -> Result<Cursor<T>> {
         let mut options = options.into();
-        resolve_options!(self, options, [read_concern, selection_criteria]);
+        resolve_options!(self, options, [read_concern, selection_criteria, timeout]);
 
         let find = Find::new(self.namespace(), filter.into(), options);
         let client = self.client();
@@ -878,6 +1246,7 @@ where
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_selection_criteria_with_session!(self, options, Some(&mut *session))?;
+        resolve_options!(self, options, [timeout]);
 
         let find = Find::new(self.namespace(), filter.into(), options);
         let client = self.client();
@@ -892,7 +1261,7 @@ where
         options: impl Into<Option<FindOneOptions>>,
     ) -> Result<Option<T>> {
         let mut options = options.into();
-        resolve_options!(self, options, [read_concern, selection_criteria]);
+        resolve_options!(self, options, [read_concern, selection_criteria, timeout]);
 
         let options: FindOptions = options.map(Into::into).unwrap_or_else(Default::default);
         let mut cursor = self.find(filter, Some(options)).await?;
@@ -910,6 +1279,7 @@ where
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_selection_criteria_with_session!(self, options, Some(&mut *session))?;
+        resolve_options!(self, options, [timeout]);
 
         let options: FindOptions = options.map(Into::into).unwrap_or_else(Default::default);
         let mut cursor = self
@@ -934,6 +1304,7 @@ where
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let op = FindAndModify::<T>::with_delete(self.namespace(), filter, options);
         self.client().execute_operation(op, session).await
@@ -983,6 +1354,7 @@ where
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let op = FindAndModify::<T>::with_update(self.namespace(), filter, update, options)?;
         self.client().execute_operation(op, session).await
@@ -1045,6 +1417,7 @@ where
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let op = FindAndModify::<T>::with_replace(self.namespace(), filter, replacement, options)?;
         self.client().execute_operation(op, session).await
@@ -1090,8 +1463,181 @@ impl<T> Collection<T>
 where
     T: Serialize,
 {
+    async fn bulk_write_common(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+        session: Option<&mut ClientSession>,
+    ) -> Result<BulkWriteResult> {
+        let execution = self.bulk_write_batches(models, options, session);
+        // Each batch is executed as an operation of its own, so their commands are grouped under
+        // a single span for the whole bulk write.
+        #[cfg(feature = "tracing")]
+        let execution = crate::trace::span::instrument_operation(
+            <BulkWrite as crate::operation::Operation>::NAME,
+            execution,
+        );
+        execution.await
+    }
+
     #[allow(clippy::needless_option_as_deref)]
+    async fn bulk_write_batches(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+        mut session: Option<&mut ClientSession>,
+    ) -> Result<BulkWriteResult> {
+        let mut options = options.into();
+        resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
+
+        let mut statements = models
+            .into_iter()
+            .enumerate()
+            .map(|(index, model)| WriteStatement::new(index, &model))
+            .collect::<Result<Vec<_>>>()?;
+
+        if statements.is_empty() {
+            return Err(ErrorKind::InvalidArgument {
+                message: "No write models provided to bulk_write".to_string(),
+            }
+            .into());
+        }
+
+        let ordered = options.as_ref().and_then(|o| o.ordered).unwrap_or(true);
+        if !ordered {
+            // the order of unordered writes doesn't matter, so group them by kind to minimize the
+            // number of batches that need to be sent.
+            statements.sort_by_key(WriteStatement::kind);
+        }
+
+        let mut cumulative_failure: Option<BulkWriteFailure> = None;
+        let mut error_labels: HashSet<String> = Default::default();
+        let mut cumulative_result = BulkWriteResult::new();
+
+        let mut n_attempted = 0;
+
+        while n_attempted < statements.len() {
+            let bulk_write = BulkWrite::new(
+                self.namespace(),
+                &statements[n_attempted..],
+                options.clone(),
+            );
+
+            match self
+                .client()
+                .execute_operation(bulk_write, session.as_deref_mut())
+                .await
+            {
+                Ok(batch) => {
+                    cumulative_result.merge(batch.result);
+                    n_attempted += batch.size;
+                }
+                Err(e) => {
+                    let labels = e.labels().clone();
+                    match *e.kind {
+                        ErrorKind::BulkWrite(bw) => {
+                            if let Some(result) = bw.partial_result {
+                                cumulative_result.merge(*result);
+                            }
+
+                            let failure_ref =
+                                cumulative_failure.get_or_insert_with(BulkWriteFailure::new);
+                            let has_write_errors = bw.write_errors.is_some();
+                            if let Some(write_errors) = bw.write_errors {
+                                failure_ref
+                                    .write_errors
+                                    .get_or_insert_with(Default::default)
+                                    .extend(write_errors);
+                            }
+
+                            if let Some(wc_error) = bw.write_concern_error {
+                                failure_ref.write_concern_error = Some(wc_error);
+                            }
+
+                            error_labels.extend(labels);
+
+                            // ordered writes stop at the first write error, but continue after
+                            // write concern errors.
+                            if ordered && has_write_errors {
+                                break;
+                            }
+                            n_attempted += bw.batch_size;
+                        }
+                        _ => return Err(e),
+                    }
+                }
+            }
+        }
+
+        match cumulative_failure {
+            Some(mut failure) => {
+                if let Some(ref mut write_errors) = failure.write_errors {
+                    write_errors.sort_by_key(|error| error.index);
+                }
+                failure.partial_result = Some(Box::new(cumulative_result));
+                Err(Error::new(
+                    ErrorKind::BulkWrite(failure),
+                    Some(error_labels),
+                ))
+            }
+            None => Ok(cumulative_result),
+        }
+    }
+
+    /// Executes the given inserts, updates, replacements, and deletes against the collection,
+    /// sending them to the server in as few batches as possible.
+    ///
+    /// If any of the writes fail, an [`ErrorKind::BulkWrite`] error is returned whose
+    /// [`BulkWriteFailure::partial_result`] contains the results of the writes that succeeded.
+    /// Ordered bulk writes (the default) stop at the first failed write, whereas unordered bulk
+    /// writes attempt all of the writes and may execute them in any order.
+    ///
+    /// Batches that only contain `InsertOne`, `UpdateOne`, `ReplaceOne`, and `DeleteOne` writes
+    /// will retry once upon failure if the connection and encountered error support
+    /// retryability. See the documentation
+    /// [here](https://docs.mongodb.com/manual/core/retryable-writes/) for more information on
+    /// retryable writes.
+    pub async fn bulk_write(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+    ) -> Result<BulkWriteResult> {
+        self.bulk_write_common(models, options, None).await
+    }
+
+    /// Executes the given inserts, updates, replacements, and deletes against the collection
+    /// using the provided `ClientSession`.
+    ///
+    /// See [`Collection::bulk_write`] for more details.
+    pub async fn bulk_write_with_session(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<BulkWriteResult> {
+        self.bulk_write_common(models, options, Some(session)).await
+    }
+
     async fn insert_many_common(
+        &self,
+        docs: impl IntoIterator<Item = impl Borrow<T>>,
+        options: impl Into<Option<InsertManyOptions>>,
+        session: Option<&mut ClientSession>,
+    ) -> Result<InsertManyResult> {
+        let execution = self.insert_many_batches(docs, options, session);
+        // Each batch is executed as an operation of its own, so their commands are grouped under
+        // a single span for the whole insert.
+        #[cfg(feature = "tracing")]
+        let execution = crate::trace::span::instrument_operation(
+            <Insert<T> as crate::operation::Operation>::NAME,
+            execution,
+        );
+        execution.await
+    }
+
+    #[allow(clippy::needless_option_as_deref)]
+    async fn insert_many_batches(
         &self,
         docs: impl IntoIterator<Item = impl Borrow<T>>,
         options: impl Into<Option<InsertManyOptions>>,
@@ -1100,6 +1646,7 @@ where
         let ds: Vec<_> = docs.into_iter().collect();
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         if ds.is_empty() {
             return Err(ErrorKind::InvalidArgument {
@@ -1135,6 +1682,7 @@ where
                             .inserted_ids
                             .insert(index + n_attempted, id);
                     }
+                    cumulative_result.acknowledged = result.acknowledged;
 
                     n_attempted += current_batch_size;
                 }
@@ -1240,6 +1788,7 @@ where
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let insert = Insert::new(
             self.namespace(),
@@ -1303,6 +1852,7 @@ where
 
         let mut options = options.into();
         resolve_write_concern_with_session!(self, options, session.as_ref())?;
+        resolve_options!(self, options, [timeout]);
 
         let update = Update::new(
             self.namespace(),
@@ -1350,7 +1900,7 @@ where
 }
 
 /// A struct modeling the canonical name for a collection in MongoDB.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Namespace {
     /// The name of the database associated with this namespace.
     pub db: String,
@@ -9,7 +9,7 @@ use crate::{
     bson::{doc, Bson, Document},
     bson_util,
     concern::{ReadConcern, WriteConcern},
-    options::Collation,
+    options::{Collation, ValidationAction, ValidationLevel},
     selection_criteria::SelectionCriteria,
 };
 
@@ -28,6 +28,14 @@ pub struct CollectionOptions {
 
     /// The default write concern for operations.
     pub write_concern: Option<WriteConcern>,
+
+    /// The default timeout for operations.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies whether a
@@ -93,6 +101,25 @@ pub enum CursorType {
     TailableAwait,
 }
 
+/// Specifies how much information is returned when explaining an operation, e.g. with
+/// [`Collection::find_explain`](../struct.Collection.html#method.find_explain).
+///
+/// See the MongoDB [manual](https://www.mongodb.com/docs/manual/reference/command/explain/#verbosity-modes)
+/// for more information.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ExplainVerbosity {
+    /// Return information about the plan selected by the query planner. This is the default.
+    QueryPlanner,
+
+    /// Additionally execute the winning plan and return statistics describing its execution.
+    ExecutionStats,
+
+    /// Additionally return statistics about the execution of the other candidate plans.
+    AllPlansExecution,
+}
+
 /// Specifies the options to a
 /// [`Collection::insert_one`](../struct.Collection.html#method.insert_one) operation.
 #[derive(Clone, Debug, Default, Deserialize, TypedBuilder)]
@@ -105,6 +132,17 @@ pub struct InsertOneOptions {
 
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -127,6 +165,18 @@ pub struct InsertManyOptions {
     /// The write concern for the operation.
     #[serde(skip_deserializing)]
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 impl InsertManyOptions {
@@ -135,6 +185,7 @@ impl InsertManyOptions {
             bypass_document_validation: options.bypass_document_validation,
             ordered: None,
             write_concern: options.write_concern,
+            timeout: options.timeout,
         }
     }
 }
@@ -211,6 +262,17 @@ pub struct UpdateOptions {
 
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 impl UpdateOptions {
@@ -221,6 +283,7 @@ impl UpdateOptions {
             hint: options.hint,
             write_concern: options.write_concern,
             collation: options.collation,
+            timeout: options.timeout,
             ..Default::default()
         }
     }
@@ -253,6 +316,17 @@ pub struct ReplaceOptions {
 
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -276,6 +350,224 @@ pub struct DeleteOptions {
     /// The index to use for the operation.
     /// Only available in MongoDB 4.4+.
     pub hint: Option<Hint>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Collection::bulk_write`](../struct.Collection.html#method.bulk_write) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[builder(field_defaults(default, setter(into)))]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BulkWriteOptions {
+    /// Opt out of document-level validation.
+    pub bypass_document_validation: Option<bool>,
+
+    /// If true, when a write fails, return without performing the remaining writes. If false,
+    /// when a write fails, continue with the remaining writes, if any.
+    ///
+    /// Defaults to true.
+    pub ordered: Option<bool>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// A single write to be performed as part of a
+/// [`Collection::bulk_write`](../struct.Collection.html#method.bulk_write) operation.
+///
+/// Each of the model types implements `Into<WriteModel<T>>`, so they can be converted with
+/// `into()` rather than wrapped in the corresponding variant.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum WriteModel<T> {
+    /// Inserts a single document.
+    InsertOne(InsertOneModel<T>),
+
+    /// Updates up to one document matching a filter.
+    UpdateOne(UpdateOneModel),
+
+    /// Updates all documents matching a filter.
+    UpdateMany(UpdateManyModel),
+
+    /// Replaces up to one document matching a filter.
+    ReplaceOne(ReplaceOneModel<T>),
+
+    /// Deletes up to one document matching a filter.
+    DeleteOne(DeleteOneModel),
+
+    /// Deletes all documents matching a filter.
+    DeleteMany(DeleteManyModel),
+}
+
+/// Inserts a single document as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[non_exhaustive]
+pub struct InsertOneModel<T> {
+    /// The document to insert.
+    pub document: T,
+}
+
+/// Updates up to one document as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct UpdateOneModel {
+    /// The filter to match the document to update.
+    #[builder(!default)]
+    pub filter: Document,
+
+    /// The modifications to apply to the matched document.
+    #[builder(!default)]
+    pub update: UpdateModifications,
+
+    /// A set of filters specifying to which array elements the update should apply.
+    pub array_filters: Option<Vec<Document>>,
+
+    /// The collation to use when matching the document.
+    pub collation: Option<Collation>,
+
+    /// The index to use when matching the document. Only available in MongoDB 4.2+.
+    pub hint: Option<Hint>,
+
+    /// If true, insert a document if no matching document is found.
+    pub upsert: Option<bool>,
+}
+
+/// Updates all matching documents as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct UpdateManyModel {
+    /// The filter to match the documents to update.
+    #[builder(!default)]
+    pub filter: Document,
+
+    /// The modifications to apply to the matched documents.
+    #[builder(!default)]
+    pub update: UpdateModifications,
+
+    /// A set of filters specifying to which array elements the update should apply.
+    pub array_filters: Option<Vec<Document>>,
+
+    /// The collation to use when matching the documents.
+    pub collation: Option<Collation>,
+
+    /// The index to use when matching the documents. Only available in MongoDB 4.2+.
+    pub hint: Option<Hint>,
+
+    /// If true, insert a document if no matching document is found.
+    pub upsert: Option<bool>,
+}
+
+/// Replaces up to one document as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct ReplaceOneModel<T> {
+    /// The filter to match the document to replace.
+    #[builder(!default)]
+    pub filter: Document,
+
+    /// The document to replace the matched document with.
+    #[builder(!default)]
+    pub replacement: T,
+
+    /// The collation to use when matching the document.
+    pub collation: Option<Collation>,
+
+    /// The index to use when matching the document. Only available in MongoDB 4.2+.
+    pub hint: Option<Hint>,
+
+    /// If true, insert the replacement document if no matching document is found.
+    pub upsert: Option<bool>,
+}
+
+/// Deletes up to one document as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DeleteOneModel {
+    /// The filter to match the document to delete.
+    #[builder(!default)]
+    pub filter: Document,
+
+    /// The collation to use when matching the document.
+    pub collation: Option<Collation>,
+
+    /// The index to use when matching the document. Only available in MongoDB 4.4+.
+    pub hint: Option<Hint>,
+}
+
+/// Deletes all matching documents as part of a bulk write.
+#[derive(Clone, Debug, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DeleteManyModel {
+    /// The filter to match the documents to delete.
+    #[builder(!default)]
+    pub filter: Document,
+
+    /// The collation to use when matching the documents.
+    pub collation: Option<Collation>,
+
+    /// The index to use when matching the documents. Only available in MongoDB 4.4+.
+    pub hint: Option<Hint>,
+}
+
+impl<T> From<InsertOneModel<T>> for WriteModel<T> {
+    fn from(model: InsertOneModel<T>) -> Self {
+        Self::InsertOne(model)
+    }
+}
+
+impl<T> From<UpdateOneModel> for WriteModel<T> {
+    fn from(model: UpdateOneModel) -> Self {
+        Self::UpdateOne(model)
+    }
+}
+
+impl<T> From<UpdateManyModel> for WriteModel<T> {
+    fn from(model: UpdateManyModel) -> Self {
+        Self::UpdateMany(model)
+    }
+}
+
+impl<T> From<ReplaceOneModel<T>> for WriteModel<T> {
+    fn from(model: ReplaceOneModel<T>) -> Self {
+        Self::ReplaceOne(model)
+    }
+}
+
+impl<T> From<DeleteOneModel> for WriteModel<T> {
+    fn from(model: DeleteOneModel) -> Self {
+        Self::DeleteOne(model)
+    }
+}
+
+impl<T> From<DeleteManyModel> for WriteModel<T> {
+    fn from(model: DeleteManyModel) -> Self {
+        Self::DeleteMany(model)
+    }
 }
 
 /// Specifies the options to a
@@ -310,6 +602,17 @@ pub struct FindOneAndDeleteOptions {
     /// The index to use for the operation.
     /// Only available in MongoDB 4.4+.
     pub hint: Option<Hint>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -353,6 +656,17 @@ pub struct FindOneAndReplaceOptions {
     /// The index to use for the operation.
     /// Only available in MongoDB 4.4+.
     pub hint: Option<Hint>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -402,6 +716,17 @@ pub struct FindOneAndUpdateOptions {
     /// The index to use for the operation.
     /// Only available in MongoDB 4.4+.
     pub hint: Option<Hint>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a [`Collection::aggregate`](../struct.Collection.html#method.aggregate)
@@ -496,6 +821,18 @@ pub struct AggregateOptions {
     /// This feature is only available on server versions 5.0 and above.
     #[serde(rename = "let")]
     pub let_vars: Option<Document>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -538,6 +875,17 @@ pub struct CountOptions {
     /// The level of the read concern.
     #[serde(skip_serializing)]
     pub read_concern: Option<ReadConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 // rustfmt tries to split the link up when it's all on one line, which breaks the link, so we wrap
@@ -574,6 +922,18 @@ pub struct EstimatedDocumentCountOptions {
     /// The level of the read concern.
     #[serde(skip_serializing)]
     pub read_concern: Option<ReadConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a [`Collection::distinct`](../struct.Collection.html#method.distinct)
@@ -611,6 +971,18 @@ pub struct DistinctOptions {
     /// See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more
     /// information on how to use this option.
     pub collation: Option<Collation>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a [`Collection::find`](../struct.Collection.html#method.find)
@@ -720,6 +1092,18 @@ pub struct FindOptions {
     /// See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more
     /// information on how to use this option.
     pub collation: Option<Collation>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 impl From<FindOneOptions> for FindOptions {
@@ -746,6 +1130,7 @@ impl From<FindOneOptions> for FindOptions {
             max_await_time: None,
             no_cursor_timeout: None,
             sort: options.sort,
+            timeout: options.timeout,
         }
     }
 }
@@ -834,6 +1219,17 @@ pub struct FindOneOptions {
 
     /// The order of the documents for the purposes of the operation.
     pub sort: Option<Document>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -864,6 +1260,13 @@ pub struct CreateIndexOptions {
 
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a [`Collection::drop`](../struct.Collection.html#method.drop)
@@ -876,6 +1279,180 @@ pub struct CreateIndexOptions {
 pub struct DropCollectionOptions {
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a [`Collection::rename`](../struct.Collection.html#method.rename)
+/// operation.
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct RenameCollectionOptions {
+    /// Whether to drop an existing collection with the new name before renaming. If this is not
+    /// set to true and a collection with the new name exists, the rename will fail.
+    pub drop_target: Option<bool>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a [`Collection::modify`](../struct.Collection.html#method.modify)
+/// operation.
+///
+/// See the documentation for the [`collMod`](https://www.mongodb.com/docs/manual/reference/command/collMod/)
+/// command for more information on each of the options.
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct ModifyCollectionOptions {
+    /// The validator used to restrict the schema of documents in the collection.
+    pub validator: Option<Document>,
+
+    /// Specifies how strictly the database should apply the validation rules to existing documents
+    /// during an update.
+    pub validation_level: Option<ValidationLevel>,
+
+    /// Specifies whether the database should return an error or simply raise a warning if
+    /// documents do not pass the validation.
+    pub validation_action: Option<ValidationAction>,
+
+    /// The amount of time after which documents in a time series collection are automatically
+    /// deleted.
+    #[serde(
+        default,
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_seconds",
+        serialize_with = "bson_util::serialize_duration_option_as_int_secs"
+    )]
+    pub expire_after_seconds: Option<Duration>,
+
+    /// Whether change streams opened on the collection can include the pre- and post-images of
+    /// modified documents.
+    ///
+    /// This option is only supported on server versions 6.0 and above.
+    pub change_stream_pre_and_post_images: Option<ChangeStreamPreAndPostImages>,
+
+    /// Changes the properties of an existing index.
+    pub index: Option<ModifyIndex>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies whether a collection records the pre- and post-images of modified documents for use
+/// by change streams.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChangeStreamPreAndPostImages {
+    /// Whether pre- and post-images are recorded.
+    pub enabled: bool,
+}
+
+impl ChangeStreamPreAndPostImages {
+    /// Creates a new `ChangeStreamPreAndPostImages` with the given setting.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+/// Specifies an index to change with [`ModifyCollectionOptions::index`], and the changes to make.
+///
+/// Exactly one of `name` and `key_pattern` must be set to identify the index.
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct ModifyIndex {
+    /// The name of the index to change.
+    pub name: Option<String>,
+
+    /// The key pattern of the index to change.
+    pub key_pattern: Option<Document>,
+
+    /// The new amount of time after which documents are deleted from the collection by this TTL
+    /// index.
+    #[serde(
+        default,
+        rename = "expireAfterSeconds",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_seconds",
+        serialize_with = "bson_util::serialize_duration_option_as_int_secs"
+    )]
+    pub expire_after: Option<Duration>,
+
+    /// Whether the index is hidden from the query planner.
+    pub hidden: Option<bool>,
+}
+
+/// Specifies the options to a [`Collection::stats`](../struct.Collection.html#method.stats)
+/// operation.
+#[serde_with::skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct CollectionStatsOptions {
+    /// The factor to divide the reported sizes by, e.g. 1024 to report sizes in kilobytes.
+    /// Defaults to 1.
+    #[serde(serialize_with = "bson_util::serialize_u32_option_as_i32")]
+    pub scale: Option<u32>,
+
+    /// The criteria used to select a server for this operation.
+    ///
+    /// If none specified, the default set on the collection will be used.
+    #[serde(skip_serializing)]
+    pub selection_criteria: Option<SelectionCriteria>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -901,6 +1478,18 @@ pub struct DropIndexOptions {
 
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -926,6 +1515,43 @@ pub struct ListIndexesOptions {
     /// The number of indexes the server should return per cursor batch.
     #[serde(default, skip_serializing)]
     pub batch_size: Option<u32>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Collection::sync_indexes`](../struct.Collection.html#method.sync_indexes) operation.
+#[derive(Clone, Debug, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct SyncIndexOptions {
+    /// Whether to drop existing indexes that do not match any of the desired indexes. The `_id`
+    /// index is never dropped. Defaults to false.
+    pub drop_extraneous: Option<bool>,
+
+    /// Whether to only compute the changes that would be made, without modifying the collection.
+    /// Defaults to false.
+    pub dry_run: Option<bool>,
+
+    /// The write concern for the commands that create and drop indexes.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time each of the commands sent by the operation may take, including
+    /// any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    pub timeout: Option<Duration>,
 }
 
 /// The minimum number of data-bearing voting replica set members (i.e. commit quorum), including
@@ -0,0 +1,112 @@
+//! Contains the [`RunnableCommand`] trait, used to run server commands that the driver does not
+//! otherwise provide a method for with typed output and the driver's usual routing and error
+//! handling.
+//!
+//! For example, a wrapper for the `serverStatus` command might look like the following:
+//!
+//! ```rust
+//! # use mongodb::{
+//! #     bson::{doc, Document},
+//! #     command::{CommandRetryability, RunnableCommand},
+//! #     error::Result,
+//! #     Client,
+//! # };
+//! #
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct ServerStatus {
+//!     host: String,
+//!     version: String,
+//!     uptime: f64,
+//! }
+//!
+//! struct GetServerStatus;
+//!
+//! impl RunnableCommand for GetServerStatus {
+//!     type Output = ServerStatus;
+//!
+//!     fn body(&self) -> Result<Document> {
+//!         Ok(doc! { "serverStatus": 1 })
+//!     }
+//!
+//!     fn handle_response(&self, response: Document) -> Result<ServerStatus> {
+//!         Ok(mongodb::bson::from_document(response)?)
+//!     }
+//!
+//!     fn retryability(&self) -> CommandRetryability {
+//!         CommandRetryability::Read
+//!     }
+//! }
+//!
+//! # async fn do_stuff() -> Result<()> {
+//! # let client = Client::with_uri_str("mongodb://example.com").await?;
+//! let status = client.run_typed_command(GetServerStatus).await?;
+//! println!("{} is running {}", status.host, status.version);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    bson::Document,
+    error::Result,
+    options::{SelectionCriteria, WriteConcern},
+};
+
+/// A server command that can be run with
+/// [`Database::run_typed_command`](../struct.Database.html#method.run_typed_command) or
+/// [`Client::run_typed_command`](../struct.Client.html#method.run_typed_command).
+///
+/// Unlike [`Database::run_command`](../struct.Database.html#method.run_command), commands run this
+/// way are routed using their selection criteria, retried according to their retryability, and
+/// have any write concern errors in their responses reported as errors before the response is
+/// passed to [`RunnableCommand::handle_response`].
+pub trait RunnableCommand: Send + Sync {
+    /// The type of the value produced from the server's response.
+    type Output;
+
+    /// Returns the body of the command to send. The first key of the document must be the name of
+    /// the command.
+    ///
+    /// The driver will add the fields common to all commands, such as `$db` and `lsid`, so they
+    /// should not be included here. If [`RunnableCommand::write_concern`] returns a write concern
+    /// and the body does not already contain a `writeConcern` field, it will be added as well.
+    fn body(&self) -> Result<Document>;
+
+    /// Interprets the server's response to the command. This is only called for responses that
+    /// indicate success.
+    fn handle_response(&self, response: Document) -> Result<Self::Output>;
+
+    /// The criteria used to select the server to run the command on. Defaults to `None`, in which
+    /// case the primary is used.
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        None
+    }
+
+    /// The write concern for the command, if it performs writes. Unacknowledged write concerns
+    /// are not supported, since a response is always needed.
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        None
+    }
+
+    /// Whether the command may be retried once after a retryable error. Defaults to
+    /// [`CommandRetryability::None`].
+    fn retryability(&self) -> CommandRetryability {
+        CommandRetryability::None
+    }
+}
+
+/// Specifies whether a [`RunnableCommand`] may be retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandRetryability {
+    /// The command is never retried.
+    None,
+
+    /// The command only reads data and may be retried once under the same conditions as
+    /// retryable reads.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/core/retryable-reads/)
+    /// for more information on retryable reads.
+    Read,
+}
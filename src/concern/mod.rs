@@ -225,8 +225,9 @@ pub struct WriteConcern {
 pub enum Acknowledgment {
     /// Requires acknowledgement that the write has reached the specified number of nodes.
     ///
-    /// Note: specifying 0 here indicates that the write concern is unacknowledged, which is
-    /// currently unsupported and will result in an error during operation execution.
+    /// Note: specifying 0 here indicates that the write concern is unacknowledged. Unacknowledged
+    /// writes are sent without waiting for a reply from the server, so their results will have
+    /// `acknowledged` set to `false`. They cannot be used with explicit sessions or transactions.
     Nodes(u32),
 
     /// Requires acknowledgement that the write has reached the majority of nodes.
@@ -6,7 +6,7 @@ use std::{
     time::Duration,
 };
 
-use bson::RawDocumentBuf;
+use bson::{RawDocumentBuf, Timestamp};
 use derivative::Derivative;
 use futures_core::{Future, Stream};
 use serde::de::DeserializeOwned;
@@ -194,6 +194,20 @@ where
     }
 }
 
+/// Retrieves the next document from the given cursor stream without deserializing it, fetching
+/// more batches from the server as needed. Returns `None` once the cursor has been exhausted.
+pub(crate) async fn next_raw_document<S: CursorStream>(
+    stream: &mut S,
+) -> Result<Option<RawDocumentBuf>> {
+    loop {
+        match NextInBatchFuture::new(stream).await? {
+            BatchValue::Some { doc, .. } => return Ok(Some(doc)),
+            BatchValue::Empty => continue,
+            BatchValue::Exhausted => return Ok(None),
+        }
+    }
+}
+
 pub(crate) struct NextInBatchFuture<'a, T>(&'a mut T);
 
 impl<'a, T> NextInBatchFuture<'a, T>
@@ -281,6 +295,9 @@ pub(crate) struct CursorSpecification {
     pub(crate) info: CursorInformation,
     pub(crate) initial_buffer: VecDeque<RawDocumentBuf>,
     pub(crate) post_batch_resume_token: Option<ResumeToken>,
+    /// The `operationTime` of the reply that created the cursor. This is only recorded for
+    /// aggregations, where it is used to resume change streams.
+    pub(crate) operation_time: Option<Timestamp>,
 }
 
 impl CursorSpecification {
@@ -289,6 +306,7 @@ impl CursorSpecification {
         address: ServerAddress,
         batch_size: impl Into<Option<u32>>,
         max_time: impl Into<Option<Duration>>,
+        timeout: impl Into<Option<Duration>>,
     ) -> Self {
         Self {
             info: CursorInformation {
@@ -297,9 +315,11 @@ impl CursorSpecification {
                 address,
                 batch_size: batch_size.into(),
                 max_time: max_time.into(),
+                timeout: timeout.into(),
             },
             initial_buffer: info.first_batch,
             post_batch_resume_token: ResumeToken::from_raw(info.post_batch_resume_token),
+            operation_time: None,
         }
     }
 
@@ -331,6 +351,8 @@ pub(crate) struct CursorInformation {
     pub(crate) id: i64,
     pub(crate) batch_size: Option<u32>,
     pub(crate) max_time: Option<Duration>,
+    /// The timeout of the operation that created the cursor, which each `getMore` inherits.
+    pub(crate) timeout: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -6,6 +6,7 @@ use std::{
     task::{Context, Poll},
 };
 
+use bson::{RawDocument, RawDocumentBuf};
 use futures_core::{future::BoxFuture, Stream};
 use serde::de::DeserializeOwned;
 #[cfg(test)]
@@ -20,7 +21,13 @@ use crate::{
     Client,
     ClientSession,
 };
-use common::{kill_cursor, GenericCursor, GetMoreProvider, GetMoreProviderResult};
+use common::{
+    kill_cursor,
+    next_raw_document,
+    GenericCursor,
+    GetMoreProvider,
+    GetMoreProviderResult,
+};
 pub(crate) use common::{
     stream_poll_next,
     BatchValue,
@@ -88,6 +95,26 @@ pub(crate) use common::{
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Documents can also be inspected in place without being deserialized by using
+/// [`Cursor::advance`] and [`Cursor::current`]. To pass documents through without deserializing
+/// them at all, use a [`Collection`](struct.Collection.html) or cursor typed as
+/// [`RawDocumentBuf`](bson::RawDocumentBuf):
+///
+/// ```rust
+/// # use mongodb::{bson::RawDocumentBuf, Client, error::Result};
+/// #
+/// # async fn do_stuff() -> Result<()> {
+/// # let client = Client::with_uri_str("mongodb://example.com").await?;
+/// let coll = client.database("foo").collection::<RawDocumentBuf>("bar");
+/// let mut cursor = coll.find(None, None).await?;
+/// while cursor.advance().await? {
+///     println!("{:?}", cursor.current().get_str("name"));
+/// }
+/// #
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Cursor<T>
 where
@@ -97,6 +124,8 @@ where
     // `wrapped_cursor` is an `Option` so that it can be `None` for the `drop` impl for a cursor
     // that's had `with_type` called; in all other circumstances it will be `Some`.
     wrapped_cursor: Option<ImplicitSessionCursor<T>>,
+    /// The document most recently retrieved by `advance`.
+    current: Option<RawDocumentBuf>,
     #[cfg(test)]
     kill_watcher: Option<oneshot::Sender<()>>,
     _phantom: std::marker::PhantomData<T>,
@@ -122,6 +151,7 @@ where
                 PinnedConnection::new(pin),
                 provider,
             )),
+            current: None,
             #[cfg(test)]
             kill_watcher: None,
             _phantom: Default::default(),
@@ -138,6 +168,60 @@ where
         self.wrapped_cursor.as_ref().unwrap().is_exhausted()
     }
 
+    /// Move the cursor forward to the next document, requesting more results from the server if
+    /// the current batch has been exhausted. The document can then be accessed without being
+    /// deserialized via [`Cursor::current`], or deserialized via [`Cursor::deserialize_current`].
+    ///
+    /// Returns `true` if the cursor moved to a new document, or `false` if the cursor has been
+    /// exhausted.
+    ///
+    /// ```rust
+    /// # use mongodb::{bson::Document, Client, error::Result};
+    /// #
+    /// # async fn do_stuff() -> Result<()> {
+    /// # let client = Client::with_uri_str("mongodb://example.com").await?;
+    /// # let coll = client.database("foo").collection::<Document>("bar");
+    /// let mut cursor = coll.find(None, None).await?;
+    /// while cursor.advance().await? {
+    ///     println!("{:?}", cursor.current());
+    /// }
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn advance(&mut self) -> Result<bool> {
+        self.current = None;
+        // This `unwrap` is safe because `wrapped_cursor` is always `Some` outside of `drop`.
+        self.current = next_raw_document(self.wrapped_cursor.as_mut().unwrap()).await?;
+        Ok(self.current.is_some())
+    }
+
+    /// Returns a reference to the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Cursor::advance`] has not been called or if the last call to it did not return
+    /// `Ok(true)`.
+    pub fn current(&self) -> &RawDocument {
+        self.current
+            .as_deref()
+            .expect("Cursor::current called without a successful call to Cursor::advance")
+    }
+
+    /// Deserializes the document the cursor is currently positioned at. Because the document is
+    /// deserialized directly from the cursor's buffer, no intermediate copy of it is made.
+    ///
+    /// To deserialize the document into a type that borrows from it, use
+    /// [`bson::from_slice`](bson::from_slice) on the bytes of [`Cursor::current`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Cursor::advance`] has not been called or if the last call to it did not return
+    /// `Ok(true)`.
+    pub fn deserialize_current(&self) -> Result<T> {
+        Ok(bson::from_slice(self.current().as_bytes())?)
+    }
+
     /// Update the type streamed values will be parsed as.
     pub fn with_type<D>(mut self) -> Cursor<D>
     where
@@ -146,6 +230,7 @@ where
         Cursor {
             client: self.client.clone(),
             wrapped_cursor: self.wrapped_cursor.take().map(|c| c.with_type()),
+            current: self.current.take(),
             #[cfg(test)]
             kill_watcher: self.kill_watcher.take(),
             _phantom: Default::default(),
@@ -5,7 +5,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use bson::RawDocumentBuf;
+use bson::{RawDocument, RawDocumentBuf};
 use futures_core::{future::BoxFuture, Stream};
 use futures_util::StreamExt;
 use serde::de::DeserializeOwned;
@@ -15,6 +15,7 @@ use tokio::sync::oneshot;
 use super::{
     common::{
         kill_cursor,
+        next_raw_document,
         CursorInformation,
         GenericCursor,
         GetMoreProvider,
@@ -71,6 +72,8 @@ where
     client: Client,
     info: CursorInformation,
     buffer: VecDeque<RawDocumentBuf>,
+    /// The document most recently retrieved by `advance`.
+    current: Option<RawDocumentBuf>,
     pinned_connection: PinnedConnection,
     _phantom: PhantomData<T>,
     #[cfg(test)]
@@ -90,6 +93,7 @@ where
             client,
             info: spec.info,
             buffer: spec.initial_buffer,
+            current: None,
             pinned_connection: PinnedConnection::new(pinned),
             _phantom: Default::default(),
             #[cfg(test)]
@@ -151,6 +155,7 @@ where
             info: self.info.clone(),
             initial_buffer: std::mem::take(&mut self.buffer),
             post_batch_resume_token: None,
+            operation_time: None,
         };
         SessionCursorStream {
             generic_cursor: ExplicitSessionCursor::new(
@@ -190,6 +195,60 @@ where
         self.stream(session).next().await
     }
 
+    /// Move the cursor forward to the next document, requesting more results from the server if
+    /// the current batch has been exhausted. The session provided must be the same session used
+    /// to create the cursor. The document can then be accessed without being deserialized via
+    /// [`SessionCursor::current`], or deserialized via [`SessionCursor::deserialize_current`].
+    ///
+    /// Returns `true` if the cursor moved to a new document, or `false` if the cursor has been
+    /// exhausted.
+    ///
+    /// ```
+    /// # use bson::{doc, Document};
+    /// # use mongodb::Client;
+    /// # fn main() {
+    /// # async {
+    /// # let client = Client::with_uri_str("foo").await?;
+    /// # let coll = client.database("foo").collection::<Document>("bar");
+    /// # let mut session = client.start_session(None).await?;
+    /// let mut cursor = coll.find_with_session(doc! { "x": 1 }, None, &mut session).await?;
+    /// while cursor.advance(&mut session).await? {
+    ///     println!("{:?}", cursor.current());
+    /// }
+    /// # Ok::<(), mongodb::error::Error>(())
+    /// # };
+    /// # }
+    /// ```
+    pub async fn advance(&mut self, session: &mut ClientSession) -> Result<bool> {
+        self.current = None;
+        let next = next_raw_document(&mut self.stream(session)).await?;
+        self.current = next;
+        Ok(self.current.is_some())
+    }
+
+    /// Returns a reference to the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SessionCursor::advance`] has not been called or if the last call to it did not
+    /// return `Ok(true)`.
+    pub fn current(&self) -> &RawDocument {
+        self.current.as_deref().expect(
+            "SessionCursor::current called without a successful call to SessionCursor::advance",
+        )
+    }
+
+    /// Deserializes the document the cursor is currently positioned at. Because the document is
+    /// deserialized directly from the cursor's buffer, no intermediate copy of it is made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SessionCursor::advance`] has not been called or if the last call to it did not
+    /// return `Ok(true)`.
+    pub fn deserialize_current(&self) -> Result<T> {
+        Ok(bson::from_slice(self.current().as_bytes())?)
+    }
+
     /// Update the type streamed values will be parsed as.
     pub fn with_type<D>(mut self) -> SessionCursor<D>
     where
@@ -199,6 +258,7 @@ where
             client: self.client.clone(),
             info: self.info.clone(),
             buffer: std::mem::take(&mut self.buffer),
+            current: self.current.take(),
             pinned_connection: self.pinned_connection.take(),
             _phantom: Default::default(),
             #[cfg(test)]
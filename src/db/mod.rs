@@ -1,6 +1,6 @@
 pub mod options;
 
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, sync::Arc, time::Duration};
 
 use futures_util::stream::TryStreamExt;
 
@@ -14,19 +14,49 @@ use crate::{
     },
     client::session::TransactionState,
     cmap::conn::PinnedConnectionHandle,
+    command::RunnableCommand,
     concern::{ReadConcern, WriteConcern},
     cursor::Cursor,
     error::{Error, ErrorKind, Result},
-    operation::{Aggregate, AggregateTarget, Create, DropDatabase, ListCollections, RunCommand},
+    gridfs::{options::GridFsBucketOptions, GridFsBucket},
+    operation::{
+        Aggregate,
+        AggregateTarget,
+        Create,
+        CreateRole,
+        CreateUser,
+        DbStats,
+        DropDatabase,
+        DropRole,
+        DropUser,
+        GrantRolesToUser,
+        ListCollections,
+        RolesInfo,
+        RunCommand,
+        TypedCommand,
+        UpdateUser,
+        UsersInfo,
+    },
     options::{
         AggregateOptions,
         CollectionOptions,
         CreateCollectionOptions,
+        CreateRoleOptions,
+        CreateUserOptions,
+        CreateViewOptions,
         DatabaseOptions,
+        DatabaseStatsOptions,
         DropDatabaseOptions,
+        DropRoleOptions,
+        DropUserOptions,
+        GrantRolesOptions,
         ListCollectionsOptions,
+        Role,
+        RolesInfoOptions,
+        UpdateUserOptions,
+        UsersInfoOptions,
     },
-    results::CollectionSpecification,
+    results::{CollectionSpecification, DatabaseStats, RoleInfo, UserInfo},
     selection_criteria::SelectionCriteria,
     Client,
     ClientSession,
@@ -84,6 +114,7 @@ struct DatabaseInner {
     selection_criteria: Option<SelectionCriteria>,
     read_concern: Option<ReadConcern>,
     write_concern: Option<WriteConcern>,
+    timeout: Option<Duration>,
 }
 
 impl Database {
@@ -101,6 +132,8 @@ impl Database {
             .write_concern
             .or_else(|| client.write_concern().cloned());
 
+        let timeout = options.timeout.or_else(|| client.timeout());
+
         Self {
             inner: Arc::new(DatabaseInner {
                 client,
@@ -108,6 +141,7 @@ impl Database {
                 selection_criteria,
                 read_concern,
                 write_concern,
+                timeout,
             }),
         }
     }
@@ -137,6 +171,11 @@ impl Database {
         self.inner.write_concern.as_ref()
     }
 
+    /// Gets the timeout of the `Database`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+
     /// Gets a handle to a collection in this database with the provided name. The
     /// [`Collection`] options (e.g. read preference and write concern) will default to those of
     /// this [`Database`].
@@ -173,7 +212,7 @@ impl Database {
         session: impl Into<Option<&mut ClientSession>>,
     ) -> Result<()> {
         let mut options = options.into();
-        resolve_options!(self, options, [write_concern]);
+        resolve_options!(self, options, [write_concern, timeout]);
 
         let drop_database = DropDatabase::new(self.name().to_string(), options);
         self.client()
@@ -203,12 +242,11 @@ impl Database {
         filter: impl Into<Option<Document>>,
         options: impl Into<Option<ListCollectionsOptions>>,
     ) -> Result<Cursor<CollectionSpecification>> {
-        let list_collections = ListCollections::new(
-            self.name().to_string(),
-            filter.into(),
-            false,
-            options.into(),
-        );
+        let mut options = options.into();
+        resolve_options!(self, options, [timeout]);
+
+        let list_collections =
+            ListCollections::new(self.name().to_string(), filter.into(), false, options);
         self.client()
             .execute_cursor_operation(list_collections)
             .await
@@ -223,12 +261,11 @@ impl Database {
         options: impl Into<Option<ListCollectionsOptions>>,
         session: &mut ClientSession,
     ) -> Result<SessionCursor<CollectionSpecification>> {
-        let list_collections = ListCollections::new(
-            self.name().to_string(),
-            filter.into(),
-            false,
-            options.into(),
-        );
+        let mut options = options.into();
+        resolve_options!(self, options, [timeout]);
+
+        let list_collections =
+            ListCollections::new(self.name().to_string(), filter.into(), false, options);
         self.client()
             .execute_session_cursor_operation(list_collections, session)
             .await
@@ -258,8 +295,11 @@ impl Database {
         &self,
         filter: impl Into<Option<Document>>,
     ) -> Result<Vec<String>> {
+        let options = ListCollectionsOptions::builder()
+            .timeout(self.timeout())
+            .build();
         let list_collections =
-            ListCollections::new(self.name().to_string(), filter.into(), true, None);
+            ListCollections::new(self.name().to_string(), filter.into(), true, Some(options));
         let cursor: Cursor<Document> = self
             .client()
             .execute_cursor_operation(list_collections)
@@ -274,8 +314,11 @@ impl Database {
         filter: impl Into<Option<Document>>,
         session: &mut ClientSession,
     ) -> Result<Vec<String>> {
+        let options = ListCollectionsOptions::builder()
+            .timeout(self.timeout())
+            .build();
         let list_collections =
-            ListCollections::new(self.name().to_string(), filter.into(), true, None);
+            ListCollections::new(self.name().to_string(), filter.into(), true, Some(options));
         let mut cursor: SessionCursor<Document> = self
             .client()
             .execute_session_cursor_operation(list_collections, &mut *session)
@@ -292,7 +335,7 @@ impl Database {
         session: impl Into<Option<&mut ClientSession>>,
     ) -> Result<()> {
         let mut options = options.into();
-        resolve_options!(self, options, [write_concern]);
+        resolve_options!(self, options, [write_concern, timeout]);
 
         let create = Create::new(
             Namespace {
@@ -330,6 +373,192 @@ impl Database {
         self.create_collection_common(name, options, session).await
     }
 
+    async fn create_view_common(
+        &self,
+        name: impl AsRef<str>,
+        view_on: impl AsRef<str>,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<CreateViewOptions>>,
+        session: impl Into<Option<&mut ClientSession>>,
+    ) -> Result<()> {
+        let options = options.into().unwrap_or_default();
+        let options = CreateCollectionOptions::builder()
+            .view_on(view_on.as_ref().to_string())
+            .pipeline(pipeline.into_iter().collect::<Vec<_>>())
+            .collation(options.collation)
+            .write_concern(options.write_concern)
+            .timeout(options.timeout)
+            .build();
+        self.create_collection_common(name, options, session).await
+    }
+
+    /// Creates a read-only view with the given `name` whose contents are the results of running
+    /// `pipeline` against the collection or view named `view_on`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/core/views/) for more
+    /// information on views.
+    pub async fn create_view(
+        &self,
+        name: impl AsRef<str>,
+        view_on: impl AsRef<str>,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<CreateViewOptions>>,
+    ) -> Result<()> {
+        self.create_view_common(name, view_on, pipeline, options, None)
+            .await
+    }
+
+    /// Creates a read-only view with the given `name` whose contents are the results of running
+    /// `pipeline` against the collection or view named `view_on` using the provided
+    /// `ClientSession`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/core/views/) for more
+    /// information on views.
+    pub async fn create_view_with_session(
+        &self,
+        name: impl AsRef<str>,
+        view_on: impl AsRef<str>,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<CreateViewOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        self.create_view_common(name, view_on, pipeline, options, session)
+            .await
+    }
+
+    /// Gets storage statistics for the database, such as the number of collections and the total
+    /// size of their data and indexes.
+    pub async fn stats(
+        &self,
+        options: impl Into<Option<DatabaseStatsOptions>>,
+    ) -> Result<DatabaseStats> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let stats = DbStats::new(self.name().to_string(), options);
+        self.client().execute_operation(stats, None).await
+    }
+
+    /// Creates a user named `name` in this database.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/createUser/)
+    /// for more information.
+    pub async fn create_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<CreateUserOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = CreateUser::new(self.name().to_string(), name.as_ref().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Updates the user named `name` in this database. Only the fields set in `options` are
+    /// changed.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/updateUser/)
+    /// for more information.
+    pub async fn update_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<UpdateUserOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = UpdateUser::new(self.name().to_string(), name.as_ref().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Removes the user named `name` from this database.
+    pub async fn drop_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<DropUserOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = DropUser::new(self.name().to_string(), name.as_ref().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Gets information about the users defined in this database.
+    pub async fn users_info(
+        &self,
+        options: impl Into<Option<UsersInfoOptions>>,
+    ) -> Result<Vec<UserInfo>> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let op = UsersInfo::new(self.name().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Grants `roles` to the user named `name` in this database, in addition to the roles it
+    /// already has.
+    pub async fn grant_roles(
+        &self,
+        name: impl AsRef<str>,
+        roles: impl IntoIterator<Item = Role>,
+        options: impl Into<Option<GrantRolesOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = GrantRolesToUser::new(
+            self.name().to_string(),
+            name.as_ref().to_string(),
+            roles.into_iter().collect(),
+            options,
+        );
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Creates a user-defined role named `name` in this database.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/createRole/)
+    /// for more information.
+    pub async fn create_role(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<CreateRoleOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = CreateRole::new(self.name().to_string(), name.as_ref().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Removes the user-defined role named `name` from this database.
+    pub async fn drop_role(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<DropRoleOptions>>,
+    ) -> Result<()> {
+        let mut options = options.into();
+        resolve_options!(self, options, [write_concern, timeout]);
+
+        let op = DropRole::new(self.name().to_string(), name.as_ref().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
+    /// Gets information about the user-defined roles in this database, and optionally the
+    /// built-in roles as well.
+    pub async fn roles_info(
+        &self,
+        options: impl Into<Option<RolesInfoOptions>>,
+    ) -> Result<Vec<RoleInfo>> {
+        let mut options = options.into();
+        resolve_options!(self, options, [selection_criteria, timeout]);
+
+        let op = RolesInfo::new(self.name().to_string(), options);
+        self.client().execute_operation(op, None).await
+    }
+
     pub(crate) async fn run_command_common(
         &self,
         command: Document,
@@ -401,6 +630,38 @@ impl Database {
             .await
     }
 
+    async fn run_typed_command_common<C: RunnableCommand>(
+        &self,
+        command: C,
+        session: impl Into<Option<&mut ClientSession>>,
+    ) -> Result<C::Output> {
+        let operation = TypedCommand::new(self.name().to_string(), command)?;
+        self.client().execute_operation(operation, session).await
+    }
+
+    /// Runs a database-level command defined by a [`RunnableCommand`], returning its typed output.
+    ///
+    /// The command is routed using its selection criteria and retried according to its
+    /// retryability, and any write concern error in the response is returned as an error. The
+    /// database's default read concern, write concern and selection criteria are not applied.
+    pub async fn run_typed_command<C: RunnableCommand>(&self, command: C) -> Result<C::Output> {
+        self.run_typed_command_common(command, None).await
+    }
+
+    /// Runs a database-level command defined by a [`RunnableCommand`] using the provided
+    /// `ClientSession`, returning its typed output.
+    ///
+    /// The command is routed using its selection criteria and retried according to its
+    /// retryability, and any write concern error in the response is returned as an error. The
+    /// database's default read concern, write concern and selection criteria are not applied.
+    pub async fn run_typed_command_with_session<C: RunnableCommand>(
+        &self,
+        command: C,
+        session: &mut ClientSession,
+    ) -> Result<C::Output> {
+        self.run_typed_command_common(command, session).await
+    }
+
     /// Runs an aggregation operation.
     ///
     /// See the documentation [here](https://docs.mongodb.com/manual/aggregation/) for more
@@ -414,7 +675,7 @@ impl Database {
         resolve_options!(
             self,
             options,
-            [read_concern, write_concern, selection_criteria]
+            [read_concern, write_concern, selection_criteria, timeout]
         );
 
         let aggregate = Aggregate::new(self.name().to_string(), pipeline, options);
@@ -436,7 +697,7 @@ impl Database {
         resolve_options!(
             self,
             options,
-            [read_concern, write_concern, selection_criteria]
+            [read_concern, write_concern, selection_criteria, timeout]
         );
 
         let aggregate = Aggregate::new(self.name().to_string(), pipeline, options);
@@ -463,22 +724,20 @@ impl Database {
     ///
     /// If the pipeline alters the structure of the returned events, the parsed type will need to be
     /// changed via [`ChangeStream::with_type`].
-    #[allow(unused)]
-    pub(crate) async fn watch(
+    pub async fn watch(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
     ) -> Result<ChangeStream<ChangeStreamEvent<Document>>> {
         let mut options = options.into();
-        resolve_options!(self, options, [read_concern, selection_criteria]);
+        resolve_options!(self, options, [read_concern, selection_criteria, timeout]);
         let target = AggregateTarget::Database(self.name().to_string());
         self.client().execute_watch(pipeline, options, target).await
     }
 
     /// Starts a new [`SessionChangeStream`] that receives events for all changes in this database
     /// using the provided [`ClientSession`].  See [`Database::watch`] for more information.
-    #[allow(unused)]
-    pub(crate) async fn watch_with_session(
+    pub async fn watch_with_session(
         &self,
         pipeline: impl IntoIterator<Item = Document>,
         options: impl Into<Option<ChangeStreamOptions>>,
@@ -487,9 +746,23 @@ impl Database {
         let mut options = options.into();
         resolve_read_concern_with_session!(self, options, Some(&mut *session))?;
         resolve_selection_criteria_with_session!(self, options, Some(&mut *session))?;
+        resolve_options!(self, options, [timeout]);
         let target = AggregateTarget::Database(self.name().to_string());
         self.client()
             .execute_watch_with_session(pipeline, options, target, session)
             .await
     }
+
+    /// Creates a new [`GridFsBucket`] in the database with the given options. The bucket inherits
+    /// the read concern, write concern and selection criteria of the database unless they are
+    /// specified in the options.
+    ///
+    /// An [`ErrorKind::InvalidArgument`](crate::error::ErrorKind::InvalidArgument) error is
+    /// returned if `chunk_size_bytes` is 0.
+    pub fn gridfs_bucket(
+        &self,
+        options: impl Into<Option<GridFsBucketOptions>>,
+    ) -> Result<GridFsBucket> {
+        GridFsBucket::new(self.clone(), options.into().unwrap_or_default())
+    }
 }
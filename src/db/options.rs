@@ -1,6 +1,6 @@
-use std::time::Duration;
+use std::{fmt, time::Duration};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::skip_serializing_none;
 use typed_builder::TypedBuilder;
 
@@ -8,7 +8,7 @@ use crate::{
     bson::Document,
     bson_util,
     concern::{ReadConcern, WriteConcern},
-    options::Collation,
+    options::{AuthMechanism, Collation},
     selection_criteria::SelectionCriteria,
 };
 
@@ -26,6 +26,14 @@ pub struct DatabaseOptions {
 
     /// The default write concern for operations.
     pub write_concern: Option<WriteConcern>,
+
+    /// The default timeout for operations.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// These are the valid options for creating a collection with
@@ -103,6 +111,18 @@ pub struct CreateCollectionOptions {
         serialize_with = "bson_util::serialize_duration_option_as_int_secs"
     )]
     pub expire_after_seconds: Option<Duration>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies how strictly the database should apply validation rules to existing documents during
@@ -176,6 +196,31 @@ pub enum TimeseriesGranularity {
     Hours,
 }
 
+/// Specifies the options to a
+/// [`Database::create_view`](../struct.Database.html#method.create_view) operation.
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct CreateViewOptions {
+    /// The default collation for the view.
+    pub collation: Option<Collation>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
+}
+
 /// Specifies the options to a [`Database::drop`](../struct.Database.html#method.drop) operation.
 #[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -184,6 +229,13 @@ pub enum TimeseriesGranularity {
 pub struct DropDatabaseOptions {
     /// The write concern for the operation.
     pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -204,6 +256,18 @@ pub struct ListCollectionsOptions {
         rename(serialize = "cursor")
     )]
     pub batch_size: Option<u32>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
 }
 
 /// Specifies the options to a
@@ -216,4 +280,445 @@ pub struct ListDatabasesOptions {
     /// Determines which databases to return based on the user's access privileges. This option is
     /// only supported on server versions 4.0.5+.
     pub authorized_databases: Option<bool>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a [`Database::stats`](../struct.Database.html#method.stats)
+/// operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DatabaseStatsOptions {
+    /// The factor to divide the reported sizes by, e.g. 1024 to report sizes in kilobytes.
+    /// Defaults to 1.
+    #[serde(serialize_with = "bson_util::serialize_u32_option_as_i32")]
+    pub scale: Option<u32>,
+
+    /// The criteria used to select a server for this operation.
+    ///
+    /// If none specified, the default set on the database will be used.
+    #[serde(skip_serializing)]
+    pub selection_criteria: Option<SelectionCriteria>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis",
+        skip_serializing
+    )]
+    pub timeout: Option<Duration>,
+}
+
+/// A role, identified by its name and the database it is defined in.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Role {
+    /// The name of the role.
+    pub role: String,
+
+    /// The database the role is defined in.
+    pub db: String,
+}
+
+impl Role {
+    /// Creates a `Role` referring to the role named `role` in the database `db`.
+    pub fn new(role: impl Into<String>, db: impl Into<String>) -> Self {
+        Self {
+            role: role.into(),
+            db: db.into(),
+        }
+    }
+}
+
+/// Restricts the client and server IP addresses a user or role may authenticate from and to.
+///
+/// See the documentation [here](https://www.mongodb.com/docs/manual/reference/method/db.createUser/#authentication-restrictions)
+/// for more information.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct AuthenticationRestriction {
+    /// The IP addresses or CIDR ranges the client must connect from.
+    pub client_source: Option<Vec<String>>,
+
+    /// The IP addresses or CIDR ranges the client must connect to.
+    pub server_address: Option<Vec<String>>,
+}
+
+/// A set of actions allowed on a resource, as granted by a role.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Privilege {
+    /// The resource the actions are allowed on.
+    pub resource: PrivilegeResource,
+
+    /// The allowed actions, e.g. `"find"` or `"insert"`.
+    pub actions: Vec<String>,
+}
+
+impl Privilege {
+    /// Creates a `Privilege` allowing `actions` on `resource`.
+    pub fn new(
+        resource: PrivilegeResource,
+        actions: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            resource,
+            actions: actions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The resource a [`Privilege`] applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PrivilegeResource {
+    /// A collection. An empty `db` matches every database, and an empty `collection` matches
+    /// every collection in the database.
+    Collection {
+        /// The name of the database.
+        db: String,
+
+        /// The name of the collection.
+        collection: String,
+    },
+
+    /// The cluster as a whole, for cluster-wide actions such as `shutdown`.
+    Cluster,
+
+    /// Every resource in the deployment.
+    AnyResource,
+}
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PrivilegeResourceBody {
+    db: Option<String>,
+    collection: Option<String>,
+    cluster: Option<bool>,
+    any_resource: Option<bool>,
+}
+
+impl Serialize for PrivilegeResource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let body = match self {
+            Self::Collection { db, collection } => PrivilegeResourceBody {
+                db: Some(db.clone()),
+                collection: Some(collection.clone()),
+                cluster: None,
+                any_resource: None,
+            },
+            Self::Cluster => PrivilegeResourceBody {
+                db: None,
+                collection: None,
+                cluster: Some(true),
+                any_resource: None,
+            },
+            Self::AnyResource => PrivilegeResourceBody {
+                db: None,
+                collection: None,
+                cluster: None,
+                any_resource: Some(true),
+            },
+        };
+        body.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PrivilegeResource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let body = PrivilegeResourceBody::deserialize(deserializer)?;
+        if body.cluster == Some(true) {
+            Ok(Self::Cluster)
+        } else if body.any_resource == Some(true) {
+            Ok(Self::AnyResource)
+        } else {
+            Ok(Self::Collection {
+                db: body.db.unwrap_or_default(),
+                collection: body.collection.unwrap_or_default(),
+            })
+        }
+    }
+}
+
+/// Specifies the options to a
+/// [`Database::create_user`](../struct.Database.html#method.create_user) operation.
+#[skip_serializing_none]
+#[derive(Clone, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct CreateUserOptions {
+    /// The user's password. This must not be set for users authenticating with an external
+    /// mechanism such as X.509 or Kerberos.
+    #[serde(rename = "pwd")]
+    pub password: Option<String>,
+
+    /// Arbitrary information to store with the user.
+    pub custom_data: Option<Document>,
+
+    /// The roles granted to the user. Defaults to none.
+    pub roles: Option<Vec<Role>>,
+
+    /// The restrictions on the addresses the user may authenticate from and to.
+    pub authentication_restrictions: Option<Vec<AuthenticationRestriction>>,
+
+    /// The SCRAM mechanisms to create credentials for. Defaults to every SCRAM mechanism the
+    /// server supports.
+    #[serde(serialize_with = "bson_util::serialize_auth_mechanisms")]
+    pub mechanisms: Option<Vec<AuthMechanism>>,
+
+    /// Whether the server should digest the password, rather than the client. Defaults to true.
+    pub digest_password: Option<bool>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+impl fmt::Debug for CreateUserOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUserOptions")
+            .field("password", &self.password.as_ref().map(|_| "REDACTED"))
+            .field("custom_data", &self.custom_data)
+            .field("roles", &self.roles)
+            .field(
+                "authentication_restrictions",
+                &self.authentication_restrictions,
+            )
+            .field("mechanisms", &self.mechanisms)
+            .field("digest_password", &self.digest_password)
+            .field("write_concern", &self.write_concern)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Specifies the options to a
+/// [`Database::update_user`](../struct.Database.html#method.update_user) operation. Fields that are
+/// not set are left unchanged, and fields that are set replace the user's existing values.
+#[skip_serializing_none]
+#[derive(Clone, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct UpdateUserOptions {
+    /// The user's new password.
+    #[serde(rename = "pwd")]
+    pub password: Option<String>,
+
+    /// Arbitrary information to store with the user.
+    pub custom_data: Option<Document>,
+
+    /// The roles granted to the user.
+    pub roles: Option<Vec<Role>>,
+
+    /// The restrictions on the addresses the user may authenticate from and to.
+    pub authentication_restrictions: Option<Vec<AuthenticationRestriction>>,
+
+    /// The SCRAM mechanisms to keep credentials for. If `password` is also set, credentials are
+    /// regenerated for these mechanisms.
+    #[serde(serialize_with = "bson_util::serialize_auth_mechanisms")]
+    pub mechanisms: Option<Vec<AuthMechanism>>,
+
+    /// Whether the server should digest the password, rather than the client. Defaults to true.
+    pub digest_password: Option<bool>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+impl fmt::Debug for UpdateUserOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateUserOptions")
+            .field("password", &self.password.as_ref().map(|_| "REDACTED"))
+            .field("custom_data", &self.custom_data)
+            .field("roles", &self.roles)
+            .field(
+                "authentication_restrictions",
+                &self.authentication_restrictions,
+            )
+            .field("mechanisms", &self.mechanisms)
+            .field("digest_password", &self.digest_password)
+            .field("write_concern", &self.write_concern)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Specifies the options to a
+/// [`Database::drop_user`](../struct.Database.html#method.drop_user) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DropUserOptions {
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Database::users_info`](../struct.Database.html#method.users_info) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct UsersInfoOptions {
+    /// Only return the users matching this filter.
+    pub filter: Option<Document>,
+
+    /// Whether to include the users' credentials in the results.
+    pub show_credentials: Option<bool>,
+
+    /// Whether to include the privileges the users have through their roles in the results.
+    pub show_privileges: Option<bool>,
+
+    /// The criteria used to select a server for this operation.
+    ///
+    /// If none specified, the default set on the database will be used.
+    #[serde(skip)]
+    pub selection_criteria: Option<SelectionCriteria>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Database::grant_roles`](../struct.Database.html#method.grant_roles) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct GrantRolesOptions {
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Database::create_role`](../struct.Database.html#method.create_role) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct CreateRoleOptions {
+    /// The privileges granted directly by the role. Defaults to none.
+    pub privileges: Option<Vec<Privilege>>,
+
+    /// The roles the role inherits privileges from. Defaults to none.
+    pub roles: Option<Vec<Role>>,
+
+    /// The restrictions on the addresses users with the role may authenticate from and to.
+    pub authentication_restrictions: Option<Vec<AuthenticationRestriction>>,
+
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Database::drop_role`](../struct.Database.html#method.drop_role) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct DropRoleOptions {
+    /// The write concern for the operation.
+    pub write_concern: Option<WriteConcern>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+}
+
+/// Specifies the options to a
+/// [`Database::roles_info`](../struct.Database.html#method.roles_info) operation.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, TypedBuilder, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct RolesInfoOptions {
+    /// Whether to include the privileges granted by the roles in the results.
+    pub show_privileges: Option<bool>,
+
+    /// Whether to include the built-in roles, such as `read` and `readWrite`, in the results.
+    pub show_builtin_roles: Option<bool>,
+
+    /// The criteria used to select a server for this operation.
+    ///
+    /// If none specified, the default set on the database will be used.
+    #[serde(skip)]
+    pub selection_criteria: Option<SelectionCriteria>,
+
+    /// The maximum amount of time the operation may take in total, including any retries.
+    ///
+    /// If none is specified, the timeout defined on the object executing this operation will be
+    /// used.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
 }
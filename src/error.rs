@@ -10,7 +10,7 @@ use bson::Bson;
 use serde::Deserialize;
 use thiserror::Error;
 
-use crate::{bson::Document, options::ServerAddress};
+use crate::{bson::Document, options::ServerAddress, results::BulkWriteResult};
 
 const RECOVERING_CODES: [i32; 5] = [11600, 11602, 13436, 189, 91];
 const NOTMASTER_CODES: [i32; 3] = [10107, 13435, 10058];
@@ -21,6 +21,11 @@ const RETRYABLE_WRITE_CODES: [i32; 12] = [
     11600, 11602, 10107, 13435, 13436, 189, 91, 7, 6, 89, 9001, 262,
 ];
 const UNKNOWN_TRANSACTION_COMMIT_RESULT_LABEL_CODES: [i32; 3] = [50, 64, 91];
+const RESUMABLE_CHANGE_STREAM_CODES: [i32; 18] = [
+    6, 7, 89, 91, 189, 262, 9001, 10107, 11600, 11602, 13435, 13436, 63, 150, 13388, 234, 133, 43,
+];
+const RESUMABLE_CHANGE_STREAM_ERROR: &str = "ResumableChangeStreamError";
+const CURSOR_NOT_FOUND_CODE: i32 = 43;
 
 /// Retryable write error label. This label will be added to an error when the error is
 /// write-retryable.
@@ -105,6 +110,13 @@ impl Error {
         .into()
     }
 
+    pub(crate) fn timeout(message: impl Into<String>) -> Error {
+        ErrorKind::Timeout {
+            message: message.into(),
+        }
+        .into()
+    }
+
     pub(crate) fn is_state_change_error(&self) -> bool {
         self.is_recovering() || self.is_not_master()
     }
@@ -130,6 +142,10 @@ impl Error {
         matches!(self.kind.as_ref(), ErrorKind::ServerSelection { .. })
     }
 
+    pub(crate) fn is_timeout(&self) -> bool {
+        matches!(self.kind.as_ref(), ErrorKind::Timeout { .. })
+    }
+
     /// Whether a read operation should be retried if this error occurs.
     pub(crate) fn is_read_retryable(&self) -> bool {
         if self.is_network_error() {
@@ -141,6 +157,23 @@ impl Error {
         }
     }
 
+    /// Whether a change stream should attempt to resume after this error occurs. If
+    /// max_wire_version indicates a 4.4+ server, only errors with the "ResumableChangeStreamError"
+    /// label are resumable; older servers are checked against a list of codes. Network errors and
+    /// CursorNotFound errors are always resumable.
+    pub(crate) fn is_resumable(&self, max_wire_version: Option<i32>) -> bool {
+        if self.is_network_error() || self.code() == Some(CURSOR_NOT_FOUND_CODE) {
+            return true;
+        }
+        if max_wire_version.unwrap_or(0) > 8 {
+            return self.contains_label(RESUMABLE_CHANGE_STREAM_ERROR);
+        }
+        match self.code() {
+            Some(code) => RESUMABLE_CHANGE_STREAM_CODES.contains(&code),
+            None => false,
+        }
+    }
+
     pub(crate) fn is_write_retryable(&self) -> bool {
         self.contains_label(RETRYABLE_WRITE_ERROR)
     }
@@ -176,6 +209,11 @@ impl Error {
         }
     }
 
+    /// Whether this error is a MaxTimeMSExpired error.
+    pub(crate) fn is_max_time_ms_expired_error(&self) -> bool {
+        self.code() == Some(50)
+    }
+
     /// Whether an error originated from the server.
     pub(crate) fn is_server_error(&self) -> bool {
         matches!(
@@ -249,7 +287,7 @@ impl Error {
             ErrorKind::BulkWrite(BulkWriteFailure {
                 write_concern_error,
                 write_errors,
-                inserted_ids: _,
+                ..
             }) => {
                 let mut msg = "".to_string();
                 if let Some(wc_error) = write_concern_error {
@@ -348,6 +386,11 @@ impl From<bson::raw::Error> for ErrorKind {
 
 impl From<std::io::Error> for ErrorKind {
     fn from(err: std::io::Error) -> Self {
+        // Driver errors may be wrapped in an `io::Error` when surfaced through the `AsyncRead` and
+        // `AsyncWrite` implementations of the GridFS streams, so unwrap them here.
+        if let Some(error) = err.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+            return *error.kind.clone();
+        }
         Self::Io(Arc::new(err))
     }
 }
@@ -440,6 +483,25 @@ pub enum ErrorKind {
     #[error("The server does not support a database operation: {message}")]
     #[non_exhaustive]
     IncompatibleServer { message: String },
+
+    /// An error occurred during a GridFS operation.
+    #[error("An error occurred during a GridFS operation: {0:?}")]
+    GridFs(GridFsErrorKind),
+
+    /// An error occurred during client-side field level encryption or decryption.
+    #[error("An error occurred during client-side encryption: {message}")]
+    #[non_exhaustive]
+    Encryption { message: String },
+
+    /// The operation did not complete within the amount of time allowed by its `timeout` option.
+    #[error("{message}")]
+    #[non_exhaustive]
+    Timeout { message: String },
+
+    /// The [`Client`](../struct.Client.html) was shut down before the operation could be
+    /// performed.
+    #[error("The Client has been shut down")]
+    Shutdown,
 }
 
 impl ErrorKind {
@@ -571,8 +633,18 @@ pub struct BulkWriteFailure {
     /// The error that occurred on account of write concern failure.
     pub write_concern_error: Option<WriteConcernError>,
 
+    /// The results of the writes that succeeded, if this failure was returned from a
+    /// [`Collection::bulk_write`](crate::Collection::bulk_write) operation. The indexes in
+    /// `write_errors` refer to the write models passed to that operation.
+    #[serde(skip)]
+    pub partial_result: Option<Box<BulkWriteResult>>,
+
     #[serde(skip)]
     pub(crate) inserted_ids: HashMap<usize, Bson>,
+
+    /// The number of writes attempted in the batch that produced this failure.
+    #[serde(skip)]
+    pub(crate) batch_size: usize,
 }
 
 impl BulkWriteFailure {
@@ -580,7 +652,9 @@ impl BulkWriteFailure {
         BulkWriteFailure {
             write_errors: None,
             write_concern_error: None,
+            partial_result: None,
             inserted_ids: Default::default(),
+            batch_size: 0,
         }
     }
 }
@@ -618,6 +692,47 @@ impl WriteFailure {
     }
 }
 
+/// An error that occurred during a GridFS operation.
+#[derive(Clone, Debug)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum GridFsErrorKind {
+    /// The file with the given identifier was not found.
+    #[non_exhaustive]
+    FileNotFound { identifier: GridFsFileIdentifier },
+
+    /// The file with the given revision was not found.
+    #[non_exhaustive]
+    RevisionNotFound { revision: i32 },
+
+    /// The chunk at index `n` was missing.
+    #[non_exhaustive]
+    MissingChunk { n: u32 },
+
+    /// The chunk at index `n` was not the expected size.
+    #[non_exhaustive]
+    WrongSizeChunk {
+        actual_size: usize,
+        expected_size: u32,
+        n: u32,
+    },
+
+    /// An operation was attempted on a `GridFsUploadStream` that has already been closed or
+    /// aborted.
+    UploadStreamClosed,
+}
+
+/// An identifier for a file stored in a GridFS bucket.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum GridFsFileIdentifier {
+    /// The name of the file. Not guaranteed to be unique.
+    Filename(String),
+
+    /// The file's unique `_id` field.
+    Id(Bson),
+}
+
 /// Translates ErrorKind::BulkWriteError cases to ErrorKind::WriteErrors, leaving all other errors
 /// untouched.
 pub(crate) fn convert_bulk_errors(error: Error) -> Error {
@@ -188,6 +188,10 @@ pub struct ConnectionCheckoutFailedEvent {
 
     /// The reason a connection was unable to be checked out.
     pub reason: ConnectionCheckoutFailedReason,
+
+    /// How long was spent trying to check out a connection before the attempt failed.
+    #[serde(skip)]
+    pub duration: Duration,
 }
 
 /// The reasons a connection may not be able to be checked out.
@@ -201,6 +205,9 @@ pub enum ConnectionCheckoutFailedReason {
     /// An error occurred while trying to establish a connection (e.g. during the handshake or
     /// authentication).
     ConnectionError,
+
+    /// The pool was closed, e.g. by [`Client::shutdown`](crate::Client::shutdown).
+    PoolClosed,
 }
 
 /// Event emitted when a connection is successfully checked out.
@@ -217,6 +224,11 @@ pub struct ConnectionCheckedOutEvent {
     /// to identify other events related to this connection.
     #[serde(default = "default_connection_id")]
     pub connection_id: u32,
+
+    /// How long was spent waiting to check out the connection, including the time spent
+    /// establishing it if a new connection had to be created.
+    #[serde(skip)]
+    pub duration: Duration,
 }
 
 /// Event emitted when a connection is checked back into a connection pool.
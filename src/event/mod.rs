@@ -3,3 +3,70 @@
 pub mod cmap;
 pub mod command;
 pub mod sdam;
+
+use std::sync::Arc;
+
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::{
+    client::options::ClientOptions,
+    event::{cmap::CmapEventHandler, command::CommandEventHandler, sdam::SdamEventHandler},
+};
+
+/// Passes the handler that command events should be emitted to, if any, to `emit`. When tracing
+/// events for commands are enabled, this is an emitter that also forwards to the user's handler.
+pub(crate) fn emit_command_event(
+    options: &ClientOptions,
+    emit: impl FnOnce(&dyn CommandEventHandler),
+) {
+    let handler = options.command_event_handler.as_deref();
+
+    #[cfg(feature = "tracing")]
+    if trace::command_events_enabled() {
+        return emit(&trace::CommandTracingEventEmitter {
+            inner: handler,
+            max_document_length: options.tracing_max_document_length_bytes,
+        });
+    }
+
+    if let Some(handler) = handler {
+        emit(handler);
+    }
+}
+
+/// Passes the handler that SDAM events should be emitted to, if any, to `emit`. When tracing
+/// events for topology monitoring are enabled, this is an emitter that also forwards to the user's
+/// handler.
+pub(crate) fn emit_sdam_event(options: &ClientOptions, emit: impl FnOnce(&dyn SdamEventHandler)) {
+    let handler = options.sdam_event_handler.as_deref();
+
+    #[cfg(feature = "tracing")]
+    if trace::topology_events_enabled() {
+        return emit(&trace::TopologyTracingEventEmitter {
+            inner: handler,
+            max_document_length: options.tracing_max_document_length_bytes,
+        });
+    }
+
+    if let Some(handler) = handler {
+        emit(handler);
+    }
+}
+
+/// Passes the handler that CMAP events should be emitted to, if any, to `emit`. When tracing
+/// events for connections are enabled, this is an emitter that also forwards to the given handler.
+pub(crate) fn emit_cmap_event(
+    handler: Option<&Arc<dyn CmapEventHandler>>,
+    emit: impl FnOnce(&dyn CmapEventHandler),
+) {
+    let handler = handler.map(|handler| handler.as_ref());
+
+    #[cfg(feature = "tracing")]
+    if trace::connection_events_enabled() {
+        return emit(&trace::ConnectionTracingEventEmitter { inner: handler });
+    }
+
+    if let Some(handler) = handler {
+        emit(handler);
+    }
+}
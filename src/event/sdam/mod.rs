@@ -99,7 +99,10 @@ pub struct TopologyClosedEvent {
 pub struct ServerHeartbeatStartedEvent {
     /// The address of the server.
     pub server_address: ServerAddress,
-    // TODO RUST-560 add awaited field
+
+    /// Whether the heartbeat was awaited, i.e. the server was using the streaming protocol and
+    /// may have delayed its reply until its state changed.
+    pub awaited: bool,
 }
 
 /// Published when a server monitor's `hello` or legacy hello command succeeds.
@@ -114,7 +117,10 @@ pub struct ServerHeartbeatSucceededEvent {
 
     /// The address of the server.
     pub server_address: ServerAddress,
-    // TODO RUST-560 add awaited field
+
+    /// Whether the heartbeat was awaited, i.e. the server was using the streaming protocol and
+    /// may have delayed its reply until its state changed.
+    pub awaited: bool,
 }
 
 /// Published when a server monitor's `hello` or legacy hello command fails.
@@ -129,7 +135,10 @@ pub struct ServerHeartbeatFailedEvent {
 
     /// The address of the server.
     pub server_address: ServerAddress,
-    // TODO RUST-560 add awaited field
+
+    /// Whether the heartbeat was awaited, i.e. the server was using the streaming protocol and
+    /// may have delayed its reply until its state changed.
+    pub awaited: bool,
 }
 
 /// Applications can implement this trait to specify custom logic to run on each SDAM event sent
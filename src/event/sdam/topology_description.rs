@@ -3,7 +3,7 @@ use std::{collections::HashMap, fmt};
 use crate::{
     bson::oid::ObjectId,
     client::options::ServerAddress,
-    sdam::{ServerInfo, TopologyType},
+    sdam::{ServerInfo, ServerType, TopologyType},
     selection_criteria::{ReadPreference, SelectionCriteria},
 };
 
@@ -69,6 +69,14 @@ impl TopologyDescription {
         self.description.compatibility_error.as_ref()
     }
 
+    /// Gets the primary of the topology, if it is a replica set with a known primary.
+    pub fn primary(&self) -> Option<ServerInfo<'_>> {
+        self.description
+            .servers_with_type(&[ServerType::RsPrimary])
+            .next()
+            .map(ServerInfo::new_borrowed)
+    }
+
     /// Gets the servers in the topology.
     pub fn servers(&self) -> HashMap<&ServerAddress, ServerInfo> {
         self.description
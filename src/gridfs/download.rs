@@ -0,0 +1,170 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_io::AsyncRead;
+use futures_util::{future::BoxFuture, FutureExt, StreamExt};
+
+use super::{Chunk, FilesCollectionDocument};
+use crate::{
+    bson::doc,
+    error::{ErrorKind, GridFsErrorKind, Result},
+    options::FindOptions,
+    Collection,
+    Cursor,
+};
+
+/// A stream from which the contents of a file stored in a [`GridFsBucket`](super::GridFsBucket)
+/// can be read. A `GridFsDownloadStream` can be obtained by calling
+/// [`GridFsBucket::open_download_stream`](super::GridFsBucket::open_download_stream) or
+/// [`GridFsBucket::open_download_stream_by_name`](super::GridFsBucket::open_download_stream_by_name).
+///
+/// `GridFsDownloadStream` implements [`futures_io::AsyncRead`].
+pub struct GridFsDownloadStream {
+    file: FilesCollectionDocument,
+    state: State,
+    next_n: u32,
+}
+
+type GetNextChunkResult = (Result<Vec<u8>>, Box<Cursor<Chunk>>);
+
+enum State {
+    /// There is no outstanding request for a chunk. `buffer` contains the unread portion of the
+    /// most recently retrieved chunk.
+    Idle {
+        buffer: Vec<u8>,
+        cursor: Box<Cursor<Chunk>>,
+    },
+    /// The next chunk is being retrieved from the server.
+    Busy(BoxFuture<'static, GetNextChunkResult>),
+    /// All chunks have been read, or an error occurred.
+    Done,
+}
+
+impl std::fmt::Debug for GridFsDownloadStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridFsDownloadStream")
+            .field("file", &self.file)
+            .finish()
+    }
+}
+
+impl GridFsDownloadStream {
+    pub(super) async fn new(
+        file: FilesCollectionDocument,
+        chunks: &Collection<Chunk>,
+    ) -> Result<Self> {
+        // The number and sizes of the chunks are computed from the chunk size, so a files
+        // document with a chunk size of 0 can't be read.
+        if file.chunk_size == 0 {
+            return Err(ErrorKind::InvalidResponse {
+                message: format!(
+                    "the files collection document for file {} has a chunkSize of 0",
+                    file.id
+                ),
+            }
+            .into());
+        }
+
+        let state = if file.length == 0 {
+            State::Done
+        } else {
+            let options = FindOptions::builder().sort(doc! { "n": 1 }).build();
+            let cursor = chunks
+                .find(doc! { "files_id": file.id.clone() }, options)
+                .await?;
+            State::Idle {
+                buffer: Vec::new(),
+                cursor: Box::new(cursor),
+            }
+        };
+
+        Ok(Self {
+            file,
+            state,
+            next_n: 0,
+        })
+    }
+
+    /// Gets the [`FilesCollectionDocument`] describing the file being downloaded.
+    pub fn file(&self) -> &FilesCollectionDocument {
+        &self.file
+    }
+}
+
+impl AsyncRead for GridFsDownloadStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.as_mut().get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            match std::mem::replace(&mut this.state, State::Done) {
+                State::Idle { mut buffer, cursor } => {
+                    if !buffer.is_empty() {
+                        let bytes_read = std::cmp::min(buf.len(), buffer.len());
+                        buf[..bytes_read].copy_from_slice(&buffer[..bytes_read]);
+                        buffer.drain(..bytes_read);
+                        this.state = State::Idle { buffer, cursor };
+                        return Poll::Ready(Ok(bytes_read));
+                    }
+
+                    if this.next_n >= this.file.n() {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    let n = this.next_n;
+                    let expected_size = this.file.expected_chunk_length(n);
+                    this.state = State::Busy(get_next_chunk(cursor, n, expected_size).boxed());
+                }
+                State::Busy(mut future) => match future.poll_unpin(cx) {
+                    Poll::Ready((result, cursor)) => match result {
+                        Ok(buffer) => {
+                            this.next_n += 1;
+                            this.state = State::Idle { buffer, cursor };
+                        }
+                        Err(e) => return Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, e))),
+                    },
+                    Poll::Pending => {
+                        this.state = State::Busy(future);
+                        return Poll::Pending;
+                    }
+                },
+                State::Done => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
+}
+
+async fn get_next_chunk(
+    mut cursor: Box<Cursor<Chunk>>,
+    n: u32,
+    expected_size: u32,
+) -> GetNextChunkResult {
+    let result = match cursor.next().await {
+        Some(Ok(chunk)) => {
+            if chunk.n != n {
+                Err(ErrorKind::GridFs(GridFsErrorKind::MissingChunk { n }).into())
+            } else if chunk.data.bytes.len() != expected_size as usize {
+                Err(ErrorKind::GridFs(GridFsErrorKind::WrongSizeChunk {
+                    actual_size: chunk.data.bytes.len(),
+                    expected_size,
+                    n,
+                })
+                .into())
+            } else {
+                Ok(chunk.data.bytes)
+            }
+        }
+        Some(Err(e)) => Err(e),
+        None => Err(ErrorKind::GridFs(GridFsErrorKind::MissingChunk { n }).into()),
+    };
+    (result, cursor)
+}
@@ -0,0 +1,577 @@
+//! Contains the functionality for GridFS, a specification for storing and retrieving files that
+//! exceed the 16 MiB BSON document size limit by dividing them into chunks.
+//!
+//! Files are stored in two collections within a bucket: the `<bucket_name>.files` collection
+//! contains a [`FilesCollectionDocument`] describing each file, and the `<bucket_name>.chunks`
+//! collection contains the file contents, split into documents of at most `chunk_size_bytes`
+//! bytes each.
+//!
+//! ```rust
+//! # #[cfg(not(feature = "sync"))]
+//! # use mongodb::{Client, error::Result};
+//! # #[cfg(not(feature = "sync"))]
+//! # use futures::{AsyncReadExt, AsyncWriteExt};
+//! #
+//! # #[cfg(not(feature = "sync"))]
+//! # async fn func() -> Result<()> {
+//! # let client = Client::with_uri_str("mongodb://example.com").await?;
+//! let bucket = client.database("db").gridfs_bucket(None)?;
+//!
+//! let mut upload_stream = bucket.open_upload_stream("report.pdf", None)?;
+//! upload_stream.write_all(b"file contents").await?;
+//! upload_stream.close().await?;
+//!
+//! let mut download_stream = bucket.open_download_stream(upload_stream.id().clone()).await?;
+//! let mut contents = Vec::new();
+//! download_stream.read_to_end(&mut contents).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! See the documentation [here](https://docs.mongodb.com/manual/core/gridfs/) for more details.
+
+mod download;
+pub mod options;
+mod upload;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use futures_io::{AsyncRead, AsyncWrite};
+use futures_util::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    stream::TryStreamExt,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bson::{doc, oid::ObjectId, Binary, Bson, DateTime, Document},
+    bson_util,
+    concern::{ReadConcern, WriteConcern},
+    cursor::Cursor,
+    error::{Error, ErrorKind, GridFsErrorKind, GridFsFileIdentifier, Result},
+    options::{
+        CollectionOptions,
+        FindOneOptions,
+        FindOptions,
+        IndexOptions,
+        ReadPreference,
+        SelectionCriteria,
+    },
+    Collection,
+    Database,
+    IndexModel,
+};
+use options::{
+    GridFsBucketOptions,
+    GridFsDownloadByNameOptions,
+    GridFsFindOptions,
+    GridFsUploadOptions,
+};
+
+pub use download::GridFsDownloadStream;
+pub use upload::GridFsUploadStream;
+
+const DEFAULT_BUCKET_NAME: &str = "fs";
+const DEFAULT_CHUNK_SIZE_BYTES: u32 = 255 * 1024;
+
+/// A model for the documents stored in the chunks collection.
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct Chunk {
+    #[serde(rename = "_id")]
+    id: ObjectId,
+    files_id: Bson,
+    #[serde(serialize_with = "bson::serde_helpers::serialize_u32_as_i32")]
+    n: u32,
+    data: Binary,
+}
+
+/// A model for the documents stored in a GridFS bucket's files collection.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FilesCollectionDocument {
+    /// The file's unique identifier.
+    #[serde(rename = "_id")]
+    pub id: Bson,
+
+    /// The length of the file in bytes.
+    #[serde(
+        serialize_with = "bson::serde_helpers::serialize_u64_as_i64",
+        deserialize_with = "bson_util::deserialize_u64_from_bson_number"
+    )]
+    pub length: u64,
+
+    /// The size of the file's chunks in bytes.
+    #[serde(serialize_with = "bson::serde_helpers::serialize_u32_as_i32")]
+    pub chunk_size: u32,
+
+    /// The time at which the file was uploaded.
+    pub upload_date: DateTime,
+
+    /// The name of the file.
+    pub filename: Option<String>,
+
+    /// User-provided metadata associated with the file.
+    pub metadata: Option<Document>,
+}
+
+/// Returns an error if `chunk_size_bytes` is 0, since a file cannot be split into empty chunks.
+fn validate_chunk_size_bytes(chunk_size_bytes: Option<u32>) -> Result<()> {
+    if chunk_size_bytes == Some(0) {
+        return Err(Error::invalid_argument(
+            "chunk_size_bytes must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+impl FilesCollectionDocument {
+    /// The number of chunks the file is divided into. Must not be called if `chunk_size` is 0.
+    fn n(&self) -> u32 {
+        let chunk_size = self.chunk_size as u64;
+        ((self.length + chunk_size - 1) / chunk_size) as u32
+    }
+
+    /// The expected size of the chunk at index `n`.
+    fn expected_chunk_length(&self, n: u32) -> u32 {
+        let remainder = (self.length % self.chunk_size as u64) as u32;
+        if n + 1 == self.n() && remainder != 0 {
+            remainder
+        } else {
+            self.chunk_size
+        }
+    }
+}
+
+/// A `GridFsBucket` provides the functionality for storing and retrieving files larger than the
+/// BSON document size limit in a MongoDB database. A `GridFsBucket` can be obtained by calling
+/// [`Database::gridfs_bucket`](crate::Database::gridfs_bucket).
+///
+/// `GridFsBucket` uses [`std::sync::Arc`] internally, so it can safely be shared across threads or
+/// async tasks.
+#[derive(Clone, Debug)]
+pub struct GridFsBucket {
+    inner: Arc<GridFsBucketInner>,
+}
+
+#[derive(Debug)]
+struct GridFsBucketInner {
+    options: GridFsBucketOptions,
+    files: Collection<FilesCollectionDocument>,
+    chunks: Collection<Chunk>,
+    created_indexes: AtomicBool,
+}
+
+impl GridFsBucket {
+    pub(crate) fn new(db: Database, mut options: GridFsBucketOptions) -> Result<GridFsBucket> {
+        validate_chunk_size_bytes(options.chunk_size_bytes)?;
+
+        if options.read_concern.is_none() {
+            options.read_concern = db.read_concern().cloned();
+        }
+        if options.write_concern.is_none() {
+            options.write_concern = db.write_concern().cloned();
+        }
+        if options.selection_criteria.is_none() {
+            options.selection_criteria = db.selection_criteria().cloned();
+        }
+
+        let bucket_name = options
+            .bucket_name
+            .as_deref()
+            .unwrap_or(DEFAULT_BUCKET_NAME);
+        let collection_options = CollectionOptions::builder()
+            .read_concern(options.read_concern.clone())
+            .write_concern(options.write_concern.clone())
+            .selection_criteria(options.selection_criteria.clone())
+            .build();
+        let files = db.collection_with_options::<FilesCollectionDocument>(
+            &format!("{}.files", bucket_name),
+            collection_options.clone(),
+        );
+        let chunks = db.collection_with_options::<Chunk>(
+            &format!("{}.chunks", bucket_name),
+            collection_options,
+        );
+
+        Ok(GridFsBucket {
+            inner: Arc::new(GridFsBucketInner {
+                options,
+                files,
+                chunks,
+                created_indexes: AtomicBool::new(false),
+            }),
+        })
+    }
+
+    /// Gets the read concern of the `GridFsBucket`.
+    pub fn read_concern(&self) -> Option<&ReadConcern> {
+        self.inner.options.read_concern.as_ref()
+    }
+
+    /// Gets the write concern of the `GridFsBucket`.
+    pub fn write_concern(&self) -> Option<&WriteConcern> {
+        self.inner.options.write_concern.as_ref()
+    }
+
+    /// Gets the selection criteria of the `GridFsBucket`.
+    pub fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.inner.options.selection_criteria.as_ref()
+    }
+
+    fn chunk_size_bytes(&self) -> u32 {
+        self.inner
+            .options
+            .chunk_size_bytes
+            .unwrap_or(DEFAULT_CHUNK_SIZE_BYTES)
+    }
+
+    fn files(&self) -> &Collection<FilesCollectionDocument> {
+        &self.inner.files
+    }
+
+    fn chunks(&self) -> &Collection<Chunk> {
+        &self.inner.chunks
+    }
+
+    /// Opens a [`GridFsUploadStream`] that the application can write the contents of the file to.
+    /// The driver generates a unique [`ObjectId`] for the file, which can be retrieved via
+    /// [`GridFsUploadStream::id`].
+    ///
+    /// The file is not visible in the bucket until the stream has been closed.
+    ///
+    /// An [`ErrorKind::InvalidArgument`] error is returned if `chunk_size_bytes` is 0.
+    pub fn open_upload_stream(
+        &self,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<GridFsUploadStream> {
+        self.open_upload_stream_with_id(ObjectId::new().into(), filename, options)
+    }
+
+    /// Opens a [`GridFsUploadStream`] that the application can write the contents of the file to.
+    /// The provided `id` will be used as the `_id` of the file, and it must be unique within the
+    /// bucket.
+    ///
+    /// The file is not visible in the bucket until the stream has been closed.
+    ///
+    /// An [`ErrorKind::InvalidArgument`] error is returned if `chunk_size_bytes` is 0.
+    pub fn open_upload_stream_with_id(
+        &self,
+        id: Bson,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<GridFsUploadStream> {
+        let options = options.into().unwrap_or_default();
+        validate_chunk_size_bytes(options.chunk_size_bytes)?;
+        let chunk_size_bytes = options
+            .chunk_size_bytes
+            .unwrap_or_else(|| self.chunk_size_bytes());
+        Ok(GridFsUploadStream::new(
+            self.clone(),
+            id,
+            filename.as_ref().to_string(),
+            chunk_size_bytes,
+            options.metadata,
+        ))
+    }
+
+    /// Uploads a user file to the bucket, reading its contents from `source`. The driver generates
+    /// a unique [`ObjectId`] for the file, which is returned.
+    pub async fn upload_from_futures_0_3_reader<R>(
+        &self,
+        filename: impl AsRef<str>,
+        source: R,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<ObjectId>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let id = ObjectId::new();
+        self.upload_from_futures_0_3_reader_with_id(id.into(), filename, source, options)
+            .await?;
+        Ok(id)
+    }
+
+    /// Uploads a user file to the bucket with the given `id`, reading its contents from `source`.
+    /// The `id` must be unique within the bucket.
+    pub async fn upload_from_futures_0_3_reader_with_id<R>(
+        &self,
+        id: Bson,
+        filename: impl AsRef<str>,
+        mut source: R,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut upload_stream = self.open_upload_stream_with_id(id, filename, options)?;
+        let mut buf = vec![0u8; upload_stream.chunk_size_bytes() as usize];
+        loop {
+            let result = match source.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(bytes_read) => upload_stream.write_all(&buf[..bytes_read]).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                // The error that caused the upload to fail is more useful than any error from
+                // cleaning up after it.
+                let _: Result<()> = upload_stream.abort().await;
+                return Err(e.into());
+            }
+        }
+        upload_stream.close().await?;
+
+        Ok(())
+    }
+
+    /// Opens and returns a [`GridFsDownloadStream`] from which the application can read the
+    /// contents of the file with the given `id`.
+    pub async fn open_download_stream(&self, id: Bson) -> Result<GridFsDownloadStream> {
+        let file = self
+            .files()
+            .find_one(doc! { "_id": id.clone() }, None)
+            .await?
+            .ok_or_else(|| {
+                ErrorKind::GridFs(GridFsErrorKind::FileNotFound {
+                    identifier: GridFsFileIdentifier::Id(id),
+                })
+            })?;
+        GridFsDownloadStream::new(file, self.chunks()).await
+    }
+
+    /// Opens and returns a [`GridFsDownloadStream`] from which the application can read the
+    /// contents of the file with the given `filename`. If there are multiple files with the same
+    /// name, the revision can be selected with the `revision` option; by default, the most
+    /// recently uploaded file is returned.
+    pub async fn open_download_stream_by_name(
+        &self,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsDownloadByNameOptions>>,
+    ) -> Result<GridFsDownloadStream> {
+        let file = self.find_file_by_name(filename.as_ref(), options).await?;
+        GridFsDownloadStream::new(file, self.chunks()).await
+    }
+
+    /// Downloads the contents of the file with the given `id` and writes them to `destination`.
+    pub async fn download_to_futures_0_3_writer<W>(&self, id: Bson, destination: W) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let download_stream = self.open_download_stream(id).await?;
+        copy_to_writer(download_stream, destination).await
+    }
+
+    /// Downloads the contents of the file with the given `filename` and writes them to
+    /// `destination`. If there are multiple files with the same name, the revision can be
+    /// selected with the `revision` option; by default, the most recently uploaded file is
+    /// downloaded.
+    pub async fn download_to_futures_0_3_writer_by_name<W>(
+        &self,
+        filename: impl AsRef<str>,
+        destination: W,
+        options: impl Into<Option<GridFsDownloadByNameOptions>>,
+    ) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let download_stream = self.open_download_stream_by_name(filename, options).await?;
+        copy_to_writer(download_stream, destination).await
+    }
+
+    async fn find_file_by_name(
+        &self,
+        filename: &str,
+        options: impl Into<Option<GridFsDownloadByNameOptions>>,
+    ) -> Result<FilesCollectionDocument> {
+        let revision = options.into().and_then(|opts| opts.revision).unwrap_or(-1);
+        let (sort, skip) = if revision >= 0 {
+            (1, revision as u64)
+        } else {
+            (-1, (-1 - revision) as u64)
+        };
+        let options = FindOneOptions::builder()
+            .sort(doc! { "uploadDate": sort })
+            .skip(skip)
+            .build();
+
+        match self
+            .files()
+            .find_one(doc! { "filename": filename }, options)
+            .await?
+        {
+            Some(file) => Ok(file),
+            None => {
+                let file_exists = self
+                    .files()
+                    .find_one(doc! { "filename": filename }, None)
+                    .await?
+                    .is_some();
+                let kind = if file_exists {
+                    GridFsErrorKind::RevisionNotFound { revision }
+                } else {
+                    GridFsErrorKind::FileNotFound {
+                        identifier: GridFsFileIdentifier::Filename(filename.to_string()),
+                    }
+                };
+                Err(ErrorKind::GridFs(kind).into())
+            }
+        }
+    }
+
+    /// Deletes the file with the given `id` and all of its chunks from the bucket. Returns an
+    /// error if no file with the given `id` exists, after deleting any orphaned chunks.
+    pub async fn delete(&self, id: Bson) -> Result<()> {
+        let delete_result = self
+            .files()
+            .delete_one(doc! { "_id": id.clone() }, None)
+            .await?;
+
+        // Delete the chunks even if no file document was found so that any orphaned chunks from
+        // a partial upload are removed.
+        self.chunks()
+            .delete_many(doc! { "files_id": id.clone() }, None)
+            .await?;
+
+        if delete_result.deleted_count == 0 {
+            return Err(ErrorKind::GridFs(GridFsErrorKind::FileNotFound {
+                identifier: GridFsFileIdentifier::Id(id),
+            })
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Finds and returns the [`FilesCollectionDocument`]s within the bucket that match the given
+    /// filter.
+    pub async fn find(
+        &self,
+        filter: Document,
+        options: impl Into<Option<GridFsFindOptions>>,
+    ) -> Result<Cursor<FilesCollectionDocument>> {
+        let find_options = options.into().map(|options| {
+            FindOptions::builder()
+                .allow_disk_use(options.allow_disk_use)
+                .batch_size(options.batch_size)
+                .limit(options.limit)
+                .max_time(options.max_time)
+                .no_cursor_timeout(options.no_cursor_timeout)
+                .skip(options.skip)
+                .sort(options.sort)
+                .build()
+        });
+
+        self.files().find(filter, find_options).await
+    }
+
+    /// Renames the file with the given `id` to `new_filename`. Returns an error if no file with
+    /// the given `id` exists.
+    pub async fn rename(&self, id: Bson, new_filename: impl AsRef<str>) -> Result<()> {
+        let update_result = self
+            .files()
+            .update_one(
+                doc! { "_id": id.clone() },
+                doc! { "$set": { "filename": new_filename.as_ref() } },
+                None,
+            )
+            .await?;
+
+        if update_result.matched_count == 0 {
+            return Err(ErrorKind::GridFs(GridFsErrorKind::FileNotFound {
+                identifier: GridFsFileIdentifier::Id(id),
+            })
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Drops the files and chunks collections associated with the bucket.
+    pub async fn drop(&self) -> Result<()> {
+        self.files().drop(None).await?;
+        self.chunks().drop(None).await?;
+        Ok(())
+    }
+
+    /// Creates the indexes on the files and chunks collections required by the GridFS spec if
+    /// they don't already exist. This is only done when the files collection is empty, i.e.
+    /// before the first file is uploaded to the bucket.
+    async fn create_indexes(&self) -> Result<()> {
+        if self.inner.created_indexes.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let find_options = FindOneOptions::builder()
+            .selection_criteria(SelectionCriteria::ReadPreference(ReadPreference::Primary))
+            .projection(doc! { "_id": 1 })
+            .build();
+        if self
+            .files()
+            .clone_with_type::<Document>()
+            .find_one(None, find_options)
+            .await?
+            .is_none()
+        {
+            let files_keys = doc! { "filename": 1, "uploadDate": 1 };
+            if !self.index_exists(self.files(), &files_keys).await? {
+                self.files()
+                    .create_index(IndexModel::builder().keys(files_keys).build(), None)
+                    .await?;
+            }
+
+            let chunks_keys = doc! { "files_id": 1, "n": 1 };
+            if !self.index_exists(self.chunks(), &chunks_keys).await? {
+                let index = IndexModel::builder()
+                    .keys(chunks_keys)
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build();
+                self.chunks().create_index(index, None).await?;
+            }
+        }
+
+        self.inner.created_indexes.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Whether an index with the given keys exists on `coll`. Index keys are compared numerically
+    /// so that e.g. an index on `{ filename: 1.0 }` matches `{ filename: 1 }`.
+    async fn index_exists<T>(&self, coll: &Collection<T>, keys: &Document) -> Result<bool> {
+        let indexes: Vec<IndexModel> = match coll.list_indexes(None).await {
+            Ok(cursor) => cursor.try_collect().await?,
+            // The collection doesn't exist yet.
+            Err(e) if e.is_ns_not_found() => return Ok(false),
+            Err(e) => return Err(e),
+        };
+
+        Ok(indexes
+            .iter()
+            .any(|index| bson_util::documents_match(&index.keys, keys)))
+    }
+}
+
+async fn copy_to_writer<W>(
+    mut download_stream: GridFsDownloadStream,
+    mut destination: W,
+) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let bytes_read = (&mut download_stream)
+            .take(DEFAULT_CHUNK_SIZE_BYTES as u64)
+            .read_to_end(&mut buf)
+            .await?;
+        if bytes_read == 0 {
+            break;
+        }
+        destination.write_all(&buf).await?;
+    }
+    destination.flush().await?;
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_io::AsyncWrite;
+use futures_util::{future::BoxFuture, FutureExt};
+
+use super::{Chunk, FilesCollectionDocument, GridFsBucket};
+use crate::{
+    bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime, Document},
+    error::{Error, ErrorKind, GridFsErrorKind, Result},
+    RUNTIME,
+};
+
+/// A stream to which the contents of a file can be written in order to upload it to a
+/// [`GridFsBucket`]. A `GridFsUploadStream` can be obtained by calling
+/// [`GridFsBucket::open_upload_stream`] or [`GridFsBucket::open_upload_stream_with_id`].
+///
+/// `GridFsUploadStream` implements [`futures_io::AsyncWrite`]. The file is only uploaded to the
+/// bucket once the stream has been closed via
+/// [`AsyncWriteExt::close`](futures_util::io::AsyncWriteExt::close); if the stream is dropped
+/// before then, any chunks that have already been written will be deleted in a background task.
+/// Use [`GridFsUploadStream::abort`] to explicitly discard an upload.
+pub struct GridFsUploadStream {
+    bucket: GridFsBucket,
+    id: Bson,
+    filename: String,
+    chunk_size_bytes: u32,
+    metadata: Option<Document>,
+    buffer: Vec<u8>,
+    length: u64,
+    n: u32,
+    state: State,
+}
+
+enum State {
+    Idle,
+    Writing(BoxFuture<'static, Result<()>>),
+    Closing(BoxFuture<'static, Result<()>>),
+    /// A write or the close failed. The upload can't be completed, but the chunks that were
+    /// already written still need to be deleted.
+    Errored,
+    Closed,
+}
+
+impl std::fmt::Debug for GridFsUploadStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GridFsUploadStream")
+            .field("id", &self.id)
+            .field("filename", &self.filename)
+            .field("chunk_size_bytes", &self.chunk_size_bytes)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl GridFsUploadStream {
+    pub(super) fn new(
+        bucket: GridFsBucket,
+        id: Bson,
+        filename: String,
+        chunk_size_bytes: u32,
+        metadata: Option<Document>,
+    ) -> Self {
+        Self {
+            bucket,
+            id,
+            filename,
+            chunk_size_bytes,
+            metadata,
+            buffer: Vec::new(),
+            length: 0,
+            n: 0,
+            state: State::Idle,
+        }
+    }
+
+    /// Gets the `_id` of the file being uploaded.
+    pub fn id(&self) -> &Bson {
+        &self.id
+    }
+
+    pub(super) fn chunk_size_bytes(&self) -> u32 {
+        self.chunk_size_bytes
+    }
+
+    /// Aborts the upload, deleting any chunks that have already been written to the bucket. The
+    /// stream cannot be written to after it has been aborted.
+    pub async fn abort(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Closed) {
+            State::Closed => {
+                return Err(ErrorKind::GridFs(GridFsErrorKind::UploadStreamClosed).into());
+            }
+            // If the upload finished closing successfully, the file is complete and must not be
+            // removed.
+            State::Closing(future) => {
+                if future.await.is_ok() {
+                    return Err(ErrorKind::GridFs(GridFsErrorKind::UploadStreamClosed).into());
+                }
+            }
+            // Wait for any in-flight write to finish so that its chunks are deleted as well; its
+            // result is irrelevant since the upload is being discarded.
+            State::Writing(future) => {
+                let _ = future.await;
+            }
+            State::Idle | State::Errored => {}
+        }
+        self.buffer.clear();
+        delete_chunks(self.bucket.clone(), self.id.clone()).await
+    }
+
+    /// Takes all full chunks out of the buffer. If `flush_partial` is true, any remaining bytes are
+    /// taken as a final, smaller chunk.
+    fn take_chunks(&mut self, flush_partial: bool) -> Vec<Chunk> {
+        let chunk_size = self.chunk_size_bytes as usize;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while self.buffer.len() - start >= chunk_size
+            || (flush_partial && start < self.buffer.len())
+        {
+            let end = std::cmp::min(start + chunk_size, self.buffer.len());
+            chunks.push(Chunk {
+                id: ObjectId::new(),
+                files_id: self.id.clone(),
+                n: self.n,
+                data: Binary {
+                    subtype: BinarySubtype::Generic,
+                    bytes: self.buffer[start..end].to_vec(),
+                },
+            });
+            self.n += 1;
+            start = end;
+        }
+        self.buffer.drain(..start);
+        chunks
+    }
+
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let result = match self.state {
+            State::Writing(ref mut future) | State::Closing(ref mut future) => {
+                match future.poll_unpin(cx) {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+            State::Idle => return Poll::Ready(Ok(())),
+            State::Errored | State::Closed => return Poll::Ready(Err(closed_error())),
+        };
+
+        self.state = match (&self.state, &result) {
+            (_, Err(_)) => State::Errored,
+            (State::Closing(_), Ok(())) => State::Closed,
+            _ => State::Idle,
+        };
+        Poll::Ready(result.map_err(into_io_error))
+    }
+}
+
+impl AsyncWrite for GridFsUploadStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.as_mut().get_mut();
+        if let State::Closing(_) = this.state {
+            return Poll::Ready(Err(closed_error()));
+        }
+        futures_util::ready!(this.poll_in_flight(cx))?;
+
+        this.buffer.extend_from_slice(buf);
+        this.length += buf.len() as u64;
+
+        let chunks = this.take_chunks(false);
+        if !chunks.is_empty() {
+            let bucket = this.bucket.clone();
+            this.state = State::Writing(
+                async move {
+                    bucket.create_indexes().await?;
+                    bucket.chunks().insert_many(chunks, None).await?;
+                    Ok(())
+                }
+                .boxed(),
+            );
+        }
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.as_mut().get_mut();
+        if let State::Closing(_) = this.state {
+            return Poll::Ready(Err(closed_error()));
+        }
+        this.poll_in_flight(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.as_mut().get_mut();
+        if let State::Writing(_) = this.state {
+            futures_util::ready!(this.poll_in_flight(cx))?;
+        }
+
+        if let State::Idle = this.state {
+            let chunks = this.take_chunks(true);
+            let bucket = this.bucket.clone();
+            let file = FilesCollectionDocument {
+                id: this.id.clone(),
+                length: this.length,
+                chunk_size: this.chunk_size_bytes,
+                upload_date: DateTime::now(),
+                filename: Some(this.filename.clone()),
+                metadata: this.metadata.take(),
+            };
+            this.state = State::Closing(
+                async move {
+                    bucket.create_indexes().await?;
+                    if !chunks.is_empty() {
+                        bucket.chunks().insert_many(chunks, None).await?;
+                    }
+                    bucket.files().insert_one(file, None).await?;
+                    Ok(())
+                }
+                .boxed(),
+            );
+        }
+
+        this.poll_in_flight(cx)
+    }
+}
+
+impl Drop for GridFsUploadStream {
+    fn drop(&mut self) {
+        let state = std::mem::replace(&mut self.state, State::Closed);
+        if let State::Closed = state {
+            return;
+        }
+
+        let bucket = self.bucket.clone();
+        let id = self.id.clone();
+        RUNTIME.execute(async move {
+            match state {
+                State::Closing(future) => {
+                    if future.await.is_ok() {
+                        return;
+                    }
+                }
+                State::Writing(future) => {
+                    let _ = future.await;
+                }
+                State::Idle | State::Errored | State::Closed => {}
+            }
+            let _ = delete_chunks(bucket, id).await;
+        });
+    }
+}
+
+async fn delete_chunks(bucket: GridFsBucket, id: Bson) -> Result<()> {
+    bucket
+        .chunks()
+        .delete_many(doc! { "files_id": id }, None)
+        .await?;
+    Ok(())
+}
+
+fn closed_error() -> io::Error {
+    into_io_error(ErrorKind::GridFs(GridFsErrorKind::UploadStreamClosed).into())
+}
+
+fn into_io_error(error: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
@@ -1,4 +1,5 @@
 pub mod options;
+pub(crate) mod reconcile;
 
 use crate::bson::Document;
 
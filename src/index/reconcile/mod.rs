@@ -0,0 +1,207 @@
+//! Computes the changes needed to converge a collection's indexes to a desired set of
+//! [`IndexModel`]s for `Collection::sync_indexes`.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    bson::{Bson, Document},
+    bson_util,
+    results::IndexConflict,
+    IndexModel,
+};
+
+/// The name of the index on `_id`, which cannot be dropped.
+const ID_INDEX_NAME: &str = "_id_";
+
+/// The changes needed to converge a collection's indexes to a desired set.
+#[derive(Debug, Default)]
+pub(crate) struct IndexPlan {
+    /// The indexes that need to be created, with their names filled in.
+    pub(crate) create: Vec<IndexModel>,
+
+    /// The names of the existing indexes that need to be dropped.
+    pub(crate) drop: Vec<String>,
+
+    /// The names of the existing indexes that already match a desired index.
+    pub(crate) unchanged: Vec<String>,
+
+    /// The desired indexes that conflict with an existing index.
+    pub(crate) conflicts: Vec<IndexConflict>,
+}
+
+/// Diffs `desired` against `existing`. Existing indexes that do not correspond to any desired index
+/// are only dropped if `drop_extraneous` is true, and the `_id` index is never dropped.
+pub(crate) fn plan(
+    desired: impl IntoIterator<Item = IndexModel>,
+    existing: Vec<IndexModel>,
+    drop_extraneous: bool,
+) -> IndexPlan {
+    let mut plan = IndexPlan::default();
+    let mut matched = vec![false; existing.len()];
+
+    for mut model in desired {
+        model.update_name();
+        let name = model.get_name().unwrap_or_default();
+
+        // An index with the same name is always the same index. Otherwise, an index with the same
+        // keys and collation is, since the server would refuse to create a second one.
+        let position = existing
+            .iter()
+            .position(|index| index.get_name().as_deref() == Some(name.as_str()))
+            .or_else(|| {
+                existing.iter().position(|index| {
+                    keys_match(&model.keys, index)
+                        && !differences(&model, index).contains(&"collation")
+                })
+            });
+
+        let position = match position {
+            Some(position) => position,
+            None => {
+                plan.create.push(model);
+                continue;
+            }
+        };
+        matched[position] = true;
+
+        let index = &existing[position];
+        let differences = differences(&model, index);
+        if differences.is_empty() {
+            plan.unchanged.push(index.get_name().unwrap_or_default());
+        } else {
+            plan.conflicts.push(IndexConflict {
+                name,
+                desired: model,
+                existing: index.clone(),
+                differences: differences.into_iter().map(String::from).collect(),
+            });
+        }
+    }
+
+    if drop_extraneous {
+        plan.drop = existing
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .filter_map(|(index, _)| index.get_name())
+            .filter(|name| name != ID_INDEX_NAME)
+            .collect();
+    }
+
+    plan
+}
+
+/// Returns the names of the properties of `desired` that `existing` does not match. Options that
+/// `desired` does not set are only compared where the server's default is well defined, so that
+/// e.g. an index inheriting the collection's default collation still matches.
+fn differences(desired: &IndexModel, existing: &IndexModel) -> Vec<&'static str> {
+    let mut differences = Vec::new();
+    if !keys_match(&desired.keys, existing) {
+        differences.push("key");
+    }
+
+    let default_options = Default::default();
+    let desired = desired.options.as_ref().unwrap_or(&default_options);
+    let existing = existing.options.as_ref().unwrap_or(&default_options);
+
+    if desired.unique.unwrap_or(false) != existing.unique.unwrap_or(false) {
+        differences.push("unique");
+    }
+    if desired.sparse.unwrap_or(false) != existing.sparse.unwrap_or(false) {
+        differences.push("sparse");
+    }
+    if desired.hidden.unwrap_or(false) != existing.hidden.unwrap_or(false) {
+        differences.push("hidden");
+    }
+    if desired.expire_after != existing.expire_after {
+        differences.push("expireAfterSeconds");
+    }
+    if !optional_documents_match(
+        desired.partial_filter_expression.as_ref(),
+        existing.partial_filter_expression.as_ref(),
+    ) {
+        differences.push("partialFilterExpression");
+    }
+    if let Some(ref collation) = desired.collation {
+        let matches = match (
+            bson::to_document(collation),
+            existing.collation.as_ref().map(bson::to_document),
+        ) {
+            (Ok(desired), Some(Ok(existing))) => is_subset(&desired, &existing),
+            _ => false,
+        };
+        if !matches {
+            differences.push("collation");
+        }
+    }
+    if desired.wildcard_projection.is_some()
+        && !optional_documents_match(
+            desired.wildcard_projection.as_ref(),
+            existing.wildcard_projection.as_ref(),
+        )
+    {
+        differences.push("wildcardProjection");
+    }
+
+    differences
+}
+
+/// Whether the key pattern `keys` matches the key pattern of `existing`.
+///
+/// The server stores text indexes with the key pattern `{ _fts: "text", _ftsx: 1 }` and records the
+/// indexed fields in `weights`, so those are compared against the text fields of `keys` instead.
+fn keys_match(keys: &Document, existing: &IndexModel) -> bool {
+    if !existing.keys.contains_key("_fts") {
+        return bson_util::documents_match(keys, &existing.keys);
+    }
+
+    let is_text = |value: &Bson| value.as_str() == Some("text");
+    let other_keys = |keys: &Document, text_key: &str| -> Vec<(String, Bson)> {
+        keys.iter()
+            .filter(|(key, value)| !is_text(value) && key.as_str() != text_key)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    };
+
+    let mut text_fields: Vec<&String> = keys
+        .iter()
+        .filter(|(_, value)| is_text(value))
+        .map(|(key, _)| key)
+        .collect();
+    text_fields.sort();
+    let mut existing_text_fields: Vec<&String> = existing
+        .options
+        .as_ref()
+        .and_then(|options| options.weights.as_ref())
+        .map(|weights| weights.keys().collect())
+        .unwrap_or_default();
+    existing_text_fields.sort();
+
+    let desired_other = other_keys(keys, "");
+    let existing_other = other_keys(&existing.keys, "_ftsx");
+    text_fields == existing_text_fields
+        && desired_other.len() == existing_other.len()
+        && desired_other
+            .iter()
+            .zip(existing_other.iter())
+            .all(|((k1, v1), (k2, v2))| k1 == k2 && bson_util::value_matches(v1, v2))
+}
+
+/// Whether every field in `subset` has a matching value in `superset`.
+fn is_subset(subset: &Document, superset: &Document) -> bool {
+    subset.iter().all(|(key, value)| {
+        superset
+            .get(key)
+            .map(|other| bson_util::value_matches(value, other))
+            .unwrap_or(false)
+    })
+}
+
+fn optional_documents_match(a: Option<&Document>, b: Option<&Document>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => bson_util::documents_match(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use super::plan;
+use crate::{
+    bson::{doc, Document},
+    collation::{Collation, CollationStrength},
+    options::IndexOptions,
+    IndexModel,
+};
+
+fn index(keys: Document, options: impl Into<Option<IndexOptions>>) -> IndexModel {
+    IndexModel::builder()
+        .keys(keys)
+        .options(options.into())
+        .build()
+}
+
+fn existing(keys: Document, name: &str, options: impl Into<Option<IndexOptions>>) -> IndexModel {
+    let mut options = options.into().unwrap_or_default();
+    options.name = Some(name.to_string());
+    index(keys, options)
+}
+
+#[test]
+fn create_unchanged_and_conflict() {
+    let desired = vec![
+        index(doc! { "a": 1 }, None),
+        index(
+            doc! { "b": 1 },
+            IndexOptions::builder().unique(true).build(),
+        ),
+        index(
+            doc! { "c": 1 },
+            IndexOptions::builder()
+                .expire_after(Duration::from_secs(60))
+                .build(),
+        ),
+    ];
+    let current = vec![
+        existing(doc! { "_id": 1 }, "_id_", None),
+        existing(doc! { "a": 1 }, "a_1", None),
+        existing(doc! { "b": 1 }, "b_1", None),
+    ];
+
+    let plan = plan(desired, current, false);
+
+    assert_eq!(plan.unchanged, vec!["a_1".to_string()]);
+    assert_eq!(plan.create.len(), 1);
+    assert_eq!(plan.create[0].get_name(), Some("c_1".to_string()));
+    assert!(plan.drop.is_empty());
+
+    assert_eq!(plan.conflicts.len(), 1);
+    let conflict = &plan.conflicts[0];
+    assert_eq!(conflict.name, "b_1");
+    assert_eq!(conflict.differences, vec!["unique".to_string()]);
+}
+
+#[test]
+fn drop_extraneous_keeps_id_index() {
+    let desired = vec![index(doc! { "a": 1 }, None)];
+    let current = vec![
+        existing(doc! { "_id": 1 }, "_id_", None),
+        existing(doc! { "a": 1 }, "a_1", None),
+        existing(doc! { "old": -1 }, "old_-1", None),
+    ];
+
+    let kept = plan(desired.clone(), current.clone(), false);
+    assert!(kept.drop.is_empty());
+
+    let dropped = plan(desired, current, true);
+    assert_eq!(dropped.drop, vec!["old_-1".to_string()]);
+    assert_eq!(dropped.unchanged, vec!["a_1".to_string()]);
+}
+
+#[test]
+fn matches_by_keys_and_normalizes_numbers() {
+    // An index created under a different name is matched by its keys, and the server may report
+    // key directions as doubles.
+    let desired = vec![index(doc! { "a": 1, "b": -1 }, None)];
+    let current = vec![existing(doc! { "a": 1.0, "b": -1_i64 }, "custom", None)];
+
+    let plan = plan(desired, current, true);
+
+    assert_eq!(plan.unchanged, vec!["custom".to_string()]);
+    assert!(plan.create.is_empty());
+    assert!(plan.drop.is_empty());
+    assert!(plan.conflicts.is_empty());
+}
+
+#[test]
+fn text_index() {
+    let desired = vec![index(doc! { "title": "text", "body": "text" }, None)];
+    let current = vec![existing(
+        doc! { "_fts": "text", "_ftsx": 1 },
+        "title_text_body_text",
+        IndexOptions::builder()
+            .weights(doc! { "body": 1, "title": 1 })
+            .build(),
+    )];
+
+    let plan = plan(desired, current, true);
+
+    assert_eq!(plan.unchanged, vec!["title_text_body_text".to_string()]);
+    assert!(plan.drop.is_empty());
+}
+
+#[test]
+fn collation_and_partial_filter() {
+    let desired_collation = Collation::builder()
+        .locale("en".to_string())
+        .strength(CollationStrength::Secondary)
+        .build();
+    // The server fills in the remaining collation fields with their defaults.
+    let existing_collation = Collation::builder()
+        .locale("en".to_string())
+        .strength(CollationStrength::Secondary)
+        .case_level(false)
+        .build();
+
+    let desired = vec![index(
+        doc! { "name": 1 },
+        IndexOptions::builder()
+            .collation(desired_collation)
+            .partial_filter_expression(doc! { "active": true })
+            .build(),
+    )];
+    let current = vec![existing(
+        doc! { "name": 1 },
+        "name_1",
+        IndexOptions::builder()
+            .collation(existing_collation)
+            .partial_filter_expression(doc! { "active": true })
+            .build(),
+    )];
+    let matching = plan(desired.clone(), current, false);
+    assert_eq!(matching.unchanged, vec!["name_1".to_string()]);
+
+    let current = vec![existing(
+        doc! { "name": 1 },
+        "name_1",
+        IndexOptions::builder()
+            .collation(Collation::builder().locale("fr".to_string()).build())
+            .build(),
+    )];
+    let conflicting = plan(desired, current, false);
+    assert_eq!(conflicting.conflicts.len(), 1);
+    assert_eq!(
+        conflicting.conflicts[0].differences,
+        vec![
+            "partialFilterExpression".to_string(),
+            "collation".to_string()
+        ]
+    );
+}
@@ -1,44 +1,67 @@
-use std::{
-    sync::Arc,
-    time::{Duration, Instant},
-};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bson::{doc, oid::ObjectId, DateTime, Document, Timestamp},
+    bson::{doc, oid::ObjectId, Bson, DateTime, Document, Timestamp},
     client::{
-        options::{ServerAddress, ServerApi},
+        options::{ClientOptions, ServerAddress, ServerApi},
         ClusterTime,
     },
-    cmap::{Command, Connection},
+    cmap::{Command, Connection, RawCommandResponse},
     error::Result,
-    event::sdam::{
-        SdamEventHandler,
-        ServerHeartbeatFailedEvent,
-        ServerHeartbeatStartedEvent,
-        ServerHeartbeatSucceededEvent,
+    event::{
+        self,
+        sdam::{
+            SdamEventHandler,
+            ServerHeartbeatFailedEvent,
+            ServerHeartbeatStartedEvent,
+            ServerHeartbeatSucceededEvent,
+        },
     },
     sdam::{ServerType, Topology},
     selection_criteria::TagSet,
 };
 
-/// Construct an isMaster command.
-pub(crate) fn is_master_command(api: Option<&ServerApi>) -> Command {
+/// Construct an isMaster command. If `awaitable_options` are provided, the command will be an
+/// awaitable `hello` as used by the streaming server monitoring protocol.
+pub(crate) fn is_master_command(
+    api: Option<&ServerApi>,
+    awaitable_options: Option<AwaitableHelloOptions>,
+) -> Command {
     let command_name = if api.is_some() { "hello" } else { "isMaster" };
-    let mut command = Command::new(
-        command_name.into(),
-        "admin".into(),
-        doc! { command_name: 1 },
-    );
+    let mut body = doc! { command_name: 1 };
+    let exhaust_allowed = awaitable_options.is_some();
+    if let Some(options) = awaitable_options {
+        body.insert("topologyVersion", options.topology_version);
+        body.insert(
+            "maxAwaitTimeMS",
+            options.max_await_time.as_millis().min(i64::MAX as u128) as i64,
+        );
+    }
+
+    let mut command = Command::new(command_name.into(), "admin".into(), body);
+    command.exhaust_allowed = exhaust_allowed;
     if let Some(server_api) = api {
         command.set_server_api(server_api);
     }
     command
 }
 
-/// Execute an isMaster command, emiting events if a reference to the topology and a handler are
-/// provided.
+/// Options for an awaitable `hello` command. The server will not reply to such a command until
+/// either its topology version changes or `max_await_time` elapses, and it will keep streaming
+/// replies with the same semantics until the connection is closed.
+#[derive(Debug, Clone)]
+pub(crate) struct AwaitableHelloOptions {
+    /// The topology version from the server's most recent reply.
+    pub(crate) topology_version: TopologyVersion,
+
+    /// The maximum amount of time the server should wait before replying.
+    pub(crate) max_await_time: Duration,
+}
+
+/// Execute an isMaster command, emiting events if a reference to the topology and its options
+/// are provided.
 ///
 /// A strong reference to the topology is used here to ensure it is still in scope and has not yet
 /// emitted a `TopologyClosedEvent`.
@@ -46,27 +69,74 @@ pub(crate) async fn run_is_master(
     conn: &mut Connection,
     command: Command,
     topology: Option<&Topology>,
-    handler: &Option<Arc<dyn SdamEventHandler>>,
+    options: Option<&ClientOptions>,
 ) -> Result<IsMasterReply> {
-    emit_event(topology, handler, |handler| {
+    let awaited = command.exhaust_allowed;
+    emit_event(topology, options, |handler| {
         let event = ServerHeartbeatStartedEvent {
             server_address: conn.address.clone(),
+            awaited,
         };
         handler.handle_server_heartbeat_started_event(event);
     });
 
     let start_time = Instant::now();
     let response_result = conn.send_command(command, None).await;
-    let end_time = Instant::now();
+    let round_trip_time = Instant::now().duration_since(start_time);
+
+    handle_is_master_response(
+        conn,
+        response_result,
+        round_trip_time,
+        awaited,
+        topology,
+        options,
+    )
+}
 
-    let round_trip_time = end_time.duration_since(start_time);
+/// Receive the next isMaster reply streamed by the server in response to an awaitable `hello`,
+/// emiting events if a reference to the topology and its options are provided.
+pub(crate) async fn receive_is_master(
+    conn: &mut Connection,
+    topology: Option<&Topology>,
+    options: Option<&ClientOptions>,
+) -> Result<IsMasterReply> {
+    emit_event(topology, options, |handler| {
+        let event = ServerHeartbeatStartedEvent {
+            server_address: conn.address.clone(),
+            awaited: true,
+        };
+        handler.handle_server_heartbeat_started_event(event);
+    });
+
+    let start_time = Instant::now();
+    let response_result = conn.receive_message().await;
+    let round_trip_time = Instant::now().duration_since(start_time);
+
+    handle_is_master_response(
+        conn,
+        response_result,
+        round_trip_time,
+        true,
+        topology,
+        options,
+    )
+}
 
+fn handle_is_master_response(
+    conn: &Connection,
+    response_result: Result<RawCommandResponse>,
+    round_trip_time: Duration,
+    awaited: bool,
+    topology: Option<&Topology>,
+    options: Option<&ClientOptions>,
+) -> Result<IsMasterReply> {
     match response_result.and_then(|raw_response| {
         let is_master_reply = raw_response.to_is_master_response(round_trip_time)?;
         Ok((raw_response, is_master_reply))
     }) {
         Ok((raw_response, is_master_reply)) => {
-            emit_event(topology, handler, |handler| {
+            emit_event(topology, options, |handler| {
                 let mut reply = raw_response
                     .body::<Document>()
                     .unwrap_or_else(|e| doc! { "deserialization error": e.to_string() });
@@ -77,17 +147,19 @@ pub(crate) async fn run_is_master(
                     duration: round_trip_time,
                     reply,
                     server_address: conn.address.clone(),
+                    awaited,
                 };
                 handler.handle_server_heartbeat_succeeded_event(event);
             });
             Ok(is_master_reply)
         }
         Err(err) => {
-            emit_event(topology, handler, |handler| {
+            emit_event(topology, options, |handler| {
                 let event = ServerHeartbeatFailedEvent {
                     duration: round_trip_time,
                     failure: err.clone(),
                     server_address: conn.address.clone(),
+                    awaited,
                 };
                 handler.handle_server_heartbeat_failed_event(event);
             });
@@ -96,14 +168,12 @@ pub(crate) async fn run_is_master(
     }
 }
 
-fn emit_event<F>(topology: Option<&Topology>, handler: &Option<Arc<dyn SdamEventHandler>>, emit: F)
+fn emit_event<F>(topology: Option<&Topology>, options: Option<&ClientOptions>, emit: F)
 where
-    F: FnOnce(&Arc<dyn SdamEventHandler>),
+    F: FnOnce(&dyn SdamEventHandler),
 {
-    if let Some(handler) = handler {
-        if topology.is_some() {
-            emit(handler);
-        }
+    if let (Some(options), Some(_)) = (options, topology) {
+        event::emit_sdam_event(options, emit);
     }
 }
 
@@ -200,11 +270,14 @@ pub(crate) struct IsMasterCommandResponse {
     /// The maximum number of write operations permitted in a write batch.
     pub max_write_batch_size: i64,
 
+    /// The maximum permitted size of a wire protocol message in bytes.
+    pub max_message_size_bytes: Option<i64>,
+
     /// If the connection is to a load balancer, the id of the selected backend.
     pub service_id: Option<ObjectId>,
 
     /// For internal use.
-    pub topology_version: Option<Document>,
+    pub topology_version: Option<TopologyVersion>,
 }
 
 impl PartialEq for IsMasterCommandResponse {
@@ -252,6 +325,24 @@ impl IsMasterCommandResponse {
     }
 }
 
+/// The version of a server's view of the topology, which is used by the streaming server
+/// monitoring protocol to determine when the server should reply to an awaitable `hello`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TopologyVersion {
+    pub(crate) process_id: ObjectId,
+    pub(crate) counter: i64,
+}
+
+impl From<TopologyVersion> for Bson {
+    fn from(topology_version: TopologyVersion) -> Self {
+        Bson::Document(doc! {
+            "processId": topology_version.process_id,
+            "counter": topology_version.counter,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct LastWrite {
@@ -8,7 +8,7 @@
 //! # Installation
 //!
 //! ## Requirements
-//! - Rust 1.48+
+//! - Rust 1.70+
 //! - MongoDB 3.6+
 //!
 //! ## Importing
@@ -56,10 +56,17 @@
 //! | `async-std-runtime` | Enable support for the `async-std` runtime                                                                                            | `async-std` 1.0                     | no      |
 //! | `sync`              | Expose the synchronous API (`mongodb::sync`). This flag cannot be used in conjunction with either of the async runtime feature flags. | `async-std` 1.0                     | no      |
 //! | `aws-auth`          | Enable support for the MONGODB-AWS authentication mechanism.                                                                          | `reqwest` 0.11                      | no      |
+//! | `gssapi-auth`       | Enable support for the GSSAPI (Kerberos) authentication mechanism using the system's Kerberos library.                                | `cross-krb5` 0.4                    | no      |
+//! | `csfle`             | Enable support for client-side field level encryption.                                                                                | `aes` 0.7, `block-modes` 0.8        | no      |
+//! | `openssl-tls`       | Use OpenSSL instead of `rustls` for TLS connections.                                                                                  | `openssl` 0.10, `tokio-openssl` 0.6 | no      |
+//! | `cert-key-password` | Enable support for encrypted private keys (`tlsCertificateKeyFilePassword`) when using `rustls`. | `pkcs8` 0.10                        | no      |
+//! | `ocsp`              | Enable certificate revocation checking via OCSP stapling and OCSP responders. This flag requires `tokio-runtime`. | `reqwest` 0.11, `der` 0.7, `x509-cert` 0.2 | no      |
+//! | `tracing`           | Emit structured [`tracing`](https://docs.rs/tracing) events for commands, server selection, SDAM and connection pool activity, spans for operations and command attempts, and connection pool metrics. | `tracing` 0.1 | no      |
+//! | `test-util`         | Expose an in-process mock server (`mongodb::test_util::MockServer`) for testing code that uses the driver without a running deployment. | n/a | no      |
 //! | `bson-uuid-0_8`     | Enable support for v0.8 of the [`uuid`](docs.rs/uuid/0.8) crate in the public API of the re-exported `bson` crate.                    | n/a                                 | no      |
 //! | `bson-chrono-0_4`   | Enable support for v0.4 of the [`chrono`](docs.rs/chrono/0.4) crate in the public API of the re-exported `bson` crate.                | n/a                                 | no      |
 //! | `zlib-compression`]  | Enable support for compressing messages with [`zlib`](https://zlib.net/).                                                            | `flate2` 1.0                        | no      |
-//! | `zstd-compression`  | Enable support for compressing messages with [`zstd`](http://facebook.github.io/zstd/).  This flag requires Rust version 1.54.        | `zstd` 0.9.0                        | no      |
+//! | `zstd-compression`  | Enable support for compressing messages with [`zstd`](http://facebook.github.io/zstd/).                                                   | `zstd` 0.9.0                        | no      |
 //! | `snappy-compression` | Enable support for compressing messages with [`snappy`](http://google.github.io/snappy/).                                            | `snap` 1.0.5                        | no      |
 //!
 //! # Example Usage
@@ -281,7 +288,7 @@
 //!
 //! ## Minimum supported Rust version (MSRV)
 //!
-//! The MSRV for this crate is currently 1.48.0. This will be rarely be increased, and if it ever is,
+//! The MSRV for this crate is currently 1.70.0. This will be rarely be increased, and if it ever is,
 //! it will only happen in a minor or major version release.
 
 #![warn(missing_docs)]
@@ -309,19 +316,21 @@ pub mod options;
 
 pub use ::bson;
 
+pub mod aggregation;
 mod bson_util;
-#[allow(unused)]
-pub(crate) mod change_stream;
+pub mod change_stream;
 mod client;
 mod cmap;
 mod coll;
 mod collation;
+pub mod command;
 mod compression;
 mod concern;
 mod cursor;
 mod db;
 pub mod error;
 pub mod event;
+pub mod gridfs;
 mod index;
 mod is_master;
 mod operation;
@@ -330,11 +339,16 @@ pub(crate) mod runtime;
 mod sdam;
 mod selection_criteria;
 mod srv;
+#[cfg(feature = "tracing")]
+mod trace;
 #[cfg(any(feature = "sync", docsrs))]
 #[cfg_attr(docsrs, doc(cfg(feature = "sync")))]
 pub mod sync;
 #[cfg(test)]
 mod test;
+#[cfg(any(feature = "test-util", test))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test_util;
 
 #[cfg(test)]
 #[macro_use]
@@ -356,6 +370,9 @@ pub(crate) use crate::{
     db::Database,
 };
 
+#[cfg(feature = "csfle")]
+pub use crate::client::csfle::client_encryption;
+
 pub use {coll::Namespace, index::IndexModel, client::session::ClusterTime, sdam::public::*};
 
 #[cfg(all(
@@ -1,12 +1,14 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use crate::{
-    bson::{doc, Bson, Document},
+    bson::{doc, Bson, Document, RawBsonRef, RawDocument},
     bson_util,
     cmap::{Command, RawCommandResponse, StreamDescription},
     cursor::CursorSpecification,
-    error::Result,
+    error::{ErrorKind, Result},
     operation::{append_options, remove_empty_write_concern, Operation, Retryability},
     options::{AggregateOptions, ChangeStreamOptions, SelectionCriteria, WriteConcern},
     Namespace,
@@ -64,6 +66,15 @@ impl Operation for Aggregate {
     const NAME: &'static str = "aggregate";
 
     fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        if !self.is_acknowledged() {
+            return Err(ErrorKind::InvalidArgument {
+                message: "Aggregations with an $out or $merge stage cannot be performed with an \
+                          unacknowledged write concern"
+                    .to_string(),
+            }
+            .into());
+        }
+
         let mut body = doc! {
             Self::NAME: self.target.to_bson(),
             "pipeline": bson_util::to_bson_array(&self.pipeline),
@@ -99,18 +110,24 @@ impl Operation for Aggregate {
         response: RawCommandResponse,
         description: &StreamDescription,
     ) -> Result<Self::O> {
+        let operation_time = RawDocument::from_bytes(response.as_bytes())?
+            .get("operationTime")?
+            .and_then(RawBsonRef::as_timestamp);
         let response: CursorBody = response.body()?;
 
         if self.is_out_or_merge() {
             response.write_concern_info.validate()?;
         };
 
-        Ok(CursorSpecification::new(
+        let mut spec = CursorSpecification::new(
             response.cursor,
             description.server_address.clone(),
             self.options.as_ref().and_then(|opts| opts.batch_size),
             self.options.as_ref().and_then(|opts| opts.max_await_time),
-        ))
+            self.timeout(),
+        );
+        spec.operation_time = operation_time;
+        Ok(spec)
     }
 
     fn selection_criteria(&self) -> Option<&SelectionCriteria> {
@@ -126,6 +143,10 @@ impl Operation for Aggregate {
     }
 
     fn write_concern(&self) -> Option<&WriteConcern> {
+        // Only aggregations that write data are affected by the write concern.
+        if !self.is_out_or_merge() {
+            return None;
+        }
         self.options
             .as_ref()
             .and_then(|opts| opts.write_concern.as_ref())
@@ -138,6 +159,10 @@ impl Operation for Aggregate {
             Retryability::Read
         }
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 impl Aggregate {
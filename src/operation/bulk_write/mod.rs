@@ -0,0 +1,474 @@
+#[cfg(test)]
+mod test;
+
+use std::{cmp, collections::HashSet, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bson::{doc, oid::ObjectId, Bson, Document},
+    bson_util,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::{BulkWriteFailure, Error, ErrorKind, Result},
+    operation::{
+        append_options,
+        remove_empty_write_concern,
+        Operation,
+        Retryability,
+        WriteResponseBody,
+    },
+    options::{BulkWriteOptions, Collation, Hint, UpdateModifications, WriteConcern, WriteModel},
+    results::BulkWriteResult,
+    Namespace,
+};
+
+/// The kind of command a write statement is sent to the server with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum WriteKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl WriteKind {
+    fn command_name(self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+
+    fn statements_key(self) -> &'static str {
+        match self {
+            Self::Insert => "documents",
+            Self::Update => "updates",
+            Self::Delete => "deletes",
+        }
+    }
+}
+
+/// A single write model converted into the form it is sent to the server in, i.e. an element of
+/// the `documents`, `updates`, or `deletes` array of a write command.
+#[derive(Debug)]
+pub(crate) struct WriteStatement {
+    /// The index of the write model this statement was created from.
+    index: usize,
+
+    kind: WriteKind,
+
+    document: Document,
+
+    /// The size of `document` in bytes.
+    size: usize,
+
+    /// Whether this statement may modify more than one document.
+    multi: bool,
+
+    /// The `_id` of the document to insert, if this is an insert statement.
+    inserted_id: Option<Bson>,
+}
+
+impl WriteStatement {
+    pub(crate) fn new<T: Serialize>(index: usize, model: &WriteModel<T>) -> Result<Self> {
+        let (kind, document, multi, inserted_id) = match model {
+            WriteModel::InsertOne(model) => {
+                let mut document = bson::to_document(&model.document)?;
+                let id = match document.get("_id") {
+                    Some(id) => id.clone(),
+                    None => {
+                        let id = Bson::ObjectId(ObjectId::new());
+                        let mut with_id = doc! { "_id": id.clone() };
+                        with_id.extend(document);
+                        document = with_id;
+                        id
+                    }
+                };
+                (WriteKind::Insert, document, false, Some(id))
+            }
+            WriteModel::UpdateOne(model) => {
+                update_modifications_check(&model.update)?;
+                let statement = update_statement(
+                    &model.filter,
+                    &model.update,
+                    false,
+                    model.upsert,
+                    model.array_filters.as_ref(),
+                    model.collation.as_ref(),
+                    model.hint.as_ref(),
+                )?;
+                (WriteKind::Update, statement, false, None)
+            }
+            WriteModel::UpdateMany(model) => {
+                update_modifications_check(&model.update)?;
+                let statement = update_statement(
+                    &model.filter,
+                    &model.update,
+                    true,
+                    model.upsert,
+                    model.array_filters.as_ref(),
+                    model.collation.as_ref(),
+                    model.hint.as_ref(),
+                )?;
+                (WriteKind::Update, statement, true, None)
+            }
+            WriteModel::ReplaceOne(model) => {
+                let replacement = bson::to_document(&model.replacement)?;
+                bson_util::replacement_document_check(&replacement)?;
+                let statement = update_statement(
+                    &model.filter,
+                    &UpdateModifications::Document(replacement),
+                    false,
+                    model.upsert,
+                    None,
+                    model.collation.as_ref(),
+                    model.hint.as_ref(),
+                )?;
+                (WriteKind::Update, statement, false, None)
+            }
+            WriteModel::DeleteOne(model) => {
+                let statement = delete_statement(
+                    &model.filter,
+                    1,
+                    model.collation.as_ref(),
+                    model.hint.as_ref(),
+                )?;
+                (WriteKind::Delete, statement, false, None)
+            }
+            WriteModel::DeleteMany(model) => {
+                let statement = delete_statement(
+                    &model.filter,
+                    0,
+                    model.collation.as_ref(),
+                    model.hint.as_ref(),
+                )?;
+                (WriteKind::Delete, statement, true, None)
+            }
+        };
+
+        Ok(Self {
+            index,
+            kind,
+            size: bson::to_vec(&document)?.len(),
+            document,
+            multi,
+            inserted_id,
+        })
+    }
+
+    pub(crate) fn kind(&self) -> WriteKind {
+        self.kind
+    }
+
+    fn has_hint(&self) -> bool {
+        self.kind != WriteKind::Insert && self.document.contains_key("hint")
+    }
+}
+
+fn update_modifications_check(update: &UpdateModifications) -> Result<()> {
+    match update {
+        UpdateModifications::Document(ref d) => bson_util::update_document_check(d),
+        UpdateModifications::Pipeline(_) => Ok(()),
+    }
+}
+
+fn update_statement(
+    filter: &Document,
+    update: &UpdateModifications,
+    multi: bool,
+    upsert: Option<bool>,
+    array_filters: Option<&Vec<Document>>,
+    collation: Option<&Collation>,
+    hint: Option<&Hint>,
+) -> Result<Document> {
+    let mut statement = doc! {
+        "q": filter.clone(),
+        "u": update.to_bson(),
+    };
+    if multi {
+        statement.insert("multi", true);
+    }
+    if let Some(upsert) = upsert {
+        statement.insert("upsert", upsert);
+    }
+    if let Some(array_filters) = array_filters {
+        statement.insert("arrayFilters", bson_util::to_bson_array(array_filters));
+    }
+    if let Some(collation) = collation {
+        statement.insert("collation", bson::to_bson(collation)?);
+    }
+    if let Some(hint) = hint {
+        statement.insert("hint", hint.to_bson());
+    }
+    Ok(statement)
+}
+
+fn delete_statement(
+    filter: &Document,
+    limit: u32,
+    collation: Option<&Collation>,
+    hint: Option<&Hint>,
+) -> Result<Document> {
+    let mut statement = doc! {
+        "q": filter.clone(),
+        "limit": limit,
+    };
+    if let Some(collation) = collation {
+        statement.insert("collation", bson::to_bson(collation)?);
+    }
+    if let Some(hint) = hint {
+        statement.insert("hint", bson::to_bson(hint)?);
+    }
+    Ok(statement)
+}
+
+/// The result of executing a single batch of a bulk write.
+#[derive(Debug)]
+pub(crate) struct BulkWriteBatch {
+    /// The number of statements that were sent to the server in the batch.
+    pub(crate) size: usize,
+
+    pub(crate) result: BulkWriteResult,
+}
+
+/// Executes a batch of the leading statements of the same kind from `statements`, limited by the
+/// server's maximum write batch size and maximum message size.
+#[derive(Debug)]
+pub(crate) struct BulkWrite<'a> {
+    ns: Namespace,
+    statements: &'a [WriteStatement],
+    options: Option<BulkWriteOptions>,
+    batch_size: usize,
+}
+
+impl<'a> BulkWrite<'a> {
+    pub(crate) fn new(
+        ns: Namespace,
+        statements: &'a [WriteStatement],
+        options: Option<BulkWriteOptions>,
+    ) -> Self {
+        Self {
+            ns,
+            statements,
+            options,
+            batch_size: 0,
+        }
+    }
+
+    fn kind(&self) -> WriteKind {
+        self.statements[0].kind
+    }
+
+    fn is_ordered(&self) -> bool {
+        self.options
+            .as_ref()
+            .and_then(|o| o.ordered)
+            .unwrap_or(true)
+    }
+
+    /// The statements that may be included in the batch, i.e. the leading statements of the same
+    /// kind.
+    fn candidates(&self) -> impl Iterator<Item = &'a WriteStatement> {
+        let kind = self.kind();
+        self.statements.iter().take_while(move |s| s.kind == kind)
+    }
+
+    fn batch(&self) -> &'a [WriteStatement] {
+        &self.statements[..self.batch_size]
+    }
+}
+
+impl<'a> Operation for BulkWrite<'a> {
+    type O = BulkWriteBatch;
+    type Command = Document;
+
+    // The actual command name depends on the kind of the statements in the batch.
+    const NAME: &'static str = "bulkWrite";
+
+    fn build(&mut self, description: &StreamDescription) -> Result<Command> {
+        let max_size = cmp::min(
+            description.max_bson_object_size,
+            description.max_message_size_bytes,
+        ) as u64;
+
+        let mut statements = Vec::new();
+        let mut size = 0;
+        for (i, statement) in self
+            .candidates()
+            .take(description.max_write_batch_size as usize)
+            .enumerate()
+        {
+            let statement_size = bson_util::array_entry_size_bytes(i, statement.size);
+            if size + statement_size > max_size {
+                break;
+            }
+            statements.push(Bson::Document(statement.document.clone()));
+            size += statement_size;
+        }
+
+        if statements.is_empty() {
+            return Err(ErrorKind::InvalidArgument {
+                message: "write model exceeds maxBsonObjectSize".to_string(),
+            }
+            .into());
+        }
+        self.batch_size = statements.len();
+
+        if !self.is_acknowledged() && self.batch().iter().any(WriteStatement::has_hint) {
+            return Err(ErrorKind::InvalidArgument {
+                message: "Specifying a hint is not supported with an unacknowledged write concern"
+                    .to_string(),
+            }
+            .into());
+        }
+
+        let kind = self.kind();
+        let mut body = doc! {
+            kind.command_name(): self.ns.coll.clone(),
+            kind.statements_key(): statements,
+        };
+
+        let mut options = self.options.clone().unwrap_or_default();
+        options.ordered = Some(self.is_ordered());
+        remove_empty_write_concern!(Some(&mut options));
+        append_options(&mut body, Some(&options))?;
+
+        Ok(Command::new(
+            kind.command_name().to_string(),
+            self.ns.db.clone(),
+            body,
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        raw_response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let batch = self.batch();
+
+        if !self.is_acknowledged() {
+            // no reply is sent for unacknowledged writes, so report every document sent as
+            // inserted.
+            let result = BulkWriteResult {
+                inserted_ids: batch
+                    .iter()
+                    .filter_map(|s| s.inserted_id.clone().map(|id| (s.index, id)))
+                    .collect(),
+                acknowledged: false,
+                ..Default::default()
+            };
+            return Ok(BulkWriteBatch {
+                size: batch.len(),
+                result,
+            });
+        }
+
+        let response: WriteResponseBody<BulkWriteBody> = raw_response.body()?;
+        let mut result = BulkWriteResult {
+            acknowledged: true,
+            ..Default::default()
+        };
+
+        match self.kind() {
+            WriteKind::Insert => {
+                result.inserted_count = response.n;
+                let failed: HashSet<usize> = response
+                    .write_errors
+                    .iter()
+                    .flatten()
+                    .map(|error| error.index)
+                    .collect();
+                let attempted = if self.is_ordered() {
+                    // in ordered inserts, only the first n were inserted.
+                    response.n as usize
+                } else {
+                    batch.len()
+                };
+                for (i, statement) in batch.iter().enumerate().take(attempted) {
+                    if let (false, Some(id)) = (failed.contains(&i), &statement.inserted_id) {
+                        result.inserted_ids.insert(statement.index, id.clone());
+                    }
+                }
+            }
+            WriteKind::Update => {
+                let upserted = response.upserted.clone().unwrap_or_default();
+                result.upserted_count = upserted.len() as u64;
+                result.matched_count = response.n - result.upserted_count;
+                result.modified_count = response.n_modified.unwrap_or(0);
+                for upsert in upserted {
+                    if let Some(statement) = batch.get(upsert.index) {
+                        result.upserted_ids.insert(statement.index, upsert.id);
+                    }
+                }
+            }
+            WriteKind::Delete => {
+                result.deleted_count = response.n;
+            }
+        }
+
+        if response.write_errors.is_some() || response.write_concern_error.is_some() {
+            // translate the indexes of the write errors into indexes of the write models.
+            let write_errors = response.write_errors.map(|errors| {
+                errors
+                    .into_iter()
+                    .map(|mut error| {
+                        if let Some(statement) = batch.get(error.index) {
+                            error.index = statement.index;
+                        }
+                        error
+                    })
+                    .collect()
+            });
+            return Err(Error::new(
+                ErrorKind::BulkWrite(BulkWriteFailure {
+                    write_errors,
+                    write_concern_error: response.write_concern_error,
+                    partial_result: Some(Box::new(result)),
+                    batch_size: batch.len(),
+                    ..BulkWriteFailure::new()
+                }),
+                response.labels,
+            ));
+        }
+
+        Ok(BulkWriteBatch {
+            size: batch.len(),
+            result,
+        })
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options.as_ref().and_then(|o| o.write_concern.as_ref())
+    }
+
+    fn retryability(&self) -> Retryability {
+        if self.candidates().any(|s| s.multi) {
+            Retryability::None
+        } else {
+            Retryability::Write
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.kind().command_name()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkWriteBody {
+    n_modified: Option<u64>,
+    upserted: Option<Vec<Upserted>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Upserted {
+    index: usize,
+    #[serde(rename = "_id")]
+    id: Bson,
+}
@@ -0,0 +1,248 @@
+use crate::{
+    bson::{doc, Bson, Document},
+    cmap::StreamDescription,
+    error::ErrorKind,
+    operation::{test::handle_response_test, BulkWrite, Operation, Retryability, WriteStatement},
+    options::{
+        BulkWriteOptions,
+        DeleteManyModel,
+        DeleteOneModel,
+        InsertOneModel,
+        ReplaceOneModel,
+        UpdateManyModel,
+        UpdateOneModel,
+        WriteModel,
+    },
+    Namespace,
+};
+
+fn namespace() -> Namespace {
+    Namespace {
+        db: "test_db".to_string(),
+        coll: "test_coll".to_string(),
+    }
+}
+
+fn to_statements(models: Vec<WriteModel<Document>>) -> Vec<WriteStatement> {
+    models
+        .iter()
+        .enumerate()
+        .map(|(i, model)| WriteStatement::new(i, model).unwrap())
+        .collect()
+}
+
+fn mixed_models() -> Vec<WriteModel<Document>> {
+    vec![
+        InsertOneModel::builder()
+            .document(doc! { "_id": 1 })
+            .build()
+            .into(),
+        InsertOneModel::builder()
+            .document(doc! { "x": 2 })
+            .build()
+            .into(),
+        UpdateOneModel::builder()
+            .filter(doc! { "_id": 1 })
+            .update(doc! { "$set": { "x": 1 } })
+            .upsert(true)
+            .build()
+            .into(),
+        ReplaceOneModel::builder()
+            .filter(doc! { "_id": 2 })
+            .replacement(doc! { "x": 3 })
+            .build()
+            .into(),
+        DeleteOneModel::builder()
+            .filter(doc! { "_id": 1 })
+            .build()
+            .into(),
+    ]
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_batches_by_kind() {
+    let statements = to_statements(mixed_models());
+    let description = StreamDescription::new_testing();
+
+    let mut op = BulkWrite::new(namespace(), &statements, None);
+    let cmd = op.build(&description).unwrap();
+    assert_eq!(cmd.name, "insert");
+    assert_eq!(cmd.target_db, "test_db");
+    assert_eq!(cmd.body.get_str("insert"), Ok("test_coll"));
+    assert_eq!(cmd.body.get_bool("ordered"), Ok(true));
+    let documents = cmd.body.get_array("documents").unwrap();
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0], Bson::Document(doc! { "_id": 1 }));
+    // an _id is generated for documents that don't have one.
+    let generated = documents[1].as_document().unwrap();
+    assert!(generated.get_object_id("_id").is_ok());
+    assert_eq!(generated.get_i32("x"), Ok(2));
+
+    let mut op = BulkWrite::new(namespace(), &statements[2..], None);
+    let cmd = op.build(&description).unwrap();
+    assert_eq!(cmd.name, "update");
+    assert_eq!(
+        cmd.body.get_array("updates").unwrap(),
+        &vec![
+            Bson::Document(doc! { "q": { "_id": 1 }, "u": { "$set": { "x": 1 } }, "upsert": true }),
+            Bson::Document(doc! { "q": { "_id": 2 }, "u": { "x": 3 } }),
+        ]
+    );
+
+    let mut op = BulkWrite::new(namespace(), &statements[4..], None);
+    let cmd = op.build(&description).unwrap();
+    assert_eq!(cmd.name, "delete");
+    assert_eq!(
+        cmd.body.get_array("deletes").unwrap(),
+        &vec![Bson::Document(doc! { "q": { "_id": 1 }, "limit": 1 })]
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_respects_max_write_batch_size() {
+    let models = (0..5)
+        .map(|i| {
+            InsertOneModel::builder()
+                .document(doc! { "_id": i })
+                .build()
+                .into()
+        })
+        .collect();
+    let statements = to_statements(models);
+    let mut description = StreamDescription::new_testing();
+    description.max_write_batch_size = 2;
+
+    let mut op = BulkWrite::new(
+        namespace(),
+        &statements,
+        Some(BulkWriteOptions::builder().ordered(false).build()),
+    );
+    let cmd = op.build(&description).unwrap();
+    assert_eq!(cmd.body.get_array("documents").unwrap().len(), 2);
+    assert_eq!(cmd.body.get_bool("ordered"), Ok(false));
+
+    let result = handle_response_test(&op, doc! { "ok": 1, "n": 2 }).unwrap();
+    assert_eq!(result.size, 2);
+    assert_eq!(result.result.inserted_count, 2);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_respects_max_bson_object_size() {
+    let big = "x".repeat(600);
+    let models = (0..3)
+        .map(|i| {
+            InsertOneModel::builder()
+                .document(doc! { "_id": i, "big": big.clone() })
+                .build()
+                .into()
+        })
+        .collect();
+    let statements = to_statements(models);
+    let mut description = StreamDescription::new_testing();
+    description.max_bson_object_size = 1300;
+
+    let mut op = BulkWrite::new(namespace(), &statements, None);
+    let cmd = op.build(&description).unwrap();
+    assert_eq!(cmd.body.get_array("documents").unwrap().len(), 2);
+
+    description.max_bson_object_size = 100;
+    let mut op = BulkWrite::new(namespace(), &statements, None);
+    let error = op.build(&description).unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+}
+
+#[test]
+fn invalid_models() {
+    let update: WriteModel<Document> = UpdateOneModel::builder()
+        .filter(doc! {})
+        .update(doc! { "x": 1 })
+        .build()
+        .into();
+    assert!(WriteStatement::new(0, &update).is_err());
+
+    let replace: WriteModel<Document> = ReplaceOneModel::builder()
+        .filter(doc! {})
+        .replacement(doc! { "$set": { "x": 1 } })
+        .build()
+        .into();
+    assert!(WriteStatement::new(0, &replace).is_err());
+}
+
+#[test]
+fn retryability() {
+    let statements = to_statements(mixed_models());
+    let op = BulkWrite::new(namespace(), &statements[2..], None);
+    assert_eq!(op.retryability(), Retryability::Write);
+
+    let models: Vec<WriteModel<Document>> = vec![
+        UpdateManyModel::builder()
+            .filter(doc! {})
+            .update(doc! { "$set": { "x": 1 } })
+            .build()
+            .into(),
+        DeleteManyModel::builder().filter(doc! {}).build().into(),
+    ];
+    let multi_statements = to_statements(models);
+    let op = BulkWrite::new(namespace(), &multi_statements, None);
+    assert_eq!(op.retryability(), Retryability::None);
+    let op = BulkWrite::new(namespace(), &multi_statements[1..], None);
+    assert_eq!(op.retryability(), Retryability::None);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_update_success() {
+    let statements = to_statements(mixed_models());
+    let mut op = BulkWrite::new(namespace(), &statements[2..], None);
+    op.build(&StreamDescription::new_testing()).unwrap();
+
+    let response = doc! {
+        "ok": 1,
+        "n": 2,
+        "nModified": 1,
+        "upserted": [{ "index": 0, "_id": 1 }],
+    };
+    let batch = handle_response_test(&op, response).unwrap();
+    assert_eq!(batch.size, 2);
+    assert_eq!(batch.result.upserted_count, 1);
+    assert_eq!(batch.result.matched_count, 1);
+    assert_eq!(batch.result.modified_count, 1);
+    // indexes refer to the write models rather than the statements in the batch.
+    assert_eq!(batch.result.upserted_ids.get(&2), Some(&Bson::Int32(1)));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_write_failure() {
+    let statements = to_statements(mixed_models());
+    let mut op = BulkWrite::new(
+        namespace(),
+        &statements,
+        Some(BulkWriteOptions::builder().ordered(false).build()),
+    );
+    op.build(&StreamDescription::new_testing()).unwrap();
+
+    let response = doc! {
+        "ok": 1,
+        "n": 1,
+        "writeErrors": [{ "index": 0, "code": 11000, "errmsg": "duplicate key" }],
+    };
+    let error = handle_response_test(&op, response).unwrap_err();
+    match *error.kind {
+        ErrorKind::BulkWrite(ref failure) => {
+            let write_errors = failure.write_errors.as_ref().unwrap();
+            assert_eq!(write_errors.len(), 1);
+            assert_eq!(write_errors[0].code, 11000);
+            assert_eq!(failure.batch_size, 2);
+
+            let result = failure.partial_result.as_ref().unwrap();
+            assert_eq!(result.inserted_count, 1);
+            assert!(!result.inserted_ids.contains_key(&0));
+            assert!(result.inserted_ids.contains_key(&1));
+        }
+        ref e => panic!("expected bulk write error, got {:?}", e),
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use bson::Document;
+
+use crate::{
+    bson::doc,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::{append_options, Operation},
+    options::{CollectionStatsOptions, SelectionCriteria},
+    results::CollectionStats,
+    Namespace,
+};
+
+#[derive(Debug)]
+pub(crate) struct CollStats {
+    ns: Namespace,
+    options: Option<CollectionStatsOptions>,
+}
+
+impl CollStats {
+    pub(crate) fn new(ns: Namespace, options: Option<CollectionStatsOptions>) -> Self {
+        Self { ns, options }
+    }
+}
+
+impl Operation for CollStats {
+    type O = CollectionStats;
+    type Command = Document;
+
+    const NAME: &'static str = "collStats";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.ns.coll.clone(),
+        };
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(
+            Self::NAME.to_string(),
+            self.ns.db.clone(),
+            body,
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        response.body()
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.selection_criteria.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    operation::{
+        test::{self, handle_response_test},
+        CollStats,
+        Operation,
+    },
+    options::CollectionStatsOptions,
+    Namespace,
+};
+
+fn namespace() -> Namespace {
+    Namespace {
+        db: "test_db".to_string(),
+        coll: "test_coll".to_string(),
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let options = CollectionStatsOptions::builder().scale(1024).build();
+    let mut op = CollStats::new(namespace(), Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "collStats");
+    assert_eq!(cmd.target_db.as_str(), "test_db");
+    assert_eq!(cmd.body, doc! { "collStats": "test_coll", "scale": 1024 });
+}
+
+#[test]
+fn op_selection_criteria() {
+    test::op_selection_criteria(|selection_criteria| {
+        let options = CollectionStatsOptions::builder()
+            .selection_criteria(selection_criteria)
+            .build();
+        CollStats::new(namespace(), Some(options))
+    });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_success() {
+    let op = CollStats::new(namespace(), None);
+
+    let response = doc! {
+        "ok": 1,
+        "ns": "test_db.test_coll",
+        "count": 3,
+        "size": 150_i64,
+        "avgObjSize": 50,
+        "storageSize": 4096.0,
+        "nindexes": 2,
+        "totalIndexSize": 8192,
+        "indexSizes": { "_id_": 4096, "x_1": 4096_i64 },
+        "capped": false,
+    };
+    let stats = handle_response_test(&op, response).expect("handle should succeed");
+
+    assert_eq!(stats.ns, namespace());
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.size, 150);
+    assert_eq!(stats.storage_size, 4096);
+    assert_eq!(stats.index_count, 2);
+    assert_eq!(stats.total_index_size, 8192);
+    assert_eq!(stats.index_sizes.get("x_1"), Some(&4096));
+    assert!(!stats.capped);
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use bson::Document;
 use serde::Deserialize;
 
@@ -130,6 +132,10 @@ impl Operation for Count {
     fn retryability(&self) -> Retryability {
         Retryability::Read
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test;
 
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use bson::{doc, Document, RawDocument};
 
@@ -66,6 +66,7 @@ impl CountDocuments {
                 .collation(opts.collation)
                 .selection_criteria(opts.selection_criteria)
                 .read_concern(opts.read_concern)
+                .timeout(opts.timeout)
                 .build()
         });
 
@@ -136,4 +137,8 @@ impl Operation for CountDocuments {
     fn supports_read_concern(&self, description: &StreamDescription) -> bool {
         self.aggregate.supports_read_concern(description)
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.aggregate.timeout()
+    }
 }
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use bson::Document;
 
 use crate::{
@@ -70,4 +72,8 @@ impl Operation for Create {
             .as_ref()
             .and_then(|opts| opts.write_concern.as_ref())
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
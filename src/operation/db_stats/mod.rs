@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use bson::Document;
+
+use crate::{
+    bson::doc,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::{append_options, Operation},
+    options::{DatabaseStatsOptions, SelectionCriteria},
+    results::DatabaseStats,
+};
+
+#[derive(Debug)]
+pub(crate) struct DbStats {
+    db: String,
+    options: Option<DatabaseStatsOptions>,
+}
+
+impl DbStats {
+    pub(crate) fn new(db: String, options: Option<DatabaseStatsOptions>) -> Self {
+        Self { db, options }
+    }
+}
+
+impl Operation for DbStats {
+    type O = DatabaseStats;
+    type Command = Document;
+
+    const NAME: &'static str = "dbStats";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: 1,
+        };
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        response.body()
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.selection_criteria.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    operation::{test::handle_response_test, DbStats, Operation},
+    options::DatabaseStatsOptions,
+};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let options = DatabaseStatsOptions::builder().scale(1024).build();
+    let mut op = DbStats::new("test_db".to_string(), Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "dbStats");
+    assert_eq!(cmd.target_db.as_str(), "test_db");
+    assert_eq!(cmd.body, doc! { "dbStats": 1, "scale": 1024 });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_success() {
+    let op = DbStats::new("test_db".to_string(), None);
+
+    let response = doc! {
+        "ok": 1,
+        "db": "test_db",
+        "collections": 2,
+        "views": 1,
+        "objects": 10_i64,
+        "avgObjSize": 42.5,
+        "dataSize": 425.0,
+        "storageSize": 8192,
+        "indexes": 2,
+        "indexSize": 8192,
+    };
+    let stats = handle_response_test(&op, response).expect("handle should succeed");
+
+    assert_eq!(stats.db, "test_db");
+    assert_eq!(stats.collections, 2);
+    assert_eq!(stats.views, 1);
+    assert_eq!(stats.objects, 10);
+    assert_eq!(stats.data_size, 425);
+    assert_eq!(stats.storage_size, 8192);
+    assert_eq!(stats.indexes, 2);
+    assert_eq!(stats.index_size, 8192);
+}
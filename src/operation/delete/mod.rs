@@ -1,12 +1,14 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use crate::{
     bson::{doc, Document},
     cmap::{Command, RawCommandResponse, StreamDescription},
     coll::Namespace,
     collation::Collation,
-    error::{convert_bulk_errors, Result},
+    error::{convert_bulk_errors, ErrorKind, Result},
     operation::{
         append_options,
         remove_empty_write_concern,
@@ -66,6 +68,14 @@ impl Operation for Delete {
     const NAME: &'static str = "delete";
 
     fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        if self.hint.is_some() && !self.is_acknowledged() {
+            return Err(ErrorKind::InvalidArgument {
+                message: "Specifying a hint is not supported with an unacknowledged write concern"
+                    .to_string(),
+            }
+            .into());
+        }
+
         let mut delete = doc! {
             "q": self.filter.clone(),
             "limit": self.limit,
@@ -100,11 +110,16 @@ impl Operation for Delete {
         response: RawCommandResponse,
         _description: &StreamDescription,
     ) -> Result<Self::O> {
+        if !self.is_acknowledged() {
+            return Ok(DeleteResult::unacknowledged());
+        }
+
         let response: WriteResponseBody = response.body()?;
         response.validate().map_err(convert_bulk_errors)?;
 
         Ok(DeleteResult {
             deleted_count: response.n,
+            acknowledged: true,
         })
     }
 
@@ -121,4 +136,8 @@ impl Operation for Delete {
             Retryability::None
         }
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use bson::RawBsonRef;
 use serde::Deserialize;
 
@@ -107,6 +109,10 @@ impl Operation for Distinct {
     fn supports_read_concern(&self, _description: &StreamDescription) -> bool {
         true
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 #[derive(Debug, Deserialize)]
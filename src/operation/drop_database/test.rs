@@ -17,6 +17,7 @@ async fn build() {
                 w: Some(Acknowledgment::Custom("abc".to_string())),
                 ..Default::default()
             }),
+            timeout: None,
         }),
     };
 
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use crate::{
     bson::{doc, Document},
     cmap::{Command, RawCommandResponse, StreamDescription},
@@ -68,4 +70,8 @@ impl Operation for DropIndexes {
             .as_ref()
             .and_then(|opts| opts.write_concern.as_ref())
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
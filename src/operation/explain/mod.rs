@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use crate::{
+    bson::{self, doc, Document},
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::Operation,
+    options::{ExplainVerbosity, SelectionCriteria},
+    results::ExplainResult,
+};
+
+/// Runs the command built by another operation through `explain` rather than executing it.
+#[derive(Debug)]
+pub(crate) struct Explain<Op> {
+    inner: Op,
+    verbosity: ExplainVerbosity,
+}
+
+impl<Op> Explain<Op> {
+    pub(crate) fn new(inner: Op, verbosity: impl Into<Option<ExplainVerbosity>>) -> Self {
+        Self {
+            inner,
+            verbosity: verbosity.into().unwrap_or(ExplainVerbosity::QueryPlanner),
+        }
+    }
+}
+
+impl<Op: Operation<Command = Document>> Operation for Explain<Op> {
+    type O = ExplainResult;
+    type Command = Document;
+    const NAME: &'static str = "explain";
+
+    fn build(&mut self, description: &StreamDescription) -> Result<Command> {
+        let inner = self.inner.build(description)?;
+
+        // The write concern of an explained command is only permitted on the explain command
+        // itself, and explains never perform writes anyway.
+        let mut explained = inner.body;
+        explained.remove("writeConcern");
+
+        let body = doc! {
+            Self::NAME: explained,
+            "verbosity": bson::to_bson(&self.verbosity)?,
+        };
+
+        Ok(Command::new(Self::NAME.to_string(), inner.target_db, body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: Document = response.body()?;
+        Ok(ExplainResult::new(response))
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.inner.selection_criteria()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    coll::{options::DeleteOptions, Namespace},
+    concern::{Acknowledgment, WriteConcern},
+    operation::{
+        test::{self, handle_response_test},
+        Delete,
+        Explain,
+        Find,
+        Operation,
+    },
+    options::{ExplainVerbosity, FindOptions},
+};
+
+fn namespace() -> Namespace {
+    Namespace {
+        db: "test_db".to_string(),
+        coll: "test_coll".to_string(),
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let options = FindOptions::builder().limit(5).build();
+    let find = Find::new(namespace(), Some(doc! { "x": 1 }), Some(options));
+    let mut op = Explain::new(find, ExplainVerbosity::ExecutionStats);
+
+    let command = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(command.name, "explain");
+    assert_eq!(command.target_db, "test_db");
+    assert_eq!(
+        command.body,
+        doc! {
+            "explain": {
+                "find": "test_coll",
+                "limit": 5_i64,
+                "filter": { "x": 1 },
+            },
+            "verbosity": "executionStats",
+        }
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_write_omits_write_concern() {
+    let wc = WriteConcern {
+        w: Some(Acknowledgment::Majority),
+        ..Default::default()
+    };
+    let options = DeleteOptions::builder().write_concern(wc).build();
+    let delete = Delete::new(namespace(), doc! { "x": 1 }, Some(1), Some(options));
+    let mut op = Explain::new(delete, None);
+
+    let command = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(command.body.get_str("verbosity"), Ok("queryPlanner"));
+    let explained = command.body.get_document("explain").unwrap();
+    assert_eq!(explained.get_str("delete"), Ok("test_coll"));
+    assert!(!explained.contains_key("writeConcern"));
+}
+
+#[test]
+fn op_selection_criteria() {
+    test::op_selection_criteria(|selection_criteria| {
+        let options = FindOptions::builder()
+            .selection_criteria(selection_criteria)
+            .build();
+        Explain::new(Find::new(namespace(), None, Some(options)), None)
+    });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_query_response() {
+    let op = Explain::new(Find::new(namespace(), None, None), None);
+
+    let response = doc! {
+        "ok": 1,
+        "queryPlanner": {
+            "namespace": "test_db.test_coll",
+            "winningPlan": { "stage": "COLLSCAN" },
+        },
+        "executionStats": { "nReturned": 3 },
+    };
+    let result = handle_response_test(&op, response.clone()).expect("handle should succeed");
+
+    assert_eq!(result.winning_plan, Some(doc! { "stage": "COLLSCAN" }));
+    assert_eq!(result.execution_stats, Some(doc! { "nReturned": 3 }));
+    assert_eq!(result.raw, response);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_aggregate_stages_response() {
+    let op = Explain::new(Find::new(namespace(), None, None), None);
+
+    let response = doc! {
+        "ok": 1,
+        "stages": [
+            {
+                "$cursor": {
+                    "queryPlanner": { "winningPlan": { "stage": "IXSCAN" } },
+                },
+            },
+            { "$group": { "_id": 1 } },
+        ],
+    };
+    let result = handle_response_test(&op, response).expect("handle should succeed");
+
+    assert_eq!(result.winning_plan, Some(doc! { "stage": "IXSCAN" }));
+    assert_eq!(result.execution_stats, None);
+}
@@ -2,7 +2,7 @@ mod options;
 #[cfg(test)]
 mod test;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use serde::{de::DeserializeOwned, Deserialize};
 
@@ -113,6 +113,14 @@ where
             .into());
         }
 
+        if self.options.hint.is_some() && !self.is_acknowledged() {
+            return Err(ErrorKind::InvalidArgument {
+                message: "Specifying a hint is not supported with an unacknowledged write concern"
+                    .to_string(),
+            }
+            .into());
+        }
+
         let mut body: Document = doc! {
             Self::NAME: self.ns.coll.clone(),
             "query": self.query.clone(),
@@ -133,6 +141,11 @@ where
         response: RawCommandResponse,
         _description: &StreamDescription,
     ) -> Result<Self::O> {
+        // no reply is sent for unacknowledged writes, so there is no document to return.
+        if !self.is_acknowledged() {
+            return Ok(None);
+        }
+
         let response: Response = response.body()?;
 
         match response.value {
@@ -156,6 +169,10 @@ where
     fn retryability(&self) -> Retryability {
         Retryability::Write
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.timeout
+    }
 }
 
 #[derive(Debug, Deserialize)]
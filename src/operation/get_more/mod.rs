@@ -25,6 +25,7 @@ pub(crate) struct GetMore<'conn> {
     selection_criteria: SelectionCriteria,
     batch_size: Option<u32>,
     max_time: Option<Duration>,
+    timeout: Option<Duration>,
     pinned_connection: Option<&'conn PinnedConnectionHandle>,
 }
 
@@ -39,6 +40,7 @@ impl<'conn> GetMore<'conn> {
             selection_criteria: SelectionCriteria::from_address(info.address),
             batch_size: info.batch_size,
             max_time: info.max_time,
+            timeout: info.timeout,
             pinned_connection: pinned,
         }
     }
@@ -99,6 +101,10 @@ impl<'conn> Operation for GetMore<'conn> {
     fn pinned_connection(&self) -> Option<&PinnedConnectionHandle> {
         self.pinned_connection
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -27,6 +27,7 @@ fn build_test(
         address,
         batch_size,
         max_time,
+        timeout: None,
     };
     let mut get_more = GetMore::new(info, None);
 
@@ -118,6 +119,7 @@ async fn build_batch_size() {
         id: cursor_id,
         batch_size: Some((std::i32::MAX as u32) + 1),
         max_time: None,
+        timeout: None,
     };
     let mut op = GetMore::new(info, None);
     assert!(op.build(&StreamDescription::new_testing()).is_err())
@@ -137,6 +139,7 @@ async fn op_selection_criteria() {
         id: 123,
         batch_size: None,
         max_time: None,
+        timeout: None,
     };
     let get_more = GetMore::new(info, None);
     let server_description = ServerDescription {
@@ -182,6 +185,7 @@ async fn handle_success() {
         id: cursor_id,
         batch_size: None,
         max_time: None,
+        timeout: None,
     };
     let get_more = GetMore::new(info, None);
 
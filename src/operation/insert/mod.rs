@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test;
 
-use std::{collections::HashMap, io::Write};
+use std::{collections::HashMap, io::Write, time::Duration};
 
 use bson::{oid::ObjectId, spec::ElementType, Bson};
 use serde::Serialize;
@@ -172,6 +172,15 @@ impl<'a, T: Serialize> Operation for Insert<'a, T> {
         raw_response: RawCommandResponse,
         _description: &StreamDescription,
     ) -> Result<Self::O> {
+        if !self.is_acknowledged() {
+            // no reply is sent for unacknowledged writes, so report every document sent as
+            // attempted.
+            return Ok(InsertManyResult {
+                inserted_ids: self.inserted_ids.iter().cloned().enumerate().collect(),
+                acknowledged: false,
+            });
+        }
+
         let response: WriteResponseBody = raw_response.body()?;
 
         let mut map = HashMap::new();
@@ -205,12 +214,16 @@ impl<'a, T: Serialize> Operation for Insert<'a, T> {
                     write_errors: response.write_errors,
                     write_concern_error: response.write_concern_error,
                     inserted_ids: map,
+                    ..BulkWriteFailure::new()
                 }),
                 response.labels,
             ));
         }
 
-        Ok(InsertManyResult { inserted_ids: map })
+        Ok(InsertManyResult {
+            inserted_ids: map,
+            acknowledged: true,
+        })
     }
 
     fn write_concern(&self) -> Option<&WriteConcern> {
@@ -220,6 +233,10 @@ impl<'a, T: Serialize> Operation for Insert<'a, T> {
     fn retryability(&self) -> Retryability {
         Retryability::Write
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 /// Data used for creating a BSON array.
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use bson::RawDocumentBuf;
 use serde::Deserialize;
 
@@ -84,6 +86,10 @@ impl Operation for ListDatabases {
     fn retryability(&self) -> Retryability {
         Retryability::Read
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::{
     bson::{doc, Document},
     cmap::{Command, RawCommandResponse, StreamDescription},
@@ -69,6 +71,7 @@ impl Operation for ListIndexes {
             description.server_address.clone(),
             self.options.as_ref().and_then(|o| o.batch_size),
             self.options.as_ref().and_then(|o| o.max_time),
+            self.timeout(),
         ))
     }
 
@@ -79,4 +82,8 @@ impl Operation for ListIndexes {
     fn retryability(&self) -> Retryability {
         Retryability::Read
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
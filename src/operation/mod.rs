@@ -1,15 +1,19 @@
 mod abort_transaction;
 mod aggregate;
+mod bulk_write;
+mod coll_stats;
 mod commit_transaction;
 mod count;
 mod count_documents;
 mod create;
 mod create_indexes;
+mod db_stats;
 mod delete;
 mod distinct;
 mod drop_collection;
 mod drop_database;
 mod drop_indexes;
+mod explain;
 mod find;
 mod find_and_modify;
 mod get_more;
@@ -17,13 +21,17 @@ mod insert;
 mod list_collections;
 mod list_databases;
 mod list_indexes;
+mod modify_collection;
+mod rename_collection;
 mod run_command;
+mod typed_command;
 mod update;
+mod user_management;
 
 #[cfg(test)]
 mod test;
 
-use std::{collections::VecDeque, fmt::Debug, ops::Deref};
+use std::{collections::VecDeque, fmt::Debug, ops::Deref, time::Duration};
 
 use bson::{RawBsonRef, RawDocument, RawDocumentBuf, Timestamp};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -50,16 +58,20 @@ use crate::{
 
 pub(crate) use abort_transaction::AbortTransaction;
 pub(crate) use aggregate::{Aggregate, AggregateTarget};
+pub(crate) use bulk_write::{BulkWrite, WriteStatement};
+pub(crate) use coll_stats::CollStats;
 pub(crate) use commit_transaction::CommitTransaction;
 pub(crate) use count::Count;
 pub(crate) use count_documents::CountDocuments;
 pub(crate) use create::Create;
 pub(crate) use create_indexes::CreateIndexes;
+pub(crate) use db_stats::DbStats;
 pub(crate) use delete::Delete;
 pub(crate) use distinct::Distinct;
 pub(crate) use drop_collection::DropCollection;
 pub(crate) use drop_database::DropDatabase;
 pub(crate) use drop_indexes::DropIndexes;
+pub(crate) use explain::Explain;
 pub(crate) use find::Find;
 pub(crate) use find_and_modify::FindAndModify;
 pub(crate) use get_more::GetMore;
@@ -67,8 +79,21 @@ pub(crate) use insert::Insert;
 pub(crate) use list_collections::ListCollections;
 pub(crate) use list_databases::ListDatabases;
 pub(crate) use list_indexes::ListIndexes;
+pub(crate) use modify_collection::ModifyCollection;
+pub(crate) use rename_collection::RenameCollection;
 pub(crate) use run_command::RunCommand;
+pub(crate) use typed_command::TypedCommand;
 pub(crate) use update::Update;
+pub(crate) use user_management::{
+    CreateRole,
+    CreateUser,
+    DropRole,
+    DropUser,
+    GrantRolesToUser,
+    RolesInfo,
+    UpdateUser,
+    UsersInfo,
+};
 
 const SERVER_4_9_0_WIRE_VERSION: i32 = 12;
 const SERVER_4_2_0_WIRE_VERSION: i32 = 8;
@@ -118,6 +143,12 @@ pub(crate) trait Operation {
         None
     }
 
+    /// The maximum amount of time this operation may take in total, if one was specified on its
+    /// options.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Whether or not this operation will request acknowledgment from the server.
     fn is_acknowledged(&self) -> bool {
         self.write_concern()
@@ -304,7 +335,7 @@ impl<T> WriteResponseBody<T> {
         let failure = BulkWriteFailure {
             write_errors: self.write_errors.clone(),
             write_concern_error: self.write_concern_error.clone(),
-            inserted_ids: Default::default(),
+            ..BulkWriteFailure::new()
         };
 
         Err(Error::new(
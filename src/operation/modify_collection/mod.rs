@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use bson::Document;
+
+use crate::{
+    bson::doc,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::{append_options, remove_empty_write_concern, Operation, WriteConcernOnlyBody},
+    options::{ModifyCollectionOptions, WriteConcern},
+    Namespace,
+};
+
+#[derive(Debug)]
+pub(crate) struct ModifyCollection {
+    ns: Namespace,
+    options: Option<ModifyCollectionOptions>,
+}
+
+impl ModifyCollection {
+    pub(crate) fn new(ns: Namespace, options: Option<ModifyCollectionOptions>) -> Self {
+        Self { ns, options }
+    }
+}
+
+impl Operation for ModifyCollection {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "collMod";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.ns.coll.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(
+            Self::NAME.to_string(),
+            self.ns.db.clone(),
+            body,
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    operation::{ModifyCollection, Operation},
+    options::{
+        ChangeStreamPreAndPostImages,
+        ModifyCollectionOptions,
+        ModifyIndex,
+        ValidationAction,
+    },
+    Namespace,
+};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let options = ModifyCollectionOptions::builder()
+        .validator(doc! { "x": { "$type": "int" } })
+        .validation_action(ValidationAction::Warn)
+        .change_stream_pre_and_post_images(ChangeStreamPreAndPostImages::new(true))
+        .index(
+            ModifyIndex::builder()
+                .name("x_1".to_string())
+                .expire_after(Duration::from_secs(60))
+                .hidden(true)
+                .build(),
+        )
+        .build();
+    let ns = Namespace {
+        db: "test_db".to_string(),
+        coll: "test_coll".to_string(),
+    };
+    let mut op = ModifyCollection::new(ns, Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "collMod");
+    assert_eq!(cmd.target_db.as_str(), "test_db");
+    assert_eq!(
+        cmd.body,
+        doc! {
+            "collMod": "test_coll",
+            "validator": { "x": { "$type": "int" } },
+            "validationAction": "warn",
+            "changeStreamPreAndPostImages": { "enabled": true },
+            "index": {
+                "name": "x_1",
+                "expireAfterSeconds": 60,
+                "hidden": true,
+            },
+        }
+    );
+}
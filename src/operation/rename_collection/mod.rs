@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use bson::Document;
+
+use crate::{
+    bson::doc,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::{append_options, remove_empty_write_concern, Operation, WriteConcernOnlyBody},
+    options::{RenameCollectionOptions, WriteConcern},
+    Namespace,
+};
+
+#[derive(Debug)]
+pub(crate) struct RenameCollection {
+    from: Namespace,
+    to: Namespace,
+    options: Option<RenameCollectionOptions>,
+}
+
+impl RenameCollection {
+    pub(crate) fn new(
+        from: Namespace,
+        to: Namespace,
+        options: Option<RenameCollectionOptions>,
+    ) -> Self {
+        Self { from, to, options }
+    }
+}
+
+impl Operation for RenameCollection {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "renameCollection";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.from.to_string(),
+            "to": self.to.to_string(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        // renameCollection must be run against the admin database.
+        Ok(Command::new(Self::NAME.to_string(), "admin".into(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    concern::{Acknowledgment, WriteConcern},
+    error::{ErrorKind, WriteFailure},
+    operation::{test::handle_response_test, Operation, RenameCollection},
+    options::RenameCollectionOptions,
+    Namespace,
+};
+
+fn rename(options: Option<RenameCollectionOptions>) -> RenameCollection {
+    RenameCollection::new(
+        Namespace {
+            db: "test_db".to_string(),
+            coll: "from".to_string(),
+        },
+        Namespace {
+            db: "test_db".to_string(),
+            coll: "to".to_string(),
+        },
+        options,
+    )
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let options = RenameCollectionOptions::builder()
+        .drop_target(true)
+        .write_concern(WriteConcern {
+            w: Some(Acknowledgment::Majority),
+            ..Default::default()
+        })
+        .build();
+    let mut op = rename(Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "renameCollection");
+    assert_eq!(cmd.target_db.as_str(), "admin");
+    assert_eq!(
+        cmd.body,
+        doc! {
+            "renameCollection": "test_db.from",
+            "to": "test_db.to",
+            "dropTarget": true,
+            "writeConcern": { "w": "majority" },
+        }
+    );
+
+    let mut op = rename(None);
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+    assert_eq!(
+        cmd.body,
+        doc! { "renameCollection": "test_db.from", "to": "test_db.to" }
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_write_concern_error() {
+    let op = rename(None);
+
+    handle_response_test(&op, doc! { "ok": 1 }).expect("handle should succeed");
+
+    let response = doc! {
+        "ok": 1,
+        "writeConcernError": {
+            "code": 100,
+            "codeName": "hello world",
+            "errmsg": "12345"
+        }
+    };
+    let error = handle_response_test(&op, response).expect_err("handle should fail");
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::Write(WriteFailure::WriteConcernError(_))
+    ));
+}
@@ -36,6 +36,18 @@ impl<'conn> RunCommand<'conn> {
             .map(|doc| bson::from_bson::<WriteConcern>(doc.clone()))
             .transpose()?;
 
+        // the reply to the command is returned to the caller, so one must always be requested.
+        if !write_concern
+            .as_ref()
+            .map(WriteConcern::is_acknowledged)
+            .unwrap_or(true)
+        {
+            return Err(ErrorKind::InvalidArgument {
+                message: "run_command does not support unacknowledged write concerns".to_string(),
+            }
+            .into());
+        }
+
         Ok(Self {
             db,
             command,
@@ -99,6 +111,10 @@ impl<'conn> Operation for RunCommand<'conn> {
         self.write_concern.as_ref()
     }
 
+    fn name(&self) -> &str {
+        self.command_name().unwrap_or(Self::NAME)
+    }
+
     fn supports_sessions(&self) -> bool {
         self.command_name()
             .map(|command_name| {
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod test;
+
+use std::convert::TryInto;
+
+use crate::{
+    bson::{self, Document},
+    bson_util,
+    client::SESSIONS_UNSUPPORTED_COMMANDS,
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    command::{CommandRetryability, RunnableCommand},
+    error::{ErrorKind, Result},
+    operation::{Operation, Retryability, WriteConcernOnlyBody},
+    options::WriteConcern,
+    selection_criteria::SelectionCriteria,
+};
+
+/// Runs a user-defined [`RunnableCommand`] against a database.
+pub(crate) struct TypedCommand<C> {
+    db: String,
+    name: String,
+    body: Document,
+    command: C,
+}
+
+impl<C: RunnableCommand> TypedCommand<C> {
+    pub(crate) fn new(db: String, command: C) -> Result<Self> {
+        let mut body = command.body()?;
+        let name = bson_util::first_key(&body)
+            .ok_or_else(|| ErrorKind::InvalidArgument {
+                message: "the body of a command must not be empty".into(),
+            })?
+            .to_string();
+
+        if let Some(write_concern) = command.write_concern() {
+            // the reply to the command is returned to the caller, so one must always be requested.
+            if !write_concern.is_acknowledged() {
+                return Err(ErrorKind::InvalidArgument {
+                    message: "typed commands do not support unacknowledged write concerns"
+                        .to_string(),
+                }
+                .into());
+            }
+            if !write_concern.is_empty() && !body.contains_key("writeConcern") {
+                body.insert("writeConcern", bson::to_bson(write_concern)?);
+            }
+        }
+
+        Ok(Self {
+            db,
+            name,
+            body,
+            command,
+        })
+    }
+}
+
+impl<C: RunnableCommand> Operation for TypedCommand<C> {
+    type O = C::Output;
+    type Command = Document;
+
+    // Since we can't actually specify a string statically here, we just put a descriptive string
+    // that should fail loudly if accidentally passed to the server.
+    const NAME: &'static str = "$typedCommand";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        Ok(Command::new(
+            self.name.clone(),
+            self.db.clone(),
+            self.body.clone(),
+        ))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let write_concern_info: WriteConcernOnlyBody = response.body()?;
+        write_concern_info.validate()?;
+
+        let response: Document = response.into_raw_document_buf().try_into()?;
+        self.command.handle_response(response)
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.command.selection_criteria()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.command.write_concern()
+    }
+
+    fn supports_sessions(&self) -> bool {
+        !SESSIONS_UNSUPPORTED_COMMANDS.contains(self.name.to_lowercase().as_str())
+    }
+
+    fn retryability(&self) -> Retryability {
+        match self.command.retryability() {
+            CommandRetryability::Read => Retryability::Read,
+            CommandRetryability::None => Retryability::None,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    bson::{doc, Document},
+    cmap::StreamDescription,
+    command::{CommandRetryability, RunnableCommand},
+    concern::{Acknowledgment, WriteConcern},
+    error::{ErrorKind, Result, WriteFailure},
+    operation::{test::handle_response_test, Operation, Retryability, TypedCommand},
+};
+
+struct Compact {
+    body: Document,
+    write_concern: Option<WriteConcern>,
+}
+
+impl Compact {
+    fn new() -> Self {
+        Self {
+            body: doc! { "compact": "test_coll" },
+            write_concern: None,
+        }
+    }
+}
+
+impl RunnableCommand for Compact {
+    type Output = i64;
+
+    fn body(&self) -> Result<Document> {
+        Ok(self.body.clone())
+    }
+
+    fn handle_response(&self, response: Document) -> Result<i64> {
+        response.get_i64("bytesFreed").map_err(|e| {
+            ErrorKind::InvalidResponse {
+                message: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.write_concern.as_ref()
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build() {
+    let mut op = TypedCommand::new("test_db".to_string(), Compact::new()).unwrap();
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name, "compact");
+    assert_eq!(cmd.target_db, "test_db");
+    assert_eq!(cmd.body, doc! { "compact": "test_coll" });
+    assert_eq!(op.retryability(), Retryability::None);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_with_write_concern() {
+    let command = Compact {
+        write_concern: Some(WriteConcern {
+            w: Some(Acknowledgment::Majority),
+            ..Default::default()
+        }),
+        ..Compact::new()
+    };
+    let mut op = TypedCommand::new("test_db".to_string(), command).unwrap();
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+    assert_eq!(
+        cmd.body,
+        doc! { "compact": "test_coll", "writeConcern": { "w": "majority" } }
+    );
+
+    // a write concern in the body takes precedence.
+    let command = Compact {
+        body: doc! { "compact": "test_coll", "writeConcern": { "w": 1 } },
+        write_concern: Some(WriteConcern {
+            w: Some(Acknowledgment::Majority),
+            ..Default::default()
+        }),
+    };
+    let mut op = TypedCommand::new("test_db".to_string(), command).unwrap();
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+    assert_eq!(
+        cmd.body,
+        doc! { "compact": "test_coll", "writeConcern": { "w": 1 } }
+    );
+}
+
+#[test]
+fn invalid_commands() {
+    let command = Compact {
+        body: doc! {},
+        ..Compact::new()
+    };
+    let error = TypedCommand::new("test_db".to_string(), command)
+        .err()
+        .expect("empty command should fail");
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+
+    let command = Compact {
+        write_concern: Some(WriteConcern {
+            w: Some(Acknowledgment::Nodes(0)),
+            ..Default::default()
+        }),
+        ..Compact::new()
+    };
+    let error = TypedCommand::new("test_db".to_string(), command)
+        .err()
+        .expect("unacknowledged write concern should fail");
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+}
+
+#[test]
+fn retryability() {
+    struct Ping;
+
+    impl RunnableCommand for Ping {
+        type Output = ();
+
+        fn body(&self) -> Result<Document> {
+            Ok(doc! { "ping": 1 })
+        }
+
+        fn handle_response(&self, _response: Document) -> Result<()> {
+            Ok(())
+        }
+
+        fn retryability(&self) -> CommandRetryability {
+            CommandRetryability::Read
+        }
+    }
+
+    let op = TypedCommand::new("admin".to_string(), Ping).unwrap();
+    assert_eq!(op.retryability(), Retryability::Read);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn handle_response() {
+    let op = TypedCommand::new("test_db".to_string(), Compact::new()).unwrap();
+
+    let bytes_freed = handle_response_test(&op, doc! { "ok": 1, "bytesFreed": 1024_i64 })
+        .expect("handle should succeed");
+    assert_eq!(bytes_freed, 1024);
+
+    let response = doc! {
+        "ok": 1,
+        "bytesFreed": 1024_i64,
+        "writeConcernError": {
+            "code": 64,
+            "codeName": "WriteConcernFailed",
+            "errmsg": "waiting for replication timed out",
+        },
+    };
+    let error = handle_response_test(&op, response).expect_err("handle should fail");
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::Write(WriteFailure::WriteConcernError(_))
+    ));
+}
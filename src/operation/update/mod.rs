@@ -1,13 +1,15 @@
 #[cfg(test)]
 mod test;
 
+use std::time::Duration;
+
 use serde::Deserialize;
 
 use crate::{
     bson::{doc, Bson, Document},
     bson_util,
     cmap::{Command, RawCommandResponse, StreamDescription},
-    error::{convert_bulk_errors, Result},
+    error::{convert_bulk_errors, ErrorKind, Result},
     operation::{Operation, Retryability, WriteResponseBody},
     options::{UpdateModifications, UpdateOptions, WriteConcern},
     results::UpdateResult,
@@ -62,6 +64,20 @@ impl Operation for Update {
     const NAME: &'static str = "update";
 
     fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        if !self.is_acknowledged()
+            && self
+                .options
+                .as_ref()
+                .map(|opts| opts.hint.is_some())
+                .unwrap_or(false)
+        {
+            return Err(ErrorKind::InvalidArgument {
+                message: "Specifying a hint is not supported with an unacknowledged write concern"
+                    .to_string(),
+            }
+            .into());
+        }
+
         let mut body = doc! {
             Self::NAME: self.ns.coll.clone(),
         };
@@ -118,6 +134,10 @@ impl Operation for Update {
         raw_response: RawCommandResponse,
         _description: &StreamDescription,
     ) -> Result<Self::O> {
+        if !self.is_acknowledged() {
+            return Ok(UpdateResult::unacknowledged());
+        }
+
         let response: WriteResponseBody<UpdateBody> = raw_response.body()?;
         response.validate().map_err(convert_bulk_errors)?;
 
@@ -135,6 +155,7 @@ impl Operation for Update {
             matched_count,
             modified_count,
             upserted_id,
+            acknowledged: true,
         })
     }
 
@@ -151,6 +172,10 @@ impl Operation for Update {
             Retryability::None
         }
     }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
 }
 
 #[derive(Deserialize)]
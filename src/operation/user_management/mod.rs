@@ -0,0 +1,454 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::{
+    bson::{doc, Bson, Document},
+    cmap::{Command, RawCommandResponse, StreamDescription},
+    error::Result,
+    operation::{append_options, remove_empty_write_concern, Operation, WriteConcernOnlyBody},
+    options::{
+        CreateRoleOptions,
+        CreateUserOptions,
+        DropRoleOptions,
+        DropUserOptions,
+        GrantRolesOptions,
+        Role,
+        RolesInfoOptions,
+        SelectionCriteria,
+        UpdateUserOptions,
+        UsersInfoOptions,
+        WriteConcern,
+    },
+    results::{RoleInfo, UserInfo},
+};
+
+/// Inserts an empty array for `key` if the options did not provide one, for commands that require
+/// the field to be present.
+fn ensure_array(body: &mut Document, key: &str) {
+    if !body.contains_key(key) {
+        body.insert(key, Bson::Array(Vec::new()));
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CreateUser {
+    db: String,
+    user: String,
+    options: Option<CreateUserOptions>,
+}
+
+impl CreateUser {
+    pub(crate) fn new(db: String, user: String, options: Option<CreateUserOptions>) -> Self {
+        Self { db, user, options }
+    }
+}
+
+impl Operation for CreateUser {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "createUser";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.user.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+        ensure_array(&mut body, "roles");
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct UpdateUser {
+    db: String,
+    user: String,
+    options: Option<UpdateUserOptions>,
+}
+
+impl UpdateUser {
+    pub(crate) fn new(db: String, user: String, options: Option<UpdateUserOptions>) -> Self {
+        Self { db, user, options }
+    }
+}
+
+impl Operation for UpdateUser {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "updateUser";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.user.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DropUser {
+    db: String,
+    user: String,
+    options: Option<DropUserOptions>,
+}
+
+impl DropUser {
+    pub(crate) fn new(db: String, user: String, options: Option<DropUserOptions>) -> Self {
+        Self { db, user, options }
+    }
+}
+
+impl Operation for DropUser {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "dropUser";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.user.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct UsersInfo {
+    db: String,
+    options: Option<UsersInfoOptions>,
+}
+
+impl UsersInfo {
+    pub(crate) fn new(db: String, options: Option<UsersInfoOptions>) -> Self {
+        Self { db, options }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UsersInfoBody {
+    users: Vec<UserInfo>,
+}
+
+impl Operation for UsersInfo {
+    type O = Vec<UserInfo>;
+    type Command = Document;
+
+    const NAME: &'static str = "usersInfo";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: 1,
+        };
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: UsersInfoBody = response.body()?;
+        Ok(response.users)
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.selection_criteria.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GrantRolesToUser {
+    db: String,
+    user: String,
+    roles: Vec<Role>,
+    options: Option<GrantRolesOptions>,
+}
+
+impl GrantRolesToUser {
+    pub(crate) fn new(
+        db: String,
+        user: String,
+        roles: Vec<Role>,
+        options: Option<GrantRolesOptions>,
+    ) -> Self {
+        Self {
+            db,
+            user,
+            roles,
+            options,
+        }
+    }
+}
+
+impl Operation for GrantRolesToUser {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "grantRolesToUser";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.user.clone(),
+            "roles": bson::to_bson(&self.roles)?,
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct CreateRole {
+    db: String,
+    role: String,
+    options: Option<CreateRoleOptions>,
+}
+
+impl CreateRole {
+    pub(crate) fn new(db: String, role: String, options: Option<CreateRoleOptions>) -> Self {
+        Self { db, role, options }
+    }
+}
+
+impl Operation for CreateRole {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "createRole";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.role.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+        ensure_array(&mut body, "privileges");
+        ensure_array(&mut body, "roles");
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DropRole {
+    db: String,
+    role: String,
+    options: Option<DropRoleOptions>,
+}
+
+impl DropRole {
+    pub(crate) fn new(db: String, role: String, options: Option<DropRoleOptions>) -> Self {
+        Self { db, role, options }
+    }
+}
+
+impl Operation for DropRole {
+    type O = ();
+    type Command = Document;
+
+    const NAME: &'static str = "dropRole";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: self.role.clone(),
+        };
+
+        remove_empty_write_concern!(self.options);
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: WriteConcernOnlyBody = response.body()?;
+        response.validate()
+    }
+
+    fn write_concern(&self) -> Option<&WriteConcern> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.write_concern.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RolesInfo {
+    db: String,
+    options: Option<RolesInfoOptions>,
+}
+
+impl RolesInfo {
+    pub(crate) fn new(db: String, options: Option<RolesInfoOptions>) -> Self {
+        Self { db, options }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RolesInfoBody {
+    roles: Vec<RoleInfo>,
+}
+
+impl Operation for RolesInfo {
+    type O = Vec<RoleInfo>;
+    type Command = Document;
+
+    const NAME: &'static str = "rolesInfo";
+
+    fn build(&mut self, _description: &StreamDescription) -> Result<Command> {
+        let mut body = doc! {
+            Self::NAME: 1,
+        };
+        append_options(&mut body, self.options.as_ref())?;
+
+        Ok(Command::new(Self::NAME.to_string(), self.db.clone(), body))
+    }
+
+    fn handle_response(
+        &self,
+        response: RawCommandResponse,
+        _description: &StreamDescription,
+    ) -> Result<Self::O> {
+        let response: RolesInfoBody = response.body()?;
+        Ok(response.roles)
+    }
+
+    fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.options
+            .as_ref()
+            .and_then(|opts| opts.selection_criteria.as_ref())
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.options.as_ref().and_then(|opts| opts.timeout)
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    bson::doc,
+    cmap::StreamDescription,
+    operation::{
+        test::handle_response_test,
+        CreateRole,
+        CreateUser,
+        GrantRolesToUser,
+        Operation,
+        RolesInfo,
+        UpdateUser,
+        UsersInfo,
+    },
+    options::{
+        AuthMechanism,
+        AuthenticationRestriction,
+        CreateRoleOptions,
+        CreateUserOptions,
+        Privilege,
+        PrivilegeResource,
+        Role,
+        UpdateUserOptions,
+        UsersInfoOptions,
+        WriteConcern,
+    },
+};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_create_user() {
+    let options = CreateUserOptions::builder()
+        .password("hunter2".to_string())
+        .custom_data(doc! { "team": "billing" })
+        .roles(vec![Role::new("readWrite", "app")])
+        .authentication_restrictions(vec![AuthenticationRestriction::builder()
+            .client_source(vec!["10.0.0.0/8".to_string()])
+            .build()])
+        .mechanisms(vec![AuthMechanism::ScramSha256])
+        .write_concern(WriteConcern::builder().journal(true).build())
+        .build();
+    let mut op = CreateUser::new("app".to_string(), "alice".to_string(), Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "createUser");
+    assert_eq!(cmd.target_db.as_str(), "app");
+    assert_eq!(
+        cmd.body,
+        doc! {
+            "createUser": "alice",
+            "pwd": "hunter2",
+            "customData": { "team": "billing" },
+            "roles": [{ "role": "readWrite", "db": "app" }],
+            "authenticationRestrictions": [{ "clientSource": ["10.0.0.0/8"] }],
+            "mechanisms": ["SCRAM-SHA-256"],
+            "writeConcern": { "j": true },
+        }
+    );
+
+    // The server requires the roles field even if no roles are granted.
+    let mut op = CreateUser::new("app".to_string(), "bob".to_string(), None);
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+    assert_eq!(cmd.body, doc! { "createUser": "bob", "roles": [] });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_create_role() {
+    let options = CreateRoleOptions::builder()
+        .privileges(vec![
+            Privilege::new(
+                PrivilegeResource::Collection {
+                    db: "app".to_string(),
+                    collection: "".to_string(),
+                },
+                ["find", "insert"],
+            ),
+            Privilege::new(PrivilegeResource::Cluster, ["serverStatus"]),
+        ])
+        .build();
+    let mut op = CreateRole::new("app".to_string(), "appWriter".to_string(), Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(cmd.name.as_str(), "createRole");
+    assert_eq!(
+        cmd.body,
+        doc! {
+            "createRole": "appWriter",
+            "privileges": [
+                { "resource": { "db": "app", "collection": "" }, "actions": ["find", "insert"] },
+                { "resource": { "cluster": true }, "actions": ["serverStatus"] },
+            ],
+            "roles": [],
+        }
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn build_grant_roles() {
+    let mut op = GrantRolesToUser::new(
+        "app".to_string(),
+        "alice".to_string(),
+        vec![Role::new("read", "reporting")],
+        None,
+    );
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+
+    assert_eq!(
+        cmd.body,
+        doc! {
+            "grantRolesToUser": "alice",
+            "roles": [{ "role": "read", "db": "reporting" }],
+        }
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn users_info() {
+    let options = UsersInfoOptions::builder()
+        .filter(doc! { "user": "alice" })
+        .show_privileges(true)
+        .build();
+    let mut op = UsersInfo::new("app".to_string(), Some(options));
+
+    let cmd = op
+        .build(&StreamDescription::new_testing())
+        .expect("build should succeed");
+    assert_eq!(
+        cmd.body,
+        doc! { "usersInfo": 1, "filter": { "user": "alice" }, "showPrivileges": true }
+    );
+
+    let response = doc! {
+        "ok": 1,
+        "users": [{
+            "_id": "app.alice",
+            "user": "alice",
+            "db": "app",
+            "customData": { "team": "billing" },
+            "roles": [{ "role": "readWrite", "db": "app" }],
+            "mechanisms": ["SCRAM-SHA-1", "SCRAM-SHA-256"],
+            "inheritedRoles": [{ "role": "readWrite", "db": "app" }],
+            "inheritedPrivileges": [{
+                "resource": { "db": "app", "collection": "" },
+                "actions": ["find"],
+            }],
+        }],
+    };
+    let users = handle_response_test(&op, response).expect("handle should succeed");
+
+    assert_eq!(users.len(), 1);
+    let user = &users[0];
+    assert_eq!(user.user, "alice");
+    assert_eq!(user.db, "app");
+    assert_eq!(user.roles, vec![Role::new("readWrite", "app")]);
+    assert_eq!(user.custom_data, Some(doc! { "team": "billing" }));
+    assert_eq!(
+        user.mechanisms,
+        vec![AuthMechanism::ScramSha1, AuthMechanism::ScramSha256]
+    );
+    assert_eq!(
+        user.inherited_privileges,
+        Some(vec![Privilege::new(
+            PrivilegeResource::Collection {
+                db: "app".to_string(),
+                collection: "".to_string(),
+            },
+            ["find"],
+        )])
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn roles_info() {
+    let op = RolesInfo::new("admin".to_string(), None);
+
+    let response = doc! {
+        "ok": 1,
+        "roles": [{
+            "role": "monitor",
+            "db": "admin",
+            "isBuiltin": false,
+            "roles": [{ "role": "clusterMonitor", "db": "admin" }],
+            "inheritedRoles": [{ "role": "clusterMonitor", "db": "admin" }],
+            "privileges": [
+                { "resource": { "cluster": true }, "actions": ["serverStatus"] },
+                { "resource": { "anyResource": true }, "actions": ["collStats"] },
+            ],
+        }],
+    };
+    let roles = handle_response_test(&op, response).expect("handle should succeed");
+
+    assert_eq!(roles.len(), 1);
+    let role = &roles[0];
+    assert_eq!(role.role, "monitor");
+    assert!(!role.is_builtin);
+    assert_eq!(role.roles, vec![Role::new("clusterMonitor", "admin")]);
+    assert_eq!(
+        role.privileges,
+        Some(vec![
+            Privilege::new(PrivilegeResource::Cluster, ["serverStatus"]),
+            Privilege::new(PrivilegeResource::AnyResource, ["collStats"]),
+        ])
+    );
+    assert_eq!(role.inherited_privileges, None);
+}
+
+#[test]
+fn debug_redacts_password() {
+    let options = CreateUserOptions::builder()
+        .password("hunter2".to_string())
+        .roles(vec![Role::new("read", "app")])
+        .build();
+    let op = CreateUser::new("app".to_string(), "alice".to_string(), Some(options));
+    let formatted = format!("{:?}", op);
+    assert!(!formatted.contains("hunter2"), "{}", formatted);
+    assert!(formatted.contains("REDACTED"), "{}", formatted);
+    assert!(formatted.contains("read"), "{}", formatted);
+
+    let options = UpdateUserOptions::builder()
+        .password("correct horse".to_string())
+        .build();
+    let op = UpdateUser::new("app".to_string(), "alice".to_string(), Some(options));
+    let formatted = format!("{:?}", op);
+    assert!(!formatted.contains("correct horse"), "{}", formatted);
+    assert!(formatted.contains("REDACTED"), "{}", formatted);
+}
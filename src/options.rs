@@ -15,20 +15,21 @@
 //!                   .build();
 //! ```
 
+#[cfg(feature = "csfle")]
+pub use crate::client::csfle::options::*;
 pub use crate::{
+    change_stream::options::*,
     client::{auth::*, options::*},
     coll::options::*,
     collation::*,
     compression::*,
     concern::*,
     db::options::*,
+    gridfs::options::*,
     index::options::*,
     selection_criteria::*,
 };
 
-#[allow(unused)]
-pub(crate) use crate::change_stream::options::*;
-
 /// Updates an options struct with the read preference/read concern/write concern of a
 /// client/database/collection.
 macro_rules! resolve_options {
@@ -6,7 +6,10 @@ use crate::{
     bson::{Bson, Document},
     bson_util,
     change_stream::event::ResumeToken,
-    db::options::CreateCollectionOptions,
+    db::options::{CreateCollectionOptions, Privilege, Role},
+    options::AuthMechanism,
+    IndexModel,
+    Namespace,
 };
 
 use bson::{Binary, RawDocumentBuf};
@@ -20,12 +23,18 @@ use serde::{Deserialize, Serialize};
 pub struct InsertOneResult {
     /// The `_id` field of the document inserted.
     pub inserted_id: Bson,
+
+    /// Whether the server acknowledged the insert. This is `false` when the insert was performed
+    /// with an unacknowledged write concern (i.e. `w: 0`), in which case `inserted_id` is the
+    /// `_id` of the document that was sent to the server.
+    pub acknowledged: bool,
 }
 
 impl InsertOneResult {
     pub(crate) fn from_insert_many_result(result: InsertManyResult) -> Self {
         Self {
             inserted_id: result.inserted_ids.get(&0).cloned().unwrap_or(Bson::Null),
+            acknowledged: result.acknowledged,
         }
     }
 }
@@ -38,12 +47,18 @@ impl InsertOneResult {
 pub struct InsertManyResult {
     /// The `_id` field of the documents inserted.
     pub inserted_ids: HashMap<usize, Bson>,
+
+    /// Whether the server acknowledged the inserts. This is `false` when the inserts were
+    /// performed with an unacknowledged write concern (i.e. `w: 0`), in which case
+    /// `inserted_ids` contains the `_id`s of all of the documents that were sent to the server.
+    pub acknowledged: bool,
 }
 
 impl InsertManyResult {
     pub(crate) fn new() -> Self {
         InsertManyResult {
             inserted_ids: HashMap::new(),
+            acknowledged: true,
         }
     }
 }
@@ -64,6 +79,22 @@ pub struct UpdateResult {
 
     /// The `_id` field of the upserted document.
     pub upserted_id: Option<Bson>,
+
+    /// Whether the server acknowledged the update. This is `false` when the update was performed
+    /// with an unacknowledged write concern (i.e. `w: 0`), in which case the other fields of this
+    /// result are not meaningful.
+    pub acknowledged: bool,
+}
+
+impl UpdateResult {
+    pub(crate) fn unacknowledged() -> Self {
+        Self {
+            matched_count: 0,
+            modified_count: 0,
+            upserted_id: None,
+            acknowledged: false,
+        }
+    }
 }
 
 /// The result of a [`Collection::delete_one`](../struct.Collection.html#method.delete_one) or
@@ -75,6 +106,81 @@ pub struct DeleteResult {
     /// The number of documents deleted by the operation.
     #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
     pub deleted_count: u64,
+
+    /// Whether the server acknowledged the delete. This is `false` when the delete was performed
+    /// with an unacknowledged write concern (i.e. `w: 0`), in which case `deleted_count` is not
+    /// meaningful.
+    pub acknowledged: bool,
+}
+
+impl DeleteResult {
+    pub(crate) fn unacknowledged() -> Self {
+        Self {
+            deleted_count: 0,
+            acknowledged: false,
+        }
+    }
+}
+
+/// The result of a [`Collection::bulk_write`](../struct.Collection.html#method.bulk_write)
+/// operation.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct BulkWriteResult {
+    /// The number of documents inserted.
+    #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
+    pub inserted_count: u64,
+
+    /// The number of documents that matched the filters of updates and replacements.
+    #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
+    pub matched_count: u64,
+
+    /// The number of documents modified by updates and replacements.
+    #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
+    pub modified_count: u64,
+
+    /// The number of documents deleted.
+    #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
+    pub deleted_count: u64,
+
+    /// The number of documents upserted.
+    #[serde(serialize_with = "crate::bson::serde_helpers::serialize_u64_as_i64")]
+    pub upserted_count: u64,
+
+    /// The `_id` fields of the inserted documents, keyed by the index of the corresponding write
+    /// model.
+    pub inserted_ids: HashMap<usize, Bson>,
+
+    /// The `_id` fields of the upserted documents, keyed by the index of the corresponding write
+    /// model.
+    pub upserted_ids: HashMap<usize, Bson>,
+
+    /// Whether the server acknowledged the writes. This is `false` when the writes were performed
+    /// with an unacknowledged write concern (i.e. `w: 0`), in which case only `inserted_ids` is
+    /// meaningful.
+    pub acknowledged: bool,
+}
+
+impl BulkWriteResult {
+    pub(crate) fn new() -> Self {
+        Self {
+            acknowledged: true,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the results of a batch of writes to these results.
+    pub(crate) fn merge(&mut self, other: BulkWriteResult) {
+        self.inserted_count += other.inserted_count;
+        self.matched_count += other.matched_count;
+        self.modified_count += other.modified_count;
+        self.deleted_count += other.deleted_count;
+        self.upserted_count += other.upserted_count;
+        self.inserted_ids.extend(other.inserted_ids);
+        self.upserted_ids.extend(other.upserted_ids);
+        self.acknowledged = other.acknowledged;
+    }
 }
 
 /// Information about the index created as a result of a
@@ -103,6 +209,261 @@ impl CreateIndexesResult {
     }
 }
 
+/// The output of explaining an operation, e.g. with
+/// [`Collection::find_explain`](../struct.Collection.html#method.find_explain).
+///
+/// The shape of explain output varies across server versions and deployment types, so only the
+/// most commonly used sections are extracted here; the full response is available in `raw`. See
+/// the MongoDB [manual](https://www.mongodb.com/docs/manual/reference/explain-results/) for more
+/// information.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct ExplainResult {
+    /// The plan selected by the query planner, if the server reported one.
+    pub winning_plan: Option<Document>,
+
+    /// Statistics describing the execution of the winning plan. These are only present when the
+    /// operation was explained with a verbosity of
+    /// [`ExecutionStats`](crate::options::ExplainVerbosity::ExecutionStats) or higher.
+    pub execution_stats: Option<Document>,
+
+    /// The full response returned by the server.
+    pub raw: Document,
+}
+
+impl ExplainResult {
+    pub(crate) fn new(raw: Document) -> Self {
+        // Aggregations whose initial stages are executed as a query report the query's
+        // explain output within the `$cursor` stage rather than at the top level.
+        let explained = raw
+            .get_array("stages")
+            .ok()
+            .and_then(|stages| stages.first())
+            .and_then(Bson::as_document)
+            .and_then(|stage| stage.get_document("$cursor").ok())
+            .unwrap_or(&raw);
+
+        let winning_plan = explained
+            .get_document("queryPlanner")
+            .and_then(|planner| planner.get_document("winningPlan"))
+            .ok()
+            .cloned();
+        let execution_stats = explained.get_document("executionStats").ok().cloned();
+
+        Self {
+            winning_plan,
+            execution_stats,
+            raw,
+        }
+    }
+}
+
+/// Storage statistics for a collection, as returned by
+/// [`Collection::stats`](../struct.Collection.html#method.stats).
+///
+/// Sizes are in bytes unless a `scale` was specified. See the MongoDB
+/// [manual](https://www.mongodb.com/docs/manual/reference/command/collStats/#output) for more
+/// information.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CollectionStats {
+    /// The namespace of the collection.
+    pub ns: Namespace,
+
+    /// The number of documents in the collection.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub count: u64,
+
+    /// The total uncompressed size of the documents in the collection.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub size: u64,
+
+    /// The amount of storage allocated to the collection.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub storage_size: u64,
+
+    /// The number of indexes on the collection.
+    #[serde(
+        rename = "nindexes",
+        deserialize_with = "bson_util::deserialize_u64_from_bson_number"
+    )]
+    pub index_count: u64,
+
+    /// The total size of the indexes on the collection.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub total_index_size: u64,
+
+    /// The size of each index on the collection, keyed by index name.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_map_from_bson_numbers")]
+    pub index_sizes: HashMap<String, u64>,
+
+    /// Whether the collection is capped.
+    #[serde(default)]
+    pub capped: bool,
+}
+
+/// Storage statistics for a database, as returned by
+/// [`Database::stats`](../struct.Database.html#method.stats).
+///
+/// Sizes are in bytes unless a `scale` was specified. See the MongoDB
+/// [manual](https://www.mongodb.com/docs/manual/reference/command/dbStats/#output) for more
+/// information.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DatabaseStats {
+    /// The name of the database.
+    pub db: String,
+
+    /// The number of collections in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub collections: u64,
+
+    /// The number of views in the database.
+    #[serde(
+        default,
+        deserialize_with = "bson_util::deserialize_u64_from_bson_number"
+    )]
+    pub views: u64,
+
+    /// The number of documents in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub objects: u64,
+
+    /// The total uncompressed size of the documents in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub data_size: u64,
+
+    /// The amount of storage allocated to the collections in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub storage_size: u64,
+
+    /// The number of indexes in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub indexes: u64,
+
+    /// The total size of the indexes in the database.
+    #[serde(deserialize_with = "bson_util::deserialize_u64_from_bson_number")]
+    pub index_size: u64,
+}
+
+/// The outcome of a
+/// [`Collection::sync_indexes`](../struct.Collection.html#method.sync_indexes) operation. When the
+/// operation is run as a dry run, this describes the changes that would have been made.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SyncIndexesResult {
+    /// The names of the indexes that were created.
+    pub created: Vec<String>,
+
+    /// The names of the indexes that were dropped.
+    pub dropped: Vec<String>,
+
+    /// The names of the existing indexes that already matched a desired index. An existing index
+    /// matches a desired index with a different name if their keys and options are the same.
+    pub unchanged: Vec<String>,
+
+    /// The desired indexes that conflict with an existing index. These are left unchanged.
+    pub conflicts: Vec<IndexConflict>,
+
+    /// Whether this was a dry run, in which case the collection was not modified.
+    pub dry_run: bool,
+}
+
+/// A desired index whose definition conflicts with an existing index, as reported by
+/// [`Collection::sync_indexes`](../struct.Collection.html#method.sync_indexes).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct IndexConflict {
+    /// The name of the desired index.
+    pub name: String,
+
+    /// The desired definition of the index.
+    pub desired: IndexModel,
+
+    /// The definition of the existing index.
+    pub existing: IndexModel,
+
+    /// The properties of the index that differ, named as they are in `listIndexes` output (e.g.
+    /// `"key"`, `"unique"` or `"expireAfterSeconds"`).
+    pub differences: Vec<String>,
+}
+
+/// Information about a user, as returned from
+/// [`Database::users_info`](../struct.Database.html#method.users_info).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct UserInfo {
+    /// The name of the user.
+    pub user: String,
+
+    /// The database the user is defined in.
+    pub db: String,
+
+    /// The roles granted directly to the user.
+    #[serde(default)]
+    pub roles: Vec<Role>,
+
+    /// The custom data stored with the user, if any.
+    pub custom_data: Option<Document>,
+
+    /// The SCRAM mechanisms the user has credentials for.
+    #[serde(default, deserialize_with = "bson_util::deserialize_auth_mechanisms")]
+    pub mechanisms: Vec<AuthMechanism>,
+
+    /// The user's credentials. Only present if
+    /// [`UsersInfoOptions::show_credentials`](crate::options::UsersInfoOptions::show_credentials)
+    /// was set.
+    pub credentials: Option<Document>,
+
+    /// Every role the user has, including those inherited through other roles. Only present if
+    /// [`UsersInfoOptions::show_privileges`](crate::options::UsersInfoOptions::show_privileges)
+    /// was set.
+    pub inherited_roles: Option<Vec<Role>>,
+
+    /// Every privilege the user has through its roles. Only present if
+    /// [`UsersInfoOptions::show_privileges`](crate::options::UsersInfoOptions::show_privileges)
+    /// was set.
+    pub inherited_privileges: Option<Vec<Privilege>>,
+}
+
+/// Information about a role, as returned from
+/// [`Database::roles_info`](../struct.Database.html#method.roles_info).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct RoleInfo {
+    /// The name of the role.
+    pub role: String,
+
+    /// The database the role is defined in.
+    pub db: String,
+
+    /// Whether the role is one of the server's built-in roles.
+    #[serde(default)]
+    pub is_builtin: bool,
+
+    /// The roles this role inherits from directly.
+    #[serde(default)]
+    pub roles: Vec<Role>,
+
+    /// Every role this role inherits from, directly or indirectly.
+    #[serde(default)]
+    pub inherited_roles: Vec<Role>,
+
+    /// The privileges granted directly by the role. Only present if
+    /// [`RolesInfoOptions::show_privileges`](crate::options::RolesInfoOptions::show_privileges)
+    /// was set.
+    pub privileges: Option<Vec<Privilege>>,
+
+    /// Every privilege granted by the role, including inherited ones. Only present if
+    /// [`RolesInfoOptions::show_privileges`](crate::options::RolesInfoOptions::show_privileges)
+    /// was set.
+    pub inherited_privileges: Option<Vec<Privilege>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct GetMoreResult {
     pub(crate) batch: VecDeque<RawDocumentBuf>,
@@ -5,10 +5,32 @@ use serde::Deserialize;
 
 #[derive(Clone, Debug, Default)]
 pub(crate) struct HttpClient {
-    #[cfg(feature = "aws-auth")]
+    #[cfg(any(feature = "aws-auth", feature = "ocsp"))]
     inner: reqwest::Client,
 }
 
+#[cfg(feature = "ocsp")]
+impl HttpClient {
+    /// Executes an HTTP POST request with the given body and returns the response body as bytes.
+    pub(crate) async fn post_and_read_bytes(
+        &self,
+        uri: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> reqwest::Result<Vec<u8>> {
+        let response = self
+            .inner
+            .post(uri)
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
 #[cfg(feature = "aws-auth")]
 impl HttpClient {
     /// Executes an HTTP GET request and deserializes the JSON response.
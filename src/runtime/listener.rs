@@ -0,0 +1,53 @@
+use super::{stream::AsyncTcpStream, AsyncStream};
+use crate::error::Result;
+
+/// A runtime-agnostic TCP listener, used to accept connections for the mock server in
+/// `test_util`.
+#[derive(Debug)]
+pub(crate) enum AsyncTcpListener {
+    /// Wrapper around `tokio::net::TcpListener`.
+    #[cfg(feature = "tokio-runtime")]
+    Tokio(tokio::net::TcpListener),
+
+    /// Wrapper around `async_std::net::TcpListener`.
+    #[cfg(feature = "async-std-runtime")]
+    AsyncStd(async_std::net::TcpListener),
+}
+
+impl AsyncTcpListener {
+    /// Wraps an already bound, non-blocking standard library listener.
+    ///
+    /// Note: this must only be called from an async block or function running on a runtime.
+    pub(crate) fn from_std(listener: std::net::TcpListener) -> Result<Self> {
+        #[cfg(feature = "tokio-runtime")]
+        {
+            Ok(Self::Tokio(tokio::net::TcpListener::from_std(listener)?))
+        }
+
+        #[cfg(feature = "async-std-runtime")]
+        {
+            Ok(Self::AsyncStd(listener.into()))
+        }
+    }
+
+    /// Waits for the next incoming connection.
+    pub(crate) async fn accept(&self) -> Result<AsyncStream> {
+        let stream: AsyncTcpStream = match self {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(listener) => {
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                stream.into()
+            }
+
+            #[cfg(feature = "async-std-runtime")]
+            Self::AsyncStd(listener) => {
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                stream.into()
+            }
+        };
+
+        Ok(AsyncStream::Tcp(stream))
+    }
+}
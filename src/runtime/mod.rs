@@ -5,8 +5,18 @@ mod http;
 #[cfg(feature = "async-std-runtime")]
 mod interval;
 mod join_handle;
+#[cfg(any(feature = "test-util", test))]
+mod listener;
+#[cfg(feature = "ocsp")]
+mod ocsp;
 mod resolver;
 mod stream;
+#[cfg(feature = "openssl-tls")]
+#[path = "tls_openssl.rs"]
+mod tls;
+#[cfg(not(feature = "openssl-tls"))]
+#[path = "tls_rustls.rs"]
+mod tls;
 
 use std::{future::Future, net::SocketAddr, time::Duration};
 
@@ -16,12 +26,14 @@ pub(crate) use self::{
     async_write_ext::{AsyncLittleEndianWrite, SyncLittleEndianWrite},
     join_handle::AsyncJoinHandle,
     resolver::AsyncResolver,
-    stream::AsyncStream,
+    stream::{AsyncStream, DEFAULT_CONNECT_TIMEOUT},
 };
 use crate::{error::Result, options::ServerAddress};
 pub(crate) use http::HttpClient;
 #[cfg(feature = "async-std-runtime")]
 use interval::Interval;
+#[cfg(any(feature = "test-util", test))]
+pub(crate) use listener::AsyncTcpListener;
 #[cfg(feature = "tokio-runtime")]
 use tokio::time::Interval;
 
@@ -170,7 +182,8 @@ impl AsyncRuntime {
 
             #[cfg(feature = "async-std-runtime")]
             Self::AsyncStd => {
-                let host = (address.host(), address.port().unwrap_or(27017));
+                let host = address.host();
+                let host = (host.as_ref(), address.port().unwrap_or(27017));
                 let socket_addrs = async_std::net::ToSocketAddrs::to_socket_addrs(&host).await?;
                 Ok(socket_addrs)
             }
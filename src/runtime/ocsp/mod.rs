@@ -0,0 +1,161 @@
+//! Certificate revocation checking via OCSP ([RFC 6960](https://tools.ietf.org/html/rfc6960)).
+//!
+//! The TLS backends provide a [`RevocationChecker`] for the server's certificate, and
+//! [`check_revocation`] uses it to determine the certificate's status from a stapled response, a
+//! previously cached response, or the OCSP responder listed in the certificate, in that order.
+
+#[cfg(feature = "openssl-tls")]
+#[path = "openssl.rs"]
+mod backend;
+#[cfg(not(feature = "openssl-tls"))]
+#[path = "rustls.rs"]
+mod backend;
+#[cfg(test)]
+mod test;
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use lazy_static::lazy_static;
+
+pub(super) use self::backend::Checker;
+use super::HttpClient;
+use crate::{client::options::TlsOptions, error::Result, RUNTIME};
+
+/// How long to wait for an OCSP responder to reply before moving on.
+const OCSP_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How far in the future a response's `thisUpdate` time may be, to account for clock skew.
+pub(crate) const CLOCK_SKEW_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+lazy_static! {
+    static ref HTTP_CLIENT: HttpClient = HttpClient::default();
+
+    /// Verified OCSP responses that include a `nextUpdate` time, keyed by the DER-encoded request
+    /// for the certificate they describe. Responses are re-verified on every lookup, so expired
+    /// ones are evicted lazily.
+    static ref RESPONSE_CACHE: Mutex<HashMap<Vec<u8>, Vec<u8>>> = Default::default();
+}
+
+/// The revocation status of a certificate as reported by an OCSP response.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CertStatus {
+    Good,
+    Revoked,
+    Unknown,
+}
+
+/// The relevant contents of an OCSP response whose signature and validity period have been
+/// verified.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct VerifiedResponse {
+    pub(crate) status: CertStatus,
+
+    /// Whether the response specifies when the responder expects to have newer information
+    /// available. Responses without a `nextUpdate` time are not cached.
+    pub(crate) cacheable: bool,
+}
+
+/// Backend-specific handling of OCSP for a single certificate.
+pub(crate) trait RevocationChecker {
+    /// Returns the DER-encoded OCSP request for the certificate, or `None` if one could not be
+    /// created.
+    fn request(&self) -> Option<Vec<u8>>;
+
+    /// Returns the OCSP responder URLs listed in the certificate's Authority Information Access
+    /// extension.
+    fn responder_urls(&self) -> Vec<String>;
+
+    /// Parses the given DER-encoded OCSP response and verifies that it is a valid response for
+    /// the certificate, signed either by its issuer or by a responder delegated by its issuer,
+    /// whose validity period includes the current time.
+    fn verify_response(&self, response: &[u8]) -> Option<VerifiedResponse>;
+}
+
+/// Whether the given options call for the server's certificate to be checked for revocation.
+pub(super) fn revocation_check_enabled(options: &TlsOptions) -> bool {
+    options.allow_invalid_certificates != Some(true)
+        && options.disable_certificate_revocation_check != Some(true)
+}
+
+/// Checks whether the server's certificate has been revoked, returning an error if so.
+///
+/// This "soft fails": if no valid response can be obtained, or the responder does not know about
+/// the certificate, the certificate is assumed not to be revoked.
+pub(super) async fn check_revocation(
+    checker: &impl RevocationChecker,
+    stapled_response: Option<&[u8]>,
+    options: &TlsOptions,
+) -> Result<()> {
+    match certificate_status(checker, stapled_response, options).await {
+        Some(CertStatus::Revoked) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the server's certificate has been revoked",
+        )
+        .into()),
+        Some(CertStatus::Good) | Some(CertStatus::Unknown) | None => Ok(()),
+    }
+}
+
+async fn certificate_status(
+    checker: &impl RevocationChecker,
+    stapled_response: Option<&[u8]>,
+    options: &TlsOptions,
+) -> Option<CertStatus> {
+    let request = checker.request();
+
+    if let Some(response) = stapled_response {
+        if let Some(verified) = checker.verify_response(response) {
+            if let Some(ref request) = request {
+                cache_response(request, response, verified);
+            }
+            return Some(verified.status);
+        }
+    }
+
+    let request = request?;
+    if let Some(status) = cached_status(checker, &request) {
+        return Some(status);
+    }
+
+    if options.disable_ocsp_endpoint_check == Some(true) {
+        return None;
+    }
+
+    for url in checker.responder_urls() {
+        let fetch =
+            HTTP_CLIENT.post_and_read_bytes(&url, "application/ocsp-request", request.clone());
+        let response = match RUNTIME.timeout(OCSP_REQUEST_TIMEOUT, fetch).await {
+            Ok(Ok(response)) => response,
+            _ => continue,
+        };
+
+        if let Some(verified) = checker.verify_response(&response) {
+            cache_response(&request, &response, verified);
+            return Some(verified.status);
+        }
+    }
+
+    None
+}
+
+fn cached_status(checker: &impl RevocationChecker, request: &[u8]) -> Option<CertStatus> {
+    let mut cache = RESPONSE_CACHE.lock().unwrap();
+    let response = cache.get(request)?;
+
+    match checker.verify_response(response) {
+        Some(verified) => Some(verified.status),
+        None => {
+            cache.remove(request);
+            None
+        }
+    }
+}
+
+fn cache_response(request: &[u8], response: &[u8], verified: VerifiedResponse) {
+    if verified.cacheable {
+        RESPONSE_CACHE
+            .lock()
+            .unwrap()
+            .insert(request.to_vec(), response.to_vec());
+    }
+}
@@ -0,0 +1,91 @@
+use openssl::{
+    hash::MessageDigest,
+    ocsp::{OcspCertId, OcspCertStatus, OcspFlag, OcspRequest, OcspResponse, OcspResponseStatus},
+    stack::Stack,
+    x509::{
+        store::{X509Store, X509StoreBuilder},
+        verify::X509VerifyFlags,
+        X509,
+    },
+};
+
+use super::{CertStatus, RevocationChecker, VerifiedResponse, CLOCK_SKEW_TOLERANCE};
+
+/// Checks the revocation status of a certificate using OpenSSL's OCSP support.
+pub(crate) struct Checker {
+    leaf: X509,
+    issuer: X509,
+
+    /// A store trusting only the issuer, which has already been verified during the handshake.
+    /// Responses must be signed by the issuer or by a responder certificate it issued.
+    store: X509Store,
+}
+
+impl Checker {
+    /// Creates a checker for the first certificate in the given verified chain, whose issuer is
+    /// the second certificate.
+    pub(crate) fn from_chain(mut chain: impl Iterator<Item = X509>) -> Option<Self> {
+        let leaf = chain.next()?;
+        let issuer = chain.next()?;
+
+        let mut store = X509StoreBuilder::new().ok()?;
+        store.add_cert(issuer.clone()).ok()?;
+        // The issuer may be an intermediate certificate, so it must be treated as a trust anchor
+        // without requiring a path to a self-signed root.
+        store.set_flags(X509VerifyFlags::PARTIAL_CHAIN).ok()?;
+
+        Some(Self {
+            leaf,
+            issuer,
+            store: store.build(),
+        })
+    }
+
+    fn cert_id(&self) -> Option<OcspCertId> {
+        OcspCertId::from_cert(MessageDigest::sha1(), &self.leaf, &self.issuer).ok()
+    }
+}
+
+impl RevocationChecker for Checker {
+    fn request(&self) -> Option<Vec<u8>> {
+        let mut request = OcspRequest::new().ok()?;
+        request.add_id(self.cert_id()?).ok()?;
+        request.to_der().ok()
+    }
+
+    fn responder_urls(&self) -> Vec<String> {
+        match self.leaf.ocsp_responders() {
+            Ok(urls) => urls.iter().map(|url| url.to_string()).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn verify_response(&self, response: &[u8]) -> Option<VerifiedResponse> {
+        let response = OcspResponse::from_der(response).ok()?;
+        if response.status() != OcspResponseStatus::SUCCESSFUL {
+            return None;
+        }
+
+        let basic = response.basic().ok()?;
+        let mut certs = Stack::new().ok()?;
+        certs.push(self.issuer.clone()).ok()?;
+        basic.verify(&certs, &self.store, OcspFlag::empty()).ok()?;
+
+        let cert_id = self.cert_id()?;
+        let single = basic.find_status(&cert_id)?;
+        single
+            .check_validity(CLOCK_SKEW_TOLERANCE.as_secs() as u32, None)
+            .ok()?;
+
+        let status = match single.status {
+            OcspCertStatus::GOOD => CertStatus::Good,
+            OcspCertStatus::REVOKED => CertStatus::Revoked,
+            _ => CertStatus::Unknown,
+        };
+
+        Some(VerifiedResponse {
+            status,
+            cacheable: single.next_update().is_some(),
+        })
+    }
+}
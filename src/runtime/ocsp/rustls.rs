@@ -0,0 +1,414 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use der::{
+    asn1::{AnyRef, BitString, GeneralizedTime, Null, ObjectIdentifier, OctetString},
+    Any,
+    Choice,
+    Decode,
+    Encode,
+    Enumerated,
+    Sequence,
+    Tag,
+};
+use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+use rustls::RootCertStore;
+use sha1::{Digest, Sha1};
+use x509_cert::{
+    ext::pkix::{name::GeneralName, AuthorityInfoAccessSyntax, ExtendedKeyUsage},
+    name::Name,
+    serial_number::SerialNumber,
+    spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned},
+    Certificate,
+};
+
+use super::{CertStatus, RevocationChecker, VerifiedResponse, CLOCK_SKEW_TOLERANCE};
+
+const ID_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+const ID_PE_AUTHORITY_INFO_ACCESS: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.1.1");
+const ID_CE_EXT_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37");
+const ID_AD_OCSP: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.48.1");
+const ID_KP_OCSP_SIGNING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.9");
+const ID_PKIX_OCSP_BASIC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.48.1.1");
+
+const SHA1_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.5");
+const SHA256_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const SHA384_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const SHA512_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+/// The `OCSPRequest` structure from RFC 6960, containing only the fields the driver sends.
+#[derive(Sequence)]
+struct OcspRequest {
+    tbs_request: TbsRequest,
+}
+
+#[derive(Sequence)]
+struct TbsRequest {
+    request_list: Vec<Request>,
+}
+
+#[derive(Sequence)]
+struct Request {
+    req_cert: CertId,
+}
+
+#[derive(Clone, Debug, Sequence)]
+struct CertId {
+    hash_algorithm: AlgorithmIdentifierOwned,
+    issuer_name_hash: OctetString,
+    issuer_key_hash: OctetString,
+    serial_number: SerialNumber,
+}
+
+impl CertId {
+    /// Whether the two IDs refer to the same certificate. The hash algorithm parameters are
+    /// ignored, since responders may encode an absent parameter as NULL or vice versa.
+    fn matches(&self, other: &CertId) -> bool {
+        self.hash_algorithm.oid == other.hash_algorithm.oid
+            && self.issuer_name_hash == other.issuer_name_hash
+            && self.issuer_key_hash == other.issuer_key_hash
+            && self.serial_number == other.serial_number
+    }
+}
+
+#[derive(Sequence)]
+struct OcspResponse {
+    response_status: OcspResponseStatus,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    response_bytes: Option<ResponseBytes>,
+}
+
+#[derive(Clone, Copy, Debug, Enumerated, PartialEq)]
+#[repr(u32)]
+enum OcspResponseStatus {
+    Successful = 0,
+    MalformedRequest = 1,
+    InternalError = 2,
+    TryLater = 3,
+    SigRequired = 5,
+    Unauthorized = 6,
+}
+
+#[derive(Sequence)]
+struct ResponseBytes {
+    response_type: ObjectIdentifier,
+    response: OctetString,
+}
+
+#[derive(Sequence)]
+struct BasicOcspResponse {
+    /// Kept in its encoded form so that the signature can be verified over the exact bytes the
+    /// responder signed.
+    tbs_response_data: Any,
+    signature_algorithm: AlgorithmIdentifierOwned,
+    signature: BitString,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    certs: Option<Vec<Certificate>>,
+}
+
+#[derive(Sequence)]
+struct ResponseData {
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    version: Option<u8>,
+    responder_id: Any,
+    produced_at: GeneralizedTime,
+    responses: Vec<SingleResponse>,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    response_extensions: Option<Any>,
+}
+
+#[derive(Sequence)]
+struct SingleResponse {
+    cert_id: CertId,
+    cert_status: CertStatusInfo,
+    this_update: GeneralizedTime,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    next_update: Option<GeneralizedTime>,
+
+    #[asn1(context_specific = "1", tag_mode = "EXPLICIT", optional = "true")]
+    single_extensions: Option<Any>,
+}
+
+#[derive(Choice)]
+enum CertStatusInfo {
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT")]
+    Good(Null),
+
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", constructed = "true")]
+    Revoked(RevokedInfo),
+
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT")]
+    Unknown(Null),
+}
+
+#[derive(Sequence)]
+struct RevokedInfo {
+    revocation_time: GeneralizedTime,
+
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    revocation_reason: Option<Any>,
+}
+
+/// Checks the revocation status of a certificate using the `der` and `x509-cert` crates for
+/// parsing and `ring` for signature verification.
+pub(crate) struct Checker {
+    cert_id: CertId,
+    issuer_name: Name,
+    issuer_key: SubjectPublicKeyInfoOwned,
+    responder_urls: Vec<String>,
+}
+
+impl Checker {
+    /// Creates a checker for the certificate at the start of the given chain. The certificate's
+    /// issuer is looked for first in the rest of the chain and then in the trusted roots.
+    pub(crate) fn from_chain(chain: &[rustls::Certificate], roots: &RootCertStore) -> Option<Self> {
+        let (leaf, rest) = chain.split_first()?;
+        let leaf = Certificate::from_der(&leaf.0).ok()?;
+        let issuer_name = &leaf.tbs_certificate.issuer;
+
+        let issuer = rest
+            .iter()
+            .filter_map(|cert| Certificate::from_der(&cert.0).ok())
+            .find(|cert| &cert.tbs_certificate.subject == issuer_name)
+            .map(|cert| {
+                (
+                    cert.tbs_certificate.subject,
+                    cert.tbs_certificate.subject_public_key_info,
+                )
+            })
+            .or_else(|| {
+                roots.roots.iter().find_map(|anchor| {
+                    let anchor = anchor.to_trust_anchor();
+                    let subject: Name = decode_sequence_contents(anchor.subject)?;
+                    if &subject != issuer_name {
+                        return None;
+                    }
+                    Some((subject, decode_sequence_contents(anchor.spki)?))
+                })
+            })?;
+
+        Self::new(leaf, issuer.0, issuer.1)
+    }
+
+    fn new(
+        leaf: Certificate,
+        issuer_name: Name,
+        issuer_key: SubjectPublicKeyInfoOwned,
+    ) -> Option<Self> {
+        let cert_id = CertId {
+            hash_algorithm: AlgorithmIdentifierOwned {
+                oid: ID_SHA1,
+                parameters: Some(Any::null()),
+            },
+            issuer_name_hash: sha1_octet_string(&issuer_name.to_der().ok()?)?,
+            issuer_key_hash: sha1_octet_string(issuer_key.subject_public_key.raw_bytes())?,
+            serial_number: leaf.tbs_certificate.serial_number.clone(),
+        };
+
+        Some(Self {
+            cert_id,
+            issuer_name,
+            issuer_key,
+            responder_urls: responder_urls(&leaf),
+        })
+    }
+
+    /// Whether the given certificate was issued by the issuer of the certificate being checked
+    /// for the purpose of signing OCSP responses.
+    fn is_delegated_responder(&self, cert: &Certificate) -> bool {
+        let tbs = &cert.tbs_certificate;
+        if tbs.issuer != self.issuer_name {
+            return false;
+        }
+
+        let now = SystemTime::now();
+        if UNIX_EPOCH + tbs.validity.not_before.to_unix_duration() > now
+            || UNIX_EPOCH + tbs.validity.not_after.to_unix_duration() < now
+        {
+            return false;
+        }
+
+        let has_ocsp_signing_usage = tbs
+            .extensions
+            .iter()
+            .flatten()
+            .filter(|extension| extension.extn_id == ID_CE_EXT_KEY_USAGE)
+            .filter_map(|extension| {
+                ExtendedKeyUsage::from_der(extension.extn_value.as_bytes()).ok()
+            })
+            .any(|usage| usage.0.contains(&ID_KP_OCSP_SIGNING));
+        if !has_ocsp_signing_usage {
+            return false;
+        }
+
+        match (tbs.to_der(), cert.signature.as_bytes()) {
+            (Ok(message), Some(signature)) => verify_signature(
+                &self.issuer_key,
+                &cert.signature_algorithm.oid,
+                &message,
+                signature,
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl RevocationChecker for Checker {
+    fn request(&self) -> Option<Vec<u8>> {
+        OcspRequest {
+            tbs_request: TbsRequest {
+                request_list: vec![Request {
+                    req_cert: self.cert_id.clone(),
+                }],
+            },
+        }
+        .to_der()
+        .ok()
+    }
+
+    fn responder_urls(&self) -> Vec<String> {
+        self.responder_urls.clone()
+    }
+
+    fn verify_response(&self, response: &[u8]) -> Option<VerifiedResponse> {
+        let response = OcspResponse::from_der(response).ok()?;
+        if response.response_status != OcspResponseStatus::Successful {
+            return None;
+        }
+
+        let response_bytes = response.response_bytes?;
+        if response_bytes.response_type != ID_PKIX_OCSP_BASIC {
+            return None;
+        }
+
+        let basic = BasicOcspResponse::from_der(response_bytes.response.as_bytes()).ok()?;
+        let tbs_response_data = basic.tbs_response_data.to_der().ok()?;
+        let signature = basic.signature.as_bytes()?;
+        let algorithm = &basic.signature_algorithm.oid;
+
+        let signed_by_issuer =
+            verify_signature(&self.issuer_key, algorithm, &tbs_response_data, signature);
+        let signed_by_delegate = || {
+            basic.certs.iter().flatten().any(|cert| {
+                self.is_delegated_responder(cert)
+                    && verify_signature(
+                        &cert.tbs_certificate.subject_public_key_info,
+                        algorithm,
+                        &tbs_response_data,
+                        signature,
+                    )
+            })
+        };
+        if !signed_by_issuer && !signed_by_delegate() {
+            return None;
+        }
+
+        let data = ResponseData::from_der(&tbs_response_data).ok()?;
+        let single = data
+            .responses
+            .into_iter()
+            .find(|single| single.cert_id.matches(&self.cert_id))?;
+
+        let status = match single.cert_status {
+            CertStatusInfo::Good(_) => CertStatus::Good,
+            CertStatusInfo::Revoked(_) => CertStatus::Revoked,
+            CertStatusInfo::Unknown(_) => CertStatus::Unknown,
+        };
+
+        let now = SystemTime::now();
+        if UNIX_EPOCH + single.this_update.to_unix_duration() > now + CLOCK_SKEW_TOLERANCE {
+            return None;
+        }
+        if let Some(next_update) = single.next_update {
+            if UNIX_EPOCH + next_update.to_unix_duration() <= now {
+                return None;
+            }
+        }
+
+        Some(VerifiedResponse {
+            status,
+            cacheable: single.next_update.is_some(),
+        })
+    }
+}
+
+/// Returns the OCSP responder URLs from the certificate's Authority Information Access extension.
+fn responder_urls(cert: &Certificate) -> Vec<String> {
+    cert.tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .filter(|extension| extension.extn_id == ID_PE_AUTHORITY_INFO_ACCESS)
+        .filter_map(|extension| {
+            AuthorityInfoAccessSyntax::from_der(extension.extn_value.as_bytes()).ok()
+        })
+        .flat_map(|access| access.0)
+        .filter(|description| description.access_method == ID_AD_OCSP)
+        .filter_map(|description| match description.access_location {
+            GeneralName::UniformResourceIdentifier(uri) => Some(uri.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decodes a SEQUENCE from its contents, which is how webpki stores the fields of a trust anchor.
+fn decode_sequence_contents<T: for<'a> Decode<'a>>(contents: &[u8]) -> Option<T> {
+    let encoded = AnyRef::new(Tag::Sequence, contents).ok()?.to_der().ok()?;
+    T::from_der(&encoded).ok()
+}
+
+fn sha1_octet_string(bytes: &[u8]) -> Option<OctetString> {
+    OctetString::new(Sha1::digest(bytes).to_vec()).ok()
+}
+
+/// Verifies `signature` over `message` with the given public key, using the signature algorithm
+/// identified by `algorithm`.
+fn verify_signature(
+    key: &SubjectPublicKeyInfoOwned,
+    algorithm: &ObjectIdentifier,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    // The ECDSA algorithm identifiers do not specify the curve, so each supported curve is tried.
+    let candidates: &[&'static dyn VerificationAlgorithm] = match *algorithm {
+        SHA1_WITH_RSA_ENCRYPTION => &[&signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY],
+        SHA256_WITH_RSA_ENCRYPTION => &[&signature::RSA_PKCS1_2048_8192_SHA256],
+        SHA384_WITH_RSA_ENCRYPTION => &[&signature::RSA_PKCS1_2048_8192_SHA384],
+        SHA512_WITH_RSA_ENCRYPTION => &[&signature::RSA_PKCS1_2048_8192_SHA512],
+        ECDSA_WITH_SHA256 => &[
+            &signature::ECDSA_P256_SHA256_ASN1,
+            &signature::ECDSA_P384_SHA256_ASN1,
+        ],
+        ECDSA_WITH_SHA384 => &[
+            &signature::ECDSA_P384_SHA384_ASN1,
+            &signature::ECDSA_P256_SHA384_ASN1,
+        ],
+        ID_ED25519 => &[&signature::ED25519],
+        _ => return false,
+    };
+
+    let key = match key.subject_public_key.as_bytes() {
+        Some(key) => key,
+        None => return false,
+    };
+
+    candidates.iter().any(|candidate| {
+        UnparsedPublicKey::new(*candidate, key)
+            .verify(message, signature)
+            .is_ok()
+    })
+}
@@ -0,0 +1,258 @@
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use super::{check_revocation, CertStatus, Checker, RevocationChecker, VerifiedResponse};
+use crate::client::options::TlsOptions;
+
+const CA: &[u8] = include_bytes!("../../test/ocsp/ca.pem");
+const GOOD_CERT: &[u8] = include_bytes!("../../test/ocsp/good.pem");
+const REVOKED_CERT: &[u8] = include_bytes!("../../test/ocsp/revoked.pem");
+const GOOD_RESPONSE: &[u8] = include_bytes!("../../test/ocsp/good.der");
+const GOOD_DELEGATED_RESPONSE: &[u8] = include_bytes!("../../test/ocsp/good-delegated.der");
+const REVOKED_RESPONSE: &[u8] = include_bytes!("../../test/ocsp/revoked.der");
+
+#[cfg(not(feature = "openssl-tls"))]
+fn checker(leaf: &[u8]) -> Checker {
+    use std::io::Cursor;
+
+    let chain: Vec<_> = [leaf, CA]
+        .iter()
+        .flat_map(|pem| rustls::internal::pemfile::certs(&mut Cursor::new(pem)).unwrap())
+        .collect();
+    Checker::from_chain(&chain, &rustls::RootCertStore::empty()).unwrap()
+}
+
+#[cfg(feature = "openssl-tls")]
+fn checker(leaf: &[u8]) -> Checker {
+    use openssl::x509::X509;
+
+    let chain = vec![X509::from_pem(leaf).unwrap(), X509::from_pem(CA).unwrap()];
+    Checker::from_chain(chain.into_iter()).unwrap()
+}
+
+/// Wraps a checker to point it at a different responder and give it a cache entry of its own, so
+/// that tests don't observe responses cached by other tests.
+struct TestChecker {
+    inner: Checker,
+    responder_urls: Vec<String>,
+    cache_key: &'static str,
+}
+
+impl TestChecker {
+    fn new(leaf: &[u8], responder_urls: Vec<String>, cache_key: &'static str) -> Self {
+        Self {
+            inner: checker(leaf),
+            responder_urls,
+            cache_key,
+        }
+    }
+}
+
+impl RevocationChecker for TestChecker {
+    fn request(&self) -> Option<Vec<u8>> {
+        let mut request = self.inner.request()?;
+        request.extend_from_slice(self.cache_key.as_bytes());
+        Some(request)
+    }
+
+    fn responder_urls(&self) -> Vec<String> {
+        self.responder_urls.clone()
+    }
+
+    fn verify_response(&self, response: &[u8]) -> Option<VerifiedResponse> {
+        self.inner.verify_response(response)
+    }
+}
+
+/// Starts a mock OCSP responder that replies to a single request with the given response and
+/// returns its URL.
+async fn mock_responder(response: &'static [u8]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        // Read the full request before replying so the client doesn't see the connection reset.
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request).to_lowercase();
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let content_length: usize = text
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .map(|length| length.trim().parse().unwrap())
+                    .unwrap_or(0);
+                if n == 0 || request.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+        }
+
+        let header = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/ocsp-response\r\nContent-Length: \
+             {}\r\nConnection: close\r\n\r\n",
+            response.len()
+        );
+        stream.write_all(header.as_bytes()).await.unwrap();
+        stream.write_all(response).await.unwrap();
+    });
+
+    url
+}
+
+#[test]
+fn verify_responses() {
+    let good = checker(GOOD_CERT);
+    let revoked = checker(REVOKED_CERT);
+
+    assert_eq!(
+        good.verify_response(GOOD_RESPONSE),
+        Some(VerifiedResponse {
+            status: CertStatus::Good,
+            cacheable: true,
+        })
+    );
+    assert_eq!(
+        good.verify_response(GOOD_DELEGATED_RESPONSE),
+        Some(VerifiedResponse {
+            status: CertStatus::Good,
+            cacheable: true,
+        })
+    );
+    assert_eq!(
+        revoked.verify_response(REVOKED_RESPONSE),
+        Some(VerifiedResponse {
+            status: CertStatus::Revoked,
+            cacheable: true,
+        })
+    );
+
+    // Responses for a different certificate or that can't be parsed are rejected.
+    assert_eq!(good.verify_response(REVOKED_RESPONSE), None);
+    assert_eq!(revoked.verify_response(GOOD_RESPONSE), None);
+    assert_eq!(good.verify_response(&GOOD_RESPONSE[1..]), None);
+}
+
+#[test]
+fn verify_rejects_tampered_response() {
+    let good = checker(GOOD_CERT);
+
+    // Changing the seconds of the signed `producedAt` time (the first GeneralizedTime in the
+    // response) invalidates the signature.
+    let mut tampered = GOOD_RESPONSE.to_vec();
+    let produced_at = tampered
+        .windows(2)
+        .position(|bytes| bytes == [0x18, 0x0f])
+        .unwrap()
+        + 2;
+    tampered[produced_at + 13] = if tampered[produced_at + 13] == b'0' {
+        b'1'
+    } else {
+        b'0'
+    };
+    assert_eq!(good.verify_response(&tampered), None);
+}
+
+#[test]
+fn responder_urls() {
+    assert_eq!(
+        checker(GOOD_CERT).responder_urls(),
+        vec!["http://localhost:8100".to_string()]
+    );
+}
+
+#[cfg(not(feature = "openssl-tls"))]
+#[test]
+fn issuer_from_trusted_roots() {
+    use std::io::Cursor;
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_pem_file(&mut Cursor::new(CA)).unwrap();
+    let leaf = rustls::internal::pemfile::certs(&mut Cursor::new(GOOD_CERT)).unwrap();
+
+    let checker = Checker::from_chain(&leaf, &roots).unwrap();
+    assert_eq!(
+        checker.verify_response(GOOD_RESPONSE).map(|r| r.status),
+        Some(CertStatus::Good)
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn stapled_response() {
+    let options = TlsOptions::builder()
+        .disable_ocsp_endpoint_check(true)
+        .build();
+
+    let good = TestChecker::new(GOOD_CERT, Vec::new(), "stapled-good");
+    check_revocation(&good, Some(GOOD_RESPONSE), &options)
+        .await
+        .unwrap();
+
+    let revoked = TestChecker::new(REVOKED_CERT, Vec::new(), "stapled-revoked");
+    check_revocation(&revoked, Some(REVOKED_RESPONSE), &options)
+        .await
+        .expect_err("stapled revoked response should fail the check");
+
+    // The stapled response is cached, so the check fails even if the server stops stapling.
+    check_revocation(&revoked, None, &options)
+        .await
+        .expect_err("cached revoked response should fail the check");
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn invalid_stapled_response_falls_back_to_responder() {
+    let url = mock_responder(REVOKED_RESPONSE).await;
+    let revoked = TestChecker::new(REVOKED_CERT, vec![url], "invalid-stapled");
+
+    check_revocation(&revoked, Some(GOOD_RESPONSE), &TlsOptions::default())
+        .await
+        .expect_err("revoked response from responder should fail the check");
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn responder_response() {
+    let url = mock_responder(REVOKED_RESPONSE).await;
+    let revoked = TestChecker::new(REVOKED_CERT, vec![url], "responder-revoked");
+
+    check_revocation(&revoked, None, &TlsOptions::default())
+        .await
+        .expect_err("revoked response from responder should fail the check");
+
+    let url = mock_responder(GOOD_DELEGATED_RESPONSE).await;
+    let good = TestChecker::new(GOOD_CERT, vec![url], "responder-good");
+    check_revocation(&good, None, &TlsOptions::default())
+        .await
+        .unwrap();
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn endpoint_check_disabled() {
+    // The responder would report the certificate as revoked, but it should never be contacted.
+    let url = mock_responder(REVOKED_RESPONSE).await;
+    let revoked = TestChecker::new(REVOKED_CERT, vec![url], "endpoint-check-disabled");
+    let options = TlsOptions::builder()
+        .disable_ocsp_endpoint_check(true)
+        .build();
+
+    check_revocation(&revoked, None, &options).await.unwrap();
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn soft_fail() {
+    // Nothing is listening on the responder URL, so no status can be determined.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let revoked = TestChecker::new(REVOKED_CERT, vec![url], "soft-fail");
+    check_revocation(&revoked, None, &TlsOptions::default())
+        .await
+        .unwrap();
+}
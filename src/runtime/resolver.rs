@@ -1,11 +1,15 @@
+use std::net::IpAddr;
+
 use trust_dns_resolver::{
     config::ResolverConfig,
     error::ResolveErrorKind,
     lookup::{SrvLookup, TxtLookup},
+    proto::rr::RData,
     IntoName,
+    TryParseIp,
 };
 
-use crate::error::{Error, Result};
+use crate::error::{Error, ErrorKind, Result};
 
 /// An async runtime agnostic DNS resolver.
 pub(crate) struct AsyncResolver {
@@ -62,4 +66,47 @@ impl AsyncResolver {
             },
         }
     }
+
+    /// Resolves the given host to an IP address.
+    pub async fn lookup_ip<N: IntoName + TryParseIp>(&self, host: N) -> Result<IpAddr> {
+        let lookup = self
+            .resolver
+            .lookup_ip(host)
+            .await
+            .map_err(Error::from_resolve_error)?;
+        lookup.iter().next().ok_or_else(|| {
+            ErrorKind::DnsResolve {
+                message: format!("no addresses found for {}", lookup.query().name()),
+            }
+            .into()
+        })
+    }
+
+    /// Returns the canonical name of the given host as determined by a forward lookup, following
+    /// any CNAME records.
+    pub async fn canonical_name<N: IntoName + TryParseIp>(
+        &self,
+        host: N,
+    ) -> Result<Option<String>> {
+        let lookup = self
+            .resolver
+            .lookup_ip(host)
+            .await
+            .map_err(Error::from_resolve_error)?;
+        Ok(lookup
+            .as_lookup()
+            .record_iter()
+            .find(|record| matches!(record.rdata(), RData::A(..) | RData::AAAA(..)))
+            .map(|record| record.name().to_utf8()))
+    }
+
+    /// Returns the hostname of the given IP address as determined by a reverse lookup.
+    pub async fn reverse_lookup(&self, ip: IpAddr) -> Result<Option<String>> {
+        let lookup = self
+            .resolver
+            .reverse_lookup(ip)
+            .await
+            .map_err(Error::from_resolve_error)?;
+        Ok(lookup.iter().next().map(|name| name.to_utf8()))
+    }
 }
@@ -1,17 +1,17 @@
+#[cfg(unix)]
+use std::path::Path;
 use std::{
     net::SocketAddr,
     ops::DerefMut,
     pin::Pin,
-    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures_io::{AsyncRead, AsyncWrite};
 use tokio::io::{AsyncRead as TokioAsyncRead, AsyncWrite as TokioAsyncWrite, ReadBuf};
-use tokio_rustls::TlsConnector;
-use webpki::DNSNameRef;
 
+use super::tls::{tls_connect, TlsStream};
 use crate::{
     cmap::options::StreamOptions,
     error::{ErrorKind, Result},
@@ -19,7 +19,7 @@ use crate::{
     RUNTIME,
 };
 
-const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 const KEEPALIVE_TIME: Duration = Duration::from_secs(120);
 
 /// A runtime-agnostic async stream possibly using TLS.
@@ -32,7 +32,11 @@ pub(crate) enum AsyncStream {
     Tcp(AsyncTcpStream),
 
     /// A TLS connection over TCP.
-    Tls(tokio_rustls::client::TlsStream<AsyncTcpStream>),
+    Tls(TlsStream),
+
+    /// A connection to the server over a Unix domain socket.
+    #[cfg(unix)]
+    Unix(AsyncUnixStream),
 }
 
 /// A runtime-agnostic async stream.
@@ -47,6 +51,19 @@ pub(crate) enum AsyncTcpStream {
     AsyncStd(async_std::net::TcpStream),
 }
 
+/// A runtime-agnostic async Unix domain socket stream.
+#[cfg(unix)]
+#[derive(Debug)]
+pub(crate) enum AsyncUnixStream {
+    /// Wrapper around `tokio::net::UnixStream`.
+    #[cfg(feature = "tokio-runtime")]
+    Tokio(tokio::net::UnixStream),
+
+    /// Wrapper around `async_std::os::unix::net::UnixStream`.
+    #[cfg(feature = "async-std-runtime")]
+    AsyncStd(async_std::os::unix::net::UnixStream),
+}
+
 #[cfg(feature = "tokio-runtime")]
 impl From<tokio::net::TcpStream> for AsyncTcpStream {
     fn from(stream: tokio::net::TcpStream) -> Self {
@@ -142,26 +159,68 @@ impl AsyncTcpStream {
     }
 }
 
+#[cfg(all(unix, feature = "tokio-runtime"))]
+impl From<tokio::net::UnixStream> for AsyncUnixStream {
+    fn from(stream: tokio::net::UnixStream) -> Self {
+        Self::Tokio(stream)
+    }
+}
+
+#[cfg(all(unix, feature = "async-std-runtime"))]
+impl From<async_std::os::unix::net::UnixStream> for AsyncUnixStream {
+    fn from(stream: async_std::os::unix::net::UnixStream) -> Self {
+        Self::AsyncStd(stream)
+    }
+}
+
+#[cfg(unix)]
+impl AsyncUnixStream {
+    #[cfg(feature = "tokio-runtime")]
+    async fn try_connect(path: &Path) -> Result<Self> {
+        let stream = tokio::net::UnixStream::connect(path).await?;
+        Ok(stream.into())
+    }
+
+    #[cfg(feature = "async-std-runtime")]
+    async fn try_connect(path: &Path) -> Result<Self> {
+        let stream = async_std::os::unix::net::UnixStream::connect(path).await?;
+        Ok(stream.into())
+    }
+
+    async fn connect(path: &Path, connect_timeout: Option<Duration>) -> Result<Self> {
+        let timeout = connect_timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+
+        if timeout == Duration::from_secs(0) {
+            Self::try_connect(path).await
+        } else {
+            RUNTIME.timeout(timeout, Self::try_connect(path)).await?
+        }
+    }
+}
+
 impl AsyncStream {
     /// Creates a new Tokio TCP stream connected to the server as specified by `options`.
     pub(crate) async fn connect(options: StreamOptions) -> Result<Self> {
+        #[cfg(unix)]
+        if let ServerAddress::Unix { ref path } = options.address {
+            if options.tls_options.is_some() {
+                return Err(ErrorKind::InvalidArgument {
+                    message: "TLS is not supported over Unix domain sockets".to_string(),
+                }
+                .into());
+            }
+
+            let inner = AsyncUnixStream::connect(path, options.connect_timeout).await?;
+            return Ok(Self::Unix(inner));
+        }
+
         let inner = AsyncTcpStream::connect(&options.address, options.connect_timeout).await?;
 
-        // If there are TLS options, wrap the inner stream with rustls.
+        // If there are TLS options, wrap the inner stream in a TLS session.
         match options.tls_options {
             Some(cfg) => {
                 let host = options.address.host();
-                let name =
-                    DNSNameRef::try_from_ascii_str(host).map_err(|e| ErrorKind::DnsResolve {
-                        message: format!("could not resolve {:?}: {}", host, e.to_string()),
-                    })?;
-                let mut tls_config = cfg.into_rustls_config()?;
-                tls_config.enable_sni = true;
-
-                let connector: TlsConnector = Arc::new(tls_config).into();
-                let session = connector.connect(name, inner).await?;
-
-                Ok(Self::Tls(session))
+                Ok(Self::Tls(tls_connect(&host, inner, cfg).await?))
             }
             None => Ok(Self::Tcp(inner)),
         }
@@ -180,6 +239,8 @@ impl AsyncRead for AsyncStream {
             Self::Tls(ref mut inner) => {
                 tokio_util::io::poll_read_buf(Pin::new(inner), cx, &mut buf)
             }
+            #[cfg(unix)]
+            Self::Unix(ref mut inner) => AsyncRead::poll_read(Pin::new(inner), cx, buf),
         }
     }
 }
@@ -194,6 +255,8 @@ impl AsyncWrite for AsyncStream {
             Self::Null => Poll::Ready(Ok(0)),
             Self::Tcp(ref mut inner) => AsyncWrite::poll_write(Pin::new(inner), cx, buf),
             Self::Tls(ref mut inner) => Pin::new(inner).poll_write(cx, buf),
+            #[cfg(unix)]
+            Self::Unix(ref mut inner) => AsyncWrite::poll_write(Pin::new(inner), cx, buf),
         }
     }
 
@@ -202,6 +265,8 @@ impl AsyncWrite for AsyncStream {
             Self::Null => Poll::Ready(Ok(())),
             Self::Tcp(ref mut inner) => AsyncWrite::poll_flush(Pin::new(inner), cx),
             Self::Tls(ref mut inner) => Pin::new(inner).poll_flush(cx),
+            #[cfg(unix)]
+            Self::Unix(ref mut inner) => AsyncWrite::poll_flush(Pin::new(inner), cx),
         }
     }
 
@@ -210,6 +275,8 @@ impl AsyncWrite for AsyncStream {
             Self::Null => Poll::Ready(Ok(())),
             Self::Tcp(ref mut inner) => Pin::new(inner).poll_close(cx),
             Self::Tls(ref mut inner) => Pin::new(inner).poll_shutdown(cx),
+            #[cfg(unix)]
+            Self::Unix(ref mut inner) => AsyncWrite::poll_close(Pin::new(inner), cx),
         }
     }
 }
@@ -282,7 +349,77 @@ impl AsyncWrite for AsyncTcpStream {
     }
 }
 
-// These trait implementations are required to interface with tokio-rustls.
+#[cfg(unix)]
+impl AsyncRead for AsyncUnixStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        // We need `mut` here for the tokio impl, but it isn't used by the async-std version, so we
+        // suppress the warning.
+        #[allow(unused_mut)] mut buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.deref_mut() {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(ref mut stream) => {
+                tokio_util::io::poll_read_buf(Pin::new(stream), cx, &mut buf)
+            }
+
+            #[cfg(feature = "async-std-runtime")]
+            Self::AsyncStd(ref mut stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl AsyncWrite for AsyncUnixStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.deref_mut() {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(ref mut stream) => {
+                use tokio::io::AsyncWrite;
+
+                Pin::new(stream).poll_write(cx, buf)
+            }
+
+            #[cfg(feature = "async-std-runtime")]
+            Self::AsyncStd(ref mut stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.deref_mut() {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(ref mut stream) => {
+                use tokio::io::AsyncWrite;
+
+                Pin::new(stream).poll_flush(cx)
+            }
+
+            #[cfg(feature = "async-std-runtime")]
+            Self::AsyncStd(ref mut stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.deref_mut() {
+            #[cfg(feature = "tokio-runtime")]
+            Self::Tokio(ref mut stream) => {
+                use tokio::io::AsyncWrite;
+
+                Pin::new(stream).poll_shutdown(cx)
+            }
+
+            #[cfg(feature = "async-std-runtime")]
+            Self::AsyncStd(ref mut stream) => Pin::new(stream).poll_close(cx),
+        }
+    }
+}
+
+// These trait implementations are required to interface with the TLS libraries.
 
 impl TokioAsyncRead for AsyncTcpStream {
     fn poll_read(
@@ -0,0 +1,133 @@
+use std::pin::Pin;
+
+#[cfg(feature = "ocsp")]
+use openssl::ssl::StatusType;
+use openssl::{
+    error::ErrorStack,
+    pkey::PKey,
+    ssl::{SslConnector, SslMethod, SslVerifyMode},
+    x509::X509,
+};
+
+#[cfg(feature = "ocsp")]
+use super::ocsp;
+use super::stream::AsyncTcpStream;
+use crate::{
+    client::options::TlsOptions,
+    error::{Error, ErrorKind, Result},
+};
+
+pub(super) type TlsStream = tokio_openssl::SslStream<AsyncTcpStream>;
+
+/// Wraps the given TCP stream in a TLS session with the server at `host` using OpenSSL.
+pub(super) async fn tls_connect(
+    host: &str,
+    tcp_stream: AsyncTcpStream,
+    options: TlsOptions,
+) -> Result<TlsStream> {
+    let verify_hostname = !matches!(options.allow_invalid_hostnames, Some(true));
+    let connector = make_openssl_connector(&options)?;
+
+    let mut config = connector.configure().map_err(tls_config_error)?;
+    config.set_verify_hostname(verify_hostname);
+
+    #[cfg(feature = "ocsp")]
+    let check_revocation = ocsp::revocation_check_enabled(&options);
+    #[cfg(feature = "ocsp")]
+    if check_revocation {
+        // Ask the server to staple an OCSP response for its certificate to the handshake.
+        config
+            .set_status_type(StatusType::OCSP)
+            .map_err(tls_config_error)?;
+    }
+
+    let ssl = config.into_ssl(host).map_err(tls_config_error)?;
+
+    let mut stream = TlsStream::new(ssl, tcp_stream).map_err(tls_config_error)?;
+    Pin::new(&mut stream).connect().await.map_err(|e| {
+        let error = e
+            .into_io_error()
+            .unwrap_or_else(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+        Error::from(error)
+    })?;
+
+    #[cfg(feature = "ocsp")]
+    if check_revocation {
+        let ssl = stream.ssl();
+        let checker = ssl
+            .verified_chain()
+            .and_then(|chain| ocsp::Checker::from_chain(chain.iter().map(ToOwned::to_owned)));
+        if let Some(checker) = checker {
+            let stapled_response = ssl.ocsp_status().map(<[u8]>::to_vec);
+            ocsp::check_revocation(&checker, stapled_response.as_deref(), &options).await?;
+        }
+    }
+
+    Ok(stream)
+}
+
+/// Converts `TlsOptions` into an OpenSSL connector.
+fn make_openssl_connector(options: &TlsOptions) -> Result<SslConnector> {
+    let mut builder = SslConnector::builder(SslMethod::tls_client()).map_err(tls_config_error)?;
+
+    if let Some(true) = options.allow_invalid_certificates {
+        builder.set_verify(SslVerifyMode::NONE);
+    }
+
+    if let Some(ref path) = options.ca_file_path {
+        builder.set_ca_file(path).map_err(tls_config_error)?;
+    }
+    if let Some(ref pem) = options.ca_pem {
+        for cert in X509::stack_from_pem(pem).map_err(tls_config_error)? {
+            builder
+                .cert_store_mut()
+                .add_cert(cert)
+                .map_err(tls_config_error)?;
+        }
+    }
+
+    let cert_key = match (&options.cert_key_file_path, &options.cert_key_pem) {
+        (Some(_), Some(_)) => {
+            return Err(ErrorKind::InvalidTlsConfig {
+                message: "cannot specify both a certificate key file path and an in-memory \
+                          certificate key"
+                    .to_string(),
+            }
+            .into())
+        }
+        (Some(path), None) => Some(std::fs::read(path)?),
+        (None, pem) => pem.clone(),
+    };
+
+    if let Some(contents) = cert_key {
+        let mut certs = X509::stack_from_pem(&contents)
+            .map_err(tls_config_error)?
+            .into_iter();
+        let cert = certs.next().ok_or_else(|| ErrorKind::InvalidTlsConfig {
+            message: "No PEM-encoded client certificate in the provided certificate key"
+                .to_string(),
+        })?;
+        builder.set_certificate(&cert).map_err(tls_config_error)?;
+        for cert in certs {
+            builder
+                .add_extra_chain_cert(cert)
+                .map_err(tls_config_error)?;
+        }
+
+        let key = match options.cert_key_password {
+            Some(ref password) => PKey::private_key_from_pem_passphrase(&contents, password),
+            None => PKey::private_key_from_pem(&contents),
+        }
+        .map_err(tls_config_error)?;
+        builder.set_private_key(&key).map_err(tls_config_error)?;
+    }
+
+    Ok(builder.build())
+}
+
+fn tls_config_error(error: ErrorStack) -> Error {
+    ErrorKind::InvalidTlsConfig {
+        message: error.to_string(),
+    }
+    .into()
+}
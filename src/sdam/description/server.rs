@@ -132,10 +132,9 @@ impl ServerDescription {
         mut address: ServerAddress,
         is_master_reply: Option<Result<IsMasterReply, String>>,
     ) -> Self {
-        address = ServerAddress::Tcp {
-            host: address.host().to_lowercase(),
-            port: address.port(),
-        };
+        if let ServerAddress::Tcp { ref mut host, .. } = address {
+            *host = host.to_lowercase();
+        }
 
         let mut description = Self {
             address,
@@ -119,6 +119,7 @@ impl From<TestIsMasterCommandResponse> for IsMasterCommandResponse {
             speculative_authenticate: test.speculative_authenticate,
             max_bson_object_size: test.max_bson_object_size.unwrap_or(1234),
             max_write_batch_size: test.max_write_batch_size.unwrap_or(1234),
+            max_message_size_bytes: None,
             service_id: test.service_id,
             topology_version: None,
             compressors: None,
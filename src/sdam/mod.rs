@@ -8,7 +8,7 @@ mod state;
 #[cfg(test)]
 mod test;
 
-pub use self::public::{ServerInfo, ServerType, TopologyType};
+pub use self::public::{ServerInfo, ServerType, TopologyType, TopologyWatcher};
 
 pub(crate) use self::{
     description::{
@@ -3,6 +3,8 @@ use std::{
     time::Duration,
 };
 
+use tokio::sync::watch;
+
 use super::{
     description::server::ServerDescription,
     state::{server::Server, Topology, WeakTopology},
@@ -11,9 +13,17 @@ use super::{
 };
 use crate::{
     cmap::{Connection, Handshaker},
-    error::{Error, Result},
-    is_master::{is_master_command, run_is_master, IsMasterReply},
+    error::{Error, ErrorKind, Result},
+    is_master::{
+        is_master_command,
+        receive_is_master,
+        run_is_master,
+        AwaitableHelloOptions,
+        IsMasterReply,
+        TopologyVersion,
+    },
     options::{ClientOptions, ServerAddress},
+    runtime::{AsyncJoinHandle, DEFAULT_CONNECT_TIMEOUT},
     RUNTIME,
 };
 
@@ -27,6 +37,7 @@ pub(crate) struct Monitor {
     client_options: ClientOptions,
     update_receiver: ServerUpdateReceiver,
     topology: WeakTopology,
+    shutdown: ShutdownListener,
 }
 
 impl Monitor {
@@ -38,20 +49,29 @@ impl Monitor {
         topology: WeakTopology,
         client_options: ClientOptions,
         update_receiver: ServerUpdateReceiver,
+        shutdown_receiver: watch::Receiver<bool>,
     ) -> Self {
+        let shutdown = ShutdownListener {
+            server_removed: shutdown_receiver,
+            topology_closed: topology.watch_closed(),
+        };
         Self {
             address,
             server: server.clone(),
             client_options,
             update_receiver,
             topology,
+            shutdown,
         }
     }
 
-    /// Start the monitor tasks.
+    /// Start the monitor tasks, returning the handles used to wait for them to finish once they've
+    /// been stopped.
     /// A weak reference is used to ensure that the monitor doesn't keep the topology alive after
     /// it's been removed from the topology or the client has been dropped.
-    pub(super) fn start(self) {
+    pub(super) fn start(self) -> Vec<AsyncJoinHandle<()>> {
+        let mut tasks = Vec::new();
+
         let is_load_balanced = matches!(self.client_options.load_balanced, Some(true));
         // Load balancer servers can't have a monitoring connection.
         if !is_load_balanced {
@@ -60,24 +80,85 @@ impl Monitor {
                 Arc::downgrade(&self.server),
                 self.topology.clone(),
                 self.client_options,
+                self.shutdown.clone(),
             );
-            RUNTIME.execute(async move {
+            tasks.extend(RUNTIME.spawn(async move {
                 heartbeat_monitor.execute().await;
-            });
+            }));
         }
 
         let update_monitor = UpdateMonitor {
             server: Arc::downgrade(&self.server),
             topology: self.topology,
             update_receiver: self.update_receiver,
+            shutdown: self.shutdown,
         };
-        RUNTIME.execute(async move {
+        tasks.extend(RUNTIME.spawn(async move {
             update_monitor.execute().await;
-        });
+        }));
+
+        tasks
+    }
+}
+
+/// Handle held by a server through which its monitors are stopped. The monitors are also stopped if
+/// the handle is dropped.
+#[derive(Debug)]
+pub(crate) struct MonitorHandle {
+    sender: watch::Sender<bool>,
+}
+
+impl MonitorHandle {
+    /// Creates a new handle along with the receiver to pass to the server's `Monitor`.
+    pub(crate) fn channel() -> (Self, watch::Receiver<bool>) {
+        let (sender, receiver) = watch::channel(false);
+        (Self { sender }, receiver)
+    }
+
+    /// Stops the monitors, cancelling any checks they have in progress.
+    pub(crate) fn shutdown(&self) {
+        let _: std::result::Result<_, _> = self.sender.send(true);
+    }
+}
+
+/// Listener used by the monitors to determine when to stop, which is once their server has been
+/// removed from the topology or the topology has been closed.
+#[derive(Clone, Debug)]
+struct ShutdownListener {
+    server_removed: watch::Receiver<bool>,
+    topology_closed: watch::Receiver<bool>,
+}
+
+impl ShutdownListener {
+    fn is_shutdown(&self) -> bool {
+        *self.server_removed.borrow() || *self.topology_closed.borrow()
+    }
+
+    /// Listen until the monitors should stop.
+    /// This will not return until then, so make sure to only poll this via select.
+    async fn wait_for_shutdown(&mut self) {
+        tokio::select! {
+            _ = wait_until_set(&mut self.server_removed) => {}
+            _ = wait_until_set(&mut self.topology_closed) => {}
+        }
+    }
+}
+
+/// Waits until the value of the given receiver is true or its sender has been dropped.
+async fn wait_until_set(receiver: &mut watch::Receiver<bool>) {
+    while !*receiver.borrow() {
+        if receiver.changed().await.is_err() {
+            return;
+        }
     }
 }
 
 /// Monitor that performs regular heartbeats to determine server status.
+///
+/// If the server reports a `topologyVersion`, the monitor uses the streaming protocol: it sends an
+/// awaitable `hello`, after which the server pushes a new reply whenever its state changes or the
+/// heartbeat frequency elapses. Otherwise, the monitor polls the server with a new `hello` every
+/// heartbeat.
 struct HeartbeatMonitor {
     address: ServerAddress,
     connection: Option<Connection>,
@@ -85,6 +166,18 @@ struct HeartbeatMonitor {
     server: Weak<Server>,
     topology: WeakTopology,
     client_options: ClientOptions,
+    shutdown: ShutdownListener,
+
+    /// The topology version from the server's most recent reply on the current connection.
+    topology_version: Option<TopologyVersion>,
+
+    /// Receives round trip time samples from the RTT monitor, which is started once the monitor
+    /// begins streaming, since the duration of an awaited `hello` doesn't reflect the round trip
+    /// time to the server.
+    rtt_receiver: Option<watch::Receiver<Option<Duration>>>,
+
+    /// The RTT monitor's task, which is waited on before the heartbeat monitor finishes.
+    rtt_task: Option<AsyncJoinHandle<()>>,
 }
 
 impl HeartbeatMonitor {
@@ -93,6 +186,7 @@ impl HeartbeatMonitor {
         server: Weak<Server>,
         topology: WeakTopology,
         client_options: ClientOptions,
+        shutdown: ShutdownListener,
     ) -> Self {
         let handshaker = Handshaker::new(Some(client_options.clone().into()));
         Self {
@@ -101,17 +195,29 @@ impl HeartbeatMonitor {
             client_options,
             handshaker,
             topology,
+            shutdown,
             connection: None,
+            topology_version: None,
+            rtt_receiver: None,
+            rtt_task: None,
         }
     }
 
-    async fn execute(&mut self) {
-        let heartbeat_frequency = self
-            .client_options
+    fn heartbeat_frequency(&self) -> Duration {
+        self.client_options
             .heartbeat_freq
-            .unwrap_or(DEFAULT_HEARTBEAT_FREQUENCY);
+            .unwrap_or(DEFAULT_HEARTBEAT_FREQUENCY)
+    }
 
-        while self.topology.is_alive() {
+    /// Whether the next check will use the streaming protocol.
+    fn is_streaming(&self) -> bool {
+        self.connection.is_some() && self.topology_version.is_some()
+    }
+
+    async fn execute(&mut self) {
+        let heartbeat_frequency = self.heartbeat_frequency();
+
+        while !self.shutdown.is_shutdown() {
             let server = match self.server.upgrade() {
                 Some(server) => server,
                 None => break,
@@ -136,6 +242,12 @@ impl HeartbeatMonitor {
             drop(topology);
             drop(server);
 
+            // When streaming, the server delays its next reply until either its state changes or
+            // the heartbeat frequency elapses, so the next check can begin immediately.
+            if self.is_streaming() {
+                continue;
+            }
+
             #[cfg(test)]
             let min_frequency = self
                 .client_options
@@ -147,10 +259,23 @@ impl HeartbeatMonitor {
             #[cfg(not(test))]
             let min_frequency = MIN_HEARTBEAT_FREQUENCY;
 
-            RUNTIME.delay_for(min_frequency).await;
-            topology_check_requests_subscriber
-                .wait_for_message(heartbeat_frequency - min_frequency)
-                .await;
+            let mut shutdown = self.shutdown.clone();
+            tokio::select! {
+                _ = async {
+                    RUNTIME.delay_for(min_frequency).await;
+                    topology_check_requests_subscriber
+                        .wait_for_message(heartbeat_frequency - min_frequency)
+                        .await;
+                } => {}
+                _ = shutdown.wait_for_shutdown() => break,
+            }
+        }
+
+        // The RTT monitor stops along with this one, so this only waits for it to finish whatever
+        // it was doing.
+        self.rtt_receiver.take();
+        if let Some(rtt_task) = self.rtt_task.take() {
+            rtt_task.await;
         }
     }
 
@@ -162,6 +287,9 @@ impl HeartbeatMonitor {
         let mut retried = false;
         let check_result = match self.perform_is_master(topology).await {
             Ok(reply) => Ok(reply),
+            // The check was cancelled because the monitor is stopping, so there's nothing to
+            // report.
+            Err(_) if self.shutdown.is_shutdown() => return false,
             Err(e) => {
                 let previous_description = topology.get_server_description(&server.address).await;
                 if e.is_network_error()
@@ -189,16 +317,56 @@ impl HeartbeatMonitor {
     }
 
     async fn perform_is_master(&mut self, topology: &Topology) -> Result<IsMasterReply> {
+        if self.is_streaming() && self.rtt_receiver.is_none() {
+            self.start_rtt_monitor();
+        }
+
+        let heartbeat_frequency = self.heartbeat_frequency();
+
+        // Awaited replies can take up to the heartbeat frequency to arrive, so the usual connect
+        // timeout is extended accordingly.
+        let streaming_timeout = match self.client_options.connect_timeout {
+            Some(timeout) if timeout == Duration::from_secs(0) => None,
+            timeout => Some(timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT) + heartbeat_frequency),
+        };
+
+        let awaitable_options =
+            self.topology_version
+                .map(|topology_version| AwaitableHelloOptions {
+                    topology_version,
+                    max_await_time: heartbeat_frequency,
+                });
+
+        // Awaited replies may not arrive until the heartbeat frequency elapses, so they're
+        // cancelled if the monitor is stopped in the meantime.
+        let mut shutdown = self.shutdown.clone();
         let result = match self.connection {
+            Some(ref mut conn) if conn.is_streaming() => {
+                let is_master = receive_is_master(conn, Some(topology), Some(&self.client_options));
+                let is_master = async {
+                    match streaming_timeout {
+                        Some(timeout) => RUNTIME.timeout(timeout, is_master).await.and_then(|r| r),
+                        None => is_master.await,
+                    }
+                };
+                tokio::select! {
+                    result = is_master => result,
+                    _ = shutdown.wait_for_shutdown() => Err(ErrorKind::Shutdown.into()),
+                }
+            }
             Some(ref mut conn) => {
-                let command = is_master_command(self.client_options.server_api.as_ref());
-                run_is_master(
-                    conn,
-                    command,
-                    Some(topology),
-                    &self.client_options.sdam_event_handler,
-                )
-                .await
+                let awaited = awaitable_options.is_some();
+                let command =
+                    is_master_command(self.client_options.server_api.as_ref(), awaitable_options);
+                let is_master =
+                    run_is_master(conn, command, Some(topology), Some(&self.client_options));
+                match streaming_timeout {
+                    Some(timeout) if awaited => tokio::select! {
+                        result = RUNTIME.timeout(timeout, is_master) => result.and_then(|r| r),
+                        _ = shutdown.wait_for_shutdown() => Err(ErrorKind::Shutdown.into()),
+                    },
+                    _ => is_master.await,
+                }
             }
             None => {
                 let mut connection = Connection::connect_monitoring(
@@ -210,11 +378,7 @@ impl HeartbeatMonitor {
 
                 let res = self
                     .handshaker
-                    .handshake(
-                        &mut connection,
-                        Some(topology),
-                        &self.client_options.sdam_event_handler,
-                    )
+                    .handshake(&mut connection, Some(topology), Some(&self.client_options))
                     .await
                     .map(|r| r.is_master_reply);
                 self.connection = Some(connection);
@@ -222,11 +386,53 @@ impl HeartbeatMonitor {
             }
         };
 
-        if result.is_err() {
-            self.connection.take();
+        match result {
+            Ok(mut reply) => {
+                if self.topology_version.is_some() {
+                    // The duration of an awaited reply doesn't reflect the round trip time, so the
+                    // most recent sample from the RTT monitor is used instead, falling back to the
+                    // current average until one is available.
+                    let rtt_sample = self
+                        .rtt_receiver
+                        .as_ref()
+                        .and_then(|receiver| *receiver.borrow());
+                    let average_rtt = match rtt_sample {
+                        Some(rtt) => Some(rtt),
+                        None => topology
+                            .get_server_description(&self.address)
+                            .await
+                            .and_then(|sd| sd.average_round_trip_time),
+                    };
+                    if let Some(rtt) = average_rtt {
+                        reply.round_trip_time = rtt;
+                    }
+                }
+
+                self.topology_version = reply.command_response.topology_version;
+                Ok(reply)
+            }
+            Err(e) => {
+                self.connection.take();
+                self.topology_version.take();
+                Err(e)
+            }
         }
+    }
 
-        result
+    fn start_rtt_monitor(&mut self) {
+        let (sender, receiver) = watch::channel(None);
+        let rtt_monitor = RttMonitor {
+            address: self.address.clone(),
+            connection: None,
+            handshaker: Handshaker::new(Some(self.client_options.clone().into())),
+            client_options: self.client_options.clone(),
+            shutdown: self.shutdown.clone(),
+            sender,
+        };
+        self.rtt_task = RUNTIME.spawn(async move {
+            rtt_monitor.execute().await;
+        });
+        self.rtt_receiver = Some(receiver);
     }
 
     async fn handle_error(&mut self, error: Error, topology: &Topology, server: &Server) -> bool {
@@ -234,18 +440,102 @@ impl HeartbeatMonitor {
     }
 }
 
+/// Monitor that measures the round trip time to a server on a dedicated connection while the
+/// heartbeat monitor for the server is using the streaming protocol.
+struct RttMonitor {
+    address: ServerAddress,
+    connection: Option<Connection>,
+    handshaker: Handshaker,
+    client_options: ClientOptions,
+    shutdown: ShutdownListener,
+    sender: watch::Sender<Option<Duration>>,
+}
+
+impl RttMonitor {
+    async fn execute(mut self) {
+        let heartbeat_frequency = self
+            .client_options
+            .heartbeat_freq
+            .unwrap_or(DEFAULT_HEARTBEAT_FREQUENCY);
+
+        let mut shutdown = self.shutdown.clone();
+        while !shutdown.is_shutdown() {
+            let result = tokio::select! {
+                result = self.measure_round_trip_time() => result,
+                _ = shutdown.wait_for_shutdown() => break,
+            };
+
+            // Errors are left for the heartbeat monitor to detect and handle, so a failed check
+            // just causes a new connection to be established for the next one.
+            match result {
+                Ok(rtt) => {
+                    // The heartbeat monitor has stopped if there's no one left to receive samples.
+                    if self.sender.send(Some(rtt)).is_err() {
+                        break;
+                    }
+                }
+                Err(_) => {
+                    self.connection.take();
+                }
+            }
+
+            tokio::select! {
+                _ = RUNTIME.delay_for(heartbeat_frequency) => {}
+                _ = shutdown.wait_for_shutdown() => break,
+            }
+        }
+    }
+
+    /// Runs a `hello` command on the monitor's connection, establishing one if necessary, and
+    /// returns its round trip time. No heartbeat events are emitted.
+    async fn measure_round_trip_time(&mut self) -> Result<Duration> {
+        let reply = match self.connection {
+            Some(ref mut conn) => {
+                let command = is_master_command(self.client_options.server_api.as_ref(), None);
+                run_is_master(conn, command, None, None).await?
+            }
+            None => {
+                let mut connection = Connection::connect_monitoring(
+                    self.address.clone(),
+                    self.client_options.connect_timeout,
+                    self.client_options.tls_options(),
+                )
+                .await?;
+                let reply = self
+                    .handshaker
+                    .handshake(&mut connection, None, None)
+                    .await?
+                    .is_master_reply;
+                self.connection = Some(connection);
+                reply
+            }
+        };
+
+        Ok(reply.round_trip_time)
+    }
+}
+
 /// Monitor that listens for updates to a given server generated from operation execution.
 struct UpdateMonitor {
     server: Weak<Server>,
     topology: WeakTopology,
     update_receiver: ServerUpdateReceiver,
+    shutdown: ShutdownListener,
 }
 
 impl UpdateMonitor {
     async fn execute(mut self) {
         // If the pool encounters an error establishing a connection, it will
         // notify the update receiver and need to be handled.
-        while let Some(update) = self.update_receiver.recv().await {
+        loop {
+            let update = tokio::select! {
+                update = self.update_receiver.recv() => match update {
+                    Some(update) => update,
+                    None => return,
+                },
+                _ = self.shutdown.wait_for_shutdown() => return,
+            };
+
             let topology = match self.topology.upgrade() {
                 Some(it) => it,
                 _ => return,
@@ -1,12 +1,25 @@
-use std::{borrow::Cow, fmt, time::Duration};
+use std::{
+    borrow::Cow,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::{stream::BoxStream, Stream};
+use futures_util::stream::{self, StreamExt};
+use tokio::sync::watch;
 
 pub use crate::sdam::description::{server::ServerType, topology::TopologyType};
 use crate::{
     bson::DateTime,
+    error::{Error, ErrorKind, Result},
+    event::sdam::TopologyDescription,
     is_master::IsMasterCommandResponse,
     options::ServerAddress,
     sdam::ServerDescription,
     selection_criteria::TagSet,
+    RUNTIME,
 };
 
 /// A description of the most up-to-date information known about a server. Further details can be
@@ -171,3 +184,112 @@ impl<'a> fmt::Display for ServerInfo<'a> {
         write!(f, " }}")
     }
 }
+
+/// A stream of the descriptions of a [`Client`](crate::Client)'s topology, returned by
+/// [`Client::watch_topology`](crate::Client::watch_topology).
+///
+/// A new description is yielded each time the topology changes in a way that would emit a
+/// [`TopologyDescriptionChangedEvent`](crate::event::sdam::TopologyDescriptionChangedEvent),
+/// e.g. when a server is discovered, changes type or becomes unreachable. Round trip time updates
+/// alone do not produce a new description, but are reflected in the next one that is yielded.
+/// Changes that happen in quick succession may be coalesced into a single description. The stream
+/// ends once the `Client` has been dropped.
+pub struct TopologyWatcher {
+    receiver: watch::Receiver<crate::sdam::TopologyDescription>,
+    changes: BoxStream<'static, TopologyDescription>,
+}
+
+impl TopologyWatcher {
+    pub(crate) fn new(receiver: watch::Receiver<crate::sdam::TopologyDescription>) -> Self {
+        let last = receiver.borrow().clone();
+        let changes = stream::unfold(
+            (receiver.clone(), last),
+            |(mut receiver, mut last)| async move {
+                loop {
+                    receiver.changed().await.ok()?;
+                    let current = receiver.borrow().clone();
+                    if current != last {
+                        last = current.clone();
+                        return Some((current.into(), (receiver, last)));
+                    }
+                }
+            },
+        )
+        .boxed();
+
+        Self { receiver, changes }
+    }
+
+    /// Gets the most up-to-date description of the topology.
+    pub fn current(&self) -> TopologyDescription {
+        self.receiver.borrow().clone().into()
+    }
+
+    /// Waits until the description of the topology satisfies `predicate`, returning the first
+    /// description that does. The current description is checked before waiting for any changes.
+    ///
+    /// If no description satisfies `predicate` within `timeout`, an
+    /// [`ErrorKind::Timeout`](crate::error::ErrorKind::Timeout) error is returned. If the
+    /// `Client` is dropped first, an
+    /// [`ErrorKind::Shutdown`](crate::error::ErrorKind::Shutdown) error is returned.
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use mongodb::{error::Result, Client};
+    /// # async fn wait_for_primary(client: Client) -> Result<()> {
+    /// let description = client
+    ///     .watch_topology()
+    ///     .wait_for(|topology| topology.has_writable_server(), Duration::from_secs(30))
+    ///     .await?;
+    /// println!("writable topology: {}", description);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for(
+        &mut self,
+        predicate: impl Fn(&TopologyDescription) -> bool,
+        timeout: Duration,
+    ) -> Result<TopologyDescription> {
+        let current = self.current();
+        if predicate(&current) {
+            return Ok(current);
+        }
+
+        let changes = &mut self.changes;
+        let wait = async move {
+            while let Some(description) = changes.next().await {
+                if predicate(&description) {
+                    return Some(description);
+                }
+            }
+            None
+        };
+
+        match RUNTIME.timeout(timeout, wait).await {
+            Ok(Some(description)) => Ok(description),
+            Ok(None) => Err(ErrorKind::Shutdown.into()),
+            Err(_) => Err(Error::timeout(format!(
+                "Timed out after {:?} waiting for the topology to satisfy the predicate. Current \
+                 topology: {}",
+                timeout,
+                self.current()
+            ))),
+        }
+    }
+}
+
+impl Stream for TopologyWatcher {
+    type Item = TopologyDescription;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.changes.as_mut().poll_next(cx)
+    }
+}
+
+impl fmt::Debug for TopologyWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::result::Result<(), fmt::Error> {
+        f.debug_struct("TopologyWatcher")
+            .field("current", &self.current())
+            .finish()
+    }
+}
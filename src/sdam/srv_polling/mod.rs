@@ -11,6 +11,7 @@ use super::{
 use crate::{
     error::{Error, Result},
     options::ClientOptions,
+    runtime::AsyncJoinHandle,
     srv::{LookupHosts, SrvResolver},
     RUNTIME,
 };
@@ -46,12 +47,12 @@ impl SrvPollingMonitor {
     /// Starts a monitoring task that periodically performs SRV record lookups to determine if the
     /// set of mongos in the cluster have changed. A weak reference is used to ensure that the
     /// monitoring task doesn't keep the topology alive after the client has been dropped.
-    pub(super) fn start(topology: WeakTopology) {
-        RUNTIME.execute(async move {
+    pub(super) fn start(topology: WeakTopology) -> Option<AsyncJoinHandle<()>> {
+        RUNTIME.spawn(async move {
             if let Some(mut monitor) = Self::new(topology) {
                 monitor.execute().await;
             }
-        });
+        })
     }
 
     fn rescan_interval(&self) -> Duration {
@@ -63,8 +64,12 @@ impl SrvPollingMonitor {
             matches!(tt, TopologyType::Sharded | TopologyType::Unknown)
         }
 
+        let mut closed = self.topology.watch_closed();
         while self.topology.is_alive() {
-            RUNTIME.delay_for(self.rescan_interval()).await;
+            tokio::select! {
+                _ = RUNTIME.delay_for(self.rescan_interval()) => {}
+                _ = closed.changed() => continue,
+            }
 
             let topology = match self.topology.upgrade() {
                 Some(topology) => topology,
@@ -2,17 +2,13 @@ pub(super) mod server;
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-        Weak,
-    },
+    sync::{Arc, Weak},
     time::Duration,
 };
 
 #[cfg(test)]
 use futures_util::stream::{FuturesUnordered, StreamExt};
-use tokio::sync::{RwLock, RwLockWriteGuard};
+use tokio::sync::{watch, RwLock, RwLockWriteGuard};
 
 use self::server::Server;
 use super::{
@@ -27,16 +23,19 @@ use crate::{
     client::ClusterTime,
     cmap::{conn::ConnectionGeneration, Command, Connection, PoolGeneration},
     error::{load_balanced_mode_mismatch, Error, Result},
-    event::sdam::{
-        ServerClosedEvent,
-        ServerDescriptionChangedEvent,
-        ServerOpeningEvent,
-        TopologyClosedEvent,
-        TopologyDescriptionChangedEvent,
-        TopologyOpeningEvent,
+    event::{
+        self,
+        sdam::{
+            ServerClosedEvent,
+            ServerDescriptionChangedEvent,
+            ServerOpeningEvent,
+            TopologyClosedEvent,
+            TopologyDescriptionChangedEvent,
+            TopologyOpeningEvent,
+        },
     },
     options::{ClientOptions, SelectionCriteria, ServerAddress},
-    runtime::HttpClient,
+    runtime::{AsyncJoinHandle, HttpClient},
     sdam::{
         description::{
             server::{ServerDescription, ServerType},
@@ -68,8 +67,12 @@ pub(crate) struct WeakTopology {
 /// manager and the client options.
 #[derive(Clone, Debug)]
 struct Common {
-    is_alive: Arc<AtomicBool>,
+    /// Set to true once the topology has been closed. Monitors watch this so that they can stop
+    /// any checks in progress rather than waiting for them to complete.
+    closed_sender: Arc<watch::Sender<bool>>,
+    closed_receiver: watch::Receiver<bool>,
     message_manager: TopologyMessageManager,
+    description_receiver: watch::Receiver<TopologyDescription>,
     options: ClientOptions,
     id: ObjectId,
 }
@@ -80,9 +83,21 @@ struct Common {
 struct TopologyState {
     http_client: HttpClient,
     description: TopologyDescription,
+    description_sender: watch::Sender<TopologyDescription>,
     servers: HashMap<ServerAddress, Arc<Server>>,
     options: ClientOptions,
     id: ObjectId,
+
+    /// The tasks of the monitors for each server, which are waited on when the topology is shut
+    /// down. The tasks of servers removed from the topology are detached, since those monitors
+    /// have already been told to stop.
+    monitor_tasks: HashMap<ServerAddress, Vec<AsyncJoinHandle<()>>>,
+
+    /// The task of the SRV polling monitor, if one was started.
+    srv_polling_task: Option<AsyncJoinHandle<()>>,
+
+    /// Whether the events for the topology being closed have already been emitted.
+    closed: bool,
 }
 
 impl Topology {
@@ -92,14 +107,18 @@ impl Topology {
         let is_load_balanced = description.topology_type() == TopologyType::LoadBalanced;
 
         let id = ObjectId::new();
-        if let Some(ref handler) = options.sdam_event_handler {
+        event::emit_sdam_event(&options, |handler| {
             let event = TopologyOpeningEvent { topology_id: id };
             handler.handle_topology_opening_event(event);
-        }
+        });
 
+        let (description_sender, description_receiver) = watch::channel(description.clone());
+        let (closed_sender, closed_receiver) = watch::channel(false);
         let common = Common {
-            is_alive: Arc::new(AtomicBool::new(true)),
+            closed_sender: Arc::new(closed_sender),
+            closed_receiver,
             message_manager: TopologyMessageManager::new(),
+            description_receiver,
             options: options.clone(),
             id,
         };
@@ -108,10 +127,14 @@ impl Topology {
 
         let topology_state = TopologyState {
             description,
+            description_sender,
             servers: Default::default(),
             http_client,
             options: options.clone(),
             id,
+            monitor_tasks: Default::default(),
+            srv_polling_task: None,
+            closed: false,
         };
 
         let state = Arc::new(RwLock::new(topology_state));
@@ -124,7 +147,7 @@ impl Topology {
             topology_state.add_new_server(address.clone(), options.clone(), &topology.downgrade());
         }
 
-        if let Some(ref handler) = options.sdam_event_handler {
+        event::emit_sdam_event(&options, |handler| {
             let event = TopologyDescriptionChangedEvent {
                 topology_id: id,
                 previous_description: TopologyDescription::new_empty().into(),
@@ -139,7 +162,7 @@ impl Topology {
                 };
                 handler.handle_server_opening_event(event);
             }
-        }
+        });
 
         if is_load_balanced {
             for server_address in &options.hosts {
@@ -164,7 +187,7 @@ impl Topology {
         #[cfg(not(test))]
         let disable_monitoring_threads = false;
         if !is_load_balanced && !disable_monitoring_threads {
-            SrvPollingMonitor::start(topology.downgrade());
+            topology_state.srv_polling_task = SrvPollingMonitor::start(topology.downgrade());
         }
 
         drop(topology_state);
@@ -172,7 +195,36 @@ impl Topology {
     }
 
     pub(crate) fn close(&self) {
-        self.common.is_alive.store(false, Ordering::SeqCst);
+        let _: std::result::Result<_, _> = self.common.closed_sender.send(true);
+    }
+
+    /// Whether the topology is still open, i.e. it hasn't been closed or shut down.
+    pub(crate) fn is_alive(&self) -> bool {
+        !*self.common.closed_receiver.borrow()
+    }
+
+    /// Closes the topology, stopping its monitoring tasks and closing each server's connection
+    /// pool once all of the connections checked out of it have been returned. The monitoring tasks
+    /// have finished and the events for the servers and the topology being closed have been
+    /// emitted by the time this returns.
+    pub(crate) async fn shutdown(&self) {
+        self.close();
+
+        // The lock isn't held while waiting on the monitors, since a check that was already in
+        // progress may need it to update the topology before the monitor stops.
+        let tasks = self.state.write().await.take_monitor_tasks();
+        for task in tasks {
+            task.await;
+        }
+
+        // The lock isn't held while waiting on the pools, since operations that are still in
+        // progress may need it to handle errors before checking their connections back in.
+        let servers: Vec<Arc<Server>> = self.state.read().await.servers.values().cloned().collect();
+        for server in servers {
+            server.pool.close().await;
+        }
+
+        self.state.write().await.emit_closed_events();
     }
 
     /// Gets the addresses of the servers in the cluster.
@@ -241,6 +293,12 @@ impl Topology {
         self.common.message_manager.subscribe_to_topology_changes()
     }
 
+    /// Returns a receiver that always holds the latest topology description, including the latest
+    /// round trip times, and is notified whenever it is updated.
+    pub(crate) fn watch_description(&self) -> watch::Receiver<TopologyDescription> {
+        self.common.description_receiver.clone()
+    }
+
     /// Wakes all tasks waiting for a topology change.
     pub(crate) fn notify_topology_changed(&self) {
         self.common.message_manager.notify_topology_changed();
@@ -476,21 +534,7 @@ impl Topology {
 
 impl Drop for TopologyState {
     fn drop(&mut self) {
-        if let Some(ref handler) = self.options.sdam_event_handler {
-            if matches!(self.description.topology_type, TopologyType::LoadBalanced) {
-                for host in self.servers.keys() {
-                    let event = ServerClosedEvent {
-                        address: host.clone(),
-                        topology_id: self.id,
-                    };
-                    handler.handle_server_closed_event(event);
-                }
-            }
-            let event = TopologyClosedEvent {
-                topology_id: self.id,
-            };
-            handler.handle_topology_closed_event(event);
-        }
+        self.emit_closed_events();
     }
 }
 
@@ -504,7 +548,12 @@ impl WeakTopology {
     }
 
     pub(crate) fn is_alive(&self) -> bool {
-        self.common.is_alive.load(Ordering::SeqCst)
+        !*self.common.closed_receiver.borrow()
+    }
+
+    /// Returns a receiver whose value is set to true once the topology has been closed.
+    pub(crate) fn watch_closed(&self) -> watch::Receiver<bool> {
+        self.common.closed_receiver.clone()
     }
 
     pub(crate) fn client_options(&self) -> &ClientOptions {
@@ -532,7 +581,7 @@ impl TopologyState {
             topology.clone(),
             self.http_client.clone(),
         );
-        self.servers.insert(address, server);
+        self.servers.insert(address.clone(), server);
 
         #[cfg(test)]
         if options
@@ -543,7 +592,19 @@ impl TopologyState {
             return;
         }
 
-        monitor.start();
+        let tasks = monitor.start();
+        self.monitor_tasks.insert(address, tasks);
+    }
+
+    /// Takes the tasks of all of the monitors so that they can be waited on.
+    fn take_monitor_tasks(&mut self) -> Vec<AsyncJoinHandle<()>> {
+        let mut tasks: Vec<_> = self
+            .monitor_tasks
+            .drain()
+            .flat_map(|(_, tasks)| tasks)
+            .collect();
+        tasks.extend(self.srv_polling_task.take());
+        tasks
     }
 
     /// Updates the given `command` as needed based on the `criteria`.
@@ -576,11 +637,12 @@ impl TopologyState {
 
         let hosts: HashSet<_> = self.description.server_addresses().cloned().collect();
         self.sync_hosts(&hosts, options, &topology);
+        self.publish_description();
 
         let diff = old_description.diff(&self.description);
         let topology_changed = diff.is_some();
 
-        if let Some(ref handler) = options.sdam_event_handler {
+        event::emit_sdam_event(options, |handler| {
             if let Some(diff) = diff {
                 for (address, (previous_description, new_description)) in diff.changed_servers {
                     let event = ServerDescriptionChangedEvent {
@@ -615,7 +677,7 @@ impl TopologyState {
                 };
                 handler.handle_topology_description_changed_event(event);
             }
-        }
+        });
 
         Ok(topology_changed)
     }
@@ -630,6 +692,14 @@ impl TopologyState {
     ) {
         self.description.sync_hosts(hosts);
         self.sync_hosts(hosts, options, &topology);
+        self.publish_description();
+    }
+
+    /// Publishes the current description to the receivers returned by
+    /// `Topology::watch_description`. This is done after every update, even ones that don't change
+    /// the topology, so that the published round trip times stay current.
+    fn publish_description(&self) {
+        let _: std::result::Result<_, _> = self.description_sender.send(self.description.clone());
     }
 
     fn sync_hosts(
@@ -642,7 +712,16 @@ impl TopologyState {
             self.add_new_server(address.clone(), options.clone(), topology);
         }
 
-        self.servers.retain(|host, _| hosts.contains(host));
+        self.monitor_tasks.retain(|host, _| hosts.contains(host));
+        self.servers.retain(|host, server| {
+            let keep = hosts.contains(host);
+            if !keep {
+                // Operations may still hold a reference to the server, so its monitors are
+                // stopped explicitly rather than when the server is dropped.
+                server.shutdown_monitors();
+            }
+            keep
+        });
     }
 
     #[cfg(test)]
@@ -654,6 +733,30 @@ impl TopologyState {
             .collect();
         let _: Vec<_> = rxen.collect().await;
     }
+
+    /// Emits the events for the servers and the topology being closed, unless they have already
+    /// been emitted.
+    fn emit_closed_events(&mut self) {
+        if std::mem::replace(&mut self.closed, true) {
+            return;
+        }
+
+        event::emit_sdam_event(&self.options, |handler| {
+            if matches!(self.description.topology_type, TopologyType::LoadBalanced) {
+                for host in self.servers.keys() {
+                    let event = ServerClosedEvent {
+                        address: host.clone(),
+                        topology_id: self.id,
+                    };
+                    handler.handle_server_closed_event(event);
+                }
+            }
+            let event = TopologyClosedEvent {
+                topology_id: self.id,
+            };
+            handler.handle_topology_closed_event(event);
+        });
+    }
 }
 
 /// Enum describing a point in time during an operation's execution relative to when the MongoDB
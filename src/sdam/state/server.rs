@@ -8,7 +8,7 @@ use crate::{
     cmap::{options::ConnectionPoolOptions, ConnectionPool, EstablishError},
     options::{ClientOptions, ServerAddress},
     runtime::{AcknowledgedMessage, HttpClient},
-    sdam::monitor::Monitor,
+    sdam::monitor::{Monitor, MonitorHandle},
 };
 
 /// Contains the state for a given server in the topology.
@@ -21,6 +21,9 @@ pub(crate) struct Server {
 
     /// Number of operations currently using this server.
     operation_count: AtomicU32,
+
+    /// Handle used to stop the server's monitors once it's removed from the topology.
+    monitor_handle: MonitorHandle,
 }
 
 impl Server {
@@ -30,6 +33,7 @@ impl Server {
             address: address.clone(),
             pool: ConnectionPool::new_mocked(address),
             operation_count: AtomicU32::new(operation_count),
+            monitor_handle: MonitorHandle::channel().0,
         }
     }
 
@@ -42,6 +46,7 @@ impl Server {
         http_client: HttpClient,
     ) -> (Arc<Self>, Monitor) {
         let (update_sender, update_receiver) = ServerUpdateSender::channel();
+        let (monitor_handle, shutdown_receiver) = MonitorHandle::channel();
         let server = Arc::new(Self {
             pool: ConnectionPool::new(
                 address.clone(),
@@ -51,9 +56,17 @@ impl Server {
             ),
             address: address.clone(),
             operation_count: AtomicU32::new(0),
+            monitor_handle,
         });
 
-        let monitor = Monitor::new(address, &server, topology, options.clone(), update_receiver);
+        let monitor = Monitor::new(
+            address,
+            &server,
+            topology,
+            options.clone(),
+            update_receiver,
+            shutdown_receiver,
+        );
         (server, monitor)
     }
 
@@ -68,6 +81,11 @@ impl Server {
     pub(crate) fn operation_count(&self) -> u32 {
         self.operation_count.load(Ordering::SeqCst)
     }
+
+    /// Stops the server's monitors, cancelling any checks they have in progress.
+    pub(crate) fn shutdown_monitors(&self) {
+        self.monitor_handle.shutdown();
+    }
 }
 
 /// An event that could update the topology's view of a server.
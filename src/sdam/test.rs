@@ -3,13 +3,15 @@ use std::{
     time::{Duration, Instant},
 };
 
-use bson::{bson, doc};
+use bson::{bson, doc, oid::ObjectId};
+use futures_util::stream::StreamExt;
 use semver::VersionReq;
 use tokio::sync::RwLockWriteGuard;
 
 use crate::{
     error::ErrorKind,
-    sdam::ServerType,
+    options::ClientOptions,
+    sdam::{ServerType, TopologyType},
     test::{
         CmapEvent,
         Event,
@@ -23,6 +25,7 @@ use crate::{
         CLIENT_OPTIONS,
         LOCK,
     },
+    test_util::{MockReply, MockServer, MockServerOptions, MockTopology},
     Client,
     RUNTIME,
 };
@@ -180,6 +183,54 @@ async fn sdam_pool_management() {
         .expect("should see server heartbeat succeeded event");
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn streaming_protocol() {
+    let _guard: RwLockWriteGuard<_> = LOCK.run_exclusively().await;
+
+    let mut options = CLIENT_OPTIONS.clone();
+    if options.load_balanced.unwrap_or(false) {
+        println!("skipping streaming_protocol test due to load-balanced topology");
+        return;
+    }
+    options.hosts.drain(1..);
+    options.direct_connection = Some(true);
+    options.heartbeat_freq = Some(Duration::from_millis(500));
+
+    let event_handler = EventHandler::new();
+    let mut subscriber = event_handler.subscribe();
+
+    let client = EventClient::with_additional_options(
+        Some(options),
+        Some(Duration::from_millis(50)),
+        None,
+        event_handler.clone(),
+    )
+    .await;
+
+    if client.server_version_lt(4, 4) {
+        println!("skipping streaming_protocol test due to server not supporting topologyVersion");
+        return;
+    }
+
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::ServerHeartbeatSucceeded(e)) if e.awaited)
+        })
+        .await
+        .expect("should see awaited server heartbeat succeeded event");
+
+    // The server replies to an awaitable hello no later than maxAwaitTimeMS (i.e. the heartbeat
+    // frequency), so awaited heartbeats should keep arriving without any new checks being
+    // requested.
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::ServerHeartbeatSucceeded(e)) if e.awaited)
+        })
+        .await
+        .expect("should see another awaited server heartbeat succeeded event");
+}
+
 // prose version of minPoolSize-error.yml SDAM integration test
 // TODO: RUST-560 replace this test with the spec runner
 #[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
@@ -370,3 +421,127 @@ async fn auth_error() {
         &vec![bson!({ "_id": 5 }), bson!({ "_id": 6 })]
     );
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn watch_topology() {
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .topology(MockTopology::ReplicaSetPrimary {
+                set_name: "rs0".to_string(),
+            })
+            .hello_fields(doc! { "tags": { "dc": "east" } })
+            .build(),
+    )
+    .unwrap();
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.heartbeat_freq = Some(Duration::from_millis(500));
+    let client = Client::with_options(options).unwrap();
+
+    let mut watcher = client.watch_topology();
+    let description = watcher
+        .wait_for(
+            |topology| topology.primary().is_some(),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("primary should be discovered");
+    assert_eq!(
+        description.topology_type(),
+        TopologyType::ReplicaSetWithPrimary
+    );
+    assert_eq!(description.set_name().map(String::as_str), Some("rs0"));
+    let primary = description.primary().unwrap();
+    assert_eq!(primary.address(), server.address());
+    assert_eq!(
+        primary
+            .tags()
+            .and_then(|tags| tags.get("dc"))
+            .map(String::as_str),
+        Some("east")
+    );
+    assert!(primary.average_round_trip_time().is_some());
+
+    // The snapshot reflects the same state without waiting.
+    let snapshot = client.topology_description();
+    assert!(snapshot.has_writable_server());
+    assert_eq!(snapshot.servers().len(), 1);
+
+    // Once the server becomes unreachable, the next description yielded has no primary.
+    server.add_reply("hello", MockReply::network_error().times(1000));
+    let description = RUNTIME
+        .timeout(Duration::from_secs(5), async {
+            loop {
+                let description = watcher.next().await.expect("client is still alive");
+                if description.primary().is_none() {
+                    return description;
+                }
+            }
+        })
+        .await
+        .expect("step down should be observed");
+    assert_eq!(
+        description.topology_type(),
+        TopologyType::ReplicaSetNoPrimary
+    );
+    assert!(!description.has_writable_server());
+
+    let error = watcher
+        .wait_for(
+            |topology| topology.primary().is_some(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap_err();
+    assert!(error.is_timeout(), "{:?}", error);
+
+    // The stream ends once the client is dropped.
+    drop(client);
+    assert!(watcher.next().await.is_none());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn dropping_client_cancels_awaited_hello() {
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .hello_fields(doc! {
+                "topologyVersion": { "processId": ObjectId::new(), "counter": 0_i64 },
+            })
+            .build(),
+    )
+    .unwrap();
+
+    let handler = Arc::new(EventHandler::new());
+    let mut subscriber = handler.subscribe();
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.heartbeat_freq = Some(Duration::from_millis(500));
+    options.sdam_event_handler = Some(handler.clone());
+    let client = Client::with_options(options).unwrap();
+
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::ServerHeartbeatSucceeded(e)) if e.awaited)
+        })
+        .await
+        .expect("should see awaited server heartbeat succeeded event");
+
+    // Leave the monitors waiting on replies that won't arrive before the test times out.
+    server.add_reply(
+        "hello",
+        MockReply::hello()
+            .delay(Duration::from_secs(60))
+            .times(1000),
+    );
+    RUNTIME.delay_for(Duration::from_millis(200)).await;
+
+    // The heartbeat monitor keeps the topology alive while it's checking the server, so the
+    // topology is only closed once the awaited hello has been cancelled.
+    drop(client);
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::TopologyClosed(_)))
+        })
+        .await
+        .expect("should see topology closed event");
+}
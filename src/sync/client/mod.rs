@@ -1,11 +1,17 @@
 pub mod session;
 
-use super::{ClientSession, Database};
+use std::time::Duration;
+
+use super::{ChangeStream, ClientSession, Database, SessionChangeStream, TopologyWatcher};
 use crate::{
     bson::Document,
+    change_stream::event::ChangeStreamEvent,
+    command::RunnableCommand,
     concern::{ReadConcern, WriteConcern},
     error::Result,
+    event::sdam::TopologyDescription,
     options::{
+        ChangeStreamOptions,
         ClientOptions,
         DatabaseOptions,
         ListDatabasesOptions,
@@ -57,7 +63,7 @@ use crate::{
 /// for instructions on setting these values at the system level.
 #[derive(Clone, Debug)]
 pub struct Client {
-    async_client: AsyncClient,
+    pub(crate) async_client: AsyncClient,
 }
 
 impl From<AsyncClient> for Client {
@@ -99,6 +105,11 @@ impl Client {
         self.async_client.write_concern()
     }
 
+    /// Gets the default timeout the `Client` uses for operations.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.async_client.timeout()
+    }
+
     /// Gets a handle to a database specified by `name` in the cluster the `Client` is connected to.
     /// The `Database` options (e.g. read preference and write concern) will default to those of the
     /// `Client`.
@@ -151,10 +162,97 @@ impl Client {
         )
     }
 
+    /// Runs a command defined by a [`RunnableCommand`] against the `admin` database, returning its
+    /// typed output. See [`Database::run_typed_command`] for more information.
+    pub fn run_typed_command<C: RunnableCommand>(&self, command: C) -> Result<C::Output> {
+        RUNTIME.block_on(self.async_client.run_typed_command(command))
+    }
+
+    /// Runs a command defined by a [`RunnableCommand`] against the `admin` database using the
+    /// provided `ClientSession`, returning its typed output. See
+    /// [`Database::run_typed_command`] for more information.
+    pub fn run_typed_command_with_session<C: RunnableCommand>(
+        &self,
+        command: C,
+        session: &mut ClientSession,
+    ) -> Result<C::Output> {
+        RUNTIME.block_on(
+            self.async_client
+                .run_typed_command_with_session(command, &mut session.async_client_session),
+        )
+    }
+
     /// Starts a new `ClientSession`.
     pub fn start_session(&self, options: Option<SessionOptions>) -> Result<ClientSession> {
         RUNTIME
             .block_on(self.async_client.start_session(options))
             .map(Into::into)
     }
+
+    /// Starts a new [`ChangeStream`] that receives events for all changes in the cluster. The
+    /// stream does not observe changes from system collections or the "config", "local" or
+    /// "admin" databases. Note that this method (`watch` on a cluster) is only supported in
+    /// MongoDB 4.0 or greater.
+    ///
+    /// See the documentation [here](https://docs.mongodb.com/manual/changeStreams/) on change
+    /// streams.
+    ///
+    /// Change streams require either a "majority" read concern or no read concern. Anything else
+    /// will cause a server error.
+    ///
+    /// Note that using a `$project` stage to remove any of the `_id`, `operationType` or `ns`
+    /// fields will cause an error. The driver requires these fields to support resumability. For
+    /// more information on resumability, see the documentation for
+    /// [`ChangeStream`](struct.ChangeStream.html)
+    ///
+    /// If the pipeline alters the structure of the returned events, the parsed type will need to be
+    /// changed via [`ChangeStream::with_type`].
+    pub fn watch(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+    ) -> Result<ChangeStream<ChangeStreamEvent<Document>>> {
+        RUNTIME
+            .block_on(self.async_client.watch(pipeline, options))
+            .map(ChangeStream::new)
+    }
+
+    /// Starts a new [`SessionChangeStream`] that receives events for all changes in the cluster
+    /// using the provided [`ClientSession`].  See [`Client::watch`] for more information.
+    pub fn watch_with_session(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<SessionChangeStream<ChangeStreamEvent<Document>>> {
+        RUNTIME
+            .block_on(self.async_client.watch_with_session(
+                pipeline,
+                options,
+                &mut session.async_client_session,
+            ))
+            .map(SessionChangeStream::new)
+    }
+
+    /// Gets a snapshot of the most up-to-date description of the cluster's topology. See
+    /// [`crate::Client::topology_description`] for more information.
+    pub fn topology_description(&self) -> TopologyDescription {
+        self.async_client.topology_description()
+    }
+
+    /// Returns a [`TopologyWatcher`], which iterates over the descriptions of the cluster's
+    /// topology, yielding a new description each time the topology changes.
+    /// [`TopologyWatcher::wait_for`] can be used to wait for the topology to satisfy a condition,
+    /// e.g. that a primary has been discovered.
+    pub fn watch_topology(&self) -> TopologyWatcher {
+        TopologyWatcher::new(self.async_client.watch_topology())
+    }
+
+    /// Shuts down the `Client`, gracefully releasing the resources it holds. This ends the server
+    /// sessions in the `Client`'s session pool, stops monitoring the cluster, waits for all
+    /// connections checked out by in-progress operations to be returned, and then closes each
+    /// connection pool. See [`crate::Client::shutdown`] for more information.
+    pub fn shutdown(self) {
+        RUNTIME.block_on(self.async_client.shutdown())
+    }
 }
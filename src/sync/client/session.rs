@@ -1,8 +1,10 @@
+use std::time::Instant;
+
 use super::Client;
 use crate::{
     bson::Document,
-    client::session::ClusterTime,
-    error::Result,
+    client::session::{ClusterTime, WITH_TRANSACTION_TIMEOUT},
+    error::{Result, TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
     options::{SessionOptions, TransactionOptions},
     ClientSession as AsyncClientSession,
     RUNTIME,
@@ -135,4 +137,86 @@ impl ClientSession {
     pub fn abort_transaction(&mut self) -> Result<()> {
         RUNTIME.block_on(self.async_client_session.abort_transaction())
     }
+
+    /// Starts a transaction, runs the given callback, and commits or aborts the transaction.
+    ///
+    /// If the callback or the commit returns an error with a
+    /// [`crate::error::TRANSIENT_TRANSACTION_ERROR`] label, the entire transaction is retried, and
+    /// if the commit returns an error with a [`crate::error::UNKNOWN_TRANSACTION_COMMIT_RESULT`]
+    /// label, the commit is retried. Retries stop once 120 seconds have elapsed since this method
+    /// was called. If the callback returns any other error, the transaction is aborted and the
+    /// error is returned. See [`crate::ClientSession::with_transaction`] for more information.
+    ///
+    /// ```rust
+    /// # use mongodb::{bson::{doc, Document}, error::Result, sync::{Client, ClientSession}};
+    /// #
+    /// # async fn do_stuff() -> Result<()> {
+    /// # let client = Client::with_uri_str("mongodb://example.com")?;
+    /// # let coll = client.database("foo").collection::<Document>("bar");
+    /// # let mut session = client.start_session(None)?;
+    /// session.with_transaction(
+    ///     |session| {
+    ///         coll.insert_one_with_session(doc! { "x": 1 }, None, session)?;
+    ///         coll.delete_one_with_session(doc! { "y": 2 }, None, session)?;
+    ///         Ok(())
+    ///     },
+    ///     None,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_transaction<R, F>(
+        &mut self,
+        mut callback: F,
+        options: impl Into<Option<TransactionOptions>>,
+    ) -> Result<R>
+    where
+        F: FnMut(&mut ClientSession) -> Result<R>,
+    {
+        let options = options.into();
+        let start = Instant::now();
+
+        'transaction: loop {
+            self.start_transaction(options.clone())?;
+
+            let result = match callback(self) {
+                Ok(result) => result,
+                Err(error) => {
+                    if self.async_client_session.transaction.is_active() {
+                        self.abort_transaction()?;
+                    }
+                    if error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+                        && start.elapsed() < WITH_TRANSACTION_TIMEOUT
+                    {
+                        continue 'transaction;
+                    }
+                    return Err(error);
+                }
+            };
+
+            // the callback committed or aborted the transaction itself.
+            if !self.async_client_session.transaction.is_active() {
+                return Ok(result);
+            }
+
+            'commit: loop {
+                let error = match self.commit_transaction() {
+                    Ok(()) => return Ok(result),
+                    Err(error) => error,
+                };
+                if error.is_max_time_ms_expired_error()
+                    || start.elapsed() >= WITH_TRANSACTION_TIMEOUT
+                {
+                    return Err(error);
+                }
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue 'commit;
+                }
+                if error.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    continue 'transaction;
+                }
+                return Err(error);
+            }
+        }
+    }
 }
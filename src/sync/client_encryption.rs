@@ -0,0 +1,59 @@
+use super::Client;
+use crate::{
+    bson::{Binary, Bson},
+    client_encryption::ClientEncryption as AsyncClientEncryption,
+    error::Result,
+    options::{DataKeyOptions, EncryptOptions, KmsProviders},
+    Namespace,
+    RUNTIME,
+};
+
+/// A `ClientEncryption` is used to create data keys and to explicitly encrypt and decrypt values
+/// with them, e.g. when automatic encryption is not enabled or to encrypt values that can't be
+/// described by a schema.
+#[derive(Debug)]
+pub struct ClientEncryption {
+    async_client_encryption: AsyncClientEncryption,
+}
+
+impl ClientEncryption {
+    /// Creates a new `ClientEncryption` that stores its data keys in the `key_vault_namespace`
+    /// collection accessed via `key_vault_client`, protected by the given KMS providers.
+    pub fn new(
+        key_vault_client: Client,
+        key_vault_namespace: Namespace,
+        kms_providers: KmsProviders,
+    ) -> Self {
+        Self {
+            async_client_encryption: AsyncClientEncryption::new(
+                key_vault_client.async_client,
+                key_vault_namespace,
+                kms_providers,
+            ),
+        }
+    }
+
+    /// Creates a new data key encrypted with the master key of the KMS provider named
+    /// `kms_provider`, stores it in the key vault collection, and returns its id.
+    pub fn create_data_key(
+        &self,
+        kms_provider: impl AsRef<str>,
+        options: impl Into<Option<DataKeyOptions>>,
+    ) -> Result<Binary> {
+        RUNTIME.block_on(
+            self.async_client_encryption
+                .create_data_key(kms_provider, options),
+        )
+    }
+
+    /// Encrypts a value with the data key and algorithm specified in the options, returning the
+    /// encrypted value as a BSON binary of subtype 6.
+    pub fn encrypt(&self, value: impl Into<Bson>, options: EncryptOptions) -> Result<Binary> {
+        RUNTIME.block_on(self.async_client_encryption.encrypt(value, options))
+    }
+
+    /// Decrypts a value that was encrypted with a data key stored in the key vault collection.
+    pub fn decrypt(&self, value: &Binary) -> Result<Bson> {
+        RUNTIME.block_on(self.async_client_encryption.decrypt(value))
+    }
+}
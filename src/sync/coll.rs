@@ -1,14 +1,18 @@
-use std::{borrow::Borrow, fmt::Debug};
+use std::{borrow::Borrow, fmt::Debug, time::Duration};
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::{ClientSession, Cursor, SessionCursor};
+use super::{ChangeStream, ClientSession, Cursor, SessionChangeStream, SessionCursor};
 use crate::{
     bson::{Bson, Document},
+    change_stream::event::ChangeStreamEvent,
     error::Result,
     index::IndexModel,
     options::{
         AggregateOptions,
+        BulkWriteOptions,
+        ChangeStreamOptions,
+        CollectionStatsOptions,
         CountOptions,
         CreateIndexOptions,
         DeleteOptions,
@@ -16,6 +20,7 @@ use crate::{
         DropCollectionOptions,
         DropIndexOptions,
         EstimatedDocumentCountOptions,
+        ExplainVerbosity,
         FindOneAndDeleteOptions,
         FindOneAndReplaceOptions,
         FindOneAndUpdateOptions,
@@ -24,19 +29,27 @@ use crate::{
         InsertManyOptions,
         InsertOneOptions,
         ListIndexesOptions,
+        ModifyCollectionOptions,
         ReadConcern,
+        RenameCollectionOptions,
         ReplaceOptions,
         SelectionCriteria,
+        SyncIndexOptions,
         UpdateModifications,
         UpdateOptions,
         WriteConcern,
+        WriteModel,
     },
     results::{
+        BulkWriteResult,
+        CollectionStats,
         CreateIndexResult,
         CreateIndexesResult,
         DeleteResult,
+        ExplainResult,
         InsertManyResult,
         InsertOneResult,
+        SyncIndexesResult,
         UpdateResult,
     },
     Collection as AsyncCollection,
@@ -125,6 +138,11 @@ impl<T> Collection<T> {
         self.async_collection.write_concern()
     }
 
+    /// Gets the timeout of the `Collection`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.async_collection.timeout()
+    }
+
     /// Drops the collection, deleting all data, users, and indexes stored in it.
     pub fn drop(&self, options: impl Into<Option<DropCollectionOptions>>) -> Result<()> {
         RUNTIME.block_on(self.async_collection.drop(options.into()))
@@ -143,6 +161,84 @@ impl<T> Collection<T> {
         )
     }
 
+    /// Renames the collection to `to` within the same database.
+    ///
+    /// By default, this fails if a collection named `to` already exists; set
+    /// [`RenameCollectionOptions::drop_target`] to replace it instead. This `Collection` will
+    /// continue to refer to the old name.
+    pub fn rename(
+        &self,
+        to: impl AsRef<str>,
+        options: impl Into<Option<RenameCollectionOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_collection.rename(to.as_ref(), options.into()))
+    }
+
+    /// Renames the collection to `to` within the same database using the provided
+    /// `ClientSession`.
+    ///
+    /// By default, this fails if a collection named `to` already exists; set
+    /// [`RenameCollectionOptions::drop_target`] to replace it instead. This `Collection` will
+    /// continue to refer to the old name.
+    pub fn rename_with_session(
+        &self,
+        to: impl AsRef<str>,
+        options: impl Into<Option<RenameCollectionOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_collection.rename_with_session(
+            to.as_ref(),
+            options.into(),
+            &mut session.async_client_session,
+        ))
+    }
+
+    /// Changes the properties of the collection or one of its indexes, such as its validator or
+    /// whether an index is hidden.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/collMod/)
+    /// for more information.
+    pub fn modify(&self, options: ModifyCollectionOptions) -> Result<()> {
+        RUNTIME.block_on(self.async_collection.modify(options))
+    }
+
+    /// Changes the properties of the collection or one of its indexes, such as its validator or
+    /// whether an index is hidden, using the provided `ClientSession`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/collMod/)
+    /// for more information.
+    pub fn modify_with_session(
+        &self,
+        options: ModifyCollectionOptions,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        RUNTIME.block_on(
+            self.async_collection
+                .modify_with_session(options, &mut session.async_client_session),
+        )
+    }
+
+    /// Gets storage statistics for the collection, such as its size and the sizes of its indexes.
+    pub fn stats(
+        &self,
+        options: impl Into<Option<CollectionStatsOptions>>,
+    ) -> Result<CollectionStats> {
+        RUNTIME.block_on(self.async_collection.stats(options.into()))
+    }
+
+    /// Gets storage statistics for the collection, such as its size and the sizes of its indexes,
+    /// using the provided `ClientSession`.
+    pub fn stats_with_session(
+        &self,
+        options: impl Into<Option<CollectionStatsOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<CollectionStats> {
+        RUNTIME.block_on(
+            self.async_collection
+                .stats_with_session(options.into(), &mut session.async_client_session),
+        )
+    }
+
     /// Runs an aggregation operation.
     ///
     /// See the documentation [here](https://docs.mongodb.com/manual/aggregation/) for more
@@ -453,6 +549,17 @@ impl<T> Collection<T> {
         )
     }
 
+    /// Converges the indexes on this collection to `models`, creating missing indexes and, if
+    /// [`SyncIndexOptions::drop_extraneous`] is set, dropping indexes that match none of them.
+    /// Conflicting index definitions are reported in the result rather than being modified.
+    pub fn sync_indexes(
+        &self,
+        models: impl IntoIterator<Item = IndexModel>,
+        options: impl Into<Option<SyncIndexOptions>>,
+    ) -> Result<SyncIndexesResult> {
+        RUNTIME.block_on(self.async_collection.sync_indexes(models, options.into()))
+    }
+
     /// Updates all documents matching `query` in the collection using the provided `ClientSession`.
     ///
     /// Both `Document` and `Vec<Document>` implement `Into<UpdateModifications>`, so either can be
@@ -523,6 +630,129 @@ impl<T> Collection<T> {
             &mut session.async_client_session,
         ))
     }
+
+    /// Explains how the server would execute a [`Collection::find`](#method.find) with the given
+    /// filter and options, without returning any documents. If `verbosity` is `None`,
+    /// [`ExplainVerbosity::QueryPlanner`] is used.
+    ///
+    /// See the MongoDB [manual](https://www.mongodb.com/docs/manual/reference/command/explain/)
+    /// for more information on explaining operations.
+    pub fn find_explain(
+        &self,
+        filter: impl Into<Option<Document>>,
+        options: impl Into<Option<FindOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.find_explain(
+            filter.into(),
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a [`Collection::aggregate`](#method.aggregate) with
+    /// the given pipeline and options, without running it. If `verbosity` is `None`,
+    /// [`ExplainVerbosity::QueryPlanner`] is used.
+    ///
+    /// Note that explaining a pipeline with an `$out` or `$merge` stage with a verbosity of
+    /// [`ExplainVerbosity::ExecutionStats`] or higher is not supported by the server.
+    pub fn aggregate_explain(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<AggregateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        let pipeline: Vec<Document> = pipeline.into_iter().collect();
+        RUNTIME.block_on(self.async_collection.aggregate_explain(
+            pipeline,
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a
+    /// [`Collection::count_documents`](#method.count_documents) with the given filter and options,
+    /// without running it. If `verbosity` is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub fn count_documents_explain(
+        &self,
+        filter: impl Into<Option<Document>>,
+        options: impl Into<Option<CountOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.count_documents_explain(
+            filter.into(),
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a [`Collection::update_one`](#method.update_one)
+    /// with the given query, update and options, without modifying any documents. If `verbosity`
+    /// is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub fn update_one_explain(
+        &self,
+        query: Document,
+        update: impl Into<UpdateModifications>,
+        options: impl Into<Option<UpdateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.update_one_explain(
+            query,
+            update.into(),
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a [`Collection::update_many`](#method.update_many)
+    /// with the given query, update and options, without modifying any documents. If `verbosity`
+    /// is `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub fn update_many_explain(
+        &self,
+        query: Document,
+        update: impl Into<UpdateModifications>,
+        options: impl Into<Option<UpdateOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.update_many_explain(
+            query,
+            update.into(),
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a [`Collection::delete_one`](#method.delete_one)
+    /// with the given query and options, without deleting any documents. If `verbosity` is
+    /// `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub fn delete_one_explain(
+        &self,
+        query: Document,
+        options: impl Into<Option<DeleteOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.delete_one_explain(
+            query,
+            options.into(),
+            verbosity.into(),
+        ))
+    }
+
+    /// Explains how the server would execute a [`Collection::delete_many`](#method.delete_many)
+    /// with the given query and options, without deleting any documents. If `verbosity` is
+    /// `None`, [`ExplainVerbosity::QueryPlanner`] is used.
+    pub fn delete_many_explain(
+        &self,
+        query: Document,
+        options: impl Into<Option<DeleteOptions>>,
+        verbosity: impl Into<Option<ExplainVerbosity>>,
+    ) -> Result<ExplainResult> {
+        RUNTIME.block_on(self.async_collection.delete_many_explain(
+            query,
+            options.into(),
+            verbosity.into(),
+        ))
+    }
 }
 
 impl<T> Collection<T>
@@ -582,6 +812,48 @@ where
             &mut session.async_client_session,
         ))
     }
+    /// Starts a new [`ChangeStream`] that receives events for all changes in this collection. A
+    /// [`ChangeStream`] cannot be started on system collections.
+    ///
+    /// See the documentation [here](https://docs.mongodb.com/manual/changeStreams/) on change
+    /// streams.
+    ///
+    /// Change streams require either a "majority" read concern or no read concern. Anything else
+    /// will cause a server error.
+    ///
+    /// Note that using a `$project` stage to remove any of the `_id`, `operationType` or `ns`
+    /// fields will cause an error. The driver requires these fields to support resumability. For
+    /// more information on resumability, see the documentation for
+    /// [`ChangeStream`](struct.ChangeStream.html)
+    ///
+    /// If the pipeline alters the structure of the returned events, the parsed type will need to be
+    /// changed via [`ChangeStream::with_type`].
+    pub fn watch(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+    ) -> Result<ChangeStream<ChangeStreamEvent<T>>> {
+        RUNTIME
+            .block_on(self.async_collection.watch(pipeline, options))
+            .map(ChangeStream::new)
+    }
+
+    /// Starts a new [`SessionChangeStream`] that receives events for all changes in this collection
+    /// using the provided [`ClientSession`].  See [`Collection::watch`] for more information.
+    pub fn watch_with_session(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<SessionChangeStream<ChangeStreamEvent<T>>> {
+        RUNTIME
+            .block_on(self.async_collection.watch_with_session(
+                pipeline,
+                options,
+                &mut session.async_client_session,
+            ))
+            .map(SessionChangeStream::new)
+    }
 }
 
 impl<T> Collection<T>
@@ -723,6 +995,46 @@ impl<T> Collection<T>
 where
     T: Serialize,
 {
+    /// Executes the given inserts, updates, replacements, and deletes against the collection,
+    /// sending them to the server in as few batches as possible.
+    ///
+    /// If any of the writes fail, an [`ErrorKind::BulkWrite`](crate::error::ErrorKind::BulkWrite)
+    /// error is returned whose
+    /// [`BulkWriteFailure::partial_result`](crate::error::BulkWriteFailure::partial_result)
+    /// contains the results of the writes that succeeded. Ordered bulk writes (the default) stop
+    /// at the first failed write, whereas unordered bulk writes attempt all of the writes and may
+    /// execute them in any order.
+    ///
+    /// Batches that only contain `InsertOne`, `UpdateOne`, `ReplaceOne`, and `DeleteOne` writes
+    /// will retry once upon failure if the connection and encountered error support
+    /// retryability. See the documentation
+    /// [here](https://docs.mongodb.com/manual/core/retryable-writes/) for more information on
+    /// retryable writes.
+    pub fn bulk_write(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+    ) -> Result<BulkWriteResult> {
+        RUNTIME.block_on(self.async_collection.bulk_write(models, options.into()))
+    }
+
+    /// Executes the given inserts, updates, replacements, and deletes against the collection
+    /// using the provided `ClientSession`.
+    ///
+    /// See [`Collection::bulk_write`] for more details.
+    pub fn bulk_write_with_session(
+        &self,
+        models: impl IntoIterator<Item = WriteModel<T>>,
+        options: impl Into<Option<BulkWriteOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<BulkWriteResult> {
+        RUNTIME.block_on(self.async_collection.bulk_write_with_session(
+            models,
+            options.into(),
+            &mut session.async_client_session,
+        ))
+    }
+
     /// Inserts the documents in `docs` into the collection.
     ///
     /// This operation will retry once upon failure if the connection and encountered error support
@@ -3,7 +3,7 @@ use serde::de::DeserializeOwned;
 
 use super::ClientSession;
 use crate::{
-    bson::Document,
+    bson::{Document, RawDocument},
     error::Result,
     Cursor as AsyncCursor,
     SessionCursor as AsyncSessionCursor,
@@ -83,6 +83,36 @@ where
     pub(crate) fn new(async_cursor: AsyncCursor<T>) -> Self {
         Self { async_cursor }
     }
+
+    /// Move the cursor forward to the next document, requesting more results from the server if
+    /// the current batch has been exhausted. The document can then be accessed without being
+    /// deserialized via [`Cursor::current`], or deserialized via [`Cursor::deserialize_current`].
+    ///
+    /// Returns `true` if the cursor moved to a new document, or `false` if the cursor has been
+    /// exhausted.
+    pub fn advance(&mut self) -> Result<bool> {
+        RUNTIME.block_on(self.async_cursor.advance())
+    }
+
+    /// Returns a reference to the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Cursor::advance`] has not been called or if the last call to it did not return
+    /// `Ok(true)`.
+    pub fn current(&self) -> &RawDocument {
+        self.async_cursor.current()
+    }
+
+    /// Deserializes the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Cursor::advance`] has not been called or if the last call to it did not return
+    /// `Ok(true)`.
+    pub fn deserialize_current(&self) -> Result<T> {
+        self.async_cursor.deserialize_current()
+    }
 }
 
 impl<T> Iterator for Cursor<T>
@@ -166,6 +196,36 @@ where
     pub fn next(&mut self, session: &mut ClientSession) -> Option<Result<T>> {
         self.iter(session).next()
     }
+
+    /// Move the cursor forward to the next document, requesting more results from the server if
+    /// the current batch has been exhausted. The session provided must be the same session used
+    /// to create the cursor.
+    ///
+    /// Returns `true` if the cursor moved to a new document, or `false` if the cursor has been
+    /// exhausted.
+    pub fn advance(&mut self, session: &mut ClientSession) -> Result<bool> {
+        RUNTIME.block_on(self.async_cursor.advance(&mut session.async_client_session))
+    }
+
+    /// Returns a reference to the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SessionCursor::advance`] has not been called or if the last call to it did not
+    /// return `Ok(true)`.
+    pub fn current(&self) -> &RawDocument {
+        self.async_cursor.current()
+    }
+
+    /// Deserializes the document the cursor is currently positioned at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SessionCursor::advance`] has not been called or if the last call to it did not
+    /// return `Ok(true)`.
+    pub fn deserialize_current(&self) -> Result<T> {
+        self.async_cursor.deserialize_current()
+    }
 }
 
 /// A handle that borrows a `ClientSession` temporarily for executing getMores or iterating through
@@ -1,20 +1,43 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
-use super::{ClientSession, Collection, Cursor, SessionCursor};
+use super::{
+    ChangeStream,
+    ClientSession,
+    Collection,
+    Cursor,
+    GridFsBucket,
+    SessionChangeStream,
+    SessionCursor,
+};
 use crate::{
     bson::Document,
+    change_stream::event::ChangeStreamEvent,
+    command::RunnableCommand,
     error::Result,
     options::{
         AggregateOptions,
+        ChangeStreamOptions,
         CollectionOptions,
         CreateCollectionOptions,
+        CreateRoleOptions,
+        CreateUserOptions,
+        CreateViewOptions,
+        DatabaseStatsOptions,
         DropDatabaseOptions,
+        DropRoleOptions,
+        DropUserOptions,
+        GrantRolesOptions,
+        GridFsBucketOptions,
         ListCollectionsOptions,
         ReadConcern,
+        Role,
+        RolesInfoOptions,
         SelectionCriteria,
+        UpdateUserOptions,
+        UsersInfoOptions,
         WriteConcern,
     },
-    results::CollectionSpecification,
+    results::{CollectionSpecification, DatabaseStats, RoleInfo, UserInfo},
     Database as AsyncDatabase,
     RUNTIME,
 };
@@ -81,6 +104,11 @@ impl Database {
         self.async_database.write_concern()
     }
 
+    /// Gets the timeout of the `Database`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.async_database.timeout()
+    }
+
     /// Gets a handle to a collection with type `T` specified by `name` of the database. The
     /// `Collection` options (e.g. read preference and write concern) will default to those of the
     /// `Database`.
@@ -211,6 +239,140 @@ impl Database {
         ))
     }
 
+    /// Creates a read-only view with the given `name` whose contents are the results of running
+    /// `pipeline` against the collection or view named `view_on`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/core/views/) for more
+    /// information on views.
+    pub fn create_view(
+        &self,
+        name: impl AsRef<str>,
+        view_on: impl AsRef<str>,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<CreateViewOptions>>,
+    ) -> Result<()> {
+        let pipeline: Vec<Document> = pipeline.into_iter().collect();
+        RUNTIME.block_on(self.async_database.create_view(
+            name.as_ref(),
+            view_on.as_ref(),
+            pipeline,
+            options.into(),
+        ))
+    }
+
+    /// Creates a read-only view with the given `name` whose contents are the results of running
+    /// `pipeline` against the collection or view named `view_on` using the provided
+    /// `ClientSession`.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/core/views/) for more
+    /// information on views.
+    pub fn create_view_with_session(
+        &self,
+        name: impl AsRef<str>,
+        view_on: impl AsRef<str>,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<CreateViewOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<()> {
+        let pipeline: Vec<Document> = pipeline.into_iter().collect();
+        RUNTIME.block_on(self.async_database.create_view_with_session(
+            name.as_ref(),
+            view_on.as_ref(),
+            pipeline,
+            options.into(),
+            &mut session.async_client_session,
+        ))
+    }
+
+    /// Gets storage statistics for the database, such as the number of collections and the total
+    /// size of their data and indexes.
+    pub fn stats(&self, options: impl Into<Option<DatabaseStatsOptions>>) -> Result<DatabaseStats> {
+        RUNTIME.block_on(self.async_database.stats(options.into()))
+    }
+
+    /// Creates a user named `name` in this database.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/createUser/)
+    /// for more information.
+    pub fn create_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<CreateUserOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.create_user(name, options.into()))
+    }
+
+    /// Updates the user named `name` in this database. Only the fields set in `options` are
+    /// changed.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/updateUser/)
+    /// for more information.
+    pub fn update_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<UpdateUserOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.update_user(name, options.into()))
+    }
+
+    /// Removes the user named `name` from this database.
+    pub fn drop_user(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<DropUserOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.drop_user(name, options.into()))
+    }
+
+    /// Gets information about the users defined in this database.
+    pub fn users_info(
+        &self,
+        options: impl Into<Option<UsersInfoOptions>>,
+    ) -> Result<Vec<UserInfo>> {
+        RUNTIME.block_on(self.async_database.users_info(options.into()))
+    }
+
+    /// Grants `roles` to the user named `name` in this database, in addition to the roles it
+    /// already has.
+    pub fn grant_roles(
+        &self,
+        name: impl AsRef<str>,
+        roles: impl IntoIterator<Item = Role>,
+        options: impl Into<Option<GrantRolesOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.grant_roles(name, roles, options.into()))
+    }
+
+    /// Creates a user-defined role named `name` in this database.
+    ///
+    /// See the documentation [here](https://www.mongodb.com/docs/manual/reference/command/createRole/)
+    /// for more information.
+    pub fn create_role(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<CreateRoleOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.create_role(name, options.into()))
+    }
+
+    /// Removes the user-defined role named `name` from this database.
+    pub fn drop_role(
+        &self,
+        name: impl AsRef<str>,
+        options: impl Into<Option<DropRoleOptions>>,
+    ) -> Result<()> {
+        RUNTIME.block_on(self.async_database.drop_role(name, options.into()))
+    }
+
+    /// Gets information about the user-defined roles in this database, and optionally the
+    /// built-in roles as well.
+    pub fn roles_info(
+        &self,
+        options: impl Into<Option<RolesInfoOptions>>,
+    ) -> Result<Vec<RoleInfo>> {
+        RUNTIME.block_on(self.async_database.roles_info(options.into()))
+    }
+
     /// Runs a database-level command.
     ///
     /// Note that no inspection is done on `doc`, so the command will not use the database's default
@@ -245,6 +407,32 @@ impl Database {
         ))
     }
 
+    /// Runs a database-level command defined by a [`RunnableCommand`], returning its typed output.
+    ///
+    /// The command is routed using its selection criteria and retried according to its
+    /// retryability, and any write concern error in the response is returned as an error. The
+    /// database's default read concern, write concern and selection criteria are not applied.
+    pub fn run_typed_command<C: RunnableCommand>(&self, command: C) -> Result<C::Output> {
+        RUNTIME.block_on(self.async_database.run_typed_command(command))
+    }
+
+    /// Runs a database-level command defined by a [`RunnableCommand`] using the provided
+    /// `ClientSession`, returning its typed output.
+    ///
+    /// The command is routed using its selection criteria and retried according to its
+    /// retryability, and any write concern error in the response is returned as an error. The
+    /// database's default read concern, write concern and selection criteria are not applied.
+    pub fn run_typed_command_with_session<C: RunnableCommand>(
+        &self,
+        command: C,
+        session: &mut ClientSession,
+    ) -> Result<C::Output> {
+        RUNTIME.block_on(
+            self.async_database
+                .run_typed_command_with_session(command, &mut session.async_client_session),
+        )
+    }
+
     /// Runs an aggregation operation.
     ///
     /// See the documentation [here](https://docs.mongodb.com/manual/aggregation/) for more
@@ -279,4 +467,63 @@ impl Database {
             ))
             .map(SessionCursor::new)
     }
+
+    /// Starts a new [`ChangeStream`] that receives events for all changes in this database. The
+    /// stream does not observe changes from system collections and cannot be started on "config",
+    /// "local" or "admin" databases.
+    ///
+    /// See the documentation [here](https://docs.mongodb.com/manual/changeStreams/) on change
+    /// streams.
+    ///
+    /// Change streams require either a "majority" read concern or no read concern. Anything else
+    /// will cause a server error.
+    ///
+    /// Note that using a `$project` stage to remove any of the `_id`, `operationType` or `ns`
+    /// fields will cause an error. The driver requires these fields to support resumability. For
+    /// more information on resumability, see the documentation for
+    /// [`ChangeStream`](struct.ChangeStream.html)
+    ///
+    /// If the pipeline alters the structure of the returned events, the parsed type will need to be
+    /// changed via [`ChangeStream::with_type`].
+    pub fn watch(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+    ) -> Result<ChangeStream<ChangeStreamEvent<Document>>> {
+        RUNTIME
+            .block_on(self.async_database.watch(pipeline, options))
+            .map(ChangeStream::new)
+    }
+
+    /// Starts a new [`SessionChangeStream`] that receives events for all changes in this database
+    /// using the provided [`ClientSession`].  See [`Database::watch`] for more information.
+    pub fn watch_with_session(
+        &self,
+        pipeline: impl IntoIterator<Item = Document>,
+        options: impl Into<Option<ChangeStreamOptions>>,
+        session: &mut ClientSession,
+    ) -> Result<SessionChangeStream<ChangeStreamEvent<Document>>> {
+        RUNTIME
+            .block_on(self.async_database.watch_with_session(
+                pipeline,
+                options,
+                &mut session.async_client_session,
+            ))
+            .map(SessionChangeStream::new)
+    }
+
+    /// Creates a new [`GridFsBucket`] in the database with the given options. The bucket inherits
+    /// the read concern, write concern and selection criteria of the database unless they are
+    /// specified in the options.
+    ///
+    /// An [`ErrorKind::InvalidArgument`](crate::error::ErrorKind::InvalidArgument) error is
+    /// returned if `chunk_size_bytes` is 0.
+    pub fn gridfs_bucket(
+        &self,
+        options: impl Into<Option<GridFsBucketOptions>>,
+    ) -> Result<GridFsBucket> {
+        self.async_database
+            .gridfs_bucket(options)
+            .map(GridFsBucket::new)
+    }
 }
@@ -0,0 +1,258 @@
+use std::io::{Read, Write};
+
+use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+use super::Cursor;
+use crate::{
+    bson::{oid::ObjectId, Bson, Document},
+    concern::{ReadConcern, WriteConcern},
+    error::Result,
+    gridfs::{
+        options::{GridFsDownloadByNameOptions, GridFsFindOptions, GridFsUploadOptions},
+        FilesCollectionDocument,
+        GridFsBucket as AsyncGridFsBucket,
+        GridFsDownloadStream as AsyncGridFsDownloadStream,
+        GridFsUploadStream as AsyncGridFsUploadStream,
+    },
+    selection_criteria::SelectionCriteria,
+    RUNTIME,
+};
+
+/// A `GridFsBucket` provides the functionality for storing and retrieving files larger than the
+/// BSON document size limit in a MongoDB database. A `GridFsBucket` can be obtained by calling
+/// [`Database::gridfs_bucket`](super::Database::gridfs_bucket).
+///
+/// `GridFsBucket` uses [`std::sync::Arc`] internally, so it can safely be shared across threads.
+#[derive(Clone, Debug)]
+pub struct GridFsBucket {
+    async_bucket: AsyncGridFsBucket,
+}
+
+impl GridFsBucket {
+    pub(crate) fn new(async_bucket: AsyncGridFsBucket) -> Self {
+        Self { async_bucket }
+    }
+
+    /// Gets the read concern of the `GridFsBucket`.
+    pub fn read_concern(&self) -> Option<&ReadConcern> {
+        self.async_bucket.read_concern()
+    }
+
+    /// Gets the write concern of the `GridFsBucket`.
+    pub fn write_concern(&self) -> Option<&WriteConcern> {
+        self.async_bucket.write_concern()
+    }
+
+    /// Gets the selection criteria of the `GridFsBucket`.
+    pub fn selection_criteria(&self) -> Option<&SelectionCriteria> {
+        self.async_bucket.selection_criteria()
+    }
+
+    /// Opens a [`GridFsUploadStream`] that the application can write the contents of the file to.
+    /// The driver generates a unique [`ObjectId`] for the file, which can be retrieved via
+    /// [`GridFsUploadStream::id`].
+    ///
+    /// The file is not visible in the bucket until the stream has been closed via
+    /// [`GridFsUploadStream::close`].
+    pub fn open_upload_stream(
+        &self,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<GridFsUploadStream> {
+        let async_stream = self.async_bucket.open_upload_stream(filename, options)?;
+        Ok(GridFsUploadStream { async_stream })
+    }
+
+    /// Opens a [`GridFsUploadStream`] that the application can write the contents of the file to.
+    /// The provided `id` will be used as the `_id` of the file, and it must be unique within the
+    /// bucket.
+    ///
+    /// The file is not visible in the bucket until the stream has been closed via
+    /// [`GridFsUploadStream::close`].
+    pub fn open_upload_stream_with_id(
+        &self,
+        id: Bson,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<GridFsUploadStream> {
+        let async_stream = self
+            .async_bucket
+            .open_upload_stream_with_id(id, filename, options)?;
+        Ok(GridFsUploadStream { async_stream })
+    }
+
+    /// Uploads a user file to the bucket, reading its contents from `source`. The driver generates
+    /// a unique [`ObjectId`] for the file, which is returned.
+    pub fn upload_from_reader(
+        &self,
+        filename: impl AsRef<str>,
+        source: impl Read,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<ObjectId> {
+        let id = ObjectId::new();
+        self.upload_from_reader_with_id(id.into(), filename, source, options)?;
+        Ok(id)
+    }
+
+    /// Uploads a user file to the bucket with the given `id`, reading its contents from `source`.
+    /// The `id` must be unique within the bucket.
+    pub fn upload_from_reader_with_id(
+        &self,
+        id: Bson,
+        filename: impl AsRef<str>,
+        mut source: impl Read,
+        options: impl Into<Option<GridFsUploadOptions>>,
+    ) -> Result<()> {
+        let mut upload_stream = self.open_upload_stream_with_id(id, filename, options)?;
+        if let Err(e) = std::io::copy(&mut source, &mut upload_stream) {
+            upload_stream.abort()?;
+            return Err(e.into());
+        }
+        upload_stream.close()
+    }
+
+    /// Opens and returns a [`GridFsDownloadStream`] from which the application can read the
+    /// contents of the file with the given `id`.
+    pub fn open_download_stream(&self, id: Bson) -> Result<GridFsDownloadStream> {
+        RUNTIME
+            .block_on(self.async_bucket.open_download_stream(id))
+            .map(|async_stream| GridFsDownloadStream { async_stream })
+    }
+
+    /// Opens and returns a [`GridFsDownloadStream`] from which the application can read the
+    /// contents of the file with the given `filename`. See
+    /// [`GridFsBucket::open_download_stream_by_name`](crate::gridfs::GridFsBucket::open_download_stream_by_name)
+    /// for more information.
+    pub fn open_download_stream_by_name(
+        &self,
+        filename: impl AsRef<str>,
+        options: impl Into<Option<GridFsDownloadByNameOptions>>,
+    ) -> Result<GridFsDownloadStream> {
+        RUNTIME
+            .block_on(
+                self.async_bucket
+                    .open_download_stream_by_name(filename, options),
+            )
+            .map(|async_stream| GridFsDownloadStream { async_stream })
+    }
+
+    /// Downloads the contents of the file with the given `id` and writes them to `destination`.
+    pub fn download_to_writer(&self, id: Bson, mut destination: impl Write) -> Result<()> {
+        let mut download_stream = self.open_download_stream(id)?;
+        std::io::copy(&mut download_stream, &mut destination)?;
+        Ok(())
+    }
+
+    /// Downloads the contents of the file with the given `filename` and writes them to
+    /// `destination`. See
+    /// [`GridFsBucket::open_download_stream_by_name`](crate::gridfs::GridFsBucket::open_download_stream_by_name)
+    /// for more information.
+    pub fn download_to_writer_by_name(
+        &self,
+        filename: impl AsRef<str>,
+        mut destination: impl Write,
+        options: impl Into<Option<GridFsDownloadByNameOptions>>,
+    ) -> Result<()> {
+        let mut download_stream = self.open_download_stream_by_name(filename, options)?;
+        std::io::copy(&mut download_stream, &mut destination)?;
+        Ok(())
+    }
+
+    /// Deletes the file with the given `id` and all of its chunks from the bucket. Returns an
+    /// error if no file with the given `id` exists.
+    pub fn delete(&self, id: Bson) -> Result<()> {
+        RUNTIME.block_on(self.async_bucket.delete(id))
+    }
+
+    /// Finds and returns the [`FilesCollectionDocument`]s within the bucket that match the given
+    /// filter.
+    pub fn find(
+        &self,
+        filter: Document,
+        options: impl Into<Option<GridFsFindOptions>>,
+    ) -> Result<Cursor<FilesCollectionDocument>> {
+        RUNTIME
+            .block_on(self.async_bucket.find(filter, options))
+            .map(Cursor::new)
+    }
+
+    /// Renames the file with the given `id` to `new_filename`. Returns an error if no file with
+    /// the given `id` exists.
+    pub fn rename(&self, id: Bson, new_filename: impl AsRef<str>) -> Result<()> {
+        RUNTIME.block_on(self.async_bucket.rename(id, new_filename))
+    }
+
+    /// Drops the files and chunks collections associated with the bucket.
+    pub fn drop(&self) -> Result<()> {
+        RUNTIME.block_on(self.async_bucket.drop())
+    }
+}
+
+/// A stream to which the contents of a file can be written in order to upload it to a
+/// [`GridFsBucket`]. `GridFsUploadStream` implements [`std::io::Write`].
+///
+/// The file is only uploaded to the bucket once [`GridFsUploadStream::close`] has been called; if
+/// the stream is dropped before then, any chunks that have already been written will be deleted.
+#[derive(Debug)]
+pub struct GridFsUploadStream {
+    async_stream: AsyncGridFsUploadStream,
+}
+
+impl GridFsUploadStream {
+    /// Gets the `_id` of the file being uploaded.
+    pub fn id(&self) -> &Bson {
+        self.async_stream.id()
+    }
+
+    /// Writes any buffered data to the bucket and then uploads the files collection document,
+    /// completing the upload.
+    pub fn close(&mut self) -> Result<()> {
+        RUNTIME.block_on(self.async_stream.close())?;
+        Ok(())
+    }
+
+    /// Aborts the upload, deleting any chunks that have already been written to the bucket. The
+    /// stream cannot be written to after it has been aborted.
+    pub fn abort(&mut self) -> Result<()> {
+        RUNTIME.block_on(self.async_stream.abort())
+    }
+}
+
+impl Write for GridFsUploadStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        RUNTIME.block_on(self.async_stream.write(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        RUNTIME.block_on(self.async_stream.flush())
+    }
+}
+
+impl Drop for GridFsUploadStream {
+    fn drop(&mut self) {
+        // The async stream cleans up in a background task when dropped, which isn't possible
+        // outside of an async runtime, so abort the upload here instead. This returns an error if
+        // the stream was already closed or aborted, which can safely be ignored.
+        let _ = RUNTIME.block_on(self.async_stream.abort());
+    }
+}
+
+/// A stream from which the contents of a file stored in a [`GridFsBucket`] can be read.
+/// `GridFsDownloadStream` implements [`std::io::Read`].
+#[derive(Debug)]
+pub struct GridFsDownloadStream {
+    async_stream: AsyncGridFsDownloadStream,
+}
+
+impl GridFsDownloadStream {
+    /// Gets the [`FilesCollectionDocument`] describing the file being downloaded.
+    pub fn file(&self) -> &FilesCollectionDocument {
+        self.async_stream.file()
+    }
+}
+
+impl Read for GridFsDownloadStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        RUNTIME.block_on(self.async_stream.read(buf))
+    }
+}
@@ -1,14 +1,24 @@
 //! Contains the sync API. This is only available when the `sync` feature is enabled.
 
+mod change_stream;
 mod client;
+#[cfg(feature = "csfle")]
+mod client_encryption;
 mod coll;
 mod cursor;
 mod db;
+mod gridfs;
+mod topology_watcher;
 
 #[cfg(test)]
 mod test;
 
+pub use change_stream::{ChangeStream, SessionChangeStream, SessionChangeStreamIter};
 pub use client::{session::ClientSession, Client};
+#[cfg(feature = "csfle")]
+pub use client_encryption::ClientEncryption;
 pub use coll::Collection;
 pub use cursor::{Cursor, SessionCursor, SessionCursorIter};
 pub use db::Database;
+pub use gridfs::{GridFsBucket, GridFsDownloadStream, GridFsUploadStream};
+pub use topology_watcher::TopologyWatcher;
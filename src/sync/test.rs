@@ -267,6 +267,15 @@ fn transactions() {
     session
         .abort_transaction()
         .expect("abort transaction should succeed");
+
+    let result = session.with_transaction(
+        |session| {
+            coll.insert_one_with_session(doc! { "x": 2 }, None, session)?;
+            Ok(2)
+        },
+        None,
+    );
+    assert_eq!(result.expect("with transaction should succeed"), 2);
 }
 
 #[test]
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use futures_util::stream::StreamExt;
+
+use crate::{
+    error::Result,
+    event::sdam::TopologyDescription,
+    TopologyWatcher as AsyncTopologyWatcher,
+    RUNTIME,
+};
+
+/// An [`Iterator`] over the descriptions of a [`Client`](struct.Client.html)'s topology, returned
+/// by [`Client::watch_topology`](struct.Client.html#method.watch_topology). See
+/// [`crate::TopologyWatcher`] for more information.
+///
+/// Each call to `next` blocks until the topology changes, and iteration ends once the `Client` has
+/// been dropped.
+#[derive(Debug)]
+pub struct TopologyWatcher {
+    async_watcher: AsyncTopologyWatcher,
+}
+
+impl TopologyWatcher {
+    pub(crate) fn new(async_watcher: AsyncTopologyWatcher) -> Self {
+        Self { async_watcher }
+    }
+
+    /// Gets the most up-to-date description of the topology.
+    pub fn current(&self) -> TopologyDescription {
+        self.async_watcher.current()
+    }
+
+    /// Blocks until the description of the topology satisfies `predicate`, returning the first
+    /// description that does. See [`crate::TopologyWatcher::wait_for`] for more information.
+    pub fn wait_for(
+        &mut self,
+        predicate: impl Fn(&TopologyDescription) -> bool,
+        timeout: Duration,
+    ) -> Result<TopologyDescription> {
+        RUNTIME.block_on(self.async_watcher.wait_for(predicate, timeout))
+    }
+}
+
+impl Iterator for TopologyWatcher {
+    type Item = TopologyDescription;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        RUNTIME.block_on(self.async_watcher.next())
+    }
+}
@@ -0,0 +1,287 @@
+use futures::StreamExt;
+use tokio::sync::{RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{
+    bson::{doc, Document, Timestamp},
+    change_stream::event::OperationType,
+    options::ClientOptions,
+    test::{EventClient, FailCommandOptions, FailPoint, FailPointMode, TestClient, LOCK},
+    test_util::{MockReply, MockServer, MockServerOptions},
+    Client,
+};
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn watch_collection_insert() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    if !client.is_replica_set() {
+        println!("skipping watch_collection_insert due to non-replica set topology");
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    let mut change_stream = coll.watch(None, None).await.unwrap();
+    assert!(change_stream.is_alive());
+
+    coll.insert_one(doc! { "x": 1 }, None).await.unwrap();
+
+    let event = change_stream.next().await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+    assert_eq!(
+        event.full_document.and_then(|d| d.get_i32("x").ok()),
+        Some(1)
+    );
+    assert!(change_stream.resume_token().is_some());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn resume_after_get_more_error() {
+    let _guard: RwLockWriteGuard<()> = LOCK.run_exclusively().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || !client.supports_fail_command().await {
+        println!("skipping resume_after_get_more_error due to unsupported topology or failCommand");
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    let mut change_stream = coll.watch(None, None).await.unwrap();
+
+    let options = FailCommandOptions::builder().error_code(91).build();
+    let failpoint = FailPoint::fail_command(&["getMore"], FailPointMode::Times(1), Some(options));
+    let _fp_guard = client.enable_failpoint(failpoint, None).await.unwrap();
+
+    coll.insert_one(doc! { "x": 1 }, None).await.unwrap();
+
+    let event = change_stream.next().await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+
+    let aggregates = client.get_command_started_events(&["aggregate"]);
+    assert_eq!(aggregates.len(), 2);
+    assert!(aggregates[1]
+        .command
+        .get_array("pipeline")
+        .unwrap()
+        .iter()
+        .any(|stage| stage
+            .as_document()
+            .and_then(|s| s.get_document("$changeStream").ok())
+            .map(|cs| cs.contains_key("resumeAfter") || cs.contains_key("startAtOperationTime"))
+            .unwrap_or(false)));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn session_resume_after_get_more_error() {
+    let _guard: RwLockWriteGuard<()> = LOCK.run_exclusively().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || !client.supports_fail_command().await {
+        println!(
+            "skipping session_resume_after_get_more_error due to unsupported topology or \
+             failCommand"
+        );
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    let mut session = client.start_session(None).await.unwrap();
+    let mut change_stream = coll
+        .watch_with_session(None, None, &mut session)
+        .await
+        .unwrap();
+
+    let options = FailCommandOptions::builder().error_code(91).build();
+    let failpoint = FailPoint::fail_command(&["getMore"], FailPointMode::Times(1), Some(options));
+    let _fp_guard = client.enable_failpoint(failpoint, None).await.unwrap();
+
+    coll.insert_one(doc! { "x": 1 }, None).await.unwrap();
+
+    let event = change_stream.next(&mut session).await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+    assert_eq!(client.get_command_started_events(&["aggregate"]).len(), 2);
+}
+
+const OPERATION_TIME: Timestamp = Timestamp {
+    time: 1234,
+    increment: 5,
+};
+
+/// Replies to the initial change stream aggregation with an open cursor and an empty first batch,
+/// so that no resume token is cached before the first `getMore`.
+fn empty_change_stream_reply() -> MockReply {
+    MockReply::document(doc! {
+        "cursor": {
+            "id": 1_i64,
+            "ns": "db.coll",
+            "firstBatch": [],
+        },
+        "operationTime": OPERATION_TIME,
+    })
+}
+
+/// Replies to the resumed aggregation with a single change event.
+fn resumed_change_stream_reply() -> MockReply {
+    MockReply::cursor(
+        "db.coll",
+        vec![doc! {
+            "_id": { "_data": "token" },
+            "operationType": "insert",
+            "ns": "db.coll",
+            "documentKey": { "_id": 1 },
+            "fullDocument": { "_id": 1 },
+        }],
+    )
+}
+
+fn change_stream_stage(aggregate: &Document) -> Document {
+    aggregate.get_array("pipeline").unwrap()[0]
+        .as_document()
+        .unwrap()
+        .get_document("$changeStream")
+        .unwrap()
+        .clone()
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn resume_before_first_batch_at_operation_time() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+
+    server.add_reply("aggregate", empty_change_stream_reply());
+    server.add_reply(
+        "getMore",
+        MockReply::document(doc! {
+            "ok": 0,
+            "code": 10107,
+            "codeName": "NotWritablePrimary",
+            "errmsg": "not primary",
+            "errorLabels": ["ResumableChangeStreamError"],
+        }),
+    );
+    server.add_reply("aggregate", resumed_change_stream_reply());
+
+    let mut change_stream = coll.watch(None, None).await.unwrap();
+    let event = change_stream.next().await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+
+    let aggregates = server.commands_named("aggregate");
+    assert_eq!(aggregates.len(), 2);
+    assert!(!change_stream_stage(&aggregates[0]).contains_key("startAtOperationTime"));
+    let resumed = change_stream_stage(&aggregates[1]);
+    assert_eq!(
+        resumed.get_timestamp("startAtOperationTime").unwrap(),
+        OPERATION_TIME
+    );
+    assert!(!resumed.contains_key("resumeAfter"));
+    assert!(!resumed.contains_key("startAfter"));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn session_resume_before_first_batch_at_operation_time() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let client = Client::with_options(options).unwrap();
+    let coll = client.database("db").collection::<Document>("coll");
+
+    server.add_reply("aggregate", empty_change_stream_reply());
+    server.add_reply(
+        "getMore",
+        MockReply::command_error(43, "CursorNotFound", "cursor not found"),
+    );
+    server.add_reply("aggregate", resumed_change_stream_reply());
+
+    // The session has already seen a later operation time than the aggregation's, which must not
+    // be used to resume.
+    let later = Timestamp {
+        time: OPERATION_TIME.time + 1,
+        increment: 0,
+    };
+    server.add_reply("ping", MockReply::document(doc! { "operationTime": later }));
+    let mut session = client.start_session(None).await.unwrap();
+    client
+        .database("db")
+        .run_command_with_session(doc! { "ping": 1 }, None, &mut session)
+        .await
+        .unwrap();
+    let mut change_stream = coll
+        .watch_with_session(None, None, &mut session)
+        .await
+        .unwrap();
+    assert_eq!(session.operation_time(), Some(later));
+    let event = change_stream.next(&mut session).await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+
+    let aggregates = server.commands_named("aggregate");
+    assert_eq!(aggregates.len(), 2);
+    assert_eq!(
+        change_stream_stage(&aggregates[1])
+            .get_timestamp("startAtOperationTime")
+            .unwrap(),
+        OPERATION_TIME
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn resumable_errors_depend_on_wire_version() {
+    let not_primary = || MockReply::command_error(10107, "NotWritablePrimary", "not primary");
+
+    // 4.4+ servers label resumable errors, so an unlabeled error is returned to the caller.
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+    server.add_reply("aggregate", empty_change_stream_reply());
+    server.add_reply("getMore", not_primary());
+
+    let mut change_stream = coll.watch(None, None).await.unwrap();
+    let error = change_stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.code(), Some(10107));
+    assert_eq!(server.commands_named("aggregate").len(), 1);
+
+    // Older servers are checked against the list of resumable codes.
+    let server =
+        MockServer::start(MockServerOptions::builder().max_wire_version(8).build()).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+    server.add_reply("aggregate", empty_change_stream_reply());
+    server.add_reply("getMore", not_primary());
+    server.add_reply("aggregate", resumed_change_stream_reply());
+
+    let mut change_stream = coll.watch(None, None).await.unwrap();
+    let event = change_stream.next().await.unwrap().unwrap();
+    assert_eq!(event.operation_type, OperationType::Insert);
+
+    let aggregates = server.commands_named("aggregate");
+    assert_eq!(aggregates.len(), 2);
+    assert_eq!(
+        change_stream_stage(&aggregates[1])
+            .get_timestamp("startAtOperationTime")
+            .unwrap(),
+        OPERATION_TIME
+    );
+}
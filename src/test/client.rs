@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap, time::Duration};
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
 
 use bson::Document;
 use serde::Deserialize;
@@ -9,7 +9,13 @@ use crate::{
     error::{CommandError, Error, ErrorKind},
     options::{AuthMechanism, ClientOptions, Credential, ListDatabasesOptions, ServerAddress},
     selection_criteria::{ReadPreference, ReadPreferenceOptions, SelectionCriteria},
-    test::{util::TestClient, CLIENT_OPTIONS, LOCK},
+    test::{
+        spec::ExpectedEventType,
+        util::{CmapEvent, Event, EventClient, EventHandler, SdamEvent, TestClient},
+        CLIENT_OPTIONS,
+        LOCK,
+    },
+    test_util::{MockReply, MockServer},
     Client,
     RUNTIME,
 };
@@ -657,3 +663,203 @@ async fn plain_auth() {
         }
     );
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn shutdown() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = EventClient::new().await;
+    let handler = client.handler.clone();
+    let server_count = client.topology_description().servers().len();
+
+    let coll = client
+        .database(function_name!())
+        .collection::<Document>(function_name!());
+    coll.insert_one(doc! { "x": 1 }, None).await.unwrap();
+
+    Client::clone(&client).shutdown().await;
+
+    // the implicit session used for the insert is ended.
+    if client.server_version_gte(3, 6) {
+        let end_sessions = handler.get_command_started_events(&["endSessions"]);
+        assert_eq!(end_sessions.len(), 1);
+        assert!(end_sessions[0].command.get("lsid").is_none());
+        assert_eq!(
+            end_sessions[0]
+                .command
+                .get_array("endSessions")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    let pool_closed_count = handler
+        .get_filtered_events(ExpectedEventType::Cmap, |event| {
+            matches!(event, Event::Cmap(CmapEvent::PoolClosed(_)))
+        })
+        .len();
+    assert_eq!(pool_closed_count, server_count);
+
+    let error = coll.insert_one(doc! { "x": 2 }, None).await.unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Shutdown));
+
+    // dropping the client after shutting it down doesn't emit the closed events again.
+    drop(coll);
+    drop(client);
+    let topology_closed_count = handler
+        .get_all_sdam_events()
+        .into_iter()
+        .filter(|event| matches!(event, SdamEvent::TopologyClosed(_)))
+        .count();
+    assert_eq!(topology_closed_count, 1);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn shutdown_waits_for_monitors() {
+    let server = MockServer::start(None).unwrap();
+    let handler = Arc::new(EventHandler::new());
+    let mut subscriber = handler.subscribe();
+
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.heartbeat_freq = Some(Duration::from_millis(500));
+    options.sdam_event_handler = Some(handler.clone());
+    let client = Client::with_options(options).unwrap();
+
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::ServerHeartbeatSucceeded(_)))
+        })
+        .await
+        .expect("should see server heartbeat succeeded event");
+
+    // Delay the replies to the following heartbeats so that the client is shut down while a
+    // check is in progress.
+    server.add_reply(
+        "hello",
+        MockReply::hello()
+            .delay(Duration::from_millis(500))
+            .times(1000),
+    );
+    subscriber
+        .wait_for_event(Duration::from_secs(2), |event| {
+            matches!(event, Event::Sdam(SdamEvent::ServerHeartbeatStarted(_)))
+        })
+        .await
+        .expect("should see server heartbeat started event");
+
+    client.shutdown().await;
+
+    let is_heartbeat = |event: &SdamEvent| {
+        matches!(
+            event,
+            SdamEvent::ServerHeartbeatStarted(_)
+                | SdamEvent::ServerHeartbeatSucceeded(_)
+                | SdamEvent::ServerHeartbeatFailed(_)
+        )
+    };
+
+    // The check in progress is completed before shutdown returns...
+    let heartbeats: Vec<_> = handler
+        .get_all_sdam_events()
+        .into_iter()
+        .filter(is_heartbeat)
+        .collect();
+    assert!(
+        matches!(
+            heartbeats.last(),
+            Some(SdamEvent::ServerHeartbeatSucceeded(_))
+        ),
+        "{:?}",
+        heartbeats
+    );
+
+    // ...and no checks are started afterwards.
+    RUNTIME.delay_for(Duration::from_secs(1)).await;
+    assert!(!handler.get_all_sdam_events().iter().any(is_heartbeat));
+}
+
+#[cfg(all(unix, feature = "tokio-runtime"))]
+#[tokio::test]
+async fn unix_domain_socket() {
+    use std::sync::Mutex;
+
+    use crate::{
+        bson::oid::ObjectId,
+        cmap::conn::wire::{Message, MessageFlags, MessageSection},
+        runtime::AsyncStream,
+    };
+
+    let path = std::env::temp_dir().join(format!("mongodb-{}.sock", ObjectId::new()));
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+    // Answers every command with a standalone's hello reply, recording the command names.
+    let commands = Arc::new(Mutex::new(Vec::new()));
+    let server_commands = commands.clone();
+    RUNTIME.execute(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let commands = server_commands.clone();
+            RUNTIME.execute(async move {
+                let mut stream = AsyncStream::Unix(stream.into());
+                while let Ok(message) = Message::read_from(&mut stream).await {
+                    if let Some(MessageSection::Document(ref bytes)) = message.sections.first() {
+                        let command: Document = bson::from_slice(bytes).unwrap();
+                        commands
+                            .lock()
+                            .unwrap()
+                            .push(command.keys().next().unwrap().clone());
+                    }
+                    let reply = doc! {
+                        "helloOk": true,
+                        "isWritablePrimary": true,
+                        "maxBsonObjectSize": 16 * 1024 * 1024,
+                        "maxWriteBatchSize": 100_000,
+                        "maxWireVersion": 13,
+                        "minWireVersion": 0,
+                        "ok": 1,
+                    };
+                    let reply = Message {
+                        response_to: message.request_id.unwrap_or_default(),
+                        flags: MessageFlags::empty(),
+                        sections: vec![MessageSection::Document(bson::to_vec(&reply).unwrap())],
+                        checksum: None,
+                        request_id: None,
+                    };
+                    if reply.write_to(&mut stream).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    let uri = format!("mongodb://{}", path.to_str().unwrap().replace('/', "%2F"));
+    let options = ClientOptions::parse_without_srv_resolution(&uri).unwrap();
+    assert_eq!(
+        options.hosts,
+        vec![ServerAddress::Unix { path: path.clone() }]
+    );
+    let client = Client::with_options(options).unwrap();
+    client
+        .database("admin")
+        .run_command(doc! { "ping": 1 }, None)
+        .await
+        .unwrap();
+
+    // The connection handshake is a hello over the socket.
+    let commands = commands.lock().unwrap().clone();
+    assert!(
+        commands
+            .iter()
+            .any(|name| name == "hello" || name.eq_ignore_ascii_case("ismaster")),
+        "{:?}",
+        commands
+    );
+    assert!(commands.contains(&"ping".to_string()), "{:?}", commands);
+
+    drop(client);
+    std::fs::remove_file(&path).unwrap();
+}
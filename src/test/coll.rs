@@ -12,26 +12,41 @@ use crate::{
     options::{
         Acknowledgment,
         AggregateOptions,
+        BulkWriteOptions,
+        ClientOptions,
         CollectionOptions,
+        DeleteManyModel,
+        DeleteOneModel,
         DeleteOptions,
         DropCollectionOptions,
+        ExplainVerbosity,
         FindOneAndDeleteOptions,
         FindOneOptions,
         FindOptions,
         Hint,
         InsertManyOptions,
+        InsertOneModel,
         ReadConcern,
         ReadPreference,
+        ReplaceOneModel,
         SelectionCriteria,
+        UpdateManyModel,
+        UpdateOneModel,
         UpdateOptions,
         WriteConcern,
+        WriteModel,
     },
     results::DeleteResult,
     test::{
         util::{drop_collection, EventClient, TestClient},
+        FailCommandOptions,
+        FailPoint,
+        FailPointMode,
         CLIENT_OPTIONS,
         LOCK,
     },
+    test_util::{MockReply, MockServer, MockServerOptions, MockTopology},
+    Client,
     Collection,
     RUNTIME,
 };
@@ -148,6 +163,60 @@ async fn find() {
     }
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn explain() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    coll.insert_many((0..5).map(|i| doc! { "x": i }).collect::<Vec<_>>(), None)
+        .await
+        .unwrap();
+
+    let result = coll
+        .find_explain(doc! { "x": { "$gt": 1 } }, None, None)
+        .await
+        .unwrap();
+    assert!(result.winning_plan.is_some());
+    assert!(result.execution_stats.is_none());
+
+    let result = coll
+        .find_explain(
+            doc! { "x": { "$gt": 1 } },
+            None,
+            ExplainVerbosity::ExecutionStats,
+        )
+        .await
+        .unwrap();
+    let stats = result
+        .execution_stats
+        .expect("execution stats should be present");
+    assert_eq!(stats.get_i32("nReturned"), Ok(3));
+
+    let result = coll
+        .count_documents_explain(doc! { "x": 1 }, None, None)
+        .await
+        .unwrap();
+    assert!(result.winning_plan.is_some());
+
+    // Explaining a write must not perform it.
+    coll.delete_many_explain(doc! {}, None, ExplainVerbosity::ExecutionStats)
+        .await
+        .unwrap();
+    coll.update_many_explain(doc! {}, doc! { "$set": { "y": 1 } }, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        coll.count_documents(doc! { "y": 1 }, None).await.unwrap(),
+        0
+    );
+    assert_eq!(coll.estimated_document_count(None).await.unwrap(), 5);
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 #[function_name::named]
@@ -1089,3 +1158,400 @@ async fn cursor_batch_size() {
     let docs: Vec<_> = cursor.stream(&mut session).try_collect().await.unwrap();
     assert_eq!(docs.len(), 10);
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn unacknowledged_writes() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = EventClient::new().await;
+    let options = CollectionOptions::builder()
+        .write_concern(WriteConcern::builder().w(Acknowledgment::Nodes(0)).build())
+        .build();
+    let coll = client
+        .database(function_name!())
+        .collection_with_options::<Document>(function_name!(), options);
+
+    let result = coll.insert_one(doc! { "_id": 1 }, None).await.unwrap();
+    assert!(!result.acknowledged);
+    assert_eq!(result.inserted_id, Bson::Int32(1));
+    let (started, succeeded) = client.get_successful_command_execution("insert");
+    assert_eq!(
+        started.command.get_document("writeConcern"),
+        Ok(&doc! { "w": 0 })
+    );
+    assert_eq!(succeeded.reply, doc! { "ok": 1 });
+
+    let result = coll
+        .insert_many(vec![doc! { "_id": 2 }, doc! { "_id": 3 }], None)
+        .await
+        .unwrap();
+    assert!(!result.acknowledged);
+    assert_eq!(result.inserted_ids.len(), 2);
+
+    let result = coll
+        .update_one(doc! { "_id": 1 }, doc! { "$set": { "x": 1 } }, None)
+        .await
+        .unwrap();
+    assert!(!result.acknowledged);
+
+    let result = coll.delete_many(doc! {}, None).await.unwrap();
+    assert!(!result.acknowledged);
+
+    let options = DeleteOptions::builder()
+        .hint(Hint::Name("_id_".to_string()))
+        .build();
+    let error = coll
+        .delete_one(doc! { "_id": 1 }, options)
+        .await
+        .expect_err("hint with an unacknowledged write concern should fail");
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn unacknowledged_write_with_session_rejected() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let options = CollectionOptions::builder()
+        .write_concern(WriteConcern::builder().w(Acknowledgment::Nodes(0)).build())
+        .build();
+    let coll = client
+        .database(function_name!())
+        .collection_with_options::<Document>(function_name!(), options);
+
+    let mut session = client.start_session(None).await.unwrap();
+    let error = coll
+        .insert_one_with_session(doc! {}, None, &mut session)
+        .await
+        .expect_err("unacknowledged write with a session should fail");
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+}
+
+fn bulk_write_models() -> Vec<WriteModel<Document>> {
+    vec![
+        InsertOneModel::builder()
+            .document(doc! { "_id": 1, "x": 1 })
+            .build()
+            .into(),
+        InsertOneModel::builder()
+            .document(doc! { "_id": 2, "x": 2 })
+            .build()
+            .into(),
+        UpdateOneModel::builder()
+            .filter(doc! { "_id": 1 })
+            .update(doc! { "$inc": { "x": 10 } })
+            .build()
+            .into(),
+        UpdateManyModel::builder()
+            .filter(doc! { "_id": { "$gt": 2 } })
+            .update(doc! { "$set": { "x": 3 } })
+            .upsert(true)
+            .build()
+            .into(),
+        ReplaceOneModel::builder()
+            .filter(doc! { "_id": 2 })
+            .replacement(doc! { "y": 2 })
+            .build()
+            .into(),
+        DeleteOneModel::builder()
+            .filter(doc! { "_id": 1 })
+            .build()
+            .into(),
+        InsertOneModel::builder()
+            .document(doc! { "_id": 4 })
+            .build()
+            .into(),
+    ]
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn bulk_write() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = EventClient::new().await;
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+
+    let result = coll.bulk_write(bulk_write_models(), None).await.unwrap();
+    assert!(result.acknowledged);
+    assert_eq!(result.inserted_count, 3);
+    assert_eq!(result.matched_count, 2);
+    assert_eq!(result.modified_count, 2);
+    assert_eq!(result.upserted_count, 1);
+    assert_eq!(result.deleted_count, 1);
+    assert_eq!(result.inserted_ids.len(), 3);
+    assert_eq!(result.inserted_ids.get(&6), Some(&Bson::Int32(4)));
+    assert!(result.upserted_ids.contains_key(&3));
+
+    // consecutive writes of the same kind are sent in a single command.
+    let command_names: Vec<_> = client
+        .get_all_command_started_events()
+        .into_iter()
+        .map(|event| event.command_name)
+        .filter(|name| ["insert", "update", "delete"].contains(&name.as_str()))
+        .collect();
+    assert_eq!(command_names, vec!["insert", "update", "delete", "insert"]);
+
+    let mut docs: Vec<Document> = coll
+        .find(None, None)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    // the upserted document has a generated _id, so it sorts first.
+    docs.sort_by_key(|doc| doc.get_i32("_id").unwrap_or(0));
+    assert_eq!(docs.len(), 3);
+    assert_eq!(docs[0].get_i32("x"), Ok(3));
+    assert_eq!(docs[1], doc! { "_id": 2, "y": 2 });
+    assert_eq!(docs[2], doc! { "_id": 4 });
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn bulk_write_with_errors() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+
+    let models = || -> Vec<WriteModel<Document>> {
+        vec![
+            InsertOneModel::builder()
+                .document(doc! { "_id": 1 })
+                .build()
+                .into(),
+            InsertOneModel::builder()
+                .document(doc! { "_id": 1 })
+                .build()
+                .into(),
+            DeleteManyModel::builder().filter(doc! {}).build().into(),
+            InsertOneModel::builder()
+                .document(doc! { "_id": 2 })
+                .build()
+                .into(),
+        ]
+    };
+
+    let error = coll.bulk_write(models(), None).await.unwrap_err();
+    match *error.kind {
+        ErrorKind::BulkWrite(ref failure) => {
+            let write_errors = failure.write_errors.as_ref().unwrap();
+            assert_eq!(write_errors.len(), 1);
+            assert_eq!(write_errors[0].index, 1);
+            let partial_result = failure.partial_result.as_ref().unwrap();
+            assert_eq!(partial_result.inserted_count, 1);
+            assert_eq!(partial_result.deleted_count, 0);
+        }
+        ref e => panic!("expected bulk write error, got {:?}", e),
+    }
+    assert_eq!(coll.count_documents(None, None).await.unwrap(), 1);
+
+    coll.delete_many(doc! {}, None).await.unwrap();
+    let options = BulkWriteOptions::builder().ordered(false).build();
+    let error = coll.bulk_write(models(), options).await.unwrap_err();
+    match *error.kind {
+        ErrorKind::BulkWrite(ref failure) => {
+            let write_errors = failure.write_errors.as_ref().unwrap();
+            assert_eq!(write_errors.len(), 1);
+            assert_eq!(write_errors[0].index, 1);
+            let partial_result = failure.partial_result.as_ref().unwrap();
+            assert_eq!(partial_result.inserted_count, 2);
+            assert_eq!(partial_result.deleted_count, 2);
+            assert_eq!(partial_result.inserted_ids.len(), 2);
+        }
+        ref e => panic!("expected bulk write error, got {:?}", e),
+    }
+
+    let error = coll
+        .bulk_write(Vec::<WriteModel<Document>>::new(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn operation_timeout() {
+    let _guard: RwLockWriteGuard<()> = LOCK.run_exclusively().await;
+
+    let client = EventClient::new().await;
+    if !client.supports_block_connection() {
+        println!("skipping operation_timeout due to blockConnection not being supported");
+        return;
+    }
+
+    client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await
+        .insert_one(doc! { "x": 1 }, None)
+        .await
+        .unwrap();
+
+    let options = FailCommandOptions::builder()
+        .block_connection(Duration::from_millis(500))
+        .build();
+    let failpoint = FailPoint::fail_command(&["find"], FailPointMode::Times(1), Some(options));
+    let _fp_guard = client.enable_failpoint(failpoint, None).await.unwrap();
+
+    let coll: Collection<Document> = client.database(function_name!()).collection_with_options(
+        function_name!(),
+        CollectionOptions::builder()
+            .timeout(Duration::from_millis(100))
+            .build(),
+    );
+    let error = coll
+        .find_one(None, None)
+        .await
+        .expect_err("find should time out");
+    assert!(
+        matches!(*error.kind, ErrorKind::Timeout { .. }),
+        "expected timeout error, got {:?}",
+        error
+    );
+
+    let events = client.get_command_started_events(&["find"]);
+    let max_time = events[0].command.get_i64("maxTimeMS").unwrap();
+    assert!(max_time > 0 && max_time <= 100);
+
+    // A timeout of zero on the operation disables the timeout inherited from the collection.
+    let options = FindOneOptions::builder()
+        .timeout(Duration::from_secs(0))
+        .build();
+    coll.find_one(None, options).await.unwrap();
+    let events = client.get_command_started_events(&["find"]);
+    assert!(events[1].command.get("maxTimeMS").is_none());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn operation_timeout_retries() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+    let retryable_error = || MockReply::command_error(89, "NetworkTimeout", "timed out");
+
+    // Without a timeout, a read is retried once.
+    server.add_reply("find", retryable_error().times(2));
+    let error = coll.find_one(None, None).await.unwrap_err();
+    assert_eq!(error.code(), Some(89), "{:?}", error);
+    assert_eq!(server.commands_named("find").len(), 2);
+
+    // With one, it is retried for as long as time remains.
+    server.clear_commands();
+    server.add_reply("find", retryable_error().times(2));
+    server.add_reply("find", MockReply::cursor("db.coll", vec![doc! { "x": 1 }]));
+    let options = FindOneOptions::builder()
+        .timeout(Duration::from_secs(10))
+        .build();
+    let found = coll.find_one(None, options).await.unwrap();
+    assert_eq!(found, Some(doc! { "x": 1 }));
+    assert_eq!(server.commands_named("find").len(), 3);
+
+    // Retrying stops once the deadline passes.
+    server.clear_commands();
+    server.add_reply(
+        "find",
+        retryable_error()
+            .delay(Duration::from_millis(100))
+            .times(100),
+    );
+    let options = FindOneOptions::builder()
+        .timeout(Duration::from_millis(350))
+        .build();
+    coll.find_one(None, options).await.unwrap_err();
+    let attempts = server.commands_named("find").len();
+    assert!((3..=5).contains(&attempts), "{} attempts", attempts);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn operation_timeout_in_transaction() {
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .topology(MockTopology::ReplicaSetPrimary {
+                set_name: "rs0".to_string(),
+            })
+            .build(),
+    )
+    .unwrap();
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.timeout = Some(Duration::from_secs(10));
+    let client = Client::with_options(options).unwrap();
+    let coll = client.database("db").collection::<Document>("coll");
+
+    server.add_reply("insert", MockReply::document(doc! { "n": 1 }).times(2));
+    let mut session = client.start_session(None).await.unwrap();
+    session.start_transaction(None).await.unwrap();
+    coll.insert_one_with_session(doc! { "x": 1 }, None, &mut session)
+        .await
+        .unwrap();
+    coll.insert_one_with_session(doc! { "x": 2 }, None, &mut session)
+        .await
+        .unwrap();
+    session.commit_transaction().await.unwrap();
+
+    // Only the command that starts the transaction is sent with a maxTimeMS.
+    let inserts = server.commands_named("insert");
+    assert!(inserts[0].get("maxTimeMS").is_some());
+    assert!(inserts[1].get("maxTimeMS").is_none());
+    let commits = server.commands_named("commitTransaction");
+    assert!(commits[0].get("maxTimeMS").is_some());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn operation_timeout_get_more() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+
+    server.add_reply(
+        "find",
+        MockReply::document(doc! {
+            "cursor": { "id": 1_i64, "ns": "db.coll", "firstBatch": [{ "x": 1 }] }
+        }),
+    );
+    server.add_reply(
+        "getMore",
+        MockReply::document(doc! {
+            "cursor": { "id": 0_i64, "ns": "db.coll", "nextBatch": [{ "x": 2 }] }
+        })
+        .delay(Duration::from_secs(5)),
+    );
+    let options = FindOptions::builder()
+        .timeout(Duration::from_millis(200))
+        .build();
+    let mut cursor = coll.find(None, options).await.unwrap();
+    assert_eq!(cursor.next().await.unwrap().unwrap(), doc! { "x": 1 });
+
+    // The getMore is bounded by the timeout of the find that created the cursor.
+    let error = RUNTIME
+        .timeout(Duration::from_secs(2), cursor.next())
+        .await
+        .expect("getMore should time out")
+        .unwrap()
+        .unwrap_err();
+    assert!(
+        matches!(*error.kind, ErrorKind::Timeout { .. }),
+        "expected timeout error, got {:?}",
+        error
+    );
+}
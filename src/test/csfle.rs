@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use tokio::sync::RwLockReadGuard;
+
+use crate::{
+    bson::{doc, spec::BinarySubtype, Bson, Document},
+    client_encryption::ClientEncryption,
+    error::ErrorKind,
+    options::{
+        Algorithm,
+        AutoEncryptionOptions,
+        DataKeyOptions,
+        EncryptOptions,
+        KmsProviders,
+    },
+    test::{TestClient, CLIENT_OPTIONS, LOCK},
+    Client,
+    Namespace,
+};
+
+fn key_vault_namespace() -> Namespace {
+    Namespace {
+        db: "keyvault".to_string(),
+        coll: "datakeys".to_string(),
+    }
+}
+
+fn kms_providers() -> KmsProviders {
+    KmsProviders::local((0..96).collect::<Vec<u8>>()).unwrap()
+}
+
+async fn client_encryption(client: &TestClient) -> ClientEncryption {
+    let namespace = key_vault_namespace();
+    client
+        .database(&namespace.db)
+        .collection::<Document>(&namespace.coll)
+        .drop(None)
+        .await
+        .unwrap();
+    ClientEncryption::new(Client::clone(client), namespace, kms_providers())
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn explicit_encryption() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let client_encryption = client_encryption(&client).await;
+
+    let key_id = client_encryption
+        .create_data_key(
+            "local",
+            DataKeyOptions::builder()
+                .key_alt_names(vec!["alt".to_string()])
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(key_id.subtype, BinarySubtype::Uuid);
+
+    let key_vault = key_vault_namespace();
+    let data_key = client
+        .database(&key_vault.db)
+        .collection::<Document>(&key_vault.coll)
+        .find_one(doc! { "_id": key_id.clone() }, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        data_key.get_document("masterKey").unwrap(),
+        &doc! { "provider": "local" }
+    );
+
+    let options = EncryptOptions::builder()
+        .algorithm(Algorithm::Deterministic)
+        .key_id(key_id)
+        .build();
+    let by_id = client_encryption.encrypt("secret", options).await.unwrap();
+    assert_eq!(by_id.subtype, BinarySubtype::Encrypted);
+    assert_eq!(
+        client_encryption.decrypt(&by_id).await.unwrap(),
+        Bson::String("secret".to_string())
+    );
+
+    let by_name = client_encryption
+        .encrypt(
+            "secret",
+            EncryptOptions::builder()
+                .algorithm(Algorithm::Deterministic)
+                .key_alt_name("alt".to_string())
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(by_id, by_name);
+
+    let error = client_encryption
+        .encrypt(
+            "secret",
+            EncryptOptions::builder()
+                .algorithm(Algorithm::Random)
+                .key_alt_name("missing".to_string())
+                .build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Encryption { .. }));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn automatic_encryption() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let client_encryption = client_encryption(&client).await;
+    let key_id = client_encryption
+        .create_data_key("local", None)
+        .await
+        .unwrap();
+
+    let schema = doc! {
+        "bsonType": "object",
+        "encryptMetadata": { "keyId": [key_id] },
+        "properties": {
+            "ssn": {
+                "encrypt": {
+                    "bsonType": "string",
+                    "algorithm": "AEAD_AES_256_CBC_HMAC_SHA_512-Deterministic",
+                },
+            },
+        },
+    };
+    let mut schema_map = HashMap::new();
+    schema_map.insert(format!("{}.{}", function_name!(), function_name!()), schema);
+
+    let mut options = CLIENT_OPTIONS.clone();
+    options.auto_encryption_options = Some(
+        AutoEncryptionOptions::builder()
+            .key_vault_namespace(key_vault_namespace())
+            .kms_providers(kms_providers())
+            .schema_map(schema_map)
+            .build(),
+    );
+    let encrypted_client = Client::with_options(options).unwrap();
+
+    let coll = encrypted_client
+        .database(function_name!())
+        .collection::<Document>(function_name!());
+    coll.drop(None).await.unwrap();
+    coll.insert_one(doc! { "name": "Alice", "ssn": "123-45-6789" }, None)
+        .await
+        .unwrap();
+
+    // The value is stored encrypted.
+    let stored = client
+        .database(function_name!())
+        .collection::<Document>(function_name!())
+        .find_one(None, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        stored.get("ssn"),
+        Some(Bson::Binary(b)) if b.subtype == BinarySubtype::Encrypted
+    ));
+
+    // Queries on the encrypted field match, and the results are decrypted.
+    let found = coll
+        .find_one(doc! { "ssn": "123-45-6789" }, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.get_str("name"), Ok("Alice"));
+    assert_eq!(found.get_str("ssn"), Ok("123-45-6789"));
+}
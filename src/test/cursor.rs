@@ -4,7 +4,7 @@ use futures::{future::Either, StreamExt, TryStreamExt};
 use tokio::sync::RwLockReadGuard;
 
 use crate::{
-    bson::doc,
+    bson::{doc, RawDocumentBuf},
     options::{CreateCollectionOptions, CursorType, FindOptions},
     test::{util::EventClient, TestClient, LOCK},
     RUNTIME,
@@ -156,3 +156,113 @@ async fn batch_exhaustion() {
     let id = cursor.get_i64("id").unwrap();
     assert_eq!(0, id);
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn cursor_advance() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let coll = client
+        .create_fresh_collection(function_name!(), function_name!(), None)
+        .await;
+
+    coll.insert_many(
+        (0..5).map(|i| doc! { "_id": i, "name": format!("doc {}", i) }),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let opts = FindOptions::builder()
+        .batch_size(2)
+        .sort(doc! { "_id": 1 })
+        .build();
+    let mut cursor = coll.find(None, opts).await.unwrap();
+
+    let mut i = 0;
+    while cursor.advance().await.unwrap() {
+        assert_eq!(cursor.current().get_i32("_id").unwrap(), i);
+        assert_eq!(
+            cursor.current().get_str("name").unwrap(),
+            format!("doc {}", i)
+        );
+        assert_eq!(
+            cursor.deserialize_current().unwrap(),
+            doc! { "_id": i, "name": format!("doc {}", i) }
+        );
+        i += 1;
+    }
+    assert_eq!(i, 5);
+    assert!(!cursor.advance().await.unwrap());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn raw_document_collection() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let coll = client
+        .create_fresh_collection(function_name!(), function_name!(), None)
+        .await;
+
+    coll.insert_many((0..5).map(|i| doc! { "_id": i }), None)
+        .await
+        .unwrap();
+
+    let raw_coll = coll.clone_with_type::<RawDocumentBuf>();
+    let opts = FindOptions::builder()
+        .batch_size(2)
+        .sort(doc! { "_id": 1 })
+        .build();
+    let docs: Vec<RawDocumentBuf> = raw_coll
+        .find(None, opts)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let ids: Vec<i32> = docs.iter().map(|doc| doc.get_i32("_id").unwrap()).collect();
+    assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+
+    let found = raw_coll
+        .find_one(doc! { "_id": 3 }, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.get_i32("_id").unwrap(), 3);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn session_cursor_advance() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let mut session = client.start_session(None).await.unwrap();
+
+    let coll = client
+        .create_fresh_collection(function_name!(), function_name!(), None)
+        .await;
+
+    coll.insert_many_with_session((0..5).map(|i| doc! { "_id": i }), None, &mut session)
+        .await
+        .unwrap();
+
+    let opts = FindOptions::builder().batch_size(1).build();
+    let mut cursor = coll
+        .find_with_session(None, opts, &mut session)
+        .await
+        .unwrap();
+
+    for i in 0..5 {
+        assert!(cursor.advance(&mut session).await.unwrap());
+        assert_eq!(cursor.current().get_i32("_id").unwrap(), i);
+        assert_eq!(cursor.deserialize_current().unwrap(), doc! { "_id": i });
+    }
+    assert!(!cursor.advance(&mut session).await.unwrap());
+}
@@ -7,12 +7,24 @@ use tokio::sync::RwLockReadGuard;
 
 use crate::{
     bson::{doc, Bson, Document},
-    error::Result,
+    command::{CommandRetryability, RunnableCommand},
+    error::{ErrorKind, Result},
     options::{
         AggregateOptions,
         Collation,
         CreateCollectionOptions,
+        CreateRoleOptions,
+        CreateUserOptions,
         IndexOptionDefaults,
+        ModifyCollectionOptions,
+        ModifyIndex,
+        Privilege,
+        PrivilegeResource,
+        RenameCollectionOptions,
+        Role,
+        RolesInfoOptions,
+        UpdateUserOptions,
+        UsersInfoOptions,
         ValidationAction,
         ValidationLevel,
     },
@@ -20,8 +32,11 @@ use crate::{
     test::{
         util::{EventClient, TestClient},
         LOCK,
+        SERVERLESS,
     },
     Database,
+    IndexModel,
+    RUNTIME,
 };
 
 #[derive(Deserialize)]
@@ -258,6 +273,88 @@ async fn collection_management() {
     assert!(coll3.id_index.is_none());
 }
 
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn collection_management_helpers() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let db = client.database(function_name!());
+    db.drop(None).await.unwrap();
+
+    let coll = db.collection::<Document>("source");
+    coll.insert_many(vec![doc! { "x": 1 }, doc! { "x": 2 }], None)
+        .await
+        .unwrap();
+    coll.create_index(IndexModel::builder().keys(doc! { "x": 1 }).build(), None)
+        .await
+        .unwrap();
+
+    let stats = coll.stats(None).await.unwrap();
+    assert_eq!(stats.ns, coll.namespace());
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.index_count, 2);
+    assert!(stats.index_sizes.contains_key("x_1"));
+
+    let mut session = client.start_session(None).await.unwrap();
+    let stats = coll.stats_with_session(None, &mut session).await.unwrap();
+    assert_eq!(stats.count, 2);
+
+    db.create_view("view", "source", vec![doc! { "$match": { "x": 2 } }], None)
+        .await
+        .unwrap();
+    let view_docs: Vec<Document> = db
+        .collection::<Document>("view")
+        .find(None, None)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(view_docs.len(), 1);
+
+    let stats = db.stats(None).await.unwrap();
+    assert_eq!(stats.db, function_name!());
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.objects, 2);
+
+    if client.server_version_gte(4, 4) {
+        coll.modify(
+            ModifyCollectionOptions::builder()
+                .validator(doc! { "x": { "$type": "int" } })
+                .index(
+                    ModifyIndex::builder()
+                        .name("x_1".to_string())
+                        .hidden(true)
+                        .build(),
+                )
+                .build(),
+        )
+        .await
+        .unwrap();
+        let info = get_coll_info(&db, Some(doc! { "name": "source" })).await;
+        assert_eq!(
+            info[0].options.validator,
+            Some(doc! { "x": { "$type": "int" } })
+        );
+    }
+
+    if client.is_sharded() {
+        return;
+    }
+
+    db.create_collection("target", None).await.unwrap();
+    let error = coll.rename("target", None).await.unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::Command(_)));
+
+    let options = RenameCollectionOptions::builder().drop_target(true).build();
+    coll.rename("target", options).await.unwrap();
+    let names = db.list_collection_names(None).await.unwrap();
+    assert!(names.contains(&"target".to_string()));
+    assert!(!names.contains(&"source".to_string()));
+}
+
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 #[cfg_attr(feature = "async-std-runtime", async_std::test)]
 async fn db_aggregate() {
@@ -388,3 +485,158 @@ async fn index_option_defaults_test(defaults: Option<IndexOptionDefaults>, name:
     };
     assert_eq!(event_defaults, defaults);
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn run_typed_command() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    #[derive(Debug, Deserialize)]
+    struct BuildInfo {
+        version: String,
+    }
+
+    struct GetBuildInfo;
+
+    impl RunnableCommand for GetBuildInfo {
+        type Output = BuildInfo;
+
+        fn body(&self) -> Result<Document> {
+            Ok(doc! { "buildInfo": 1 })
+        }
+
+        fn handle_response(&self, response: Document) -> Result<BuildInfo> {
+            Ok(bson::from_document(response)?)
+        }
+
+        fn retryability(&self) -> CommandRetryability {
+            CommandRetryability::Read
+        }
+    }
+
+    struct Count(String);
+
+    impl RunnableCommand for Count {
+        type Output = i64;
+
+        fn body(&self) -> Result<Document> {
+            Ok(doc! { "count": self.0.as_str() })
+        }
+
+        fn handle_response(&self, response: Document) -> Result<i64> {
+            Ok(response.get_i32("n").map(i64::from).unwrap_or_default())
+        }
+    }
+
+    let client = TestClient::new().await;
+    let info = client.run_typed_command(GetBuildInfo).await.unwrap();
+    assert!(!info.version.is_empty());
+
+    let db = client.database(function_name!());
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    coll.insert_one(doc! { "x": 1 }, None).await.unwrap();
+
+    // the returned future must be spawnable.
+    let count = RUNTIME
+        .spawn(async move {
+            db.run_typed_command(Count(function_name!().to_string()))
+                .await
+        })
+        .unwrap()
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn user_and_role_management() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    if *SERVERLESS {
+        return;
+    }
+
+    let client = TestClient::new().await;
+
+    let db = client.database(function_name!());
+    let user = format!("{}_user", function_name!());
+    let role = format!("{}_role", function_name!());
+    let _ = db.drop_user(&user, None).await;
+    let _ = db.drop_role(&role, None).await;
+
+    db.create_role(
+        &role,
+        CreateRoleOptions::builder()
+            .privileges(vec![Privilege::new(
+                PrivilegeResource::Collection {
+                    db: function_name!().to_string(),
+                    collection: "".to_string(),
+                },
+                ["find"],
+            )])
+            .build(),
+    )
+    .await
+    .unwrap();
+    db.create_user(
+        &user,
+        CreateUserOptions::builder()
+            .password("pencil".to_string())
+            .custom_data(doc! { "x": 1 })
+            .roles(vec![Role::new("read", function_name!())])
+            .build(),
+    )
+    .await
+    .unwrap();
+    db.grant_roles(&user, vec![Role::new(&role, function_name!())], None)
+        .await
+        .unwrap();
+    db.update_user(
+        &user,
+        UpdateUserOptions::builder()
+            .custom_data(doc! { "x": 2 })
+            .build(),
+    )
+    .await
+    .unwrap();
+
+    let users = db
+        .users_info(
+            UsersInfoOptions::builder()
+                .filter(doc! { "user": &user })
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].custom_data, Some(doc! { "x": 2 }));
+    assert!(users[0]
+        .roles
+        .contains(&Role::new("read", function_name!())));
+    assert!(users[0].roles.contains(&Role::new(&role, function_name!())));
+
+    let roles = db
+        .roles_info(RolesInfoOptions::builder().show_privileges(true).build())
+        .await
+        .unwrap();
+    let info = roles.iter().find(|info| info.role == role).unwrap();
+    assert!(!info.is_builtin);
+    assert_eq!(info.privileges.as_ref().unwrap()[0].actions, vec!["find"]);
+
+    db.drop_user(&user, None).await.unwrap();
+    db.drop_role(&role, None).await.unwrap();
+    assert!(db
+        .users_info(
+            UsersInfoOptions::builder()
+                .filter(doc! { "user": &user })
+                .build()
+        )
+        .await
+        .unwrap()
+        .is_empty());
+}
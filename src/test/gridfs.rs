@@ -0,0 +1,352 @@
+use futures::{AsyncReadExt, AsyncWriteExt, TryStreamExt};
+use tokio::sync::RwLockReadGuard;
+
+use crate::{
+    bson::{doc, Bson, DateTime, Document},
+    error::{ErrorKind, GridFsErrorKind},
+    gridfs::GridFsBucket,
+    options::{
+        ClientOptions,
+        GridFsBucketOptions,
+        GridFsDownloadByNameOptions,
+        GridFsFindOptions,
+        GridFsUploadOptions,
+    },
+    test::{TestClient, LOCK},
+    test_util::{MockReply, MockServer},
+    Client,
+};
+
+async fn new_bucket(client: &TestClient, name: &str, chunk_size_bytes: u32) -> GridFsBucket {
+    let options = GridFsBucketOptions::builder()
+        .bucket_name(name.to_string())
+        .chunk_size_bytes(chunk_size_bytes)
+        .build();
+    let bucket = client.database(name).gridfs_bucket(options).unwrap();
+    bucket.drop().await.unwrap();
+    bucket
+}
+
+async fn download(bucket: &GridFsBucket, id: Bson) -> Vec<u8> {
+    let mut contents = Vec::new();
+    bucket
+        .open_download_stream(id)
+        .await
+        .unwrap()
+        .read_to_end(&mut contents)
+        .await
+        .unwrap();
+    contents
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn upload_download_round_trip() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let bucket = new_bucket(&client, function_name!(), 4).await;
+
+    let data: Vec<u8> = (0..10).collect();
+    let mut upload_stream = bucket
+        .open_upload_stream(
+            "file",
+            GridFsUploadOptions::builder()
+                .metadata(doc! { "x": 1 })
+                .build(),
+        )
+        .unwrap();
+    // Write in pieces that don't line up with the chunk boundaries.
+    upload_stream.write_all(&data[..3]).await.unwrap();
+    upload_stream.write_all(&data[3..]).await.unwrap();
+    upload_stream.close().await.unwrap();
+    let id = upload_stream.id().clone();
+
+    let chunks: Vec<Document> = client
+        .database(function_name!())
+        .collection::<Document>(&format!("{}.chunks", function_name!()))
+        .find(doc! { "files_id": id.clone() }, None)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(chunks.len(), 3);
+
+    let download_stream = bucket.open_download_stream(id.clone()).await.unwrap();
+    let file = download_stream.file();
+    assert_eq!(file.length, 10);
+    assert_eq!(file.chunk_size, 4);
+    assert_eq!(file.filename.as_deref(), Some("file"));
+    assert_eq!(file.metadata, Some(doc! { "x": 1 }));
+
+    assert_eq!(download(&bucket, id).await, data);
+
+    let id = bucket
+        .upload_from_futures_0_3_reader("empty", &b""[..], None)
+        .await
+        .unwrap();
+    assert!(download(&bucket, id.into()).await.is_empty());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn download_by_name_revisions() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let bucket = new_bucket(&client, function_name!(), 4).await;
+
+    for revision in 0u8..3 {
+        bucket
+            .upload_from_futures_0_3_reader("file", &[revision][..], None)
+            .await
+            .unwrap();
+    }
+
+    let download_revision = |revision: Option<i32>| {
+        let bucket = bucket.clone();
+        async move {
+            let options = GridFsDownloadByNameOptions::builder()
+                .revision(revision)
+                .build();
+            let mut contents = Vec::new();
+            bucket
+                .download_to_futures_0_3_writer_by_name("file", &mut contents, options)
+                .await
+                .map(|_| contents)
+        }
+    };
+
+    assert_eq!(download_revision(None).await.unwrap(), vec![2]);
+    assert_eq!(download_revision(Some(0)).await.unwrap(), vec![0]);
+    assert_eq!(download_revision(Some(1)).await.unwrap(), vec![1]);
+    assert_eq!(download_revision(Some(-1)).await.unwrap(), vec![2]);
+    assert_eq!(download_revision(Some(-3)).await.unwrap(), vec![0]);
+
+    let error = download_revision(Some(3)).await.unwrap_err();
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::GridFs(GridFsErrorKind::RevisionNotFound { revision: 3 })
+    ));
+
+    let error = bucket
+        .open_download_stream_by_name("missing", None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. })
+    ));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn delete_rename_and_find() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let bucket = new_bucket(&client, function_name!(), 4).await;
+
+    let a = bucket
+        .upload_from_futures_0_3_reader("a", &b"aaaaaa"[..], None)
+        .await
+        .unwrap();
+    let b = bucket
+        .upload_from_futures_0_3_reader("b", &b"bb"[..], None)
+        .await
+        .unwrap();
+
+    bucket.rename(b.into(), "c").await.unwrap();
+    let options = GridFsFindOptions::builder()
+        .sort(doc! { "filename": 1 })
+        .build();
+    let filenames: Vec<Option<String>> = bucket
+        .find(doc! {}, options)
+        .await
+        .unwrap()
+        .map_ok(|file| file.filename)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        filenames,
+        vec![Some("a".to_string()), Some("c".to_string())]
+    );
+
+    bucket.delete(a.into()).await.unwrap();
+    let error = bucket.open_download_stream(a.into()).await.unwrap_err();
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. })
+    ));
+    let remaining_chunks = client
+        .database(function_name!())
+        .collection::<Document>(&format!("{}.chunks", function_name!()))
+        .count_documents(doc! { "files_id": a }, None)
+        .await
+        .unwrap();
+    assert_eq!(remaining_chunks, 0);
+
+    let error = bucket.delete(a.into()).await.unwrap_err();
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. })
+    ));
+    let error = bucket.rename(a.into(), "d").await.unwrap_err();
+    assert!(matches!(
+        *error.kind,
+        ErrorKind::GridFs(GridFsErrorKind::FileNotFound { .. })
+    ));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn indexes_created_and_abort() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = TestClient::new().await;
+    let bucket = new_bucket(&client, function_name!(), 4).await;
+
+    let mut upload_stream = bucket.open_upload_stream("aborted", None).unwrap();
+    upload_stream.write_all(b"abcdefgh").await.unwrap();
+    upload_stream.flush().await.unwrap();
+
+    let db = client.database(function_name!());
+    let chunks = db.collection::<Document>(&format!("{}.chunks", function_name!()));
+    let id = upload_stream.id().clone();
+    assert_eq!(
+        chunks
+            .count_documents(doc! { "files_id": id.clone() }, None)
+            .await
+            .unwrap(),
+        2
+    );
+
+    upload_stream.abort().await.unwrap();
+    assert_eq!(
+        chunks
+            .count_documents(doc! { "files_id": id }, None)
+            .await
+            .unwrap(),
+        0
+    );
+    assert!(upload_stream.write_all(b"more").await.is_err());
+
+    let index_names = chunks.list_index_names().await.unwrap();
+    assert!(index_names.contains(&"files_id_1_n_1".to_string()));
+    let index_names = db
+        .collection::<Document>(&format!("{}.files", function_name!()))
+        .list_index_names()
+        .await
+        .unwrap();
+    assert!(index_names.contains(&"filename_1_uploadDate_1".to_string()));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn zero_chunk_size() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let db = Client::with_options(options).unwrap().database("db");
+
+    let options = GridFsBucketOptions::builder().chunk_size_bytes(0).build();
+    let error = db.gridfs_bucket(options).unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+
+    let bucket = db.gridfs_bucket(None).unwrap();
+    let options = GridFsUploadOptions::builder().chunk_size_bytes(0).build();
+    let error = bucket
+        .open_upload_stream("file", options.clone())
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+    let error = bucket
+        .upload_from_futures_0_3_reader("file", &b"contents"[..], options)
+        .await
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+    assert!(server.commands().is_empty());
+
+    // A files document with a chunk size of 0 is rejected rather than read.
+    server.add_reply(
+        "find",
+        MockReply::cursor(
+            "db.fs.files",
+            vec![doc! {
+                "_id": 1,
+                "length": 10_i64,
+                "chunkSize": 0,
+                "uploadDate": DateTime::now(),
+                "filename": "file",
+            }],
+        ),
+    );
+    let error = bucket
+        .open_download_stream(Bson::Int32(1))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(*error.kind, ErrorKind::InvalidResponse { .. }),
+        "{:?}",
+        error
+    );
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn failed_upload_deletes_chunks() {
+    let server = MockServer::start(None).unwrap();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let db = Client::with_options(options).unwrap().database("db");
+    let bucket = db
+        .gridfs_bucket(GridFsBucketOptions::builder().chunk_size_bytes(4).build())
+        .unwrap();
+
+    // An existing file means the bucket doesn't try to create its indexes.
+    server.add_reply(
+        "find",
+        MockReply::cursor("db.fs.files", vec![doc! { "_id": 0 }]),
+    );
+    server.add_reply("insert", MockReply::document(doc! { "n": 1 }));
+    server.add_reply(
+        "insert",
+        MockReply::command_error(11000, "DuplicateKey", "duplicate key"),
+    );
+    server.add_reply("delete", MockReply::document(doc! { "n": 1 }));
+
+    // The insert error is returned rather than an error from cleaning up after it.
+    let data: Vec<u8> = (0..12).collect();
+    let error = bucket
+        .upload_from_futures_0_3_reader_with_id(Bson::Int32(1), "file", &data[..], None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), Some(11000), "{:?}", error);
+    let deletes = server.commands_named("delete");
+    assert_eq!(deletes.len(), 1);
+    assert_eq!(
+        deletes[0].get_array("deletes").unwrap()[0]
+            .as_document()
+            .unwrap()
+            .get_document("q")
+            .unwrap(),
+        &doc! { "files_id": 1 }
+    );
+
+    // A stream whose write failed can still be aborted.
+    server.add_reply(
+        "insert",
+        MockReply::command_error(11000, "DuplicateKey", "duplicate key"),
+    );
+    server.add_reply("delete", MockReply::document(doc! { "n": 0 }));
+    let mut upload_stream = bucket
+        .open_upload_stream_with_id(Bson::Int32(2), "file", None)
+        .unwrap();
+    upload_stream.write_all(&data[..4]).await.unwrap();
+    upload_stream.flush().await.unwrap_err();
+    upload_stream.abort().await.unwrap();
+    assert_eq!(server.commands_named("delete").len(), 2);
+}
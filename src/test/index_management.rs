@@ -4,7 +4,7 @@ use tokio::sync::RwLockReadGuard;
 use crate::{
     bson::doc,
     error::ErrorKind,
-    options::{CommitQuorum, CreateIndexOptions, IndexOptions},
+    options::{CommitQuorum, CreateIndexOptions, IndexOptions, SyncIndexOptions},
     test::{
         util::{EventClient, TestClient},
         LOCK,
@@ -306,3 +306,79 @@ async fn commit_quorum_error() {
         assert!(result.is_ok());
     }
 }
+
+// Test that syncing indexes creates, keeps and drops indexes as expected.
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn index_management_syncs() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+    let client = TestClient::new().await;
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+
+    coll.create_indexes(
+        vec![
+            IndexModel::builder().keys(doc! { "a": 1 }).build(),
+            IndexModel::builder().keys(doc! { "old": 1 }).build(),
+            IndexModel::builder()
+                .keys(doc! { "u": 1 })
+                .options(IndexOptions::builder().unique(true).build())
+                .build(),
+        ],
+        None,
+    )
+    .await
+    .expect("Test failed to create indexes");
+
+    let desired = vec![
+        IndexModel::builder().keys(doc! { "a": 1 }).build(),
+        IndexModel::builder().keys(doc! { "b": 1 }).build(),
+        IndexModel::builder().keys(doc! { "u": 1 }).build(),
+    ];
+
+    // A dry run reports the changes without making them.
+    let options = SyncIndexOptions::builder()
+        .drop_extraneous(true)
+        .dry_run(true)
+        .build();
+    let result = coll
+        .sync_indexes(desired.clone(), options)
+        .await
+        .expect("Test failed to sync indexes");
+    assert!(result.dry_run);
+    assert_eq!(result.created, vec!["b_1"]);
+    assert_eq!(result.dropped, vec!["old_1"]);
+    assert_eq!(result.unchanged, vec!["a_1"]);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].name, "u_1");
+    assert_eq!(result.conflicts[0].differences, vec!["unique"]);
+    let names = coll
+        .list_index_names()
+        .await
+        .expect("Test failed to list index names");
+    assert_eq!(names, vec!["_id_", "a_1", "old_1", "u_1"]);
+
+    let options = SyncIndexOptions::builder().drop_extraneous(true).build();
+    let result = coll
+        .sync_indexes(desired.clone(), options)
+        .await
+        .expect("Test failed to sync indexes");
+    assert!(!result.dry_run);
+    let names = coll
+        .list_index_names()
+        .await
+        .expect("Test failed to list index names");
+    assert_eq!(names, vec!["_id_", "a_1", "u_1", "b_1"]);
+
+    // Syncing again is a no-op, and the conflicting index is left in place.
+    let result = coll
+        .sync_indexes(desired, None)
+        .await
+        .expect("Test failed to sync indexes");
+    assert!(result.created.is_empty());
+    assert!(result.dropped.is_empty());
+    assert_eq!(result.unchanged, vec!["a_1", "b_1"]);
+    assert_eq!(result.conflicts.len(), 1);
+}
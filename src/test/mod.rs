@@ -1,12 +1,16 @@
 #[cfg(not(feature = "sync"))]
 mod atlas_connectivity;
 mod auth_aws;
+mod change_stream;
 mod client;
 mod coll;
+#[cfg(feature = "csfle")]
+mod csfle;
 mod cursor;
 mod db;
 #[cfg(not(feature = "sync"))]
 mod documentation_examples;
+mod gridfs;
 mod index_management;
 mod spec;
 mod util;
@@ -0,0 +1,49 @@
+#!/bin/sh
+# Regenerates the certificates and OCSP responses used by the OCSP unit tests.
+# The responses are valid for 100 years so that the fixtures don't need to be regenerated.
+
+set -o errexit
+cd "$(dirname "$0")"
+TMP=$(mktemp -d)
+
+cat > "$TMP/ext.cnf" <<CNF
+[ca]
+basicConstraints = critical, CA:TRUE
+keyUsage = critical, keyCertSign, cRLSign
+[leaf]
+basicConstraints = CA:FALSE
+authorityInfoAccess = OCSP;URI:http://localhost:8100
+[responder]
+basicConstraints = CA:FALSE
+extendedKeyUsage = OCSPSigning
+CNF
+
+openssl req -x509 -newkey rsa:2048 -nodes -keyout "$TMP/ca.key" -out ca.pem -days 36500 \
+    -subj "/CN=OCSP Test CA" -sha256 -extensions ca -config "$TMP/ext.cnf"
+
+for name in good revoked responder; do
+    if [ "$name" = "responder" ]; then ext=responder; else ext=leaf; fi
+    openssl req -newkey rsa:2048 -nodes -keyout "$TMP/$name.key" -out "$TMP/$name.csr" \
+        -subj "/CN=$name"
+    openssl x509 -req -in "$TMP/$name.csr" -CA ca.pem -CAkey "$TMP/ca.key" -out "$name.pem" \
+        -days 36500 -sha256 -set_serial "0x$(openssl rand -hex 8)" \
+        -extfile "$TMP/ext.cnf" -extensions $ext
+done
+
+expiry=$(date -u -d "+100 years" +%y%m%d%H%M%SZ 2>/dev/null || echo 991231235959Z)
+revoked_at=$(date -u +%y%m%d%H%M%SZ)
+good_serial=$(openssl x509 -in good.pem -noout -serial | cut -d= -f2)
+revoked_serial=$(openssl x509 -in revoked.pem -noout -serial | cut -d= -f2)
+printf 'V\t%s\t\t%s\tunknown\t/CN=good\n' "$expiry" "$good_serial" > "$TMP/index.txt"
+printf 'R\t%s\t%s\t%s\tunknown\t/CN=revoked\n' "$expiry" "$revoked_at" "$revoked_serial" \
+    >> "$TMP/index.txt"
+
+respond() {
+    openssl ocsp -index "$TMP/index.txt" -CA ca.pem -issuer ca.pem -cert "$1.pem" \
+        -rsigner "$2.pem" -rkey "$TMP/$2.key" -ndays 36500 -respout "$3" -no_nonce
+}
+respond good ca good.der
+respond revoked ca revoked.der
+respond good responder good-delegated.der
+
+rm -rf "$TMP"
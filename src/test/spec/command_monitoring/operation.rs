@@ -175,6 +175,7 @@ impl TestOperation for Find {
                 read_concern: None,
                 selection_criteria: None,
                 collation: None,
+                timeout: None,
             };
 
             let mut cursor = collection.find(self.filter.clone(), options).await?;
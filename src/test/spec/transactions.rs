@@ -1,10 +1,20 @@
+use futures::{future::FutureExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{RwLockReadGuard, RwLockWriteGuard};
 
 use crate::{
     bson::{doc, serde_helpers::serialize_u64_as_i32, Document},
     client::session::TransactionState,
-    test::{run_spec_test, TestClient, LOCK},
+    error::{Error, ErrorKind, TRANSIENT_TRANSACTION_ERROR},
+    test::{
+        run_spec_test,
+        EventClient,
+        FailCommandOptions,
+        FailPoint,
+        FailPointMode,
+        TestClient,
+        LOCK,
+    },
     Collection,
 };
 
@@ -139,3 +149,258 @@ async fn deserialize_recovery_token() {
     // Nevertheless, the recovery token should have been retrieved from the ok: 1 response.
     assert!(session.transaction.recovery_token.is_some());
 }
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn with_transaction_retries_transient_errors() {
+    let _guard: RwLockWriteGuard<()> = LOCK.run_exclusively().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || client.server_version_lt(4, 4) {
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    client
+        .database(function_name!())
+        .create_collection(function_name!(), None)
+        .await
+        .unwrap();
+
+    let options = FailCommandOptions::builder()
+        .error_code(112)
+        .error_labels(vec![TRANSIENT_TRANSACTION_ERROR.to_string()])
+        .build();
+    let failpoint = FailPoint::fail_command(&["insert"], FailPointMode::Times(1), options);
+    let _fp_guard = client.enable_failpoint(failpoint, None).await.unwrap();
+
+    let mut session = client.start_session(None).await.unwrap();
+    let mut attempts = 0;
+    let result = session
+        .with_transaction(
+            |session| {
+                attempts += 1;
+                let coll = coll.clone();
+                async move {
+                    coll.insert_one_with_session(doc! { "x": 1 }, None, session)
+                        .await?;
+                    Ok(attempts)
+                }
+                .boxed()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, 2);
+
+    assert_eq!(
+        client
+            .get_command_started_events(&["abortTransaction"])
+            .len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_command_started_events(&["commitTransaction"])
+            .len(),
+        1
+    );
+    assert_eq!(coll.count_documents(None, None).await.unwrap(), 1);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn with_transaction_retries_commit() {
+    let _guard: RwLockWriteGuard<()> = LOCK.run_exclusively().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || client.server_version_lt(4, 0) {
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    client
+        .database(function_name!())
+        .create_collection(function_name!(), None)
+        .await
+        .unwrap();
+
+    // ShutdownInProgress errors from commitTransaction are labeled UnknownTransactionCommitResult.
+    // The executor retries commitTransaction once by itself, so the fail point must outlast that
+    // retry for with_transaction to have to retry the commit.
+    let options = FailCommandOptions::builder().error_code(91).build();
+    let failpoint =
+        FailPoint::fail_command(&["commitTransaction"], FailPointMode::Times(2), options);
+    let _fp_guard = client.enable_failpoint(failpoint, None).await.unwrap();
+
+    let mut session = client.start_session(None).await.unwrap();
+    let mut attempts = 0;
+    session
+        .with_transaction(
+            |session| {
+                attempts += 1;
+                let coll = coll.clone();
+                async move {
+                    coll.insert_one_with_session(doc! { "x": 1 }, None, session)
+                        .await?;
+                    Ok(())
+                }
+                .boxed()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(attempts, 1);
+
+    // One insert, then the failed commit and its retry by the executor, then the successful commit
+    // retried by with_transaction with a majority write concern.
+    assert_eq!(client.get_command_started_events(&["insert"]).len(), 1);
+    let commits = client.get_command_started_events(&["commitTransaction"]);
+    assert_eq!(commits.len(), 3);
+    let write_concern = commits[2].command.get_document("writeConcern").unwrap();
+    assert_eq!(write_concern.get_str("w"), Ok("majority"));
+    assert!(client
+        .get_command_started_events(&["abortTransaction"])
+        .is_empty());
+    assert_eq!(coll.count_documents(None, None).await.unwrap(), 1);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn with_transaction_retries_transient_callback_errors() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || client.server_version_lt(4, 0) {
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    client
+        .database(function_name!())
+        .create_collection(function_name!(), None)
+        .await
+        .unwrap();
+
+    // The callback itself returns an error labeled TransientTransactionError on its first attempt,
+    // after having written in the transaction.
+    let mut session = client.start_session(None).await.unwrap();
+    let mut attempts = 0;
+    let result = session
+        .with_transaction(
+            |session| {
+                attempts += 1;
+                let attempt = attempts;
+                let coll = coll.clone();
+                async move {
+                    coll.insert_one_with_session(doc! { "attempt": attempt }, None, session)
+                        .await?;
+                    if attempt == 1 {
+                        return Err(Error::new(
+                            ErrorKind::InvalidArgument {
+                                message: "transient callback error".to_string(),
+                            },
+                            Some(vec![TRANSIENT_TRANSACTION_ERROR.to_string()]),
+                        ));
+                    }
+                    Ok(attempt)
+                }
+                .boxed()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, 2);
+
+    // The first attempt's insert is aborted along with its transaction.
+    assert_eq!(client.get_command_started_events(&["insert"]).len(), 2);
+    assert_eq!(
+        client
+            .get_command_started_events(&["abortTransaction"])
+            .len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_command_started_events(&["commitTransaction"])
+            .len(),
+        1
+    );
+    let documents: Vec<Document> = coll
+        .find(None, None)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].get_i32("attempt"), Ok(2));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+#[function_name::named]
+async fn with_transaction_aborts_on_callback_error() {
+    let _guard: RwLockReadGuard<()> = LOCK.run_concurrently().await;
+
+    let client = EventClient::new().await;
+    if !client.is_replica_set() || client.server_version_lt(4, 0) {
+        return;
+    }
+
+    let coll = client
+        .init_db_and_coll(function_name!(), function_name!())
+        .await;
+    client
+        .database(function_name!())
+        .create_collection(function_name!(), None)
+        .await
+        .unwrap();
+
+    let mut session = client.start_session(None).await.unwrap();
+    let error = session
+        .with_transaction(
+            |session| {
+                let coll = coll.clone();
+                async move {
+                    coll.insert_one_with_session(doc! { "x": 1 }, None, session)
+                        .await?;
+                    Err::<(), _>(
+                        ErrorKind::InvalidArgument {
+                            message: "callback error".to_string(),
+                        }
+                        .into(),
+                    )
+                }
+                .boxed()
+            },
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(*error.kind, ErrorKind::InvalidArgument { .. }));
+    assert_eq!(session.transaction.state, TransactionState::Aborted);
+
+    assert_eq!(
+        client
+            .get_command_started_events(&["abortTransaction"])
+            .len(),
+        1
+    );
+    assert!(client
+        .get_command_started_events(&["commitTransaction"])
+        .is_empty());
+    assert_eq!(coll.count_documents(None, None).await.unwrap(), 0);
+}
@@ -12,6 +12,7 @@ use super::{Entity, ExpectError, FindCursor, TestRunner};
 
 use crate::{
     bson::{doc, to_bson, Bson, Deserializer as BsonDeserializer, Document},
+    bson_util,
     client::session::{ClientSession, TransactionState},
     coll::options::Hint,
     collation::Collation,
@@ -337,6 +338,12 @@ pub(super) struct Find {
     skip: Option<u64>,
     sort: Option<Document>,
     collation: Option<Collation>,
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    timeout: Option<Duration>,
 }
 
 impl Find {
@@ -370,6 +377,7 @@ impl Find {
             cursor_type: None,
             max_await_time: None,
             selection_criteria: None,
+            timeout: self.timeout,
         };
         match &self.session {
             Some(session_id) => {
@@ -450,6 +458,12 @@ pub(super) struct CreateFindCursor {
     skip: Option<u64>,
     sort: Option<Document>,
     collation: Option<Collation>,
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    timeout: Option<Duration>,
 }
 
 impl TestOperation for CreateFindCursor {
@@ -480,6 +494,7 @@ impl TestOperation for CreateFindCursor {
                 skip: self.skip,
                 sort: self.sort.clone(),
                 collation: self.collation.clone(),
+                timeout: self.timeout,
             };
             let cursor = find.get_cursor(id, test_runner).await?;
             Ok(Some(Entity::FindCursor(cursor)))
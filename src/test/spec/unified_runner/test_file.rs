@@ -7,6 +7,7 @@ use super::{results_match, ExpectedEvent, ObserveEvent, Operation};
 
 use crate::{
     bson::{doc, Bson, Deserializer as BsonDeserializer, Document},
+    bson_util,
     client::options::{ServerApi, ServerApiVersion, SessionOptions},
     concern::{Acknowledgment, ReadConcernLevel},
     error::Error,
@@ -258,6 +259,12 @@ pub struct CollectionOrDatabaseOptions {
     #[serde(rename = "readPreference")]
     pub selection_criteria: Option<SelectionCriteria>,
     pub write_concern: Option<WriteConcern>,
+    #[serde(
+        default,
+        rename = "timeoutMS",
+        deserialize_with = "bson_util::deserialize_duration_option_from_u64_millis"
+    )]
+    pub timeout: Option<Duration>,
 }
 
 impl CollectionOrDatabaseOptions {
@@ -266,6 +273,7 @@ impl CollectionOrDatabaseOptions {
             read_concern: self.read_concern.clone(),
             selection_criteria: self.selection_criteria.clone(),
             write_concern: self.write_concern.clone(),
+            timeout: self.timeout,
         }
     }
 
@@ -274,6 +282,7 @@ impl CollectionOrDatabaseOptions {
             read_concern: self.read_concern.clone(),
             selection_criteria: self.selection_criteria.clone(),
             write_concern: self.write_concern.clone(),
+            timeout: self.timeout,
         }
     }
 }
@@ -0,0 +1,483 @@
+//! Contains an in-process mock server that can be used to test code that uses the driver without
+//! a running MongoDB deployment.
+//!
+//! A [`MockServer`] listens on a local TCP port and speaks the OP_MSG wire protocol. It answers
+//! the `hello` commands used for connection handshakes and server monitoring on its own, reporting
+//! whichever [`MockTopology`] it was started with, and replies to every other command with either
+//! a reply scripted via [`MockServer::add_reply`] or `{ ok: 1 }`. The commands the driver sends
+//! are recorded and can be inspected with [`MockServer::commands`].
+//!
+//! This module is only available when the `test-util` feature flag is enabled.
+
+#[cfg(test)]
+mod test;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use futures_util::future::{select, Either};
+use tokio::sync::watch;
+use typed_builder::TypedBuilder;
+
+use crate::{
+    bson::{doc, oid::ObjectId, Bson, DateTime, Document},
+    cmap::conn::wire::{Message, MessageFlags, MessageSection},
+    error::{ErrorKind, Result},
+    options::ServerAddress,
+    runtime::{AsyncStream, AsyncTcpListener},
+    RUNTIME,
+};
+
+const DEFAULT_MAX_WIRE_VERSION: i32 = 13;
+
+/// The kind of server a [`MockServer`] reports itself as in its `hello` responses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MockTopology {
+    /// A standalone `mongod`.
+    Standalone,
+
+    /// The primary of a single-member replica set with the given name.
+    ReplicaSetPrimary {
+        /// The name of the replica set.
+        set_name: String,
+    },
+
+    /// A `mongos` router.
+    Mongos,
+
+    /// A `mongos` behind a load balancer. Clients must connect with `loadBalanced=true`.
+    LoadBalancer,
+}
+
+/// Specifies the options to a [`MockServer`].
+#[derive(Clone, Debug, Default, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[non_exhaustive]
+pub struct MockServerOptions {
+    /// The kind of server to report in `hello` responses. Defaults to
+    /// [`MockTopology::Standalone`].
+    pub topology: Option<MockTopology>,
+
+    /// The `maxWireVersion` to report in `hello` responses. Defaults to 13 (MongoDB 5.0).
+    pub max_wire_version: Option<i32>,
+
+    /// Additional fields to include in `hello` responses. These take precedence over the fields
+    /// the server would otherwise report.
+    pub hello_fields: Option<Document>,
+}
+
+/// A reply that a [`MockServer`] sends in response to a command.
+#[derive(Clone, Debug)]
+pub struct MockReply {
+    kind: MockReplyKind,
+    delay: Option<Duration>,
+    times: usize,
+}
+
+#[derive(Clone, Debug)]
+enum MockReplyKind {
+    Document(Document),
+    Hello(Document),
+    NetworkError,
+}
+
+impl MockReply {
+    fn with_kind(kind: MockReplyKind) -> Self {
+        Self {
+            kind,
+            delay: None,
+            times: 1,
+        }
+    }
+
+    /// Replies with the given document. `ok: 1` is added to the document if it does not contain
+    /// an `ok` field.
+    pub fn document(reply: Document) -> Self {
+        Self::with_kind(MockReplyKind::Document(reply))
+    }
+
+    /// Replies with a command error with the given code and message.
+    pub fn command_error(code: i32, code_name: impl AsRef<str>, message: impl AsRef<str>) -> Self {
+        Self::document(doc! {
+            "ok": 0,
+            "code": code,
+            "codeName": code_name.as_ref(),
+            "errmsg": message.as_ref(),
+        })
+    }
+
+    /// Replies with a cursor that has no further batches, as returned by `find` or `aggregate`.
+    /// `ns` is the namespace of the cursor in the form `"<db>.<collection>"`.
+    pub fn cursor(ns: impl AsRef<str>, first_batch: impl IntoIterator<Item = Document>) -> Self {
+        let first_batch: Vec<Document> = first_batch.into_iter().collect();
+        Self::document(doc! {
+            "cursor": {
+                "id": 0_i64,
+                "ns": ns.as_ref(),
+                "firstBatch": first_batch,
+            }
+        })
+    }
+
+    /// Replies with the server's usual `hello` response. This is useful to inject latency into
+    /// handshakes and heartbeats with [`MockReply::delay`].
+    pub fn hello() -> Self {
+        Self::with_kind(MockReplyKind::Hello(Document::new()))
+    }
+
+    /// Replies with the server's usual `hello` response, with the given fields added to or
+    /// replaced in it.
+    pub fn hello_with(fields: Document) -> Self {
+        Self::with_kind(MockReplyKind::Hello(fields))
+    }
+
+    /// Closes the connection without replying, causing a network error in the driver.
+    pub fn network_error() -> Self {
+        Self::with_kind(MockReplyKind::NetworkError)
+    }
+
+    /// Waits for the given duration before sending the reply.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Uses this reply for the next `times` matching commands rather than only the next one.
+    pub fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+}
+
+/// An in-process mock MongoDB server listening on a local TCP port.
+///
+/// The server stops listening and closes all of its connections when it is dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    shared: Arc<Shared>,
+    _shutdown: watch::Sender<()>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    address: ServerAddress,
+    topology: MockTopology,
+    max_wire_version: i32,
+    hello_fields: Document,
+    service_id: ObjectId,
+    next_connection_id: AtomicI32,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    replies: HashMap<String, VecDeque<MockReply>>,
+    commands: Vec<Document>,
+}
+
+impl MockServer {
+    /// Starts a new mock server listening on an ephemeral port on the loopback interface.
+    ///
+    /// When using the `tokio` runtime, this must be called from within the runtime.
+    pub fn start(options: impl Into<Option<MockServerOptions>>) -> Result<Self> {
+        let options = options.into().unwrap_or_default();
+
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+        listener.set_nonblocking(true)?;
+        let local_address = listener.local_addr()?;
+
+        let shared = Arc::new(Shared {
+            address: ServerAddress::Tcp {
+                host: local_address.ip().to_string(),
+                port: Some(local_address.port()),
+            },
+            topology: options.topology.unwrap_or(MockTopology::Standalone),
+            max_wire_version: options
+                .max_wire_version
+                .unwrap_or(DEFAULT_MAX_WIRE_VERSION),
+            hello_fields: options.hello_fields.unwrap_or_default(),
+            service_id: ObjectId::new(),
+            next_connection_id: AtomicI32::new(1),
+            state: Default::default(),
+        });
+
+        let (shutdown_sender, shutdown_receiver) = watch::channel(());
+        RUNTIME
+            .spawn(accept_connections(
+                shared.clone(),
+                listener,
+                shutdown_receiver,
+            ))
+            .ok_or_else(|| ErrorKind::Internal {
+                message: "a mock server must be started from within an async runtime".to_string(),
+            })?;
+
+        Ok(Self {
+            shared,
+            _shutdown: shutdown_sender,
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn address(&self) -> &ServerAddress {
+        &self.shared.address
+    }
+
+    /// A connection string for connecting to the server. This includes the `replicaSet` or
+    /// `loadBalanced` options required by the server's topology.
+    pub fn uri(&self) -> String {
+        let mut uri = format!("mongodb://{}/", self.shared.address);
+        match self.shared.topology {
+            MockTopology::ReplicaSetPrimary { ref set_name } => {
+                uri.push_str("?replicaSet=");
+                uri.push_str(set_name);
+            }
+            MockTopology::LoadBalancer => uri.push_str("?loadBalanced=true"),
+            MockTopology::Standalone | MockTopology::Mongos => {}
+        }
+        uri
+    }
+
+    /// Queues a reply for the next command with the given name. Replies for the same command
+    /// are used in the order they were added; once they are exhausted, the server goes back to
+    /// its default behavior for that command.
+    ///
+    /// `hello`, `isMaster` and `ismaster` are treated as the same command.
+    pub fn add_reply(&self, command_name: impl AsRef<str>, reply: MockReply) {
+        self.shared
+            .state
+            .lock()
+            .unwrap()
+            .replies
+            .entry(normalize_command_name(command_name.as_ref()).to_string())
+            .or_default()
+            .push_back(reply);
+    }
+
+    /// Returns the commands the server has received so far, in the order they were received.
+    ///
+    /// The commands are returned exactly as the driver sent them, including fields such as `$db`
+    /// and `lsid`, with any document sequences included as arrays. `hello` commands are not
+    /// recorded, since the driver sends them in the background to monitor the server.
+    pub fn commands(&self) -> Vec<Document> {
+        self.shared.state.lock().unwrap().commands.clone()
+    }
+
+    /// Returns the commands with the given name that the server has received so far.
+    pub fn commands_named(&self, command_name: impl AsRef<str>) -> Vec<Document> {
+        let command_name = command_name.as_ref();
+        self.shared
+            .state
+            .lock()
+            .unwrap()
+            .commands
+            .iter()
+            .filter(|command| command.keys().next().map(String::as_str) == Some(command_name))
+            .cloned()
+            .collect()
+    }
+
+    /// Clears the commands the server has recorded.
+    pub fn clear_commands(&self) {
+        self.shared.state.lock().unwrap().commands.clear();
+    }
+}
+
+impl Shared {
+    /// Records the command and returns the reply to send for it.
+    fn next_reply(&self, command: Document) -> MockReply {
+        let name = command
+            .keys()
+            .next()
+            .map(|name| normalize_command_name(name))
+            .unwrap_or_default()
+            .to_string();
+
+        let mut state = self.state.lock().unwrap();
+        let is_hello = name == "hello";
+        if !is_hello {
+            state.commands.push(command);
+        }
+
+        if let Some(replies) = state.replies.get_mut(&name) {
+            if let Some(reply) = replies.front_mut() {
+                if reply.times > 1 {
+                    reply.times -= 1;
+                    return reply.clone();
+                }
+                if let Some(reply) = replies.pop_front() {
+                    return reply;
+                }
+            }
+        }
+
+        if is_hello {
+            MockReply::hello()
+        } else {
+            MockReply::document(Document::new())
+        }
+    }
+
+    fn hello_response(&self, connection_id: i32, fields: Document) -> Document {
+        let address = self.address.to_string();
+
+        let mut response = doc! {
+            "helloOk": true,
+            "isWritablePrimary": true,
+            "ismaster": true,
+            "maxBsonObjectSize": 16 * 1024 * 1024,
+            "maxMessageSizeBytes": 48_000_000,
+            "maxWriteBatchSize": 100_000,
+            "localTime": DateTime::now(),
+            "logicalSessionTimeoutMinutes": 30,
+            "connectionId": connection_id,
+            "minWireVersion": 0,
+            "maxWireVersion": self.max_wire_version,
+        };
+
+        match self.topology {
+            MockTopology::Standalone => {}
+            MockTopology::ReplicaSetPrimary { ref set_name } => {
+                response.insert("setName", set_name.as_str());
+                response.insert("setVersion", 1);
+                response.insert("electionId", ObjectId::from_bytes([0; 12]));
+                response.insert("hosts", vec![address.as_str()]);
+                response.insert("primary", address.as_str());
+                response.insert("me", address.as_str());
+            }
+            MockTopology::Mongos => {
+                response.insert("msg", "isdbgrid");
+            }
+            MockTopology::LoadBalancer => {
+                response.insert("msg", "isdbgrid");
+                response.insert("serviceId", self.service_id);
+            }
+        }
+
+        response.extend(self.hello_fields.clone());
+        response.extend(fields);
+        response.insert("ok", 1);
+        response
+    }
+}
+
+fn normalize_command_name(name: &str) -> &str {
+    if name.eq_ignore_ascii_case("ismaster") {
+        "hello"
+    } else {
+        name
+    }
+}
+
+/// Runs `future` to completion, or returns `None` if the server is shut down first.
+async fn until_shutdown<F: Future>(
+    shutdown: &mut watch::Receiver<()>,
+    future: F,
+) -> Option<F::Output> {
+    let shutdown = shutdown.changed();
+    futures_util::pin_mut!(future, shutdown);
+    match select(future, shutdown).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+async fn accept_connections(
+    shared: Arc<Shared>,
+    listener: std::net::TcpListener,
+    mut shutdown: watch::Receiver<()>,
+) -> Result<()> {
+    let listener = AsyncTcpListener::from_std(listener)?;
+
+    while let Some(stream) = until_shutdown(&mut shutdown, listener.accept()).await {
+        if let Ok(stream) = stream {
+            RUNTIME.execute(serve_connection(shared.clone(), stream, shutdown.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn serve_connection(
+    shared: Arc<Shared>,
+    mut stream: AsyncStream,
+    mut shutdown: watch::Receiver<()>,
+) -> Result<()> {
+    let connection_id = shared.next_connection_id.fetch_add(1, Ordering::SeqCst);
+
+    while let Some(message) = until_shutdown(&mut shutdown, Message::read_from(&mut stream)).await {
+        let message = message?;
+        let request_id = message.request_id.unwrap_or_default();
+        let more_to_come = message.flags.contains(MessageFlags::MORE_TO_COME);
+
+        let reply = shared.next_reply(command_from_message(message)?);
+        if let Some(delay) = reply.delay {
+            if until_shutdown(&mut shutdown, RUNTIME.delay_for(delay))
+                .await
+                .is_none()
+            {
+                break;
+            }
+        }
+
+        let mut response = match reply.kind {
+            MockReplyKind::Document(document) => document,
+            MockReplyKind::Hello(fields) => shared.hello_response(connection_id, fields),
+            MockReplyKind::NetworkError => break,
+        };
+        if !response.contains_key("ok") {
+            response.insert("ok", 1);
+        }
+
+        // Commands sent with the moreToCome flag do not expect a reply.
+        if more_to_come {
+            continue;
+        }
+
+        let reply = Message {
+            response_to: request_id,
+            flags: MessageFlags::empty(),
+            sections: vec![MessageSection::Document(bson::to_vec(&response)?)],
+            checksum: None,
+            request_id: None,
+        };
+        reply.write_to(&mut stream).await?;
+    }
+
+    Ok(())
+}
+
+/// Reassembles the command document from the sections of an OP_MSG.
+fn command_from_message(message: Message) -> Result<Document> {
+    let mut command = Document::new();
+
+    for section in message.sections {
+        match section {
+            MessageSection::Document(bytes) => {
+                command.extend(bson::from_slice::<Document>(bytes.as_slice())?);
+            }
+            MessageSection::Sequence {
+                identifier,
+                documents,
+                ..
+            } => {
+                let documents = documents
+                    .iter()
+                    .map(|bytes| bson::from_slice(bytes.as_slice()).map(Bson::Document))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                command.insert(identifier, documents);
+            }
+        }
+    }
+
+    Ok(command)
+}
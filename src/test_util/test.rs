@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use super::{MockReply, MockServer, MockServerOptions, MockTopology};
+use crate::{
+    bson::{doc, Document},
+    options::ClientOptions,
+    Client,
+};
+
+fn client_for(server: &MockServer) -> Client {
+    let mut options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    options.server_selection_timeout = Some(Duration::from_secs(5));
+    Client::with_options(options).unwrap()
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn scripted_replies() {
+    let server = MockServer::start(None).unwrap();
+    let client = client_for(&server);
+    let coll = client.database("db").collection::<Document>("coll");
+
+    server.add_reply("insert", MockReply::document(doc! { "n": 1 }));
+    server.add_reply(
+        "find",
+        MockReply::cursor("db.coll", vec![doc! { "x": 1 }, doc! { "x": 2 }]),
+    );
+
+    coll.insert_one(doc! { "_id": 1, "x": 1 }, None)
+        .await
+        .unwrap();
+    let mut cursor = coll.find(doc! { "x": { "$gt": 0 } }, None).await.unwrap();
+    let mut found = Vec::new();
+    while cursor.advance().await.unwrap() {
+        found.push(cursor.deserialize_current().unwrap());
+    }
+    assert_eq!(found, vec![doc! { "x": 1 }, doc! { "x": 2 }]);
+
+    let commands = server.commands();
+    let names: Vec<&str> = commands
+        .iter()
+        .map(|command| command.keys().next().unwrap().as_str())
+        .collect();
+    assert_eq!(names, vec!["insert", "find"]);
+
+    let insert = &server.commands_named("insert")[0];
+    assert_eq!(insert.get_str("insert"), Ok("coll"));
+    assert_eq!(insert.get_str("$db"), Ok("db"));
+    assert_eq!(
+        insert.get_array("documents").unwrap(),
+        &vec![doc! { "_id": 1, "x": 1 }.into()]
+    );
+    assert!(insert.contains_key("lsid"));
+
+    let find = &server.commands_named("find")[0];
+    assert_eq!(find.get_document("filter"), Ok(&doc! { "x": { "$gt": 0 } }));
+
+    server.clear_commands();
+    assert!(server.commands().is_empty());
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn errors_and_latency() {
+    let server = MockServer::start(None).unwrap();
+    let client = client_for(&server);
+    let db = client.database("db");
+
+    server.add_reply(
+        "ping",
+        MockReply::command_error(11600, "InterruptedAtShutdown", "shutting down"),
+    );
+    let error = db.run_command(doc! { "ping": 1 }, None).await.unwrap_err();
+    assert_eq!(error.code(), Some(11600));
+
+    server.add_reply("ping", MockReply::network_error().times(2));
+    let error = db.run_command(doc! { "ping": 1 }, None).await.unwrap_err();
+    assert!(error.is_network_error(), "{:?}", error);
+    let error = db.run_command(doc! { "ping": 1 }, None).await.unwrap_err();
+    assert!(error.is_network_error(), "{:?}", error);
+
+    let delay = Duration::from_millis(200);
+    server.add_reply("ping", MockReply::document(doc! {}).delay(delay));
+    let start = Instant::now();
+    db.run_command(doc! { "ping": 1 }, None).await.unwrap();
+    assert!(start.elapsed() >= delay);
+
+    // Once the scripted replies are used up the server falls back to `{ ok: 1 }`.
+    db.run_command(doc! { "ping": 1 }, None).await.unwrap();
+    assert_eq!(server.commands_named("ping").len(), 5);
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn topologies() {
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .topology(MockTopology::ReplicaSetPrimary {
+                set_name: "rs0".to_string(),
+            })
+            .hello_fields(doc! { "tags": { "dc": "east" } })
+            .build(),
+    )
+    .unwrap();
+    assert!(server.uri().ends_with("/?replicaSet=rs0"));
+    let client = client_for(&server);
+    client
+        .database("admin")
+        .run_command(doc! { "ping": 1 }, None)
+        .await
+        .unwrap();
+
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .topology(MockTopology::LoadBalancer)
+            .build(),
+    )
+    .unwrap();
+    assert!(server.uri().ends_with("/?loadBalanced=true"));
+    let client = client_for(&server);
+    client
+        .database("admin")
+        .run_command(doc! { "ping": 1 }, None)
+        .await
+        .unwrap();
+    assert_eq!(server.commands_named("ping").len(), 1);
+}
@@ -0,0 +1,556 @@
+//! Emission of [`tracing`](https://docs.rs/tracing) events when the `tracing` feature flag is
+//! enabled.
+//!
+//! Events are emitted at the `DEBUG` level with one target per component:
+//!   * `mongodb::command`: command started, succeeded and failed events
+//!   * `mongodb::topology`: topology and server monitoring events
+//!   * `mongodb::server_selection`: server selection events
+//!   * `mongodb::connection`: connection pool and connection lifecycle events
+//!
+//! Connection pool metrics are emitted at the `INFO` level as events with the `mongodb::metrics`
+//! target. Their fields use the `counter.`, `monotonic_counter.` and `histogram.` prefixes that
+//! `tracing-opentelemetry` maps to OpenTelemetry instruments. Spans are also opened for each
+//! operation and each attempt at executing its command, both named after the command (see
+//! [`span`]).
+
+pub(crate) mod span;
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use tracing::Level;
+
+use crate::{
+    bson::{Bson, Document},
+    client::{HELLO_COMMAND_NAMES, REDACTED_COMMANDS},
+    error::{Error, Result},
+    event::{
+        cmap::{
+            CmapEventHandler,
+            ConnectionCheckedInEvent,
+            ConnectionCheckedOutEvent,
+            ConnectionCheckoutFailedEvent,
+            ConnectionCheckoutStartedEvent,
+            ConnectionClosedEvent,
+            ConnectionCreatedEvent,
+            ConnectionReadyEvent,
+            PoolClearedEvent,
+            PoolClosedEvent,
+            PoolCreatedEvent,
+            PoolReadyEvent,
+        },
+        command::{
+            CommandEventHandler,
+            CommandFailedEvent,
+            CommandStartedEvent,
+            CommandSucceededEvent,
+        },
+        sdam::{
+            SdamEventHandler,
+            ServerClosedEvent,
+            ServerDescriptionChangedEvent,
+            ServerHeartbeatFailedEvent,
+            ServerHeartbeatStartedEvent,
+            ServerHeartbeatSucceededEvent,
+            ServerOpeningEvent,
+            TopologyClosedEvent,
+            TopologyDescriptionChangedEvent,
+            TopologyOpeningEvent,
+        },
+    },
+    options::ServerAddress,
+    sdam::SelectedServer,
+    selection_criteria::SelectionCriteria,
+};
+
+pub(crate) const COMMAND_TRACING_EVENT_TARGET: &str = "mongodb::command";
+pub(crate) const TOPOLOGY_TRACING_EVENT_TARGET: &str = "mongodb::topology";
+pub(crate) const SERVER_SELECTION_TRACING_EVENT_TARGET: &str = "mongodb::server_selection";
+pub(crate) const CONNECTION_TRACING_EVENT_TARGET: &str = "mongodb::connection";
+pub(crate) const METRICS_TRACING_EVENT_TARGET: &str = "mongodb::metrics";
+
+/// The default maximum length, in bytes, of the documents included in tracing events.
+pub(crate) const DEFAULT_MAX_DOCUMENT_LENGTH_BYTES: usize = 1000;
+
+/// Whether tracing events for commands are enabled.
+pub(crate) fn command_events_enabled() -> bool {
+    tracing::enabled!(target: COMMAND_TRACING_EVENT_TARGET, Level::DEBUG)
+}
+
+/// Whether tracing events for topology and server monitoring are enabled.
+pub(crate) fn topology_events_enabled() -> bool {
+    tracing::enabled!(target: TOPOLOGY_TRACING_EVENT_TARGET, Level::DEBUG)
+}
+
+/// Whether tracing events for connections or connection pool metrics are enabled.
+pub(crate) fn connection_events_enabled() -> bool {
+    tracing::enabled!(target: CONNECTION_TRACING_EVENT_TARGET, Level::DEBUG)
+        || tracing::enabled!(target: METRICS_TRACING_EVENT_TARGET, Level::INFO)
+}
+
+/// Serializes the given document as relaxed extended JSON, truncated to `max_length` bytes.
+pub(crate) fn serialize_document(document: &Document, max_length: usize) -> String {
+    let mut json = Bson::Document(document.clone())
+        .into_relaxed_extjson()
+        .to_string();
+    truncate_on_char_boundary(&mut json, max_length);
+    json
+}
+
+/// Serializes a command or reply document, omitting its contents if the command is sensitive.
+pub(crate) fn serialize_command_or_reply(
+    command_name: &str,
+    document: &Document,
+    max_length: usize,
+) -> String {
+    let name = command_name.to_lowercase();
+    let is_sensitive_hello = HELLO_COMMAND_NAMES.contains(name.as_str())
+        && document.contains_key("speculativeAuthenticate");
+
+    if REDACTED_COMMANDS.contains(name.as_str()) || is_sensitive_hello {
+        serialize_document(&Document::new(), max_length)
+    } else {
+        serialize_document(document, max_length)
+    }
+}
+
+/// Truncates the string to at most `max_length` bytes, adjusted down to the nearest character
+/// boundary, and appends an ellipsis if anything was removed.
+pub(crate) fn truncate_on_char_boundary(s: &mut String, max_length: usize) {
+    if s.len() <= max_length {
+        return;
+    }
+
+    let mut end = max_length;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s.truncate(end);
+    s.push_str("...");
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
+
+fn port(address: &ServerAddress) -> Option<u16> {
+    address.port()
+}
+
+/// Emits command events as tracing events before forwarding them to the user's handler, if any.
+pub(crate) struct CommandTracingEventEmitter<'a> {
+    pub(crate) inner: Option<&'a dyn CommandEventHandler>,
+    pub(crate) max_document_length: Option<usize>,
+}
+
+impl CommandTracingEventEmitter<'_> {
+    fn max_document_length(&self) -> usize {
+        self.max_document_length
+            .unwrap_or(DEFAULT_MAX_DOCUMENT_LENGTH_BYTES)
+    }
+}
+
+impl CommandEventHandler for CommandTracingEventEmitter<'_> {
+    fn handle_command_started_event(&self, event: CommandStartedEvent) {
+        tracing::debug!(
+            target: COMMAND_TRACING_EVENT_TARGET,
+            command = serialize_command_or_reply(
+                &event.command_name,
+                &event.command,
+                self.max_document_length()
+            )
+            .as_str(),
+            databaseName = event.db.as_str(),
+            commandName = event.command_name.as_str(),
+            requestId = event.request_id,
+            driverConnectionId = event.connection.id,
+            serverHost = event.connection.address.host().as_ref(),
+            serverPort = port(&event.connection.address),
+            serviceId = ?event.service_id,
+            "Command started"
+        );
+
+        if let Some(inner) = self.inner {
+            inner.handle_command_started_event(event);
+        }
+    }
+
+    fn handle_command_succeeded_event(&self, event: CommandSucceededEvent) {
+        tracing::debug!(
+            target: COMMAND_TRACING_EVENT_TARGET,
+            reply = serialize_command_or_reply(
+                &event.command_name,
+                &event.reply,
+                self.max_document_length()
+            )
+            .as_str(),
+            commandName = event.command_name.as_str(),
+            requestId = event.request_id,
+            driverConnectionId = event.connection.id,
+            serverHost = event.connection.address.host().as_ref(),
+            serverPort = port(&event.connection.address),
+            serviceId = ?event.service_id,
+            durationMS = duration_ms(event.duration),
+            "Command succeeded"
+        );
+
+        if let Some(inner) = self.inner {
+            inner.handle_command_succeeded_event(event);
+        }
+    }
+
+    fn handle_command_failed_event(&self, event: CommandFailedEvent) {
+        tracing::debug!(
+            target: COMMAND_TRACING_EVENT_TARGET,
+            failure = %event.failure,
+            commandName = event.command_name.as_str(),
+            requestId = event.request_id,
+            driverConnectionId = event.connection.id,
+            serverHost = event.connection.address.host().as_ref(),
+            serverPort = port(&event.connection.address),
+            serviceId = ?event.service_id,
+            durationMS = duration_ms(event.duration),
+            "Command failed"
+        );
+
+        if let Some(inner) = self.inner {
+            inner.handle_command_failed_event(event);
+        }
+    }
+}
+
+/// Emits connection and connection pool events as tracing events before forwarding them to the
+/// user's handler, if any.
+pub(crate) struct ConnectionTracingEventEmitter<'a> {
+    pub(crate) inner: Option<&'a dyn CmapEventHandler>,
+}
+
+impl ConnectionTracingEventEmitter<'_> {
+    fn forward(&self, emit: impl FnOnce(&dyn CmapEventHandler)) {
+        if let Some(inner) = self.inner {
+            emit(inner);
+        }
+    }
+}
+
+impl CmapEventHandler for ConnectionTracingEventEmitter<'_> {
+    fn handle_pool_created_event(&self, event: PoolCreatedEvent) {
+        let options = event.options.as_ref();
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            maxIdleTimeMS = options.and_then(|o| o.max_idle_time).map(duration_ms),
+            minPoolSize = options.and_then(|o| o.min_pool_size),
+            maxPoolSize = options.and_then(|o| o.max_pool_size),
+            "Connection pool created"
+        );
+        self.forward(|inner| inner.handle_pool_created_event(event));
+    }
+
+    fn handle_pool_ready_event(&self, event: PoolReadyEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            "Connection pool ready"
+        );
+        self.forward(|inner| inner.handle_pool_ready_event(event));
+    }
+
+    fn handle_pool_cleared_event(&self, event: PoolClearedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            serviceId = ?event.service_id,
+            "Connection pool cleared"
+        );
+        tracing::info!(
+            target: METRICS_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            monotonic_counter.mongodb.pool.clears = 1_u64,
+            "Connection pool metrics updated"
+        );
+        self.forward(|inner| inner.handle_pool_cleared_event(event));
+    }
+
+    fn handle_pool_closed_event(&self, event: PoolClosedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            "Connection pool closed"
+        );
+        self.forward(|inner| inner.handle_pool_closed_event(event));
+    }
+
+    fn handle_connection_created_event(&self, event: ConnectionCreatedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            driverConnectionId = event.connection_id,
+            "Connection created"
+        );
+        self.forward(|inner| inner.handle_connection_created_event(event));
+    }
+
+    fn handle_connection_ready_event(&self, event: ConnectionReadyEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            driverConnectionId = event.connection_id,
+            "Connection ready"
+        );
+        self.forward(|inner| inner.handle_connection_ready_event(event));
+    }
+
+    fn handle_connection_closed_event(&self, event: ConnectionClosedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            driverConnectionId = event.connection_id,
+            reason = ?event.reason,
+            "Connection closed"
+        );
+        self.forward(|inner| inner.handle_connection_closed_event(event));
+    }
+
+    fn handle_connection_checkout_started_event(&self, event: ConnectionCheckoutStartedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            "Connection checkout started"
+        );
+        self.forward(|inner| inner.handle_connection_checkout_started_event(event));
+    }
+
+    fn handle_connection_checkout_failed_event(&self, event: ConnectionCheckoutFailedEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            reason = ?event.reason,
+            durationMS = duration_ms(event.duration),
+            "Connection checkout failed"
+        );
+        tracing::info!(
+            target: METRICS_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            monotonic_counter.mongodb.pool.checkout_failures = 1_u64,
+            histogram.mongodb.pool.wait_time_ms = event.duration.as_secs_f64() * 1000.0,
+            "Connection pool metrics updated"
+        );
+        self.forward(|inner| inner.handle_connection_checkout_failed_event(event));
+    }
+
+    fn handle_connection_checked_out_event(&self, event: ConnectionCheckedOutEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            driverConnectionId = event.connection_id,
+            durationMS = duration_ms(event.duration),
+            "Connection checked out"
+        );
+        tracing::info!(
+            target: METRICS_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            counter.mongodb.pool.checked_out_connections = 1_i64,
+            histogram.mongodb.pool.wait_time_ms = event.duration.as_secs_f64() * 1000.0,
+            "Connection pool metrics updated"
+        );
+        self.forward(|inner| inner.handle_connection_checked_out_event(event));
+    }
+
+    fn handle_connection_checked_in_event(&self, event: ConnectionCheckedInEvent) {
+        tracing::debug!(
+            target: CONNECTION_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            driverConnectionId = event.connection_id,
+            "Connection checked in"
+        );
+        tracing::info!(
+            target: METRICS_TRACING_EVENT_TARGET,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            counter.mongodb.pool.checked_out_connections = -1_i64,
+            "Connection pool metrics updated"
+        );
+        self.forward(|inner| inner.handle_connection_checked_in_event(event));
+    }
+}
+
+/// Emits topology and server monitoring events as tracing events before forwarding them to the
+/// user's handler, if any.
+pub(crate) struct TopologyTracingEventEmitter<'a> {
+    pub(crate) inner: Option<&'a dyn SdamEventHandler>,
+    pub(crate) max_document_length: Option<usize>,
+}
+
+impl TopologyTracingEventEmitter<'_> {
+    fn forward(&self, emit: impl FnOnce(&dyn SdamEventHandler)) {
+        if let Some(inner) = self.inner {
+            emit(inner);
+        }
+    }
+}
+
+impl SdamEventHandler for TopologyTracingEventEmitter<'_> {
+    fn handle_server_description_changed_event(&self, event: ServerDescriptionChangedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            previousDescription = %event.previous_description,
+            newDescription = %event.new_description,
+            "Server description changed"
+        );
+        self.forward(|inner| inner.handle_server_description_changed_event(event));
+    }
+
+    fn handle_server_opening_event(&self, event: ServerOpeningEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            "Starting server monitoring"
+        );
+        self.forward(|inner| inner.handle_server_opening_event(event));
+    }
+
+    fn handle_server_closed_event(&self, event: ServerClosedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            serverHost = event.address.host().as_ref(),
+            serverPort = port(&event.address),
+            "Stopped server monitoring"
+        );
+        self.forward(|inner| inner.handle_server_closed_event(event));
+    }
+
+    fn handle_topology_description_changed_event(&self, event: TopologyDescriptionChangedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            previousDescription = %event.previous_description,
+            newDescription = %event.new_description,
+            "Topology description changed"
+        );
+        self.forward(|inner| inner.handle_topology_description_changed_event(event));
+    }
+
+    fn handle_topology_opening_event(&self, event: TopologyOpeningEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            "Starting topology monitoring"
+        );
+        self.forward(|inner| inner.handle_topology_opening_event(event));
+    }
+
+    fn handle_topology_closed_event(&self, event: TopologyClosedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            topologyId = %event.topology_id,
+            "Stopped topology monitoring"
+        );
+        self.forward(|inner| inner.handle_topology_closed_event(event));
+    }
+
+    fn handle_server_heartbeat_started_event(&self, event: ServerHeartbeatStartedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            serverHost = event.server_address.host().as_ref(),
+            serverPort = port(&event.server_address),
+            awaited = event.awaited,
+            "Server heartbeat started"
+        );
+        self.forward(|inner| inner.handle_server_heartbeat_started_event(event));
+    }
+
+    fn handle_server_heartbeat_succeeded_event(&self, event: ServerHeartbeatSucceededEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            serverHost = event.server_address.host().as_ref(),
+            serverPort = port(&event.server_address),
+            awaited = event.awaited,
+            reply = serialize_document(
+                &event.reply,
+                self.max_document_length
+                    .unwrap_or(DEFAULT_MAX_DOCUMENT_LENGTH_BYTES)
+            )
+            .as_str(),
+            durationMS = duration_ms(event.duration),
+            "Server heartbeat succeeded"
+        );
+        self.forward(|inner| inner.handle_server_heartbeat_succeeded_event(event));
+    }
+
+    fn handle_server_heartbeat_failed_event(&self, event: ServerHeartbeatFailedEvent) {
+        tracing::debug!(
+            target: TOPOLOGY_TRACING_EVENT_TARGET,
+            serverHost = event.server_address.host().as_ref(),
+            serverPort = port(&event.server_address),
+            awaited = event.awaited,
+            failure = %event.failure,
+            durationMS = duration_ms(event.duration),
+            "Server heartbeat failed"
+        );
+        self.forward(|inner| inner.handle_server_heartbeat_failed_event(event));
+    }
+}
+
+pub(crate) fn server_selection_started(criteria: &SelectionCriteria) {
+    tracing::debug!(
+        target: SERVER_SELECTION_TRACING_EVENT_TARGET,
+        selector = ?criteria,
+        "Server selection started"
+    );
+}
+
+pub(crate) fn server_selection_waiting(criteria: &SelectionCriteria, remaining_time: Duration) {
+    tracing::debug!(
+        target: SERVER_SELECTION_TRACING_EVENT_TARGET,
+        selector = ?criteria,
+        remainingTimeMS = duration_ms(remaining_time),
+        "Waiting for suitable server to become available"
+    );
+}
+
+pub(crate) fn server_selection_finished(
+    criteria: &SelectionCriteria,
+    result: &Result<SelectedServer>,
+) {
+    match result {
+        Ok(server) => tracing::debug!(
+            target: SERVER_SELECTION_TRACING_EVENT_TARGET,
+            selector = ?criteria,
+            serverHost = server.address.host().as_ref(),
+            serverPort = port(&server.address),
+            "Server selection succeeded"
+        ),
+        Err(error) => server_selection_failed(criteria, error),
+    }
+}
+
+fn server_selection_failed(criteria: &SelectionCriteria, error: &Error) {
+    tracing::debug!(
+        target: SERVER_SELECTION_TRACING_EVENT_TARGET,
+        selector = ?criteria,
+        failure = %error,
+        "Server selection failed"
+    );
+}
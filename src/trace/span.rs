@@ -0,0 +1,114 @@
+//! Spans covering driver operations and the individual command attempts made to execute them.
+//!
+//! Operation spans are named after the command that the operation runs rather than the method
+//! that started it, e.g. `find` for `Collection::find_one` and `aggregate` for
+//! `Collection::count_documents`.
+//!
+//! Span fields follow the OpenTelemetry semantic conventions for database clients, so the spans
+//! can be exported as-is via a bridge such as `tracing-opentelemetry`.
+
+use std::future::Future;
+
+use tracing::{field::Empty, Instrument, Span};
+
+use crate::{bson::RawDocument, error::Result, options::ServerAddress};
+
+pub(crate) const OPERATION_SPAN_TARGET: &str = "mongodb::operation";
+pub(crate) const COMMAND_SPAN_TARGET: &str = "mongodb::command";
+
+/// Runs the given operation within a span named after the command it runs, recording its outcome
+/// on the span.
+///
+/// Methods that execute several operations, such as `Collection::insert_many` sending its
+/// documents in batches, open a span around all of them. When already within an operation span,
+/// the operation runs in that span rather than opening one of its own.
+pub(crate) async fn instrument_operation<O>(
+    name: &str,
+    execution: impl Future<Output = Result<O>>,
+) -> Result<O> {
+    if Span::current()
+        .metadata()
+        .map_or(false, |metadata| metadata.target() == OPERATION_SPAN_TARGET)
+    {
+        return execution.await;
+    }
+
+    let span = tracing::info_span!(
+        target: OPERATION_SPAN_TARGET,
+        "mongodb.operation",
+        otel.name = name,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        db.system = "mongodb",
+        db.operation = name,
+        db.mongodb.error_labels = Empty,
+    );
+
+    let result = execution.instrument(span.clone()).await;
+    record_outcome(&span, &result);
+    result
+}
+
+/// Runs a single attempt at executing a command within a span that is a child of the current
+/// operation span, recording its outcome on the span.
+pub(crate) async fn instrument_command_attempt<O>(
+    name: &str,
+    address: &ServerAddress,
+    retry_attempt: u32,
+    attempt: impl Future<Output = Result<O>>,
+) -> Result<O> {
+    let span = tracing::info_span!(
+        target: COMMAND_SPAN_TARGET,
+        "mongodb.command",
+        otel.name = name,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        db.system = "mongodb",
+        db.operation = name,
+        db.name = Empty,
+        db.mongodb.collection = Empty,
+        db.mongodb.retry_attempt = retry_attempt,
+        db.mongodb.error_labels = Empty,
+        net.peer.name = address.host().as_ref(),
+        net.peer.port = address.port(),
+    );
+
+    let result = attempt.instrument(span.clone()).await;
+    record_outcome(&span, &result);
+    result
+}
+
+/// Records the name and namespace of the command sent by the current command attempt on its span.
+/// The collection is taken from the value of the command name field, if that value is a string.
+pub(crate) fn record_command(target_db: &str, command_name: &str, command: &[u8]) {
+    let span = Span::current();
+    span.record("db.operation", command_name);
+    span.record("db.name", target_db);
+
+    let collection = RawDocument::from_bytes(command)
+        .ok()
+        .and_then(|command| command.get_str(command_name).ok());
+    if let Some(collection) = collection {
+        span.record("db.mongodb.collection", collection);
+    }
+}
+
+fn record_outcome<O>(span: &Span, result: &Result<O>) {
+    match result {
+        Ok(_) => {
+            span.record("otel.status_code", "OK");
+        }
+        Err(error) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("otel.status_message", error.to_string().as_str());
+
+            let mut labels: Vec<_> = error.labels().iter().map(String::as_str).collect();
+            if !labels.is_empty() {
+                labels.sort_unstable();
+                span.record("db.mongodb.error_labels", labels.join(",").as_str());
+            }
+        }
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use lazy_static::lazy_static;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event,
+    Level,
+    Metadata,
+    Subscriber,
+};
+use tracing_core::span::Current;
+
+use super::{serialize_command_or_reply, serialize_document, truncate_on_char_boundary};
+use crate::{
+    bson::{doc, Document},
+    options::{ClientOptions, InsertOneModel},
+    test_util::{MockReply, MockServer, MockServerOptions},
+    Client,
+};
+
+#[test]
+fn truncation() {
+    let mut s = String::from("hello world");
+    truncate_on_char_boundary(&mut s, 100);
+    assert_eq!(s, "hello world");
+
+    let mut s = String::from("hello world");
+    truncate_on_char_boundary(&mut s, 5);
+    assert_eq!(s, "hello...");
+
+    // "é" is two bytes long, so truncating in the middle of it should back up to the start.
+    let mut s = String::from("héllo");
+    truncate_on_char_boundary(&mut s, 2);
+    assert_eq!(s, "h...");
+}
+
+#[test]
+fn documents_are_truncated() {
+    let document = doc! { "x": "a".repeat(50) };
+
+    let json = serialize_document(&document, 1000);
+    assert_eq!(json, format!(r#"{{"x":"{}"}}"#, "a".repeat(50)));
+
+    let json = serialize_document(&document, 10);
+    assert_eq!(json, r#"{"x":"aaaa..."#);
+}
+
+#[test]
+fn sensitive_commands_are_redacted() {
+    let command = doc! { "saslStart": 1, "payload": "secret" };
+    assert_eq!(
+        serialize_command_or_reply("saslStart", &command, 1000),
+        "{}"
+    );
+
+    let hello = doc! { "hello": 1, "speculativeAuthenticate": { "payload": "secret" } };
+    assert_eq!(serialize_command_or_reply("hello", &hello, 1000), "{}");
+
+    let hello = doc! { "hello": 1 };
+    assert_eq!(
+        serialize_command_or_reply("hello", &hello, 1000),
+        r#"{"hello":1}"#
+    );
+}
+
+/// A tracing event recorded by the [`CapturingSubscriber`].
+#[derive(Clone, Debug)]
+pub(crate) struct CapturedEvent {
+    pub(crate) target: String,
+    pub(crate) level: Level,
+    pub(crate) fields: HashMap<String, String>,
+}
+
+impl CapturedEvent {
+    pub(crate) fn message(&self) -> &str {
+        self.field("message").unwrap_or_default()
+    }
+
+    pub(crate) fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// A span recorded by the [`CapturingSubscriber`], along with the fields recorded on it after it
+/// was created.
+#[derive(Clone, Debug)]
+pub(crate) struct CapturedSpan {
+    pub(crate) id: u64,
+    pub(crate) parent: Option<u64>,
+    pub(crate) name: String,
+    pub(crate) target: String,
+    pub(crate) fields: HashMap<String, String>,
+    metadata: &'static Metadata<'static>,
+}
+
+impl CapturedSpan {
+    pub(crate) fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// A subscriber that records every span and event. It is installed as the global default so that
+/// it also sees the events emitted from the driver's background tasks; tests share it, so they
+/// must filter what it recorded by something unique to them, such as a mock server's port.
+#[derive(Default)]
+pub(crate) struct CapturingSubscriber {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, CapturedSpan>>,
+    events: Mutex<Vec<CapturedEvent>>,
+}
+
+lazy_static! {
+    static ref SUBSCRIBER: &'static CapturingSubscriber = {
+        let subscriber: &'static CapturingSubscriber = Box::leak(Box::default());
+        tracing::subscriber::set_global_default(SharedSubscriber(subscriber))
+            .expect("global tracing subscriber already set");
+        subscriber
+    };
+}
+
+thread_local! {
+    static CURRENT_SPANS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+impl CapturingSubscriber {
+    /// Returns the global capturing subscriber, installing it if necessary.
+    pub(crate) fn global() -> &'static CapturingSubscriber {
+        &SUBSCRIBER
+    }
+
+    pub(crate) fn events(&self) -> Vec<CapturedEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    pub(crate) fn spans(&self) -> Vec<CapturedSpan> {
+        let mut spans: Vec<_> = self.spans.lock().unwrap().values().cloned().collect();
+        spans.sort_by_key(|span| span.id);
+        spans
+    }
+}
+
+struct SharedSubscriber(&'static CapturingSubscriber);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for SharedSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let id = self.0.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let parent = match attributes.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attributes.is_contextual() => {
+                CURRENT_SPANS.with(|current| current.borrow().last().copied())
+            }
+            None => None,
+        };
+
+        let mut fields = HashMap::new();
+        attributes.record(&mut FieldVisitor(&mut fields));
+        self.0.spans.lock().unwrap().insert(
+            id,
+            CapturedSpan {
+                id,
+                parent,
+                name: attributes.metadata().name().to_string(),
+                target: attributes.metadata().target().to_string(),
+                fields,
+                metadata: attributes.metadata(),
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        if let Some(span) = self.0.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            values.record(&mut FieldVisitor(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = HashMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.0.events.lock().unwrap().push(CapturedEvent {
+            target: event.metadata().target().to_string(),
+            level: *event.metadata().level(),
+            fields,
+        });
+    }
+
+    fn current_span(&self) -> Current {
+        let current = CURRENT_SPANS.with(|current| current.borrow().last().copied());
+        let spans = self.0.spans.lock().unwrap();
+        match current.and_then(|id| spans.get(&id)) {
+            Some(span) => Current::new(Id::from_u64(span.id), span.metadata),
+            None => Current::none(),
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        CURRENT_SPANS.with(|current| current.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        CURRENT_SPANS.with(|current| {
+            let mut current = current.borrow_mut();
+            if let Some(position) = current.iter().rposition(|id| *id == span.into_u64()) {
+                current.remove(position);
+            }
+        });
+    }
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn events_are_emitted() {
+    let subscriber = CapturingSubscriber::global();
+
+    let server = MockServer::start(None).unwrap();
+    let port = server.address().port().unwrap().to_string();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let db = Client::with_options(options).unwrap().database("db");
+
+    server.add_reply("ping", MockReply::document(doc! { "ok": 1, "pong": true }));
+    db.run_command(doc! { "ping": 1 }, None).await.unwrap();
+    db.run_command(doc! { "saslStart": 1, "payload": "secret" }, None)
+        .await
+        .unwrap();
+
+    // Other tests share the subscriber, so only consider the events for this test's server.
+    let events: Vec<_> = subscriber
+        .events()
+        .into_iter()
+        .filter(|event| {
+            event.field("serverPort") == Some(port.as_str())
+                || event
+                    .field("newDescription")
+                    .map_or(false, |description| description.contains(&port))
+        })
+        .collect();
+    let find = |target: &str, message: &str| -> Vec<CapturedEvent> {
+        events
+            .iter()
+            .filter(|event| event.target == target && event.message() == message)
+            .cloned()
+            .collect()
+    };
+
+    let started = find("mongodb::command", "Command started");
+    let ping = started
+        .iter()
+        .find(|event| event.field("commandName") == Some("ping"))
+        .expect("no command started event for ping");
+    assert_eq!(ping.level, Level::DEBUG);
+    assert_eq!(ping.field("databaseName"), Some("db"));
+    assert!(ping.field("command").unwrap().contains(r#""ping":1"#));
+    assert!(ping.field("requestId").is_some());
+    assert!(ping.field("driverConnectionId").is_some());
+    assert!(ping.field("serverHost").is_some());
+
+    let sasl = started
+        .iter()
+        .find(|event| event.field("commandName") == Some("saslStart"))
+        .expect("no command started event for saslStart");
+    assert_eq!(sasl.field("command"), Some("{}"));
+
+    let succeeded = find("mongodb::command", "Command succeeded");
+    let ping = succeeded
+        .iter()
+        .find(|event| event.field("commandName") == Some("ping"))
+        .expect("no command succeeded event for ping");
+    assert!(ping.field("reply").unwrap().contains(r#""pong":true"#));
+    assert!(ping.field("durationMS").is_some());
+    let sasl = succeeded
+        .iter()
+        .find(|event| event.field("commandName") == Some("saslStart"))
+        .expect("no command succeeded event for saslStart");
+    assert_eq!(sasl.field("reply"), Some("{}"));
+
+    assert!(!find("mongodb::topology", "Starting server monitoring").is_empty());
+    assert!(!find("mongodb::topology", "Topology description changed").is_empty());
+    let heartbeats = find("mongodb::topology", "Server heartbeat succeeded");
+    assert!(heartbeats
+        .iter()
+        .all(|event| event.field("reply").is_some() && event.field("durationMS").is_some()));
+    assert!(!heartbeats.is_empty());
+
+    assert!(!find("mongodb::connection", "Connection pool created").is_empty());
+    assert!(!find("mongodb::connection", "Connection ready").is_empty());
+    let checked_out = find("mongodb::connection", "Connection checked out");
+    assert!(!checked_out.is_empty());
+    assert!(checked_out
+        .iter()
+        .all(|event| event.field("driverConnectionId").is_some()
+            && event.field("durationMS").is_some()));
+    assert!(!find("mongodb::connection", "Connection checked in").is_empty());
+
+    let selected = find("mongodb::server_selection", "Server selection succeeded");
+    assert!(!selected.is_empty());
+    assert!(selected
+        .iter()
+        .all(|event| event.field("selector").is_some()));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn spans_are_recorded() {
+    let subscriber = CapturingSubscriber::global();
+
+    let server = MockServer::start(None).unwrap();
+    let port = server.address().port().unwrap().to_string();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let db = Client::with_options(options).unwrap().database("db");
+
+    // The first attempt fails with a network error and the read is retried.
+    server.add_reply("find", MockReply::network_error());
+    server.add_reply(
+        "find",
+        MockReply::cursor("db.coll", vec![doc! { "_id": 1 }]),
+    );
+    let found = db
+        .collection::<Document>("coll")
+        .find_one(None, None)
+        .await
+        .unwrap();
+    assert_eq!(found, Some(doc! { "_id": 1 }));
+
+    server.add_reply(
+        "ping",
+        MockReply::document(doc! {
+            "ok": 0,
+            "code": 2,
+            "codeName": "BadValue",
+            "errmsg": "bad ping",
+            "errorLabels": ["TestLabel"],
+        }),
+    );
+    db.run_command(doc! { "ping": 1 }, None).await.unwrap_err();
+
+    let spans = subscriber.spans();
+    let span = |id: u64| spans.iter().find(|span| span.id == id).unwrap();
+    // Other tests share the subscriber, so only consider the spans for this test's server.
+    let attempts: Vec<_> = spans
+        .iter()
+        .filter(|span| {
+            span.name == "mongodb.command" && span.field("net.peer.port") == Some(port.as_str())
+        })
+        .collect();
+    for attempt in &attempts {
+        assert_eq!(attempt.target, "mongodb::command");
+        assert_eq!(attempt.field("otel.kind"), Some("client"));
+        assert_eq!(attempt.field("db.system"), Some("mongodb"));
+        assert_eq!(attempt.field("db.name"), Some("db"));
+        assert!(attempt.field("net.peer.name").is_some());
+    }
+
+    let finds: Vec<_> = attempts
+        .iter()
+        .filter(|span| span.field("otel.name") == Some("find"))
+        .collect();
+    assert_eq!(finds.len(), 2);
+    assert_eq!(finds[0].field("db.mongodb.retry_attempt"), Some("0"));
+    assert_eq!(finds[0].field("otel.status_code"), Some("ERROR"));
+    assert_eq!(finds[1].field("db.mongodb.retry_attempt"), Some("1"));
+    assert_eq!(finds[1].field("otel.status_code"), Some("OK"));
+    assert_eq!(finds[1].field("db.mongodb.collection"), Some("coll"));
+    assert_eq!(finds[0].parent, finds[1].parent);
+
+    // Operation spans are named after the command that the operation runs.
+    let find_operation = span(finds[0].parent.expect("command span has no parent"));
+    assert_eq!(find_operation.name, "mongodb.operation");
+    assert_eq!(find_operation.target, "mongodb::operation");
+    assert_eq!(find_operation.field("otel.name"), Some("find"));
+    assert_eq!(find_operation.field("db.operation"), Some("find"));
+    assert_eq!(find_operation.field("otel.status_code"), Some("OK"));
+
+    let ping = attempts
+        .iter()
+        .find(|span| span.field("otel.name") == Some("ping"))
+        .expect("no command span for ping");
+    assert_eq!(ping.field("db.mongodb.retry_attempt"), Some("0"));
+    assert_eq!(ping.field("otel.status_code"), Some("ERROR"));
+    let ping_operation = span(ping.parent.expect("command span has no parent"));
+    assert_eq!(ping_operation.field("otel.name"), Some("ping"));
+    assert_eq!(ping_operation.field("otel.status_code"), Some("ERROR"));
+    assert!(ping_operation
+        .field("otel.status_message")
+        .unwrap()
+        .contains("bad ping"));
+    assert_eq!(
+        ping_operation.field("db.mongodb.error_labels"),
+        Some("TestLabel")
+    );
+
+    let metrics: Vec<_> = subscriber
+        .events()
+        .into_iter()
+        .filter(|event| {
+            event.target == "mongodb::metrics" && event.field("serverPort") == Some(port.as_str())
+        })
+        .collect();
+    assert!(metrics.iter().all(|event| event.level == Level::INFO));
+    assert!(metrics.iter().any(|event| {
+        event.field("counter.mongodb.pool.checked_out_connections") == Some("1")
+            && event.field("histogram.mongodb.pool.wait_time_ms").is_some()
+    }));
+    assert!(metrics
+        .iter()
+        .any(|event| event.field("counter.mongodb.pool.checked_out_connections") == Some("-1")));
+    assert!(metrics
+        .iter()
+        .any(|event| event.field("monotonic_counter.mongodb.pool.clears") == Some("1")));
+}
+
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+#[cfg_attr(feature = "async-std-runtime", async_std::test)]
+async fn batches_share_an_operation_span() {
+    let subscriber = CapturingSubscriber::global();
+
+    let server = MockServer::start(
+        MockServerOptions::builder()
+            .hello_fields(doc! { "maxWriteBatchSize": 2 })
+            .build(),
+    )
+    .unwrap();
+    let port = server.address().port().unwrap().to_string();
+    let options = ClientOptions::parse_without_srv_resolution(&server.uri()).unwrap();
+    let coll = Client::with_options(options)
+        .unwrap()
+        .database("db")
+        .collection::<Document>("coll");
+
+    let docs: Vec<_> = (0..5).map(|i| doc! { "_id": i }).collect();
+    server.add_reply("insert", MockReply::document(doc! { "n": 2 }).times(6));
+    coll.insert_many(docs.clone(), None).await.unwrap();
+    let models = docs
+        .into_iter()
+        .map(|document| InsertOneModel::builder().document(document).build().into());
+    coll.bulk_write(models, None).await.unwrap();
+    assert_eq!(server.commands_named("insert").len(), 6);
+
+    let spans = subscriber.spans();
+    let span = |id: u64| spans.iter().find(|span| span.id == id).unwrap();
+    let inserts: Vec<_> = spans
+        .iter()
+        .filter(|span| {
+            span.name == "mongodb.command" && span.field("net.peer.port") == Some(port.as_str())
+        })
+        .collect();
+    assert_eq!(inserts.len(), 6);
+
+    // The batches of each call are recorded under one operation span for the whole call, rather
+    // than an operation span per batch.
+    for (batches, name) in [(&inserts[..3], "insert"), (&inserts[3..], "bulkWrite")] {
+        let parent = batches[0].parent.expect("command span has no parent");
+        assert!(batches.iter().all(|batch| batch.parent == Some(parent)));
+        let operation = span(parent);
+        assert_eq!(operation.name, "mongodb.operation");
+        assert_eq!(operation.field("otel.name"), Some(name));
+        assert_eq!(operation.field("otel.status_code"), Some("OK"));
+        assert!(operation
+            .parent
+            .map_or(true, |id| span(id).name != "mongodb.operation"));
+    }
+    assert_ne!(inserts[0].parent, inserts[3].parent);
+}